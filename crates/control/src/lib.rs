@@ -0,0 +1,81 @@
+//! Wire protocol of Dioxus Feed's control channel, shared by the app and
+//! the `dioxus-feed-ctl` companion binary.
+//!
+//! A client connects, writes one [`ControlRequest`] as a JSON line and reads
+//! one [`ControlResponse`] line back.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Directory name used under the platform data directory, as in `persistence`
+#[cfg(not(windows))]
+const APP_DATA_DIR: &str = "dioxus-feed";
+
+/// Owner-only directory in the data directory holding the socket
+#[cfg(not(windows))]
+const SOCKET_DIR: &str = "control";
+
+/// Socket file in [`SOCKET_DIR`]
+#[cfg(not(windows))]
+const SOCKET_FILE: &str = "control.sock";
+
+/// Pipe name on Windows
+#[cfg(windows)]
+const PIPE_NAME: &str = r"\\.\pipe\dioxus-feed-control";
+
+/// Where the running app listens for control requests: a Unix socket in a
+/// directory of the data directory, or a named pipe on Windows
+#[cfg(not(windows))]
+pub fn control_path() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| std::env::current_dir().unwrap_or_default())
+        .join(APP_DATA_DIR)
+        .join(SOCKET_DIR)
+        .join(SOCKET_FILE)
+}
+
+#[cfg(windows)]
+pub fn control_path() -> PathBuf {
+    PathBuf::from(PIPE_NAME)
+}
+
+/// An item as sent by an integration
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct PushedItem {
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    /// `http(s)` URL or `data:image/…` URL of the item's image
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+}
+
+/// A request sent over the control channel. Feeds are named by their tab
+/// key (`home`, `folder:News`, …); `None` means the feed on screen.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum ControlRequest {
+    /// Add an item to the inbox
+    Post { item: PushedItem },
+    /// Refresh a feed as the refresh button would
+    Refresh {
+        #[serde(default)]
+        feed: Option<String>,
+    },
+    /// Count the loaded items newer than the newest one read
+    UnreadCount {
+        #[serde(default)]
+        feed: Option<String>,
+    },
+}
+
+/// Answer to a [`ControlRequest`]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ControlResponse {
+    Ok { message: String },
+    UnreadCount { feed: String, count: usize },
+    Error { message: String },
+}
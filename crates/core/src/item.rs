@@ -0,0 +1,242 @@
+use serde::{Deserialize, Serialize};
+
+/// Pixel width and height of an image
+pub type Dimensions = (u32, u32);
+
+/// Position of an item in the feed's overall order.
+///
+/// Sequences are assigned when items enter the store and stay with them, so
+/// unlike array indices they don't shift when items are prepended or evicted.
+/// Items hold consecutive sequences, oldest first.
+pub type Sequence = i64;
+
+/// An item of a feed, as sources deliver it and rows show it
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct VirtualFeedItem {
+    pub id: String,
+    pub content: String,
+    pub image_url: String,
+    pub link: Option<String>, // Linked article, opened in reader mode
+    #[serde(default)]
+    pub author: Option<String>,
+    // Publication time in Unix milliseconds, when the source knows it
+    #[serde(default)]
+    pub published_at: Option<i64>,
+    // Likes, comments or similar counts from the source, used for ranking
+    #[serde(default)]
+    pub engagement: Option<u32>,
+    // Pixel size of the image when the source knows it, so the row can
+    // reserve its space before it loads
+    #[serde(default)]
+    pub image_width: Option<u32>,
+    #[serde(default)]
+    pub image_height: Option<u32>,
+    // Shape of the image as width over height, for sources that know it
+    // without the pixel size
+    #[serde(default)]
+    pub image_aspect_ratio: Option<f64>,
+    // Tried in order when the image fails, e.g. another codec or a placeholder
+    #[serde(default)]
+    pub image_fallbacks: Vec<String>,
+    // Description of the main image given by the source, for screen readers
+    #[serde(default)]
+    pub image_alt: Option<String>,
+    // Names of the sources the item arrived from, when it was aggregated
+    #[serde(default)]
+    pub sources: Vec<String>,
+    // Near-duplicates from other sources collapsed into this item
+    #[serde(default)]
+    pub also_shared: Vec<SharedCopy>,
+    // Labels added by item processors when the item was loaded
+    #[serde(default)]
+    pub annotations: Vec<Annotation>,
+    // Content warnings given by the source, e.g. a post marked sensitive
+    #[serde(default)]
+    pub flags: Vec<ContentFlag>,
+}
+
+// Why an item is blurred until the reader reveals it
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentFlag {
+    Sensitive,
+    Spam,
+}
+
+impl ContentFlag {
+    pub fn label(self) -> &'static str {
+        match self {
+            ContentFlag::Sensitive => "Sensitive content",
+            ContentFlag::Spam => "Possible spam",
+        }
+    }
+}
+
+// A near-duplicate of an item, shown under it instead of as its own row
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SharedCopy {
+    pub id: String,
+    pub content: String,
+    pub link: Option<String>,
+    pub sources: Vec<String>,
+}
+
+/// A label attached to an item by a processor
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Annotation {
+    pub label: String,
+    /// Longer explanation, shown as the label's tooltip
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Name of the processor that added it, set by the host
+    #[serde(default)]
+    pub processor: String,
+    /// Marks the item sensitive or spam, so it is blurred until revealed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flag: Option<ContentFlag>,
+}
+
+impl VirtualFeedItem {
+    // Item showing the image at `image_url`, an asset or remote URL valid
+    // for the platform
+    pub fn new(id: String, content: String, image_url: impl Into<String>) -> Self {
+        Self {
+            id,
+            content,
+            image_url: image_url.into(),
+            link: None,
+            author: None,
+            published_at: None,
+            engagement: None,
+            image_width: None,
+            image_height: None,
+            image_aspect_ratio: None,
+            image_fallbacks: Vec::new(),
+            image_alt: None,
+            sources: Vec::new(),
+            also_shared: Vec::new(),
+            annotations: Vec::new(),
+            flags: Vec::new(),
+        }
+    }
+    
+    pub fn with_link(mut self, link: impl Into<String>) -> Self {
+        self.link = Some(link.into());
+        self
+    }
+    
+    pub fn with_author(mut self, author: impl Into<String>) -> Self {
+        self.author = Some(author.into());
+        self
+    }
+    
+    pub fn with_published_at(mut self, published_at: i64) -> Self {
+        self.published_at = Some(published_at);
+        self
+    }
+    
+    pub fn with_engagement(mut self, engagement: u32) -> Self {
+        self.engagement = Some(engagement);
+        self
+    }
+    
+    // Add an image URL to try after the ones already given
+    pub fn with_fallback(mut self, url: impl Into<String>) -> Self {
+        self.image_fallbacks.push(url.into());
+        self
+    }
+    
+    // The main image followed by its fallbacks, in the order they are tried
+    pub fn image_candidates(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.image_url.as_str()).chain(self.image_fallbacks.iter().map(String::as_str))
+    }
+    
+    pub fn with_flag(mut self, flag: ContentFlag) -> Self {
+        if !self.flags.contains(&flag) {
+            self.flags.push(flag);
+        }
+        self
+    }
+    
+    // Flags from the source followed by those added by processors
+    pub fn content_flags(&self) -> impl Iterator<Item = ContentFlag> + '_ {
+        self.flags
+            .iter()
+            .copied()
+            .chain(self.annotations.iter().filter_map(|annotation| annotation.flag))
+    }
+    
+    pub fn with_image_size(mut self, width: u32, height: u32) -> Self {
+        self.image_width = Some(width);
+        self.image_height = Some(height);
+        self
+    }
+    
+    // Image size from the item model, if the source provided both dimensions
+    pub fn image_size(&self) -> Option<Dimensions> {
+        self.image_width.zip(self.image_height).filter(|(w, h)| *w > 0 && *h > 0)
+    }
+    
+    pub fn with_image_aspect_ratio(mut self, ratio: f64) -> Self {
+        self.image_aspect_ratio = Some(ratio);
+        self
+    }
+    
+    // Width over height of the image, from its declared size or else its
+    // declared ratio
+    pub fn image_aspect(&self) -> Option<f64> {
+        self.image_size()
+            .map(|(width, height)| width as f64 / height as f64)
+            .or(self.image_aspect_ratio)
+            .filter(|ratio| ratio.is_finite() && *ratio > 0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item() -> VirtualFeedItem {
+        VirtualFeedItem::new("1".to_string(), "Hello".to_string(), "https://example.com/a.png")
+    }
+
+    #[test]
+    fn image_aspect_prefers_the_pixel_size() {
+        assert_eq!(item().image_aspect(), None);
+        assert_eq!(item().with_image_aspect_ratio(1.5).image_aspect(), Some(1.5));
+        assert_eq!(item().with_image_aspect_ratio(1.5).with_image_size(400, 200).image_aspect(), Some(2.0));
+        // A zero dimension isn't a size, and a broken ratio isn't a shape
+        assert_eq!(item().with_image_size(400, 0).image_size(), None);
+        assert_eq!(item().with_image_aspect_ratio(f64::NAN).image_aspect(), None);
+    }
+
+    #[test]
+    fn flags_are_kept_once_and_joined_by_annotations() {
+        let mut item = item().with_flag(ContentFlag::Sensitive).with_flag(ContentFlag::Sensitive);
+        assert_eq!(item.flags, vec![ContentFlag::Sensitive]);
+
+        item.annotations.push(Annotation {
+            label: "Spam?".to_string(),
+            detail: None,
+            processor: "spam".to_string(),
+            flag: Some(ContentFlag::Spam),
+        });
+        let flags: Vec<ContentFlag> = item.content_flags().collect();
+        assert_eq!(flags, vec![ContentFlag::Sensitive, ContentFlag::Spam]);
+    }
+
+    #[test]
+    fn image_candidates_follow_the_fallbacks() {
+        let item = item().with_fallback("b.png").with_fallback("c.png");
+        let candidates: Vec<&str> = item.image_candidates().collect();
+        assert_eq!(candidates, vec!["https://example.com/a.png", "b.png", "c.png"]);
+    }
+
+    #[test]
+    fn older_documents_deserialize_with_defaults() {
+        let item: VirtualFeedItem =
+            serde_json::from_str(r#"{"id":"1","content":"Hello","image_url":"a.png","link":null}"#).unwrap();
+        assert_eq!(item.author, None);
+        assert!(item.flags.is_empty() && item.annotations.is_empty());
+    }
+}
@@ -0,0 +1,9 @@
+//! Data model of Dioxus Feed, shared by every layer of the app.
+//!
+//! Sources produce [`VirtualFeedItem`]s, the store holds them, persistence
+//! writes them to disk and the components render them. The model only knows
+//! URLs: bundled images are resolved to platform asset URLs by the app.
+
+pub mod item;
+
+pub use item::{Annotation, ContentFlag, Dimensions, Sequence, SharedCopy, VirtualFeedItem};
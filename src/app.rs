@@ -0,0 +1,191 @@
+//! Root component of the feed app, with the contexts every feature reads.
+
+use dioxus::prelude::*;
+use crate::auth::AuthManager;
+use crate::clock::{AcceleratedClock, ClockHandle, DeterministicClock};
+use crate::commands::CommandRegistry;
+use crate::ingest::{use_inbox_pump, Inbox};
+use crate::components::feed_tabs::FeedTabs;
+use crate::components::onboarding::Onboarding;
+use crate::components::profile_switcher::ProfileSwitcher;
+use crate::components::scroll_fallback::ScrollDiagnostics;
+use crate::components::snackbar::{Snackbar, SnackbarHost};
+use crate::gallery::{Example, Gallery};
+use crate::persistence::alerts::Alerts;
+use crate::persistence::bookmarks::Bookmarks;
+use crate::persistence::captions::CaptionCache;
+use crate::persistence::filters::ContentFilters;
+use crate::persistence::gating::ContentGating;
+use crate::persistence::pane_layouts::PaneLayouts;
+use crate::persistence::polling::PollingSettings;
+use crate::persistence::privacy::PrivacySettings;
+use crate::persistence::read_state::ReadState;
+use crate::persistence::reading_width::ReadingWidthSettings;
+use crate::persistence::row_heights::RowHeightCache;
+use crate::persistence::scroll_positions::ScrollPositions;
+use crate::persistence::search_index::SearchIndex;
+use crate::persistence::seen_stories::SeenStories;
+use crate::persistence::setup::SetupConfig;
+use crate::persistence::shortcuts::ShortcutMap;
+use crate::persistence::subscriptions::Subscriptions;
+use crate::persistence::summarizer::SummarizerSettings;
+use crate::persistence::theme::{Theme, ThemeSettings};
+use crate::processors::Processors;
+#[cfg(feature = "desktop")]
+use crate::protocol::audit::AuditLog;
+#[cfg(feature = "desktop")]
+use crate::protocol::myprotocol::{register_myprotocol_handler, ProtocolPolicy};
+use crate::sources::health::SourceHealth;
+use crate::startup::{use_first_paint, StartupTrace};
+use crate::work::WorkProgress;
+use crate::{e2e, MAIN_CSS};
+
+// Colors are set inline throughout, so the dark theme inverts the whole page
+// and turns media back. Filtering the root element keeps fixed overlays
+// positioned against the viewport.
+const DARK_THEME_CSS: &str = "
+    html { background: white; filter: invert(1) hue-rotate(180deg); }
+    img, video, picture, canvas { filter: invert(1) hue-rotate(180deg); }
+";
+
+#[component]
+pub fn App() -> Element {
+    // First, so documents failing to load below can be reported
+    use_context_provider(Snackbar::new);
+    let mut startup = use_context_provider(StartupTrace::new);
+    use_first_paint();
+    use_context_provider(WorkProgress::new);
+    use_context_provider(ScrollDiagnostics::new);
+    let setup = use_context_provider(SetupConfig::load);
+    // Bundled assets, plus the folders granted under Settings or on first run
+    #[cfg(feature = "desktop")]
+    let policy = use_context_provider(|| {
+        let mut directories = vec!["assets".to_string()];
+        directories.extend(setup.media_directories());
+        ProtocolPolicy::new(directories)
+    });
+    use_context_provider(Bookmarks::load);
+    use_context_provider(Alerts::load);
+    use_context_provider(SeenStories::load);
+    use_context_provider(ReadState::load);
+    use_context_provider(ScrollPositions::load);
+    use_context_provider(RowHeightCache::load);
+    use_context_provider(PaneLayouts::load);
+    use_context_provider(SearchIndex::load);
+    use_context_provider(Subscriptions::load);
+    use_context_provider(PrivacySettings::load);
+    use_context_provider(PollingSettings::load);
+    use_context_provider(SummarizerSettings::load);
+    use_context_provider(CaptionCache::load);
+    use_context_provider(ContentGating::load);
+    use_context_provider(ContentFilters::load);
+    use_context_provider(ShortcutMap::load);
+    use_context_provider(CommandRegistry::new);
+    let theme = use_context_provider(ThemeSettings::load);
+    use_context_provider(ReadingWidthSettings::load);
+    use_context_provider(AuthManager::load);
+    use_context_provider(SourceHealth::new);
+    use_context_provider(Processors::load);
+    // End-to-end runs get timestamps independent of the launch time, and
+    // soak runs simulate hours in minutes
+    let soak = use_hook(e2e::soak::soak_duration);
+    use_context_provider(|| {
+        if soak.is_some() {
+            ClockHandle::new(AcceleratedClock::new(e2e::soak::SOAK_SPEEDUP, chrono::DateTime::UNIX_EPOCH))
+        } else if e2e::enabled() {
+            ClockHandle::new(DeterministicClock::new(chrono::DateTime::UNIX_EPOCH))
+        } else {
+            ClockHandle::default()
+        }
+    });
+    use_context_provider(Inbox::new);
+    let integrations = use_inbox_pump();
+    use_context_provider(|| integrations);
+    use_hook(|| startup.mark("contexts loaded"));
+    let example = use_hook(Example::from_args);
+
+    // Registered after the first paint. Images wait for it too, so nothing
+    // asks for the protocol before it is served.
+    #[cfg(feature = "desktop")]
+    let asset_protocol = startup.painted().then(|| rsx! { AssetProtocol { policy } });
+    #[cfg(not(feature = "desktop"))]
+    let asset_protocol: Option<Element> = None;
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: MAIN_CSS }
+        if theme.theme() == Theme::Dark {
+            document::Style { {DARK_THEME_CSS} }
+        }
+        
+        div {
+            style: "
+            	font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif;
+                box-sizing: border-box;
+                margin: 0;
+                padding: 20px;
+                background: #f8fafc;
+                min-height: 100vh;
+                display: flex;
+                justify-content: center;
+                align-items: center;
+            ",
+
+            div {
+                style: "
+                    max-width: 800px;
+                    width: 100%;
+                    background: white;
+                    border-radius: 8px;
+                    border: 1px solid #e2e8f0;
+                    overflow: hidden;
+                    padding: 20px;
+                    box-sizing: border-box;
+                ",
+                
+                header {
+                    class: "feed-header",
+                    style: "display: flex; align-items: center; justify-content: space-between; gap: 12px;",
+                    div {
+                        h1 {
+                            class: "feed-title",
+                            "Feed"
+                        }
+                        p {
+                            class: "feed-desc",
+                            "Latest updates"
+                        }
+                    }
+                    ProfileSwitcher {}
+                }
+                
+                // Soak runs and named examples replace the feeds;
+                // end-to-end runs start from the feeds
+                if let Some(duration) = soak {
+                    e2e::soak::SoakRunner { duration }
+                } else if let Some(example) = example {
+                    Gallery { initial: example }
+                } else if setup.is_completed() || e2e::enabled() {
+                    FeedTabs {}
+                } else {
+                    Onboarding {}
+                }
+                
+                if e2e::enabled() {
+                    e2e::E2eRunner {}
+                }
+            }
+        }
+
+        SnackbarHost {}
+        {asset_protocol}
+    }
+}
+
+// Serves `myprotocol` requests; mounted after the first paint
+#[cfg(feature = "desktop")]
+#[component]
+fn AssetProtocol(policy: ProtocolPolicy) -> Element {
+    register_myprotocol_handler(policy, use_hook(AuditLog::from_env));
+    rsx! {}
+}
+
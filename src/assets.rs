@@ -0,0 +1,581 @@
+//! Platform-specific URLs for app assets and remote media.
+//!
+//! The desktop build serves files through the `myprotocol` asset handler.
+//! Browser builds have no custom protocols, so the same assets are requested
+//! over plain HTTP from the server hosting the app, and remote images are
+//! loaded directly.
+//!
+//! Asset URLs are built with [`ProtocolUrl::builder`], which percent-encodes
+//! every path segment. Components reference images through [`MediaSrc`],
+//! which additionally checks the target is an image. Items showing one of
+//! the bundled images are built through [`BundledImages`].
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use dioxus_feed_core::VirtualFeedItem;
+
+// URL of an asset at an already encoded path relative to the app directory
+fn asset_url(encoded_path: &str) -> String {
+    if cfg!(feature = "desktop") {
+        format!("myprotocol/{}", encoded_path)
+    } else {
+        format!("/{}", encoded_path)
+    }
+}
+
+/// URL for displaying a remote image found in fetched content
+#[cfg(feature = "desktop")]
+pub fn remote_media_url(url: &str) -> String {
+    crate::protocol::myprotocol::remote_proxy_url(url)
+}
+
+/// URL for displaying a remote image found in fetched content
+#[cfg(not(feature = "desktop"))]
+pub fn remote_media_url(url: &str) -> String {
+    url.to_string()
+}
+
+/// The http(s) or `data:` URL a displayed image was loaded from, unwrapping
+/// the remote proxy; `None` for bundled assets
+pub fn media_origin(src: &str) -> Option<String> {
+    if src.starts_with("http://") || src.starts_with("https://") || src.starts_with("data:image/") {
+        return Some(src.to_string());
+    }
+    #[cfg(feature = "desktop")]
+    if let Some(url) = crate::protocol::myprotocol::remote_proxy_target(src) {
+        return Some(url);
+    }
+    None
+}
+
+// Append transform parameters as a query string
+fn write_transform_params(
+    f: &mut std::fmt::Formatter<'_>,
+    url: &str,
+    width: Option<u32>,
+    height: Option<u32>,
+) -> std::fmt::Result {
+    let mut separator = if url.contains('?') { '&' } else { '?' };
+    for (name, value) in [("w", width), ("h", height)] {
+        if let Some(value) = value {
+            write!(f, "{}{}={}", separator, name, value)?;
+            separator = '&';
+        }
+    }
+    Ok(())
+}
+
+/// Custom error type for protocol URLs
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProtocolUrlError {
+    MissingFile,
+    /// Empty, `.` or `..` segments, or a file name containing `/`
+    InvalidSegment(String),
+}
+
+impl std::fmt::Display for ProtocolUrlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProtocolUrlError::MissingFile => write!(f, "No file name given"),
+            ProtocolUrlError::InvalidSegment(segment) => write!(f, "Invalid path segment: {:?}", segment),
+        }
+    }
+}
+
+/// URL of a file served through the asset protocol, relative to the app
+/// directory. This is the sanctioned way to reference protocol assets:
+///
+/// ```ignore
+/// let url = ProtocolUrl::builder()
+///     .dir("assets/images")
+///     .file("my photo #1.png")
+///     .width(240)
+///     .build()?;
+/// // myprotocol/assets/images/my%20photo%20%231.png?w=240
+/// ```
+///
+/// Each segment is percent-encoded, so names with spaces, `#` or non-ASCII
+/// characters survive the round trip through the protocol handler.
+/// Transform parameters are sent as query parameters for handlers that can
+/// resize; handlers without resizing serve the original.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProtocolUrl {
+    segments: Vec<String>,
+    width: Option<u32>,
+    height: Option<u32>,
+}
+
+impl ProtocolUrl {
+    pub fn builder() -> ProtocolUrlBuilder {
+        ProtocolUrlBuilder::default()
+    }
+
+    /// Parse a URL produced by this type's `Display` impl, `None` for any
+    /// other URL
+    pub fn parse(url: &str) -> Option<Self> {
+        let rest = url.strip_prefix(&asset_url(""))?;
+        let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let mut segments = path
+            .split('/')
+            .map(|segment| urlencoding::decode(segment).ok().map(|s| s.into_owned()))
+            .collect::<Option<Vec<_>>>()?;
+        let file = segments.pop()?;
+
+        let mut builder = ProtocolUrlBuilder {
+            dirs: segments,
+            ..Default::default()
+        }
+        .file(&file);
+        for (name, value) in query.split('&').filter_map(|param| param.split_once('=')) {
+            match name {
+                "w" => builder = builder.width(value.parse().ok()?),
+                "h" => builder = builder.height(value.parse().ok()?),
+                _ => {}
+            }
+        }
+        builder.build().ok()
+    }
+
+    /// Path of the file relative to the app directory, before encoding
+    pub fn path(&self) -> String {
+        self.segments.join("/")
+    }
+
+    /// URL of the file's metadata on the `myprotocol` metadata endpoint
+    #[cfg(feature = "desktop")]
+    pub fn meta_url(&self) -> String {
+        use crate::protocol::myprotocol::META_SEGMENT;
+        asset_url(&format!("{}{}", META_SEGMENT, self.encoded_path()))
+    }
+
+    fn encoded_path(&self) -> String {
+        self.segments
+            .iter()
+            .map(|segment| urlencoding::encode(segment).into_owned())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+impl std::fmt::Display for ProtocolUrl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let url = asset_url(&self.encoded_path());
+        write!(f, "{}", url)?;
+        write_transform_params(f, &url, self.width, self.height)
+    }
+}
+
+/// Size, type and (for images) pixel dimensions of a protocol asset, as
+/// returned by the `myprotocol/__meta/<path>` endpoint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileMeta {
+    pub size: u64,
+    pub mime: String,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+}
+
+// Fetches the JSON sent from Rust through the webview, where the protocol lives
+#[cfg(feature = "desktop")]
+const FETCH_META_SCRIPT: &str = r#"
+    const url = await dioxus.recv();
+    const response = await fetch(url);
+    return response.ok ? await response.json() : null;
+"#;
+
+/// Fetch metadata for a protocol asset, `None` if it is unavailable
+#[cfg(feature = "desktop")]
+pub async fn fetch_meta(url: &ProtocolUrl) -> Option<FileMeta> {
+    let eval = document::eval(FETCH_META_SCRIPT);
+    if let Err(e) = eval.send(url.meta_url()) {
+        eprintln!("Failed to request file metadata: {}", e);
+        return None;
+    }
+    match eval.join::<Option<FileMeta>>().await {
+        Ok(meta) => meta,
+        Err(e) => {
+            eprintln!("Failed to fetch file metadata: {}", e);
+            None
+        }
+    }
+}
+
+// Requests the URL again and reports the response status, or 0 when the
+// request itself failed
+const PROBE_STATUS_SCRIPT: &str = r#"
+    const url = await dioxus.recv();
+    try {
+        const response = await fetch(url, { method: 'HEAD', cache: 'no-store' });
+        return response.status;
+    } catch (e) {
+        return 0;
+    }
+"#;
+
+/// HTTP status the asset protocol (or server, in the browser) returns for
+/// `url`, `None` if no response was received.
+///
+/// Image elements don't expose why a load failed; this tells a missing file
+/// apart from a transient error.
+pub async fn probe_status(url: &str) -> Option<u16> {
+    let eval = document::eval(PROBE_STATUS_SCRIPT);
+    if let Err(e) = eval.send(url.to_string()) {
+        eprintln!("Failed to probe media status: {}", e);
+        return None;
+    }
+    match eval.join::<u16>().await {
+        Ok(0) => None,
+        Ok(status) => Some(status),
+        Err(e) => {
+            eprintln!("Failed to probe media status: {}", e);
+            None
+        }
+    }
+}
+
+// Reads each URL sent from Rust into a data URL, preferring cached responses
+const DATA_URLS_SCRIPT: &str = r#"
+    const urls = await dioxus.recv();
+    const result = {};
+    for (const url of urls) {
+        try {
+            const response = await fetch(url, { cache: 'force-cache' });
+            if (!response.ok) { continue; }
+            const blob = await response.blob();
+            result[url] = await new Promise((resolve, reject) => {
+                const reader = new FileReader();
+                reader.onload = () => resolve(reader.result);
+                reader.onerror = reject;
+                reader.readAsDataURL(blob);
+            });
+        } catch (e) {}
+    }
+    return result;
+"#;
+
+/// Data URLs for the images at `urls`, read through the webview so images
+/// already shown come from its cache. Images that fail to load are left out.
+pub async fn fetch_data_urls(urls: Vec<String>) -> HashMap<String, String> {
+    let eval = document::eval(DATA_URLS_SCRIPT);
+    if let Err(e) = eval.send(urls) {
+        eprintln!("Failed to request images: {}", e);
+        return HashMap::new();
+    }
+    eval.join::<HashMap<String, String>>().await.unwrap_or_else(|e| {
+        eprintln!("Failed to read images: {}", e);
+        HashMap::new()
+    })
+}
+
+/// `url` with a parameter that makes the webview fetch it again instead of
+/// reusing a failed response; the protocol handler ignores it
+pub fn reload_url(url: &str, reload: u32) -> String {
+    if reload == 0 {
+        return url.to_string();
+    }
+    let separator = if url.contains('?') { '&' } else { '?' };
+    format!("{}{}reload={}", url, separator, reload)
+}
+
+/// Builder for [`ProtocolUrl`]
+#[derive(Debug, Clone, Default)]
+pub struct ProtocolUrlBuilder {
+    dirs: Vec<String>,
+    file: Option<String>,
+    width: Option<u32>,
+    height: Option<u32>,
+}
+
+impl ProtocolUrlBuilder {
+    /// Directory path, `/`-separated; may be called repeatedly to nest
+    pub fn dir(mut self, dir: &str) -> Self {
+        self.dirs.extend(dir.trim_matches('/').split('/').map(str::to_string));
+        self
+    }
+
+    /// File name inside the directory, used verbatim as a single segment
+    pub fn file(mut self, name: &str) -> Self {
+        self.file = Some(name.to_string());
+        self
+    }
+
+    /// Request the image scaled to `width` pixels
+    pub fn width(mut self, width: u32) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Request the image scaled to `height` pixels
+    pub fn height(mut self, height: u32) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    pub fn build(self) -> Result<ProtocolUrl, ProtocolUrlError> {
+        let file = self.file.ok_or(ProtocolUrlError::MissingFile)?;
+        if file.contains('/') {
+            return Err(ProtocolUrlError::InvalidSegment(file));
+        }
+
+        let mut segments = self.dirs;
+        segments.push(file);
+        if let Some(segment) = segments.iter().find(|segment| matches!(segment.as_str(), "" | "." | "..")) {
+            return Err(ProtocolUrlError::InvalidSegment(segment.clone()));
+        }
+
+        Ok(ProtocolUrl {
+            segments,
+            width: self.width,
+            height: self.height,
+        })
+    }
+}
+
+/// Custom error type for media sources
+#[derive(Debug, Clone, PartialEq)]
+pub enum MediaSrcError {
+    InvalidPath(ProtocolUrlError),
+    /// Files that don't have an image type
+    UnsupportedMedia(String),
+    InvalidUrl(String),
+}
+
+impl std::fmt::Display for MediaSrcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MediaSrcError::InvalidPath(e) => write!(f, "Invalid media path: {}", e),
+            MediaSrcError::UnsupportedMedia(path) => write!(f, "Unsupported media type: {}", path),
+            MediaSrcError::InvalidUrl(msg) => write!(f, "Invalid media URL: {}", msg),
+        }
+    }
+}
+
+impl From<ProtocolUrlError> for MediaSrcError {
+    fn from(e: ProtocolUrlError) -> Self {
+        MediaSrcError::InvalidPath(e)
+    }
+}
+
+/// Source URL of an image, valid for the current platform.
+///
+/// Only built through its constructors, which validate and encode the input,
+/// so the `Display` output can always be used as an `img` `src`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaSrc {
+    url: String,
+    width: Option<u32>,
+    height: Option<u32>,
+}
+
+impl MediaSrc {
+    /// Image served through the asset protocol
+    pub fn protocol(url: ProtocolUrl) -> Result<Self, MediaSrcError> {
+        let path = url.path();
+        let is_image = mime_guess::from_path(&path)
+            .first()
+            .is_some_and(|mime| mime.type_() == mime_guess::mime::IMAGE);
+        if !is_image {
+            return Err(MediaSrcError::UnsupportedMedia(path));
+        }
+        Ok(Self::from_url(url.to_string()))
+    }
+
+    /// Image bundled with the app at `path`, relative to the app directory
+    pub fn asset(path: &str) -> Result<Self, MediaSrcError> {
+        let path = path.trim_start_matches('/');
+        let builder = match path.rsplit_once('/') {
+            Some((dir, file)) => ProtocolUrl::builder().dir(dir).file(file),
+            None => ProtocolUrl::builder().file(path),
+        };
+        Self::protocol(builder.build()?)
+    }
+
+    /// Remote image at an http(s) URL
+    pub fn remote(url: &str) -> Result<Self, MediaSrcError> {
+        let parsed = url::Url::parse(url).map_err(|e| MediaSrcError::InvalidUrl(e.to_string()))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(MediaSrcError::InvalidUrl(format!("unsupported scheme {}", parsed.scheme())));
+        }
+        Ok(Self::from_url(remote_media_url(parsed.as_str())))
+    }
+
+    fn from_url(url: String) -> Self {
+        Self { url, width: None, height: None }
+    }
+
+    /// Request the image scaled to `width` pixels
+    pub fn width(mut self, width: u32) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Request the image scaled to `height` pixels
+    pub fn height(mut self, height: u32) -> Self {
+        self.height = Some(height);
+        self
+    }
+}
+
+impl std::fmt::Display for MediaSrc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.url)?;
+        write_transform_params(f, &self.url, self.width, self.height)
+    }
+}
+
+/// Media source for the asset at `path`, rebuilt only when the path changes
+pub fn use_media_src(path: String) -> Memo<Result<MediaSrc, MediaSrcError>> {
+    use_memo(use_reactive!(|path| MediaSrc::asset(&path)))
+}
+
+/// Constructors of items showing one of the images bundled with the app,
+/// picked from the item's id so an item keeps its image across loads
+pub trait BundledImages {
+    fn new_with_random_image(id: String, content: String) -> Self;
+
+    // Photos fall back to an illustration where the webview can't decode AVIF
+    fn new_with_random_photo(id: String, content: String) -> Self;
+}
+
+impl BundledImages for VirtualFeedItem {
+    fn new_with_random_image(id: String, content: String) -> Self {
+        let image = get_random_image_for_id(&id);
+        Self::new(id, content, item_image_url(image))
+    }
+
+    fn new_with_random_photo(id: String, content: String) -> Self {
+        let image = get_random_photo_for_id(&id);
+        Self::new(id, content, item_image_url(image)).with_fallback(item_image_url(FALLBACK_IMAGE))
+    }
+}
+
+// Directory the item images are served from
+const IMAGES_DIR: &str = "assets/images";
+
+// Image shown when an item's photo can't be displayed
+const FALLBACK_IMAGE: &str = "sample1.svg";
+
+// URL of a bundled item image
+fn item_image_url(image_name: &str) -> String {
+    ProtocolUrl::builder()
+        .dir(IMAGES_DIR)
+        .file(image_name)
+        .build()
+        .map_err(MediaSrcError::from)
+        .and_then(MediaSrc::protocol)
+        .map(|src| src.to_string())
+        .unwrap_or_else(|e| {
+            eprintln!("Invalid item image: {}", e);
+            String::new()
+        })
+}
+
+// Available images for random selection
+const AVAILABLE_IMAGES: &[&str] = &[
+    "sample1.svg",
+    "sample2.svg", 
+    "sample3.svg",
+    "sample4.svg",
+    "sample5.svg",
+    "sample6.svg",
+    "sample7.avif",
+    "sample8.avif",
+    "sample9.avif",
+    "sample10.avif",
+    "sample11.avif",
+    "sample12.avif",
+    "sample13.avif"
+];
+
+// Photos among the available images (the rest are illustrations)
+const PHOTO_EXTENSION: &str = ".avif";
+
+// Random image selector with better uniqueness
+fn get_random_image_for_id(id: &str) -> &'static str {
+    let index = (hash_id(id) as usize) % AVAILABLE_IMAGES.len();
+    AVAILABLE_IMAGES[index]
+}
+
+// Random photo selector, used by media-only feeds
+fn get_random_photo_for_id(id: &str) -> &'static str {
+    let photos: Vec<&'static str> = AVAILABLE_IMAGES
+        .iter()
+        .copied()
+        .filter(|image| image.ends_with(PHOTO_EXTENSION))
+        .collect();
+    let index = (hash_id(id) as usize) % photos.len();
+    photos[index]
+}
+
+fn hash_id(id: &str) -> u64 {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    
+    let mut hasher = DefaultHasher::new();
+    id.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn protocol_urls_encode_each_segment() {
+        let url = ProtocolUrl::builder()
+            .dir("assets/images")
+            .file("my photo #1.png")
+            .width(240)
+            .build()
+            .unwrap();
+        assert_eq!(url.path(), "assets/images/my photo #1.png");
+        assert_eq!(url.to_string(), asset_url("assets/images/my%20photo%20%231.png?w=240"));
+    }
+
+    #[test]
+    fn protocol_urls_round_trip_through_parse() {
+        let url = ProtocolUrl::builder()
+            .dir("/media/")
+            .dir("ünï cödé")
+            .file("a?b&c.jpg")
+            .width(120)
+            .height(80)
+            .build()
+            .unwrap();
+        assert_eq!(ProtocolUrl::parse(&url.to_string()), Some(url));
+        assert_eq!(ProtocolUrl::parse("https://example.com/a.png"), None);
+    }
+
+    #[test]
+    fn protocol_urls_refuse_bad_segments() {
+        assert_eq!(ProtocolUrl::builder().dir("assets").build(), Err(ProtocolUrlError::MissingFile));
+        assert_eq!(
+            ProtocolUrl::builder().dir("assets/../secrets").file("a.png").build(),
+            Err(ProtocolUrlError::InvalidSegment("..".to_string()))
+        );
+        assert_eq!(
+            ProtocolUrl::builder().dir("assets//images").file("a.png").build(),
+            Err(ProtocolUrlError::InvalidSegment(String::new()))
+        );
+        assert_eq!(
+            ProtocolUrl::builder().file("images/a.png").build(),
+            Err(ProtocolUrlError::InvalidSegment("images/a.png".to_string()))
+        );
+    }
+
+    #[test]
+    fn media_sources_must_be_images() {
+        assert!(MediaSrc::asset("assets/images/a.png").is_ok());
+        assert!(matches!(MediaSrc::asset("assets/notes.txt"), Err(MediaSrcError::UnsupportedMedia(_))));
+        assert!(matches!(MediaSrc::remote("ftp://example.com/a.png"), Err(MediaSrcError::InvalidUrl(_))));
+    }
+
+    #[test]
+    fn reload_urls_add_a_parameter() {
+        assert_eq!(reload_url("myprotocol/a.png", 0), "myprotocol/a.png");
+        assert_eq!(reload_url("myprotocol/a.png", 2), "myprotocol/a.png?reload=2");
+        assert_eq!(reload_url("myprotocol/a.png?w=10", 1), "myprotocol/a.png?w=10&reload=1");
+    }
+}
@@ -0,0 +1,66 @@
+//! Token storage in the OS keychain, one entry per account.
+//!
+//! Only desktop builds have a keychain; elsewhere tokens are kept for the
+//! session and accounts have to be connected again after a restart.
+
+use super::{AuthError, TokenSet};
+
+/// Keychain service the entries are stored under
+#[cfg(feature = "desktop")]
+const KEYRING_SERVICE: &str = "dioxus-feed-accounts";
+
+#[cfg(feature = "desktop")]
+fn entry(account_key: &str) -> Result<keyring::Entry, AuthError> {
+    keyring::Entry::new(KEYRING_SERVICE, account_key).map_err(|e| AuthError::Keychain(e.to_string()))
+}
+
+/// Tokens stored for an account, `None` if there are none
+#[cfg(feature = "desktop")]
+pub fn load_tokens(account_key: &str) -> Result<Option<TokenSet>, AuthError> {
+    match entry(account_key)?.get_password() {
+        Ok(json) => serde_json::from_str(&json)
+            .map(Some)
+            .map_err(|e| AuthError::Keychain(e.to_string())),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(AuthError::Keychain(e.to_string())),
+    }
+}
+
+#[cfg(feature = "desktop")]
+pub fn save_tokens(account_key: &str, tokens: &TokenSet) -> Result<(), AuthError> {
+    let json = serde_json::to_string(tokens).map_err(|e| AuthError::Keychain(e.to_string()))?;
+    entry(account_key)?
+        .set_password(&json)
+        .map_err(|e| AuthError::Keychain(e.to_string()))
+}
+
+#[cfg(feature = "desktop")]
+pub fn delete_tokens(account_key: &str) -> Result<(), AuthError> {
+    match entry(account_key)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(AuthError::Keychain(e.to_string())),
+    }
+}
+
+#[cfg(not(feature = "desktop"))]
+thread_local! {
+    static SESSION_TOKENS: std::cell::RefCell<std::collections::HashMap<String, TokenSet>> = Default::default();
+}
+
+/// Tokens stored for an account, `None` if there are none
+#[cfg(not(feature = "desktop"))]
+pub fn load_tokens(account_key: &str) -> Result<Option<TokenSet>, AuthError> {
+    Ok(SESSION_TOKENS.with(|tokens| tokens.borrow().get(account_key).cloned()))
+}
+
+#[cfg(not(feature = "desktop"))]
+pub fn save_tokens(account_key: &str, tokens: &TokenSet) -> Result<(), AuthError> {
+    SESSION_TOKENS.with(|stored| stored.borrow_mut().insert(account_key.to_string(), tokens.clone()));
+    Ok(())
+}
+
+#[cfg(not(feature = "desktop"))]
+pub fn delete_tokens(account_key: &str) -> Result<(), AuthError> {
+    SESSION_TOKENS.with(|tokens| tokens.borrow_mut().remove(account_key));
+    Ok(())
+}
@@ -0,0 +1,343 @@
+//! Accounts on authenticated sources and their OAuth2 tokens.
+//!
+//! Accounts are connected through an authorization code with PKCE (the user
+//! approves in their browser and pastes the code back) or, for providers
+//! offering it, the device flow. Several accounts can be connected per
+//! provider, one of which is active and backs that provider's feed. The
+//! account list and the active accounts are persisted to `accounts.json`;
+//! tokens live in the OS keychain, are refreshed shortly before they expire
+//! and are handed to sources with [`AuthManager::access_token`].
+
+pub mod keychain;
+pub mod oauth;
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::clock::sleep;
+use crate::error::{report, FeedError};
+use crate::persistence::{load_json, save_json};
+use oauth::{DeviceCode, OAuthClient};
+
+/// File name of the persisted account list
+const ACCOUNTS_FILE: &str = "accounts.json";
+
+/// Tokens expiring within this many milliseconds are refreshed before use
+const REFRESH_MARGIN_MS: i64 = 60_000;
+
+/// Custom error type for authentication
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuthError {
+    Network(String),
+    InvalidResponse(String),
+    /// The user or the provider refused the sign-in
+    Denied(String),
+    /// The device flow is still waiting for the user
+    Pending,
+    /// The sign-in request expired before it was approved
+    Expired,
+    Keychain(String),
+    NotConnected(String),
+}
+
+impl std::fmt::Display for AuthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuthError::Network(msg) => write!(f, "Network error: {}", msg),
+            AuthError::InvalidResponse(msg) => write!(f, "Invalid response: {}", msg),
+            AuthError::Denied(msg) => write!(f, "Sign-in refused: {}", msg),
+            AuthError::Pending => write!(f, "Waiting for approval"),
+            AuthError::Expired => write!(f, "Sign-in request expired"),
+            AuthError::Keychain(msg) => write!(f, "Keychain error: {}", msg),
+            AuthError::NotConnected(account) => write!(f, "Account not connected: {}", account),
+        }
+    }
+}
+
+impl std::error::Error for AuthError {}
+
+/// Services accounts can be connected on
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum Provider {
+    Mastodon,
+}
+
+impl Provider {
+    pub const ALL: [Provider; 1] = [Provider::Mastodon];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Provider::Mastodon => "Mastodon",
+        }
+    }
+
+    pub fn id(self) -> &'static str {
+        match self {
+            Provider::Mastodon => "mastodon",
+        }
+    }
+}
+
+/// OAuth2 tokens of one account
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct TokenSet {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    /// Unix milliseconds the access token expires at, `None` if it doesn't
+    pub expires_at: Option<i64>,
+}
+
+impl TokenSet {
+    fn expires_soon(&self, now_ms: i64) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at - REFRESH_MARGIN_MS <= now_ms)
+    }
+}
+
+/// A connected account. Its tokens are kept in the keychain, not here.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Account {
+    pub provider: Provider,
+    /// Base URL of the account's server, e.g. `https://mastodon.social`
+    pub base_url: String,
+    pub username: String,
+    /// Client the tokens were issued to, needed to refresh them
+    pub client: OAuthClient,
+}
+
+impl Account {
+    /// Stable key of the account, also its keychain entry
+    pub fn key(&self) -> String {
+        format!("{}:{}@{}", self.provider.id(), self.username, self.host())
+    }
+
+    /// Handle shown for the account, e.g. `@alice@mastodon.social`
+    pub fn label(&self) -> String {
+        format!("@{}@{}", self.username, self.host())
+    }
+
+    fn host(&self) -> &str {
+        self.base_url.trim_start_matches("https://").trim_start_matches("http://")
+    }
+}
+
+/// A sign-in waiting for the user
+#[derive(Clone, PartialEq, Debug)]
+pub enum PendingAuth {
+    /// The user opens `authorize_url`, approves, and pastes back the code or
+    /// the URL they were sent to
+    Code {
+        provider: Provider,
+        base_url: String,
+        client: OAuthClient,
+        authorize_url: String,
+        verifier: String,
+        state: String,
+    },
+    /// The user enters the device's user code at its verification URI while
+    /// the app polls
+    Device {
+        provider: Provider,
+        base_url: String,
+        client: OAuthClient,
+        device: DeviceCode,
+    },
+}
+
+/// Persisted account list
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+struct AccountsDocument {
+    accounts: Vec<Account>,
+    /// Key of the active account, by provider id
+    #[serde(default)]
+    active: HashMap<String, String>,
+}
+
+fn now_ms() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+/// Connected accounts and their tokens.
+///
+/// Provided once at the app root with `use_context_provider(AuthManager::load)`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct AuthManager {
+    document: Signal<AccountsDocument>,
+    // Tokens read from the keychain this session, by account key
+    tokens: CopyValue<HashMap<String, TokenSet>>,
+}
+
+impl AuthManager {
+    /// Load the account list from the persistence layer
+    pub fn load() -> Self {
+        let document: AccountsDocument = load_json(ACCOUNTS_FILE)
+            .unwrap_or_else(|e| {
+                report(FeedError::Load { what: "accounts", source: e });
+                None
+            })
+            .unwrap_or_default();
+
+        Self {
+            document: Signal::new(document),
+            tokens: CopyValue::new(HashMap::new()),
+        }
+    }
+
+    pub fn accounts(&self) -> Vec<Account> {
+        self.document.read().accounts.clone()
+    }
+
+    pub fn account(&self, key: &str) -> Option<Account> {
+        self.document.peek().accounts.iter().find(|account| account.key() == key).cloned()
+    }
+
+    /// Account backing the feed of `provider`: the one chosen last, or the
+    /// first connected one
+    pub fn active_account(&self, provider: Provider) -> Option<Account> {
+        let document = self.document.read();
+        let chosen = document
+            .active
+            .get(provider.id())
+            .and_then(|key| document.accounts.iter().find(|account| &account.key() == key));
+        chosen
+            .or_else(|| document.accounts.iter().find(|account| account.provider == provider))
+            .cloned()
+    }
+
+    /// Make the account with `key` the active one of its provider
+    pub fn set_active(&mut self, key: &str) {
+        let Some(account) = self.account(key) else {
+            return;
+        };
+        let provider = account.provider.id().to_string();
+        if self.document.peek().active.get(&provider).is_some_and(|active| active == key) {
+            return;
+        }
+        self.document.write().active.insert(provider, key.to_string());
+        self.save();
+    }
+
+    /// Start connecting an account on `instance` of `provider`
+    pub async fn begin(&self, provider: Provider, instance: &str) -> Result<PendingAuth, AuthError> {
+        match provider {
+            Provider::Mastodon => {
+                let base_url = oauth::mastodon_base(instance)?;
+                let client = oauth::register_mastodon_app(&base_url).await?;
+                if client.device_url.is_some() {
+                    let device = oauth::start_device(&client).await?;
+                    return Ok(PendingAuth::Device { provider, base_url, client, device });
+                }
+                let pkce = oauth::pkce();
+                let state = oauth::random_token(16);
+                Ok(PendingAuth::Code {
+                    provider,
+                    authorize_url: oauth::authorize_url(&client, &pkce.challenge, &state)?,
+                    base_url,
+                    client,
+                    verifier: pkce.verifier,
+                    state,
+                })
+            }
+        }
+    }
+
+    /// Finish a code sign-in with what the user pasted back
+    pub async fn complete(&mut self, pending: &PendingAuth, input: &str) -> Result<Account, AuthError> {
+        let PendingAuth::Code { provider, base_url, client, verifier, state, .. } = pending else {
+            return Err(AuthError::InvalidResponse("This sign-in doesn't take a code".to_string()));
+        };
+        let code = oauth::parse_code(input, state)?;
+        let tokens = oauth::exchange_code(client, &code, verifier, now_ms()).await?;
+        self.connect(*provider, base_url, client, tokens).await
+    }
+
+    /// Poll a device sign-in until the user approves or it fails
+    pub async fn wait_for_device(&mut self, pending: &PendingAuth) -> Result<Account, AuthError> {
+        let PendingAuth::Device { provider, base_url, client, device } = pending else {
+            return Err(AuthError::InvalidResponse("This sign-in isn't a device sign-in".to_string()));
+        };
+        loop {
+            sleep(device.poll_interval()).await;
+            match oauth::poll_device(client, device, now_ms()).await {
+                Ok(tokens) => return self.connect(*provider, base_url, client, tokens).await,
+                Err(AuthError::Pending) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    // Look up who the tokens belong to and store the account
+    async fn connect(
+        &mut self,
+        provider: Provider,
+        base_url: &str,
+        client: &OAuthClient,
+        tokens: TokenSet,
+    ) -> Result<Account, AuthError> {
+        let username = match provider {
+            Provider::Mastodon => oauth::verify_mastodon(base_url, &tokens.access_token).await?,
+        };
+        let account = Account {
+            provider,
+            base_url: base_url.to_string(),
+            username,
+            client: client.clone(),
+        };
+        let key = account.key();
+        keychain::save_tokens(&key, &tokens)?;
+        self.tokens.write().insert(key.clone(), tokens);
+
+        // A newly connected account becomes the active one
+        let mut document = self.document.write();
+        match document.accounts.iter_mut().find(|known| known.key() == key) {
+            Some(known) => *known = account.clone(),
+            None => document.accounts.push(account.clone()),
+        }
+        document.active.insert(provider.id().to_string(), key);
+        drop(document);
+        self.save();
+        Ok(account)
+    }
+
+    /// Forget an account and delete its tokens
+    pub fn disconnect(&mut self, key: &str) {
+        let mut document = self.document.write();
+        document.accounts.retain(|account| account.key() != key);
+        document.active.retain(|_, active| active != key);
+        drop(document);
+        self.tokens.write().remove(key);
+        if let Err(e) = keychain::delete_tokens(key) {
+            report(e.into());
+        }
+        self.save();
+    }
+
+    /// Access token for the account with `key`, refreshed first if it is
+    /// about to expire
+    pub async fn access_token(&self, key: &str) -> Result<String, AuthError> {
+        let account = self.account(key).ok_or_else(|| AuthError::NotConnected(key.to_string()))?;
+        let cached = self.tokens.peek().get(key).cloned();
+        let tokens = match cached {
+            Some(tokens) => tokens,
+            None => keychain::load_tokens(key)?.ok_or_else(|| AuthError::NotConnected(key.to_string()))?,
+        };
+        let tokens = match &tokens.refresh_token {
+            Some(refresh_token) if tokens.expires_soon(now_ms()) => {
+                let refreshed = oauth::refresh(&account.client, refresh_token, now_ms()).await?;
+                keychain::save_tokens(key, &refreshed)?;
+                refreshed
+            }
+            _ => tokens,
+        };
+        let access_token = tokens.access_token.clone();
+        let mut cache = self.tokens;
+        cache.write().insert(key.to_string(), tokens);
+        Ok(access_token)
+    }
+
+    fn save(&self) {
+        if let Err(e) = save_json(ACCOUNTS_FILE, &*self.document.peek()) {
+            report(FeedError::Save { what: "accounts", source: e });
+        }
+    }
+}
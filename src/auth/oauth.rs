@@ -0,0 +1,280 @@
+//! OAuth2 requests: authorization code with PKCE, the device flow, token
+//! refresh, and the Mastodon specifics (per-instance app registration and
+//! account lookup).
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use rand::RngCore;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+
+use super::{AuthError, TokenSet};
+
+/// Redirect for apps without a web server: the provider shows the code for
+/// the user to paste back
+pub const OUT_OF_BAND_REDIRECT: &str = "urn:ietf:wg:oauth:2.0:oob";
+
+/// Name the app registers under with providers
+const CLIENT_NAME: &str = "Dioxus Feed";
+
+/// Scopes requested from Mastodon; the home timeline only needs read access
+const MASTODON_SCOPES: &str = "read";
+
+/// Endpoints and client credentials for one provider
+#[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
+pub struct OAuthClient {
+    pub authorize_url: String,
+    pub token_url: String,
+    /// Device authorization endpoint; providers with one sign in through the
+    /// device flow, the others through an authorization code with PKCE
+    pub device_url: Option<String>,
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub scopes: String,
+    pub redirect_uri: String,
+}
+
+/// PKCE verifier and its S256 challenge
+pub struct Pkce {
+    pub verifier: String,
+    pub challenge: String,
+}
+
+/// URL-safe random string from `bytes` random bytes
+pub fn random_token(bytes: usize) -> String {
+    let mut buffer = vec![0u8; bytes];
+    rand::thread_rng().fill_bytes(&mut buffer);
+    URL_SAFE_NO_PAD.encode(buffer)
+}
+
+pub fn pkce() -> Pkce {
+    let verifier = random_token(32);
+    let challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()));
+    Pkce { verifier, challenge }
+}
+
+/// Page the user approves the sign-in on
+pub fn authorize_url(client: &OAuthClient, challenge: &str, state: &str) -> Result<String, AuthError> {
+    let mut url = url::Url::parse(&client.authorize_url).map_err(|e| AuthError::InvalidResponse(e.to_string()))?;
+    url.query_pairs_mut()
+        .append_pair("response_type", "code")
+        .append_pair("client_id", &client.client_id)
+        .append_pair("redirect_uri", &client.redirect_uri)
+        .append_pair("scope", &client.scopes)
+        .append_pair("state", state)
+        .append_pair("code_challenge", challenge)
+        .append_pair("code_challenge_method", "S256");
+    Ok(url.to_string())
+}
+
+/// Authorization code from what the user pasted: the code itself, or the URL
+/// the provider redirected to (whose `state` must match)
+pub fn parse_code(input: &str, state: &str) -> Result<String, AuthError> {
+    let input = input.trim();
+    let Ok(url) = url::Url::parse(input) else {
+        if input.is_empty() {
+            return Err(AuthError::Denied("No code entered".to_string()));
+        }
+        return Ok(input.to_string());
+    };
+    let param = |name: &str| url.query_pairs().find(|(key, _)| key == name).map(|(_, value)| value.into_owned());
+    if let Some(error) = param("error") {
+        return Err(AuthError::Denied(error));
+    }
+    if param("state").is_some_and(|returned| returned != state) {
+        return Err(AuthError::Denied("The sign-in response doesn't match this request".to_string()));
+    }
+    param("code").ok_or_else(|| AuthError::InvalidResponse("No code in the pasted URL".to_string()))
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_in: Option<i64>,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+// Post a form to the token endpoint, mapping OAuth error codes
+async fn token_request(client: &OAuthClient, form: &[(&str, &str)], now_ms: i64) -> Result<TokenSet, AuthError> {
+    let mut request = reqwest::Client::new().post(&client.token_url).form(form);
+    if let Some(secret) = &client.client_secret {
+        request = request.basic_auth(&client.client_id, Some(secret));
+    }
+    let response = request.send().await.map_err(|e| AuthError::Network(e.to_string()))?;
+    let status = response.status();
+    let body = response.text().await.map_err(|e| AuthError::Network(e.to_string()))?;
+    if !status.is_success() {
+        let error = serde_json::from_str::<ErrorResponse>(&body).map(|body| body.error);
+        return Err(match error.as_deref() {
+            Ok("authorization_pending") | Ok("slow_down") => AuthError::Pending,
+            Ok("expired_token") => AuthError::Expired,
+            Ok("invalid_grant") | Ok("access_denied") => AuthError::Denied(error.unwrap_or_default()),
+            _ => AuthError::InvalidResponse(format!("Token endpoint returned {}", status)),
+        });
+    }
+    let token: TokenResponse = serde_json::from_str(&body).map_err(|e| AuthError::InvalidResponse(e.to_string()))?;
+    Ok(TokenSet {
+        access_token: token.access_token,
+        refresh_token: token.refresh_token,
+        expires_at: token.expires_in.map(|seconds| now_ms + seconds * 1000),
+    })
+}
+
+/// Exchange an authorization code for tokens
+pub async fn exchange_code(client: &OAuthClient, code: &str, verifier: &str, now_ms: i64) -> Result<TokenSet, AuthError> {
+    token_request(
+        client,
+        &[
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", &client.redirect_uri),
+            ("client_id", &client.client_id),
+            ("code_verifier", verifier),
+        ],
+        now_ms,
+    )
+    .await
+}
+
+/// Get new tokens with a refresh token; providers that don't rotate refresh
+/// tokens keep the old one
+pub async fn refresh(client: &OAuthClient, refresh_token: &str, now_ms: i64) -> Result<TokenSet, AuthError> {
+    let mut tokens = token_request(
+        client,
+        &[
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token),
+            ("client_id", &client.client_id),
+        ],
+        now_ms,
+    )
+    .await?;
+    tokens.refresh_token.get_or_insert_with(|| refresh_token.to_string());
+    Ok(tokens)
+}
+
+/// A device authorization waiting for the user to enter `user_code`
+#[derive(Clone, PartialEq, Debug, Deserialize)]
+pub struct DeviceCode {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    #[serde(default = "default_device_interval")]
+    pub interval: u64,
+}
+
+fn default_device_interval() -> u64 {
+    5
+}
+
+impl DeviceCode {
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_secs(self.interval.max(1))
+    }
+}
+
+/// Start the device flow
+pub async fn start_device(client: &OAuthClient) -> Result<DeviceCode, AuthError> {
+    let device_url = client
+        .device_url
+        .as_deref()
+        .ok_or_else(|| AuthError::InvalidResponse("Provider has no device flow".to_string()))?;
+    let response = reqwest::Client::new()
+        .post(device_url)
+        .form(&[("client_id", client.client_id.as_str()), ("scope", client.scopes.as_str())])
+        .send()
+        .await
+        .map_err(|e| AuthError::Network(e.to_string()))?;
+    if !response.status().is_success() {
+        return Err(AuthError::InvalidResponse(format!("Device endpoint returned {}", response.status())));
+    }
+    response.json().await.map_err(|e| AuthError::InvalidResponse(e.to_string()))
+}
+
+/// Ask once whether the user approved the device; `AuthError::Pending` until
+/// they have
+pub async fn poll_device(client: &OAuthClient, device: &DeviceCode, now_ms: i64) -> Result<TokenSet, AuthError> {
+    token_request(
+        client,
+        &[
+            ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+            ("device_code", &device.device_code),
+            ("client_id", &client.client_id),
+        ],
+        now_ms,
+    )
+    .await
+}
+
+/// Base URL of a Mastodon instance from what the user typed
+pub fn mastodon_base(instance: &str) -> Result<String, AuthError> {
+    let instance = instance.trim().trim_end_matches('/');
+    let with_scheme = if instance.contains("://") {
+        instance.to_string()
+    } else {
+        format!("https://{}", instance)
+    };
+    let url = url::Url::parse(&with_scheme).map_err(|e| AuthError::InvalidResponse(e.to_string()))?;
+    let host = url.host_str().ok_or_else(|| AuthError::InvalidResponse(format!("No host in {}", instance)))?;
+    Ok(format!("https://{}", host))
+}
+
+#[derive(Deserialize)]
+struct RegisteredApp {
+    client_id: String,
+    client_secret: Option<String>,
+}
+
+/// Register the app with a Mastodon instance, which issues client
+/// credentials per instance
+pub async fn register_mastodon_app(base: &str) -> Result<OAuthClient, AuthError> {
+    let response = reqwest::Client::new()
+        .post(format!("{}/api/v1/apps", base))
+        .form(&[
+            ("client_name", CLIENT_NAME),
+            ("redirect_uris", OUT_OF_BAND_REDIRECT),
+            ("scopes", MASTODON_SCOPES),
+        ])
+        .send()
+        .await
+        .map_err(|e| AuthError::Network(e.to_string()))?;
+    if !response.status().is_success() {
+        return Err(AuthError::InvalidResponse(format!("{} returned {}", base, response.status())));
+    }
+    let app: RegisteredApp = response.json().await.map_err(|e| AuthError::InvalidResponse(e.to_string()))?;
+    Ok(OAuthClient {
+        authorize_url: format!("{}/oauth/authorize", base),
+        token_url: format!("{}/oauth/token", base),
+        device_url: None,
+        client_id: app.client_id,
+        client_secret: app.client_secret,
+        scopes: MASTODON_SCOPES.to_string(),
+        redirect_uri: OUT_OF_BAND_REDIRECT.to_string(),
+    })
+}
+
+#[derive(Deserialize)]
+struct VerifiedAccount {
+    username: String,
+}
+
+/// Username of the account an access token belongs to
+pub async fn verify_mastodon(base: &str, access_token: &str) -> Result<String, AuthError> {
+    let response = reqwest::Client::new()
+        .get(format!("{}/api/v1/accounts/verify_credentials", base))
+        .bearer_auth(access_token)
+        .send()
+        .await
+        .map_err(|e| AuthError::Network(e.to_string()))?;
+    if !response.status().is_success() {
+        return Err(AuthError::Denied(format!("{} returned {}", base, response.status())));
+    }
+    let account: VerifiedAccount = response.json().await.map_err(|e| AuthError::InvalidResponse(e.to_string()))?;
+    Ok(account.username)
+}
@@ -0,0 +1,123 @@
+//! Companion CLI controlling a running Dioxus Feed over its control channel.
+//!
+//! ```text
+//! dioxus-feed-ctl post "text" [--image PATH] [--author NAME] [--link URL]
+//! dioxus-feed-ctl refresh [FEED]
+//! dioxus-feed-ctl unread [FEED]
+//! ```
+//!
+//! Feeds are named by their tab key (`home`, `media`, `inbox`,
+//! `folder:<title>`, …); without one, the feed on screen is used.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use std::io::{BufRead, BufReader, Write};
+use std::process::ExitCode;
+
+use dioxus_feed_control::{control_path, ControlRequest, ControlResponse, PushedItem};
+
+/// Largest image accepted by `post --image`, before encoding
+const MAX_IMAGE_BYTES: u64 = 2 * 1024 * 1024;
+
+const USAGE: &str = "usage:
+  dioxus-feed-ctl post \"text\" [--image PATH] [--author NAME] [--link URL]
+  dioxus-feed-ctl refresh [FEED]
+  dioxus-feed-ctl unread [FEED]";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let request = match parse_args(&args) {
+        Ok(request) => request,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match send(&request) {
+        Ok(ControlResponse::Ok { message }) => {
+            println!("{}", message);
+            ExitCode::SUCCESS
+        }
+        Ok(ControlResponse::UnreadCount { count, .. }) => {
+            println!("{}", count);
+            ExitCode::SUCCESS
+        }
+        Ok(ControlResponse::Error { message }) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            eprintln!("Couldn't reach Dioxus Feed at {}: {}", control_path().display(), e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<ControlRequest, String> {
+    let (command, rest) = args.split_first().ok_or("No command given")?;
+    match command.as_str() {
+        "post" => {
+            let mut item = PushedItem { text: String::new(), author: None, link: None, image: None };
+            let mut rest = rest.iter();
+            while let Some(arg) = rest.next() {
+                let mut value = || rest.next().cloned().ok_or(format!("{} needs a value", arg));
+                match arg.as_str() {
+                    "--image" => item.image = Some(image_data_url(&value()?)?),
+                    "--author" => item.author = Some(value()?),
+                    "--link" => item.link = Some(value()?),
+                    flag if flag.starts_with("--") => return Err(format!("Unknown option {}", flag)),
+                    text if item.text.is_empty() => item.text = text.to_string(),
+                    extra => return Err(format!("Unexpected argument {}", extra)),
+                }
+            }
+            if item.text.trim().is_empty() {
+                return Err("post needs the item text".to_string());
+            }
+            Ok(ControlRequest::Post { item })
+        }
+        "refresh" => Ok(ControlRequest::Refresh { feed: feed_arg(rest)? }),
+        "unread" => Ok(ControlRequest::UnreadCount { feed: feed_arg(rest)? }),
+        other => Err(format!("Unknown command {}", other)),
+    }
+}
+
+fn feed_arg(rest: &[String]) -> Result<Option<String>, String> {
+    match rest {
+        [] => Ok(None),
+        [feed] => Ok(Some(feed.clone())),
+        _ => Err("Only one feed can be given".to_string()),
+    }
+}
+
+// Inline a local image, since the app can't read arbitrary paths
+fn image_data_url(path: &str) -> Result<String, String> {
+    let mime = mime_guess::from_path(path).first_or_octet_stream();
+    if mime.type_() != mime_guess::mime::IMAGE {
+        return Err(format!("{} isn't an image", path));
+    }
+    let size = std::fs::metadata(path).map_err(|e| format!("Couldn't read {}: {}", path, e))?.len();
+    if size > MAX_IMAGE_BYTES {
+        return Err(format!("{} is larger than {} bytes", path, MAX_IMAGE_BYTES));
+    }
+    let bytes = std::fs::read(path).map_err(|e| format!("Couldn't read {}: {}", path, e))?;
+    Ok(format!("data:{};base64,{}", mime, STANDARD.encode(bytes)))
+}
+
+// Send one request and read the response line
+fn send(request: &ControlRequest) -> std::io::Result<ControlResponse> {
+    let line = serde_json::to_string(request).map_err(std::io::Error::other)?;
+
+    #[cfg(unix)]
+    let mut stream = std::os::unix::net::UnixStream::connect(control_path())?;
+    #[cfg(windows)]
+    let mut stream = std::fs::OpenOptions::new().read(true).write(true).open(control_path())?;
+    #[cfg(not(any(unix, windows)))]
+    return Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "no control channel on this platform"));
+
+    writeln!(stream, "{}", line)?;
+    stream.flush()?;
+    let mut response = String::new();
+    BufReader::new(stream).read_line(&mut response)?;
+    serde_json::from_str(&response).map_err(std::io::Error::other)
+}
@@ -0,0 +1,93 @@
+//! Reference captioner backed by a vision model behind an OpenAI-compatible
+//! chat completions endpoint, the same kind of endpoint summaries use.
+
+use serde::Deserialize;
+use serde_json::json;
+
+use super::{CaptionError, CaptionFuture, Captioner};
+use crate::summarizer::http::API_KEY_ENV_VAR;
+
+/// Instructions sent with the image
+const PROMPT: &str = "Write alt text for this image: one sentence describing what it shows, \
+without starting with \"Image of\".";
+
+/// Longest caption kept
+const MAX_CAPTION_CHARS: usize = 300;
+
+/// Captioner posting to `endpoint` with the vision model `model`
+pub struct HttpCaptioner {
+    endpoint: String,
+    model: String,
+    api_key: Option<String>,
+}
+
+impl HttpCaptioner {
+    /// Captioner for `endpoint`, authenticated with the summarizer's key from
+    /// the environment when one is set
+    pub fn new(endpoint: impl Into<String>, model: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            model: model.into(),
+            api_key: std::env::var(API_KEY_ENV_VAR).ok().filter(|key| !key.is_empty()),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Completion {
+    choices: Vec<Choice>,
+}
+
+#[derive(Deserialize)]
+struct Choice {
+    message: Message,
+}
+
+#[derive(Deserialize)]
+struct Message {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+impl Captioner for HttpCaptioner {
+    fn caption(&self, image_url: String) -> CaptionFuture {
+        let endpoint = self.endpoint.clone();
+        let body = json!({
+            "model": self.model,
+            "messages": [{
+                "role": "user",
+                "content": [
+                    { "type": "text", "text": PROMPT },
+                    { "type": "image_url", "image_url": { "url": image_url } },
+                ],
+            }],
+        });
+        let api_key = self.api_key.clone();
+
+        Box::pin(async move {
+            let mut request = reqwest::Client::new().post(&endpoint).json(&body);
+            if let Some(api_key) = api_key {
+                request = request.bearer_auth(api_key);
+            }
+            let response = request
+                .send()
+                .await
+                .map_err(|e| CaptionError::Network(e.to_string()))?;
+            if !response.status().is_success() {
+                return Err(CaptionError::InvalidResponse(format!("HTTP {}", response.status())));
+            }
+            let completion: Completion = response
+                .json()
+                .await
+                .map_err(|e| CaptionError::InvalidResponse(e.to_string()))?;
+
+            let caption = completion
+                .choices
+                .into_iter()
+                .find_map(|choice| choice.message.content)
+                .map(|caption| caption.trim().trim_matches('"').chars().take(MAX_CAPTION_CHARS).collect::<String>())
+                .filter(|caption| !caption.is_empty());
+            caption.ok_or_else(|| CaptionError::InvalidResponse("empty caption".to_string()))
+        })
+    }
+}
@@ -0,0 +1,138 @@
+//! Generated alt text for images their source didn't describe.
+//!
+//! Rows ask [`use_alt_text`] for the alt text of the image they show. The
+//! source's description wins; otherwise a [`Captioner`] is asked once the
+//! image has loaded, and its caption is cached under the image's media hash
+//! (see [`crate::persistence::captions`]) so it is generated only once.
+//! [`NoopCaptioner`] is used while captions are off; [`http::HttpCaptioner`]
+//! asks an OpenAI-compatible vision model.
+
+pub mod http;
+
+use dioxus::prelude::*;
+use sha2::{Digest, Sha256};
+use std::future::Future;
+use std::ops::Deref;
+use std::pin::Pin;
+use std::rc::Rc;
+
+use crate::assets::media_origin;
+use crate::persistence::captions::CaptionCache;
+use crate::persistence::summarizer::SummarizerSettings;
+
+/// Boxed future returned by captioners, resolving to the alt text
+pub type CaptionFuture = Pin<Box<dyn Future<Output = Result<String, CaptionError>>>>;
+
+/// Custom error type for captioners
+#[derive(Debug, Clone, PartialEq)]
+pub enum CaptionError {
+    /// No captioner is configured
+    Unavailable,
+    Network(String),
+    InvalidResponse(String),
+}
+
+impl std::fmt::Display for CaptionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CaptionError::Unavailable => write!(f, "Captions are turned off"),
+            CaptionError::Network(msg) => write!(f, "Network error: {}", msg),
+            CaptionError::InvalidResponse(msg) => write!(f, "Invalid response: {}", msg),
+        }
+    }
+}
+
+/// Something that describes images
+pub trait Captioner {
+    /// Alt text for the image at `image_url`, an http(s) or `data:` URL
+    fn caption(&self, image_url: String) -> CaptionFuture;
+
+    /// Whether captions can be requested at all
+    fn is_available(&self) -> bool {
+        true
+    }
+}
+
+/// Captioner used while captions are off
+pub struct NoopCaptioner;
+
+impl Captioner for NoopCaptioner {
+    fn caption(&self, _image_url: String) -> CaptionFuture {
+        Box::pin(async { Err(CaptionError::Unavailable) })
+    }
+
+    fn is_available(&self) -> bool {
+        false
+    }
+}
+
+/// Shared handle to a captioner
+#[derive(Clone)]
+pub struct CaptionerHandle(Rc<dyn Captioner>);
+
+impl CaptionerHandle {
+    pub fn new(captioner: impl Captioner + 'static) -> Self {
+        Self(Rc::new(captioner))
+    }
+}
+
+impl Default for CaptionerHandle {
+    fn default() -> Self {
+        Self::new(NoopCaptioner)
+    }
+}
+
+impl Deref for CaptionerHandle {
+    type Target = dyn Captioner;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+/// Key captions are cached under: the hex SHA-256 of the image's origin URL,
+/// so the same image shown by several items or through the proxy shares one
+pub fn media_hash(origin: &str) -> String {
+    Sha256::digest(origin.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Alt text for the image at `image_src`: `source_alt` when the source gave
+/// one, otherwise a cached caption. A caption is requested once `wanted`
+/// (the image has loaded), at most once per image and session. Bundled
+/// images are never captioned.
+pub fn use_alt_text(image_src: String, source_alt: Option<String>, wanted: bool) -> Option<String> {
+    let cache = try_use_context::<CaptionCache>();
+    let settings = try_use_context::<SummarizerSettings>();
+    let origin = media_origin(&image_src);
+    let source_alt = source_alt.filter(|alt| !alt.trim().is_empty());
+    let cached = match (&source_alt, cache, &origin) {
+        (None, Some(cache), Some(origin)) => cache.get(&media_hash(origin)),
+        _ => None,
+    };
+    let needs_caption = wanted && source_alt.is_none() && cached.is_none() && origin.is_some();
+
+    use_effect(use_reactive!(|(origin, needs_caption)| {
+        let (Some(mut cache), Some(origin)) = (cache, origin) else {
+            return;
+        };
+        let captioner = settings.map(|settings| settings.captioner()).unwrap_or_default();
+        if !needs_caption || !captioner.is_available() {
+            return;
+        }
+        let hash = media_hash(&origin);
+        if !cache.begin(&hash) {
+            return;
+        }
+        spawn(async move {
+            match captioner.caption(origin).await {
+                Ok(caption) => cache.insert(&hash, caption),
+                Err(e) => eprintln!("Failed to caption image: {}", e),
+            }
+        });
+    }));
+
+    source_alt.or(cached)
+}
@@ -0,0 +1,253 @@
+#[cfg(feature = "full")]
+use chrono::{DateTime, Utc};
+use dioxus::prelude::*;
+use std::cell::Cell;
+use std::future::Future;
+use std::ops::Deref;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// Monotonic instant of the platform timer. Native builds use tokio's, so
+/// paused time applies; the browser has no tokio timer and uses `web-time`.
+#[cfg(not(target_arch = "wasm32"))]
+pub use tokio::time::Instant;
+#[cfg(target_arch = "wasm32")]
+pub use web_time::Instant;
+
+/// Boxed future returned by `Clock::sleep`
+pub type SleepFuture = Pin<Box<dyn Future<Output = ()>>>;
+
+/// Sleep on the platform timer: tokio natively, `setTimeout` in the browser
+pub fn sleep(duration: Duration) -> SleepFuture {
+    #[cfg(not(target_arch = "wasm32"))]
+    let sleep = tokio::time::sleep(duration);
+    #[cfg(target_arch = "wasm32")]
+    let sleep = gloo_timers::future::sleep(duration);
+    Box::pin(sleep)
+}
+
+/// Source of time for timers and timestamps.
+///
+/// Polling, highlight expiry, DOM waits and generated timestamps go through
+/// the clock provided to the component tree rather than calling tokio and
+/// chrono directly, so tests can control time. Provide one with
+/// `use_context_provider(|| ClockHandle::new(MyClock))`; [`SystemClock`] is
+/// used when none is provided.
+pub trait Clock {
+    /// Monotonic time for measuring intervals
+    fn now(&self) -> Instant;
+
+    /// Wall-clock time for timestamps
+    #[cfg(feature = "full")]
+    fn utc_now(&self) -> DateTime<Utc>;
+
+    /// Resolve once `duration` has passed
+    fn sleep(&self, duration: Duration) -> SleepFuture;
+}
+
+/// Real time on the platform timer, following tokio's paused time natively
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    #[cfg(feature = "full")]
+    fn utc_now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn sleep(&self, duration: Duration) -> SleepFuture {
+        sleep(duration)
+    }
+}
+
+/// Clock whose wall time starts at a fixed epoch and advances with tokio's
+/// monotonic time, so timestamps don't depend on when the app was launched.
+/// Under `tokio::time::pause` it is fully deterministic.
+#[cfg(feature = "full")]
+pub struct DeterministicClock {
+    epoch: DateTime<Utc>,
+    start: Instant,
+}
+
+#[cfg(feature = "full")]
+impl DeterministicClock {
+    pub fn new(epoch: DateTime<Utc>) -> Self {
+        Self {
+            epoch,
+            start: Instant::now(),
+        }
+    }
+}
+
+#[cfg(feature = "full")]
+impl Clock for DeterministicClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn utc_now(&self) -> DateTime<Utc> {
+        let elapsed = chrono::Duration::from_std(self.start.elapsed()).unwrap_or_default();
+        self.epoch + elapsed
+    }
+
+    fn sleep(&self, duration: Duration) -> SleepFuture {
+        sleep(duration)
+    }
+}
+
+/// Clock running `factor` times faster than real time: intervals measured
+/// with it and sleeps through it pass `factor` times sooner, so hours of
+/// polling can be simulated in minutes. Wall time starts at a fixed epoch.
+#[cfg(feature = "full")]
+pub struct AcceleratedClock {
+    factor: u32,
+    epoch: DateTime<Utc>,
+    start: Instant,
+}
+
+#[cfg(feature = "full")]
+impl AcceleratedClock {
+    pub fn new(factor: u32, epoch: DateTime<Utc>) -> Self {
+        Self {
+            factor: factor.max(1),
+            epoch,
+            start: Instant::now(),
+        }
+    }
+
+    /// Simulated time since the clock was created
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed() * self.factor
+    }
+}
+
+#[cfg(feature = "full")]
+impl Clock for AcceleratedClock {
+    fn now(&self) -> Instant {
+        self.start + self.elapsed()
+    }
+
+    fn utc_now(&self) -> DateTime<Utc> {
+        let elapsed = chrono::Duration::from_std(self.elapsed()).unwrap_or_default();
+        self.epoch + elapsed
+    }
+
+    fn sleep(&self, duration: Duration) -> SleepFuture {
+        sleep(duration / self.factor)
+    }
+}
+
+/// Shared handle to the active clock
+#[derive(Clone)]
+pub struct ClockHandle(Rc<dyn Clock>);
+
+impl ClockHandle {
+    pub fn new(clock: impl Clock + 'static) -> Self {
+        Self(Rc::new(clock))
+    }
+}
+
+impl Default for ClockHandle {
+    fn default() -> Self {
+        Self::new(SystemClock)
+    }
+}
+
+impl Deref for ClockHandle {
+    type Target = dyn Clock;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+/// Access the clock provided to the component tree
+pub fn use_clock() -> ClockHandle {
+    use_hook(|| try_consume_context::<ClockHandle>().unwrap_or_default())
+}
+
+/// Waits for a burst of changes to end, e.g. keys typed into a search box:
+/// each change waits `delay` on the clock and only the last one settles
+#[derive(Clone)]
+pub struct Debounce {
+    delay: Duration,
+    changes: Rc<Cell<u64>>,
+}
+
+impl Debounce {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            changes: Rc::new(Cell::new(0)),
+        }
+    }
+
+    /// Record a change now. The future resolves after the delay, to whether
+    /// no other change was recorded meanwhile.
+    pub fn settle(&self, clock: &ClockHandle) -> impl Future<Output = bool> + 'static {
+        let change = self.changes.get() + 1;
+        self.changes.set(change);
+        let changes = self.changes.clone();
+        let sleep = clock.sleep(self.delay);
+        async move {
+            sleep.await;
+            changes.get() == change
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::time::advance;
+
+    const DELAY: Duration = Duration::from_millis(150);
+
+    // Whether `future` is still waiting, without waiting for it
+    async fn pending<F: Future + Unpin>(future: &mut F) -> bool {
+        tokio::time::timeout(Duration::ZERO, future).await.is_err()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn sleeps_follow_paused_time() {
+        let clock = ClockHandle::default();
+        let start = clock.now();
+        let mut sleep = clock.sleep(DELAY);
+
+        advance(DELAY - Duration::from_millis(1)).await;
+        assert!(pending(&mut sleep).await);
+        advance(Duration::from_millis(1)).await;
+        sleep.await;
+        assert_eq!(clock.now() - start, DELAY);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn only_the_last_change_settles() {
+        let clock = ClockHandle::default();
+        let debounce = Debounce::new(DELAY);
+        let start = clock.now();
+
+        let first = debounce.settle(&clock);
+        advance(Duration::from_millis(100)).await;
+        let mut second = Box::pin(debounce.settle(&clock));
+
+        assert!(!first.await);
+        assert_eq!(clock.now() - start, DELAY);
+        assert!(pending(&mut second).await);
+        assert!(second.await);
+        assert_eq!(clock.now() - start, Duration::from_millis(100) + DELAY);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn changes_apart_each_settle() {
+        let clock = ClockHandle::default();
+        let debounce = Debounce::new(DELAY);
+
+        assert!(debounce.settle(&clock).await);
+        assert!(debounce.settle(&clock).await);
+    }
+}
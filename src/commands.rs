@@ -0,0 +1,149 @@
+//! Commands offered by the command palette.
+//!
+//! Components add what they can do to the [`CommandRegistry`] in context,
+//! and the palette lists every registered command, ranked by how well its
+//! label matches what the user typed. Host apps embedding the feed can
+//! contribute their own commands the same way.
+
+use dioxus::prelude::*;
+
+/// Something the user can run from the command palette
+#[derive(Clone, PartialEq)]
+pub struct Command {
+    /// Unique among registered commands; registering the same id again
+    /// replaces the command
+    pub id: String,
+    pub label: String,
+    /// Shown next to the label, commands of a group can be replaced together
+    pub group: Option<String>,
+    /// Placeholder of the value asked for before running, for commands that
+    /// need one
+    pub argument: Option<String>,
+    /// Runs the command with the value entered, empty without an argument
+    pub run: Callback<String>,
+}
+
+impl Command {
+    pub fn new(id: impl Into<String>, label: impl Into<String>, run: Callback<String>) -> Self {
+        Self {
+            id: id.into(),
+            label: label.into(),
+            group: None,
+            argument: None,
+            run,
+        }
+    }
+
+    pub fn with_group(mut self, group: impl Into<String>) -> Self {
+        self.group = Some(group.into());
+        self
+    }
+
+    /// Ask for a value, described by `placeholder`, before running
+    pub fn with_argument(mut self, placeholder: impl Into<String>) -> Self {
+        self.argument = Some(placeholder.into());
+        self
+    }
+}
+
+/// Every command the palette offers, in registration order.
+///
+/// Provided once at the app root with `use_context_provider(CommandRegistry::new)`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct CommandRegistry {
+    commands: Signal<Vec<Command>>,
+}
+
+impl CommandRegistry {
+    pub fn new() -> Self {
+        Self {
+            commands: Signal::new(Vec::new()),
+        }
+    }
+
+    /// Add `command`, replacing any command with the same id
+    pub fn register(&mut self, command: Command) {
+        let mut commands = self.commands.write();
+        match commands.iter_mut().find(|registered| registered.id == command.id) {
+            Some(registered) => *registered = command,
+            None => commands.push(command),
+        }
+    }
+
+    pub fn unregister(&mut self, id: &str) {
+        if !self.commands.peek().iter().any(|command| command.id == id) {
+            return;
+        }
+        self.commands.write().retain(|command| command.id != id);
+    }
+
+    /// Replace every command of `group` with `commands`, for lists that
+    /// change as the app runs (e.g. one command per open feed)
+    pub fn set_group(&mut self, group: &str, commands: Vec<Command>) {
+        let mut registered = self.commands.write();
+        registered.retain(|command| command.group.as_deref() != Some(group));
+        registered.extend(commands.into_iter().map(|command| command.with_group(group)));
+    }
+
+    /// Commands whose label fuzzily matches `query`, best match first.
+    /// Every command matches an empty query, in registration order.
+    pub fn search(&self, query: &str) -> Vec<Command> {
+        let commands = self.commands.read();
+        let mut matches: Vec<(u32, &Command)> = commands
+            .iter()
+            .filter_map(|command| {
+                // The group counts too, so "switch home" finds the Home feed
+                let score = fuzzy_score(query, &command.label).or_else(|| {
+                    let group = command.group.as_deref()?;
+                    fuzzy_score(query, &format!("{} {}", group, command.label))
+                })?;
+                Some((score, command))
+            })
+            .collect();
+        // Stable, so equal scores keep registration order
+        matches.sort_by(|(a, _), (b, _)| b.cmp(a));
+        matches.into_iter().map(|(_, command)| command.clone()).collect()
+    }
+}
+
+impl Default for CommandRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Register `command` while the calling component is mounted
+pub fn use_command(command: impl FnOnce() -> Command) {
+    let mut registry = use_context::<CommandRegistry>();
+    let id = use_hook(|| {
+        let command = command();
+        let id = command.id.clone();
+        registry.register(command);
+        id
+    });
+    use_drop(move || registry.unregister(&id));
+}
+
+/// How well `query` matches `text`, higher is better; `None` unless every
+/// character of the query appears in the text in order, ignoring case.
+/// Characters following each other or starting a word score higher.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<u32> {
+    let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous_match = None;
+    for wanted in query.chars().flat_map(char::to_lowercase).filter(|c| !c.is_whitespace()) {
+        let offset = text[next..].iter().position(|c| *c == wanted)?;
+        let index = next + offset;
+        score += 1;
+        if previous_match.is_some_and(|previous| previous + 1 == index) {
+            score += 4;
+        }
+        if index == 0 || !text[index - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous_match = Some(index);
+        next = index + 1;
+    }
+    Some(score)
+}
@@ -0,0 +1,154 @@
+use dioxus::prelude::*;
+
+use crate::auth::{AuthManager, PendingAuth, Provider};
+use crate::links::open_external;
+use crate::persistence::scroll_positions::ScrollPositions;
+
+// Style of a row holding one account or input
+const ROW_STYLE: &str = "display: flex; align-items: center; gap: 8px; margin: 0 0 8px;";
+
+// Connected accounts, with the flow for connecting another one. Shown as a
+// section of the settings panel.
+#[component]
+pub fn AccountSettings() -> Element {
+    let mut auth = use_context::<AuthManager>();
+    let mut positions = use_context::<ScrollPositions>();
+    let mut instance = use_signal(String::new);
+    let mut pending = use_signal(|| None::<PendingAuth>);
+    let mut code = use_signal(String::new);
+    let mut status = use_signal(|| None::<String>);
+    let mut busy = use_signal(|| false);
+
+    // Register with the instance and start the sign-in it supports
+    let connect = move |_| async move {
+        busy.set(true);
+        status.set(None);
+        match auth.begin(Provider::Mastodon, &instance()).await {
+            Ok(started) => {
+                match &started {
+                    PendingAuth::Code { authorize_url, .. } => open_external(authorize_url),
+                    PendingAuth::Device { .. } => {
+                        let device = started.clone();
+                        spawn(async move {
+                            let result = auth.wait_for_device(&device).await;
+                            // A cancelled or newer sign-in no longer waits on this one
+                            if pending.peek().as_ref() != Some(&device) {
+                                return;
+                            }
+                            pending.set(None);
+                            status.set(Some(match result {
+                                Ok(account) => format!("Connected {}", account.label()),
+                                Err(e) => format!("Couldn't connect: {}", e),
+                            }));
+                        });
+                    }
+                }
+                pending.set(Some(started));
+            }
+            Err(e) => status.set(Some(format!("Couldn't connect: {}", e))),
+        }
+        busy.set(false);
+    };
+
+    // Exchange the code the user pasted back
+    let finish = move |_| async move {
+        let Some(started) = pending() else {
+            return;
+        };
+        busy.set(true);
+        match auth.complete(&started, &code()).await {
+            Ok(account) => {
+                pending.set(None);
+                code.set(String::new());
+                status.set(Some(format!("Connected {}", account.label())));
+            }
+            Err(e) => status.set(Some(format!("Couldn't connect: {}", e))),
+        }
+        busy.set(false);
+    };
+
+    rsx! {
+        h3 { style: "margin: 8px 0; font-size: 13px; color: #64748b;", "Accounts" }
+
+        for account in auth.accounts() {
+            div {
+                key: "{account.key()}",
+                style: ROW_STYLE,
+                span { "{account.provider.name()}: {account.label()}" }
+                button {
+                    onclick: {
+                        let key = account.key();
+                        move |_| {
+                            auth.disconnect(&key);
+                            positions.forget(&key);
+                        }
+                    },
+                    "Disconnect"
+                }
+            }
+        }
+
+        match pending() {
+            None => rsx! {
+                div {
+                    style: ROW_STYLE,
+                    input {
+                        r#type: "text",
+                        placeholder: "mastodon.social",
+                        aria_label: "Mastodon instance",
+                        value: "{instance}",
+                        oninput: move |evt| instance.set(evt.value()),
+                    }
+                    button {
+                        disabled: busy() || instance().trim().is_empty(),
+                        onclick: connect,
+                        "Connect Mastodon"
+                    }
+                }
+            },
+            Some(PendingAuth::Code { authorize_url, .. }) => rsx! {
+                p {
+                    style: "margin: 0 0 8px;",
+                    "Approve the sign-in in your browser, then paste the code shown. "
+                    a {
+                        href: "{authorize_url}",
+                        onclick: {
+                            let authorize_url = authorize_url.clone();
+                            move |evt: MouseEvent| {
+                                evt.prevent_default();
+                                open_external(&authorize_url);
+                            }
+                        },
+                        "Open the sign-in page again"
+                    }
+                }
+                div {
+                    style: ROW_STYLE,
+                    input {
+                        r#type: "text",
+                        aria_label: "Authorization code",
+                        value: "{code}",
+                        oninput: move |evt| code.set(evt.value()),
+                    }
+                    button { disabled: busy() || code().trim().is_empty(), onclick: finish, "Finish" }
+                    button { onclick: move |_| pending.set(None), "Cancel" }
+                }
+            },
+            Some(PendingAuth::Device { device, .. }) => rsx! {
+                div {
+                    style: ROW_STYLE,
+                    span {
+                        "Enter "
+                        strong { "{device.user_code}" }
+                        " at {device.verification_uri}"
+                    }
+                    button { onclick: move |_| pending.set(None), "Cancel" }
+                }
+            },
+        }
+
+        if let Some(status) = status() {
+            p { role: "status", style: "margin: 0; color: #64748b;", "{status}" }
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use dioxus::prelude::*;
+
+use super::virtual_list::VirtualList;
+use crate::persistence::alerts::{AlertRuleKind, Alerts};
+use crate::store::FeedStore;
+
+#[derive(PartialEq, Props, Clone)]
+pub struct AlertsFeedProps {
+    // Store over the matched items
+    pub store: FeedStore,
+}
+
+// Alert rule management above the items the rules matched
+#[component]
+pub fn AlertsFeed(props: AlertsFeedProps) -> Element {
+    let mut alerts = use_context::<Alerts>();
+    let store = props.store;
+    let items = store.items;
+    let mut kind = use_signal(|| AlertRuleKind::Keyword);
+    let mut pattern = use_signal(String::new);
+    let mut rule_error = use_signal(|| None::<String>);
+
+    let mut add_rule = move || {
+        let added = alerts.add_rule(kind(), &pattern.peek());
+        match added {
+            Ok(()) => {
+                pattern.set(String::new());
+                rule_error.set(None);
+            }
+            Err(e) => rule_error.set(Some(e.to_string())),
+        }
+    };
+
+    let button_style = "
+        padding: 6px 12px;
+        border-radius: 6px;
+        border: 1px solid #e2e8f0;
+        background: white;
+        color: #0f172a;
+        cursor: pointer;
+    ";
+
+    rsx! {
+        // Rules
+        section {
+            style: "
+                display: flex;
+                flex-direction: column;
+                gap: 8px;
+                padding: 8px 0;
+                font-size: 13px;
+                color: #475569;
+            ",
+            form {
+                style: "display: flex; gap: 8px;",
+                onsubmit: move |evt| {
+                    evt.prevent_default();
+                    add_rule();
+                },
+                select {
+                    aria_label: "Rule type",
+                    onchange: move |evt| {
+                        if let Some(selected) = AlertRuleKind::ALL.into_iter().find(|kind| kind.label() == evt.value()) {
+                            kind.set(selected);
+                        }
+                    },
+                    for choice in AlertRuleKind::ALL {
+                        option {
+                            value: choice.label(),
+                            selected: choice == kind(),
+                            "{choice.label()}"
+                        }
+                    }
+                }
+                input {
+                    style: "flex: 1; padding: 6px 8px; border: 1px solid #e2e8f0; border-radius: 6px;",
+                    placeholder: "Alert me about…",
+                    value: "{pattern}",
+                    oninput: move |evt| pattern.set(evt.value()),
+                }
+                button { r#type: "submit", style: button_style, "Add rule" }
+            }
+
+            if let Some(error) = rule_error() {
+                span { role: "alert", style: "color: #b91c1c;", "{error}" }
+            }
+
+            for rule in alerts.rules() {
+                div {
+                    key: "{rule.id}",
+                    style: "display: flex; align-items: center; gap: 8px;",
+                    label {
+                        style: "flex: 1; display: flex; align-items: center; gap: 6px;",
+                        input {
+                            r#type: "checkbox",
+                            checked: rule.enabled,
+                            onchange: move |evt| alerts.set_enabled(rule.id, evt.checked()),
+                        }
+                        "{rule.describe()}"
+                    }
+                    button {
+                        style: button_style,
+                        onclick: move |_| alerts.remove_rule(rule.id),
+                        "Remove"
+                    }
+                }
+            }
+        }
+
+        // Matches toolbar
+        div {
+            style: "
+                display: flex;
+                align-items: center;
+                gap: 8px;
+                padding: 8px 0;
+                font-size: 13px;
+                color: #64748b;
+            ",
+            span {
+                style: "flex: 1;",
+                "{items.read().len()} alerts"
+            }
+            button {
+                style: button_style,
+                disabled: items.read().is_empty(),
+                onclick: move |_| alerts.clear(),
+                "Clear"
+            }
+        }
+
+        if items.read().is_empty() {
+            div {
+                style: "
+                    text-align: center;
+                    padding: 60px 20px;
+                    color: #64748b;
+                    border: 1px dashed #e2e8f0;
+                    border-radius: 8px;
+                ",
+                "No alerts yet. New items matching a rule show up here."
+            }
+        } else {
+            VirtualList { store }
+        }
+    }
+}
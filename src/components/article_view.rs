@@ -0,0 +1,345 @@
+use dioxus::prelude::*;
+
+use crate::links::use_link_policy;
+use crate::persistence::privacy::{rewrite_remote, PrivacySettings, RemoteContent, RemoteLoad};
+use crate::reader::readability::{Article, ArticleBlock};
+use crate::reader::use_article_cache;
+use crate::urlcleaner::clean_url;
+
+// Scrolls the element whose id is sent from Rust to the top of the article
+const SCROLL_TO_BLOCK_SCRIPT: &str = r#"
+    const id = await dioxus.recv();
+    const target = document.getElementById(id);
+    if (target) {
+        target.scrollIntoView({ behavior: 'smooth', block: 'start' });
+    }
+"#;
+
+// Element id of the block at `index` in the open article
+fn block_element_id(index: usize) -> String {
+    format!("reader-block-{}", index)
+}
+
+// Scroll the article to the block an in-page anchor lands on
+fn scroll_to_anchor(article: &Article, anchor: &str) {
+    let Some(&index) = article.anchors.get(anchor) else {
+        return;
+    };
+    let eval = document::eval(SCROLL_TO_BLOCK_SCRIPT);
+    if let Err(e) = eval.send(block_element_id(index)) {
+        eprintln!("Failed to scroll to anchor {}: {}", anchor, e);
+    }
+}
+
+#[derive(PartialEq, Props, Clone)]
+pub struct ArticleViewProps {
+    pub url: String,
+    pub on_close: EventHandler<()>,
+}
+
+// Detail view showing the reader mode version of a linked article
+#[component]
+pub fn ArticleView(props: ArticleViewProps) -> Element {
+    let cache = use_article_cache();
+    let url = props.url.clone();
+
+    let article = use_resource(use_reactive!(|url| {
+        let cache = cache.clone();
+        async move { cache.get_or_fetch(url).await }
+    }));
+
+    // Links into the middle of an article open scrolled to their anchor
+    let fragment = url::Url::parse(&props.url)
+        .ok()
+        .and_then(|url| url.fragment().map(str::to_string));
+    use_effect(use_reactive!(|fragment| {
+        if let (Some(Ok(article)), Some(fragment)) = (&*article.read(), fragment) {
+            scroll_to_anchor(article, &fragment);
+        }
+    }));
+
+    // Links go through the link policy rather than navigating the webview;
+    // in-page ones scroll the article
+    let links = use_link_policy();
+    let on_link = move |href: String| {
+        if let Some(Ok(article)) = &*article.peek() {
+            let base = url::Url::parse(&article.url).ok();
+            if let Some(anchor) = links.follow(&href, base.as_ref()) {
+                scroll_to_anchor(article, &anchor);
+            }
+        }
+    };
+
+    // Remote images follow the privacy setting; with "Ask" they load once
+    // allowed for this article
+    let mut privacy = use_context::<PrivacySettings>();
+    let remote_content = privacy.remote_content();
+    let mut allow_remote = use_signal(|| false);
+    let has_remote = matches!(
+        &*article.read(),
+        Some(Ok(article)) if article.blocks.iter().any(|block| matches!(block, ArticleBlock::Image { .. }))
+    );
+
+    rsx! {
+        div {
+            style: "
+                position: fixed;
+                inset: 0;
+                z-index: 500;
+                background: rgba(15, 23, 42, 0.4);
+                display: flex;
+                justify-content: center;
+                align-items: stretch;
+                padding: 40px 20px;
+                box-sizing: border-box;
+            ",
+            onclick: move |_| props.on_close.call(()),
+
+            article {
+                style: "
+                    max-width: 720px;
+                    width: 100%;
+                    background: white;
+                    border-radius: 8px;
+                    border: 1px solid #e2e8f0;
+                    overflow-y: auto;
+                    padding: 32px;
+                    box-sizing: border-box;
+                    color: #0f172a;
+                ",
+                onclick: move |evt| evt.stop_propagation(),
+
+                // Header with source link and close action
+                header {
+                    style: "
+                        display: flex;
+                        justify-content: space-between;
+                        align-items: center;
+                        gap: 16px;
+                        margin-bottom: 16px;
+                        padding-bottom: 8px;
+                        border-bottom: 1px solid #e2e8f0;
+                        font-size: 12px;
+                        color: #64748b;
+                    ",
+                    span {
+                        style: "overflow: hidden; text-overflow: ellipsis; white-space: nowrap;",
+                        "{clean_url(&props.url)}"
+                    }
+                    label {
+                        style: "display: flex; align-items: center; gap: 6px; white-space: nowrap;",
+                        "Remote content"
+                        select {
+                            style: "font-size: 12px;",
+                            onchange: move |evt| {
+                                let setting = RemoteContent::ALL
+                                    .into_iter()
+                                    .find(|setting| setting.label() == evt.value());
+                                if let Some(setting) = setting {
+                                    privacy.set_remote_content(setting);
+                                }
+                            },
+                            for setting in RemoteContent::ALL {
+                                option {
+                                    value: setting.label(),
+                                    selected: setting == remote_content,
+                                    "{setting.label()}"
+                                }
+                            }
+                        }
+                    }
+                    label {
+                        style: "display: flex; align-items: center; gap: 6px; white-space: nowrap;",
+                        title: "Follow each link's redirects before opening it, which requests it from its server",
+                        input {
+                            r#type: "checkbox",
+                            checked: privacy.resolve_redirects(),
+                            onchange: move |evt| privacy.set_resolve_redirects(evt.checked()),
+                        }
+                        "Resolve redirects"
+                    }
+                    button {
+                        style: "
+                            padding: 6px 12px;
+                            border-radius: 6px;
+                            border: 1px solid #e2e8f0;
+                            background: white;
+                            cursor: pointer;
+                        ",
+                        onclick: move |_| props.on_close.call(()),
+                        "Close"
+                    }
+                }
+
+                {
+                    match &*article.read() {
+                        None => rsx! {
+                            div {
+                                style: "color: #64748b; text-align: center; padding: 40px;",
+                                "Loading article..."
+                            }
+                        },
+                        Some(Err(e)) => rsx! {
+                            div {
+                                style: "color: #ef4444; text-align: center; padding: 40px;",
+                                "Failed to load article: {e}"
+                            }
+                        },
+                        Some(Ok(article)) => rsx! {
+                            h1 {
+                                style: "font-size: 24px; line-height: 1.3; margin: 0 0 20px 0;",
+                                "{article.title}"
+                            }
+                            if has_remote && remote_content == RemoteContent::Ask && !allow_remote() {
+                                div {
+                                    style: "
+                                        display: flex;
+                                        justify-content: space-between;
+                                        align-items: center;
+                                        gap: 12px;
+                                        margin: 0 0 16px 0;
+                                        padding: 8px 12px;
+                                        border-radius: 6px;
+                                        background: #f8fafc;
+                                        border: 1px solid #e2e8f0;
+                                        font-size: 13px;
+                                        color: #475569;
+                                    ",
+                                    "Remote images in this article are blocked."
+                                    button {
+                                        style: "
+                                            padding: 4px 10px;
+                                            border-radius: 6px;
+                                            border: 1px solid #e2e8f0;
+                                            background: white;
+                                            cursor: pointer;
+                                        ",
+                                        onclick: move |_| allow_remote.set(true),
+                                        "Load images"
+                                    }
+                                }
+                            }
+                            for (index, block) in article.blocks.iter().enumerate() {
+                                ArticleBlockView {
+                                    block: block.clone(),
+                                    id: block_element_id(index),
+                                    remote_content,
+                                    allow_remote: allow_remote(),
+                                    on_link,
+                                }
+                            }
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(PartialEq, Props, Clone)]
+struct ArticleBlockViewProps {
+    block: ArticleBlock,
+    // Element id in-page links scroll to
+    id: String,
+    remote_content: RemoteContent,
+    // Remote content was allowed for this article
+    allow_remote: bool,
+    // Called with the href of a clicked link
+    on_link: EventHandler<String>,
+}
+
+#[component]
+fn ArticleBlockView(props: ArticleBlockViewProps) -> Element {
+    let id = props.id;
+    match props.block {
+        ArticleBlock::Heading(text) => rsx! {
+            h2 {
+                id: "{id}",
+                style: "font-size: 18px; margin: 24px 0 8px 0;",
+                "{text}"
+            }
+        },
+        ArticleBlock::Paragraph(text) => rsx! {
+            p {
+                id: "{id}",
+                style: "font-size: 16px; line-height: 1.7; color: #334155; margin: 0 0 16px 0;",
+                "{text}"
+            }
+        },
+        ArticleBlock::LinkedParagraph(runs) => rsx! {
+            p {
+                id: "{id}",
+                style: "font-size: 16px; line-height: 1.7; color: #334155; margin: 0 0 16px 0;",
+                for run in runs {
+                    if let Some(href) = run.href {
+                        a {
+                            href: "{href}",
+                            style: "color: #2563eb; text-decoration: none;",
+                            onclick: move |evt| {
+                                evt.prevent_default();
+                                evt.stop_propagation();
+                                props.on_link.call(href.clone());
+                            },
+                            "{run.text}"
+                        }
+                    } else {
+                        "{run.text}"
+                    }
+                }
+            }
+        },
+        ArticleBlock::Quote(text) => rsx! {
+            blockquote {
+                id: "{id}",
+                style: "
+                    margin: 0 0 16px 0;
+                    padding-left: 16px;
+                    border-left: 3px solid #e2e8f0;
+                    color: #475569;
+                    font-style: italic;
+                ",
+                "{text}"
+            }
+        },
+        ArticleBlock::Code(code) => rsx! {
+            pre {
+                id: "{id}",
+                style: "
+                    background: #f8fafc;
+                    border: 1px solid #e2e8f0;
+                    border-radius: 6px;
+                    padding: 12px;
+                    overflow-x: auto;
+                    font-size: 13px;
+                ",
+                "{code}"
+            }
+        },
+        ArticleBlock::Image { src, alt } => match rewrite_remote(&src, props.remote_content, props.allow_remote) {
+            RemoteLoad::Load(src) => rsx! {
+                img {
+                    id: "{id}",
+                    style: "max-width: 100%; border-radius: 6px; margin: 0 0 16px 0;",
+                    src: "{src}",
+                    alt: "{alt}",
+                }
+            },
+            // Placeholder for an image the privacy setting blocks
+            RemoteLoad::Blocked => rsx! {
+                div {
+                    id: "{id}",
+                    style: "
+                        margin: 0 0 16px 0;
+                        padding: 16px;
+                        border-radius: 6px;
+                        border: 1px dashed #cbd5e1;
+                        color: #94a3b8;
+                        font-size: 13px;
+                        text-align: center;
+                    ",
+                    if alt.is_empty() { "Remote image blocked" } else { "Remote image blocked: {alt}" }
+                }
+            },
+        },
+    }
+}
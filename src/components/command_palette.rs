@@ -0,0 +1,174 @@
+use dioxus::prelude::*;
+
+use crate::commands::{Command, CommandRegistry};
+
+// Most matches listed at once
+const MAX_MATCHES: usize = 12;
+
+#[derive(PartialEq, Props, Clone)]
+pub struct CommandPaletteProps {
+    pub on_close: EventHandler<()>,
+}
+
+// Searchable list of every registered command, opened with Ctrl+K by
+// default. Commands taking a value ask for it in the same field before
+// running. Escape or a click outside closes it.
+#[component]
+pub fn CommandPalette(props: CommandPaletteProps) -> Element {
+    let on_close = props.on_close;
+    let registry = use_context::<CommandRegistry>();
+    let mut query = use_signal(String::new);
+    let mut selected = use_signal(|| 0usize);
+    // Command waiting for its value
+    let mut pending = use_signal(|| None::<Command>);
+
+    let matches: Vec<Command> = match pending() {
+        Some(_) => Vec::new(),
+        None => registry.search(&query()).into_iter().take(MAX_MATCHES).collect(),
+    };
+    let selected_index = selected().min(matches.len().saturating_sub(1));
+    let no_matches = matches.is_empty();
+
+    let mut pick = move |command: Command| {
+        if command.argument.is_some() {
+            pending.set(Some(command));
+            query.set(String::new());
+            return;
+        }
+        on_close.call(());
+        command.run.call(String::new());
+    };
+
+    let placeholder = match pending() {
+        Some(command) => command.argument.unwrap_or_default(),
+        None => "Type a command".to_string(),
+    };
+
+    rsx! {
+        div {
+            style: "
+                position: fixed;
+                inset: 0;
+                z-index: 1000;
+                display: flex;
+                justify-content: center;
+                align-items: flex-start;
+                padding-top: 15vh;
+                background: rgba(15, 23, 42, 0.5);
+            ",
+            onclick: move |_| on_close.call(()),
+            section {
+                role: "dialog",
+                aria_modal: "true",
+                aria_label: "Command palette",
+                style: "
+                    width: 480px;
+                    max-width: calc(100vw - 32px);
+                    border-radius: 8px;
+                    background: white;
+                    box-shadow: 0 20px 40px rgba(15, 23, 42, 0.25);
+                    overflow: hidden;
+                    font-size: 14px;
+                    color: #0f172a;
+                ",
+                onclick: move |evt| evt.stop_propagation(),
+                if let Some(command) = pending() {
+                    p { style: "margin: 0; padding: 8px 12px 0; font-size: 12px; color: #64748b;", "{command.label}" }
+                }
+                input {
+                    role: "combobox",
+                    aria_expanded: "{!no_matches}",
+                    aria_controls: "command-palette-list",
+                    aria_activedescendant: if !no_matches { "command-palette-{selected_index}" },
+                    style: "
+                        width: 100%;
+                        box-sizing: border-box;
+                        padding: 12px;
+                        border: none;
+                        border-bottom: 1px solid #e2e8f0;
+                        font-size: 15px;
+                        outline: none;
+                    ",
+                    placeholder: "{placeholder}",
+                    value: "{query}",
+                    onmounted: move |evt| async move {
+                        let _ = evt.set_focus(true).await;
+                    },
+                    oninput: move |evt| {
+                        query.set(evt.value());
+                        selected.set(0);
+                    },
+                    onkeydown: {
+                        let matches = matches.clone();
+                        move |evt| match evt.key() {
+                            Key::Escape => {
+                                evt.prevent_default();
+                                // Back to the list from a value prompt
+                                if pending.peek().is_some() {
+                                    pending.set(None);
+                                    query.set(String::new());
+                                } else {
+                                    on_close.call(());
+                                }
+                            }
+                            Key::ArrowDown if !matches.is_empty() => {
+                                evt.prevent_default();
+                                selected.set((selected_index + 1) % matches.len());
+                            }
+                            Key::ArrowUp if !matches.is_empty() => {
+                                evt.prevent_default();
+                                selected.set((selected_index + matches.len() - 1) % matches.len());
+                            }
+                            Key::Enter => {
+                                evt.prevent_default();
+                                let waiting = pending.peek().clone();
+                                if let Some(command) = waiting {
+                                    on_close.call(());
+                                    command.run.call(query.peek().trim().to_string());
+                                } else if let Some(command) = matches.get(selected_index) {
+                                    pick(command.clone());
+                                }
+                            }
+                            _ => {}
+                        }
+                    },
+                }
+                if pending().is_none() {
+                    ul {
+                        id: "command-palette-list",
+                        role: "listbox",
+                        style: "margin: 0; padding: 4px 0; list-style: none; max-height: 360px; overflow-y: auto;",
+                        for (index, command) in matches.iter().cloned().enumerate() {
+                            li {
+                                key: "{command.id}",
+                                id: "command-palette-{index}",
+                                role: "option",
+                                aria_selected: "{index == selected_index}",
+                                style: format!("
+                                    display: flex;
+                                    justify-content: space-between;
+                                    gap: 12px;
+                                    padding: 8px 12px;
+                                    cursor: pointer;
+                                    background: {};
+                                ", if index == selected_index { "#f1f5f9" } else { "transparent" }),
+                                onmouseenter: move |_| selected.set(index),
+                                onclick: {
+                                    let command = command.clone();
+                                    move |_| pick(command.clone())
+                                },
+                                span { "{command.label}" }
+                                if let Some(group) = command.group.as_deref() {
+                                    span { style: "color: #94a3b8; font-size: 12px;", "{group}" }
+                                }
+                            }
+                        }
+                    }
+                    if no_matches {
+                        p { style: "margin: 0; padding: 12px; color: #64748b;", "No matching commands" }
+                    }
+                }
+            }
+        }
+    }
+}
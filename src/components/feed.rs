@@ -1,6 +1,9 @@
 use dioxus::prelude::*;
 use dioxus::html::geometry::PixelsVector2D;
 
+use crate::clock;
+use crate::error::{report, FeedError, ScrollError};
+
 #[derive(PartialEq, Props, Clone)]
 pub struct FeedProps {
 }
@@ -27,7 +30,7 @@ const SCROLL_RETRY_DELAY_MS: u64 = 10; // Delay between scroll retries
 const MIN_SCROLL_OFFSET: f64 = 50.0; // Minimum scroll offset to prevent zero position
 
 // Item limit management with error handling
-fn trim_items_if_needed(items: &mut Vec<String>) -> Result<(), &'static str> {
+fn trim_items_if_needed(items: &mut Vec<String>) -> Result<(), FeedError> {
     if items.len() > MAX_ITEMS {
         let excess = items.len() - MAX_ITEMS;
         let remove_start = MAX_ITEMS / 2;
@@ -36,7 +39,7 @@ fn trim_items_if_needed(items: &mut Vec<String>) -> Result<(), &'static str> {
             items.drain(remove_start..remove_start + excess);
             Ok(())
         } else {
-            Err("Invalid trim range calculated")
+            Err(FeedError::Trim { len: items.len() })
         }
     } else {
         Ok(())
@@ -59,8 +62,8 @@ fn use_scroll_management(
         
         // Handle scroll lock enforcement
         if scroll_lock() {
-            if let Err(_) = handle_scroll_lock(scroll_top, locked_scroll_position(), scroll_element()) {
-                // Silently continue if scroll lock fails
+            if let Err(e) = handle_scroll_lock(scroll_top, locked_scroll_position(), scroll_element()) {
+                report(e);
             }
             return;
         }
@@ -89,18 +92,20 @@ fn handle_scroll_lock(
     current_scroll_top: f64,
     locked_position: f64,
     scroll_element: Option<std::rc::Rc<MountedData>>
-) -> Result<(), &'static str> {
+) -> Result<(), FeedError> {
     if (current_scroll_top - locked_position).abs() > SCROLL_POSITION_TOLERANCE {
         if let Some(element) = scroll_element {
-            let _ = spawn(async move {
-                let _ = element.scroll(
+            spawn(async move {
+                if let Err(e) = element.scroll(
                     PixelsVector2D::new(0.0, locked_position),
                     ScrollBehavior::Instant
-                ).await;
+                ).await {
+                    report(FeedError::Scroll(e.into()));
+                }
             });
             Ok(())
         } else {
-            Err("Scroll element not available")
+            Err(FeedError::Scroll(ScrollError::NotMounted))
         }
     } else {
         Ok(())
@@ -128,17 +133,19 @@ fn handle_top_scroll_trigger(
         }
         
         // Trim items if needed with error handling
-        let _ = trim_items_if_needed(&mut new_items);
+        if let Err(e) = trim_items_if_needed(&mut new_items) {
+            report(e);
+        }
         items.set(new_items);
         
         // Wait for DOM updates
-        if let Err(_) = wait_for_dom_updates().await {
-            // Continue even if timing fails
-        }
+        wait_for_dom_updates().await;
         
         // Restore scroll position
         if let Some(element) = scroll_element {
-            let _ = restore_scroll_position(element, locked_scroll_position).await;
+            if let Err(e) = restore_scroll_position(element, locked_scroll_position).await {
+                report(e);
+            }
         }
     });
 }
@@ -157,29 +164,29 @@ fn handle_bottom_scroll_trigger(
     }
     
     // Trim items if needed with error handling
-    let _ = trim_items_if_needed(&mut new_items);
+    if let Err(e) = trim_items_if_needed(&mut new_items) {
+        report(e);
+    }
     items.set(new_items);
 }
 
-// DOM update waiting logic with error handling
-async fn wait_for_dom_updates() -> Result<(), &'static str> {
+// DOM update waiting logic
+async fn wait_for_dom_updates() {
     // Initial short wait for immediate DOM changes
-    tokio::time::sleep(std::time::Duration::from_millis(DOM_UPDATE_IMMEDIATE_MS)).await;
+    clock::sleep(std::time::Duration::from_millis(DOM_UPDATE_IMMEDIATE_MS)).await;
     
     // Secondary wait for layout calculations
-    tokio::time::sleep(std::time::Duration::from_millis(DOM_UPDATE_LAYOUT_MS)).await;
+    clock::sleep(std::time::Duration::from_millis(DOM_UPDATE_LAYOUT_MS)).await;
     
     // Final wait for complete rendering
-    tokio::time::sleep(std::time::Duration::from_millis(DOM_UPDATE_RENDER_MS)).await;
-    
-    Ok(())
+    clock::sleep(std::time::Duration::from_millis(DOM_UPDATE_RENDER_MS)).await;
 }
 
 // Scroll position restoration logic with comprehensive error handling
 async fn restore_scroll_position(
     element: std::rc::Rc<MountedData>,
     mut locked_scroll_position: Signal<f64>,
-) -> Result<(), &'static str> {
+) -> Result<(), FeedError> {
     let calculated_offset = ITEMS_PER_LOAD as f64 * ITEM_HEIGHT;
     
     let target_position = if calculated_offset < MIN_SCROLL_OFFSET {
@@ -191,24 +198,29 @@ async fn restore_scroll_position(
     locked_scroll_position.set(target_position);
     
     // Attempt scroll restoration with retries
+    let mut last_error = None;
     for attempt in 1..=SCROLL_RETRY_ATTEMPTS {
         match element.scroll(
             PixelsVector2D::new(0.0, target_position), 
             ScrollBehavior::Instant
         ).await {
             Ok(_) => return Ok(()),
-            Err(_) => {
+            Err(e) => {
+                last_error = Some(e.to_string());
                 if attempt < SCROLL_RETRY_ATTEMPTS {
-                    tokio::time::sleep(std::time::Duration::from_millis(SCROLL_RETRY_DELAY_MS)).await;
+                    clock::sleep(std::time::Duration::from_millis(SCROLL_RETRY_DELAY_MS)).await;
                 }
             }
         }
     }
     
     // Extended stabilization wait
-    tokio::time::sleep(std::time::Duration::from_millis(DOM_UPDATE_STABILIZATION_MS)).await;
+    clock::sleep(std::time::Duration::from_millis(DOM_UPDATE_STABILIZATION_MS)).await;
     
-    Err("All scroll attempts failed")
+    Err(FeedError::Scroll(ScrollError::GaveUp {
+        attempts: SCROLL_RETRY_ATTEMPTS,
+        last: last_error.unwrap_or_default(),
+    }))
 }
 
 // Loading state management hook
@@ -224,15 +236,15 @@ fn use_loading_state_management(
     use_future(move || async move {
         loop {
             if reset_loading_top() {
-                tokio::time::sleep(std::time::Duration::from_millis(SCROLL_LOCK_DURATION_MS)).await;
+                clock::sleep(std::time::Duration::from_millis(SCROLL_LOCK_DURATION_MS)).await;
                 reset_loading_top.set(false);
                 reset_scroll_lock.set(false);
             }
             if reset_loading_bottom() {
-                tokio::time::sleep(std::time::Duration::from_millis(BOTTOM_LOADING_DURATION_MS)).await;
+                clock::sleep(std::time::Duration::from_millis(BOTTOM_LOADING_DURATION_MS)).await;
                 reset_loading_bottom.set(false);
             }
-            tokio::time::sleep(std::time::Duration::from_millis(DOM_UPDATE_RENDER_MS)).await;
+            clock::sleep(std::time::Duration::from_millis(DOM_UPDATE_RENDER_MS)).await;
         }
     });
 }
@@ -247,10 +259,12 @@ fn use_real_time_polling(items: Signal<Vec<String>>) {
             new_items.push(format!("New Item {}", next_num));
             
             // Trim items if needed with error handling
-            let _ = trim_items_if_needed(&mut new_items);
+            if let Err(e) = trim_items_if_needed(&mut new_items) {
+                report(e);
+            }
             items_for_poll.set(new_items);
             
-            tokio::time::sleep(std::time::Duration::from_secs(POLLING_INTERVAL_SECONDS)).await;
+            clock::sleep(std::time::Duration::from_secs(POLLING_INTERVAL_SECONDS)).await;
         }
     });
 }
@@ -0,0 +1,925 @@
+use dioxus::prelude::*;
+use dioxus_feed_control::{ControlRequest, ControlResponse};
+use dioxus_feed_core::VirtualFeedItem;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use super::alerts_feed::AlertsFeed;
+use super::command_palette::CommandPalette;
+use super::list_controller::{use_virtual_list_controller, ScrollAnchor, ScrollRequest, VirtualListController};
+use super::paged_view::PagedView;
+use super::split_view::SplitView;
+use super::saved_feed::SavedFeed;
+use super::search_panel::SearchPanel;
+use super::settings_panel::SettingsPanel;
+use super::shortcut_help::ShortcutHelp;
+use super::source_health::SourceHealthPanel;
+use super::story_rail::StoryRail;
+use super::virtual_list::VirtualList;
+use crate::auth::{Account, AuthManager, Provider};
+use crate::clock::{use_clock, ClockHandle};
+use crate::commands::{use_command, Command, CommandRegistry};
+use crate::error::{report, FeedError};
+use crate::ingest::{use_control_handler, Inbox, Integrations};
+use crate::links::{AppRoute, LinkRouter};
+use crate::persistence::alerts::Alerts;
+use crate::persistence::bookmarks::Bookmarks;
+use crate::persistence::filters::ContentFilters;
+use crate::persistence::read_state::ReadState;
+use crate::persistence::scroll_positions::ScrollPositions;
+use crate::persistence::search_index::{SearchHit, SearchIndex};
+use crate::persistence::setup::SetupConfig;
+use crate::persistence::subscriptions::Subscriptions;
+use crate::persistence::reading_width::ReadingWidthSettings;
+use crate::persistence::theme::ThemeSettings;
+use crate::shortcuts::{use_shortcuts, ShortcutAction};
+use crate::processors::Processors;
+use crate::sources::demo::DemoSource;
+use crate::sources::health::SourceHealth;
+use crate::sources::mastodon::MastodonSource;
+use crate::sources::opml::OpmlFolder;
+use crate::sources::rss::{AggregateSource, RssSource};
+use crate::sources::FeedSource;
+#[cfg(feature = "fullstack")]
+use crate::sources::server::initial_page;
+use crate::store::{FeedItems, FeedStore, Sequence};
+use crate::work::WorkProgress;
+
+// Where a named feed gets its items from
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FeedKind {
+    Home,
+    MediaOnly,
+    Saved,
+    Alerts,
+    // Items pushed by local integrations this session
+    Inbox,
+}
+
+// A named feed shown as a tab
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct FeedDefinition {
+    pub id: &'static str,
+    pub title: &'static str,
+    pub kind: FeedKind,
+}
+
+impl FeedDefinition {
+    // Source backing this feed, `None` for feeds built from local state
+    pub fn source(&self) -> Option<DemoSource> {
+        match self.kind {
+            FeedKind::Home => Some(DemoSource::new()),
+            FeedKind::MediaOnly => Some(DemoSource::photos_only()),
+            FeedKind::Saved | FeedKind::Alerts | FeedKind::Inbox => None,
+        }
+    }
+
+    // Whether the feed shows generated demo items, hidden unless demo data
+    // was picked on first run
+    fn is_demo(&self) -> bool {
+        matches!(self.kind, FeedKind::Home | FeedKind::MediaOnly)
+    }
+
+    // Create the store backing this feed, starting from `initial_items` when
+    // they were already rendered (e.g. on the server)
+    fn create_store(
+        &self,
+        context: StoreContext,
+        clock: &ClockHandle,
+        initial_items: Option<Vec<VirtualFeedItem>>,
+    ) -> FeedStore {
+        let store = match (self.source(), self.kind) {
+            (Some(source), _) => {
+                let source = source.with_clock(clock.clone());
+                let items = initial_items.unwrap_or_else(|| source.initial_items());
+                FeedStore::new(items, source)
+                    .with_health(context.health, self.title)
+                    .with_alerts(context.alerts)
+                    .with_search(context.search, self.id)
+                    .with_processors(context.processors)
+            }
+            (None, FeedKind::Alerts) => FeedStore::from_items(context.alerts.items()),
+            (None, FeedKind::Inbox) => FeedStore::from_items(context.inbox.items()),
+            (None, _) => FeedStore::from_items(context.bookmarks.items()).with_bookmarks(context.bookmarks),
+        };
+        store.with_filters(context.filters, self.title).with_clock(clock.clone())
+    }
+}
+
+// App-wide state the stores are built over
+#[derive(Clone, Copy, PartialEq)]
+struct StoreContext {
+    bookmarks: Bookmarks,
+    alerts: Alerts,
+    health: SourceHealth,
+    search: SearchIndex,
+    inbox: Inbox,
+    processors: Processors,
+    filters: ContentFilters,
+}
+
+// Feeds available in the tab bar, the first one is opened on launch
+pub const FEEDS: &[FeedDefinition] = &[
+    FeedDefinition { id: "home", title: "Home", kind: FeedKind::Home },
+    FeedDefinition { id: "media", title: "Media", kind: FeedKind::MediaOnly },
+    FeedDefinition { id: "saved", title: "Saved", kind: FeedKind::Saved },
+    FeedDefinition { id: "alerts", title: "Alerts", kind: FeedKind::Alerts },
+    FeedDefinition { id: "inbox", title: "Inbox", kind: FeedKind::Inbox },
+];
+
+// Store merging every feed in an imported folder. Each feed reports its
+// health as "Folder › Feed" next to the folder's own entry.
+fn create_folder_store(folder: &OpmlFolder, context: StoreContext, clock: &ClockHandle) -> FeedStore {
+    let sources: Vec<(String, Rc<dyn FeedSource>)> = folder
+        .feeds
+        .iter()
+        .map(|feed| (feed.title.clone(), Rc::new(RssSource::new(feed.xml_url.clone())) as Rc<dyn FeedSource>))
+        .collect();
+    let source = AggregateSource::new(folder.title.clone(), sources).with_health(context.health, clock.clone());
+    FeedStore::new(Vec::new(), source)
+        .with_health(context.health, folder.title.clone())
+        .with_alerts(context.alerts)
+        .with_search(context.search, ActiveTab::Folder(folder.title.clone()).key())
+        .with_processors(context.processors)
+        .with_filters(context.filters, folder.title.clone())
+        .with_clock(clock.clone())
+}
+
+// Store reading the home timeline of a connected account
+fn create_account_store(auth: AuthManager, account: &Account, context: StoreContext, clock: &ClockHandle) -> FeedStore {
+    let source = MastodonSource::new(auth, account.key(), account.base_url.clone());
+    FeedStore::new(Vec::new(), source)
+        .with_health(context.health, account.label())
+        .with_alerts(context.alerts)
+        .with_search(context.search, ActiveTab::Account(account.provider).key())
+        .with_processors(context.processors)
+        .with_filters(context.filters, account.label())
+        .with_clock(clock.clone())
+}
+
+// Position of the list the controller drives, if that list shows `store`;
+// right after switching tabs it can still be the previous list's
+fn anchor_in(controller: VirtualListController, store: FeedStore) -> Option<ScrollAnchor> {
+    let anchor = controller.current_anchor()?;
+    store
+        .ordered_items()
+        .iter()
+        .any(|item| item.id == anchor.item_id)
+        .then_some(anchor)
+}
+
+// How the active feed is laid out
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+enum FeedLayout {
+    #[default]
+    List,
+    // One item per page
+    Pages,
+    // The list beside the selected item
+    Split,
+}
+
+impl FeedLayout {
+    // Switch to `layout`, or back to the list when it is already shown
+    fn toggled(self, layout: FeedLayout) -> FeedLayout {
+        if self == layout {
+            FeedLayout::List
+        } else {
+            layout
+        }
+    }
+}
+
+// Tab selected in the tab bar
+#[derive(Clone, PartialEq, Debug)]
+enum ActiveTab {
+    Feed(FeedDefinition),
+    // Imported subscription folder, by title
+    Folder(String),
+    // Timeline of the provider's active account
+    Account(Provider),
+}
+
+impl ActiveTab {
+    // Key of the tab's store, also used to key its list
+    fn key(&self) -> String {
+        match self {
+            ActiveTab::Feed(feed) => feed.id.to_string(),
+            ActiveTab::Folder(title) => format!("folder:{}", title),
+            ActiveTab::Account(provider) => format!("account:{}", provider.id()),
+        }
+    }
+
+    // Tab whose store has key `key`
+    fn from_key(feeds: &[FeedDefinition], key: &str) -> Option<Self> {
+        if let Some(title) = key.strip_prefix("folder:") {
+            return Some(ActiveTab::Folder(title.to_string()));
+        }
+        if let Some(id) = key.strip_prefix("account:") {
+            return Provider::ALL.into_iter().find(|provider| provider.id() == id).map(ActiveTab::Account);
+        }
+        feeds.iter().find(|feed| feed.id == key).copied().map(ActiveTab::Feed)
+    }
+
+    fn title(&self) -> String {
+        match self {
+            ActiveTab::Feed(feed) => feed.title.to_string(),
+            ActiveTab::Folder(title) => title.clone(),
+            ActiveTab::Account(provider) => provider.name().to_string(),
+        }
+    }
+
+    fn kind(&self) -> Option<FeedKind> {
+        match self {
+            ActiveTab::Feed(feed) => Some(feed.kind),
+            ActiveTab::Folder(_) | ActiveTab::Account(_) => None,
+        }
+    }
+}
+
+// Style of a tab button
+fn tab_style(selected: bool) -> String {
+    format!("
+        padding: 8px 16px;
+        border: none;
+        border-bottom: 2px solid {};
+        background: transparent;
+        color: {};
+        font-size: 14px;
+        font-weight: 500;
+        cursor: pointer;
+    ",
+        if selected { "#0f172a" } else { "transparent" },
+        if selected { "#0f172a" } else { "#64748b" }
+    )
+}
+
+// Most items a feed or account list keeps loaded, like the old feed's cap
+const MAX_LOADED_ITEMS: usize = 500;
+
+// Suggestions interleaved into the home feed, rotating by position
+const SUGGESTIONS: &[&str] = &[
+    "Open the Media tab for photos only",
+    "Save items to read them later in the Saved tab",
+    "Try Read aloud to listen to your feed",
+];
+
+// Suggestion card rendered in the home feed's interleaved slots
+fn suggestion_slot(seq: Sequence) -> Option<Element> {
+    let suggestion = SUGGESTIONS[seq.rem_euclid(SUGGESTIONS.len() as i64) as usize];
+    Some(rsx! {
+        aside {
+            style: "
+                height: 100%;
+                box-sizing: border-box;
+                padding: 16px 20px;
+                border-radius: 8px;
+                border: 1px dashed #cbd5e1;
+                background: #f8fafc;
+                color: #475569;
+                font-size: 14px;
+                display: flex;
+                align-items: center;
+            ",
+            "Suggested for you: {suggestion}"
+        }
+    })
+}
+
+// Id of the newest item published on or before `date` (`YYYY-MM-DD`, UTC),
+// whatever order the feed is ranked in
+fn item_on_or_before(items: &FeedItems, date: &str) -> Result<String, FeedError> {
+    let date = date.trim();
+    let end_of_day = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .ok()
+        .and_then(|day| day.succ_opt())
+        .and_then(|day| day.and_hms_opt(0, 0, 0))
+        .map(|midnight| midnight.and_utc().timestamp_millis())
+        .ok_or_else(|| FeedError::InvalidDate(date.to_string()))?;
+    items
+        .iter()
+        .filter_map(|item| Some((item.published_at?, item)))
+        .filter(|(published_at, _)| *published_at < end_of_day)
+        .max_by_key(|(published_at, _)| *published_at)
+        .map(|(_, item)| item.id.clone())
+        .ok_or_else(|| FeedError::NoItemsBefore(date.to_string()))
+}
+
+#[derive(PartialEq, Props, Clone)]
+pub struct FeedTabsProps {
+    #[props(default = FEEDS)]
+    pub feeds: &'static [FeedDefinition],
+}
+
+// Tab bar switching between named feeds, each with its own store
+#[component]
+pub fn FeedTabs(props: FeedTabsProps) -> Element {
+    let feeds = props.feeds;
+
+    // First page of the launch feed, rendered on the server and reused while
+    // hydrating so the list doesn't start blank
+    #[cfg(feature = "fullstack")]
+    let initial_items = {
+        let page = use_server_future(move || initial_page(feeds[0].id.to_string()))?;
+        let items = page.peek().as_ref().and_then(|page| page.as_ref().ok()).cloned();
+        items.filter(|items| !items.is_empty())
+    };
+    #[cfg(not(feature = "fullstack"))]
+    let initial_items: Option<Vec<VirtualFeedItem>> = None;
+
+    let context = StoreContext {
+        bookmarks: use_context::<Bookmarks>(),
+        alerts: use_context::<Alerts>(),
+        health: use_context::<SourceHealth>(),
+        search: use_context::<SearchIndex>(),
+        inbox: use_context::<Inbox>(),
+        processors: use_context::<Processors>(),
+        filters: use_context::<ContentFilters>(),
+    };
+    // The inbox tab only shows while an integration can fill it, and the
+    // demo feeds only when demo data was picked
+    let inbox_shown = use_context::<Integrations>().any();
+    let setup = use_context::<SetupConfig>();
+    let shows_feed = move |feed: &FeedDefinition| {
+        (feed.kind != FeedKind::Inbox || inbox_shown) && (setup.demo_feeds() || !feed.is_demo())
+    };
+    let mut subscriptions = use_context::<Subscriptions>();
+    let auth = use_context::<AuthManager>();
+    let mut positions = use_context::<ScrollPositions>();
+    // Account each account tab's store was built for, by tab key
+    let mut store_accounts = use_hook(|| CopyValue::new(HashMap::<String, String>::new()));
+    // Position to scroll an account's rebuilt list back to once it has loaded
+    let mut pending_restore = use_signal(|| None::<ScrollAnchor>);
+    let mut show_health = use_signal(|| false);
+    let mut show_search = use_signal(|| false);
+    let mut show_settings = use_signal(|| false);
+    let mut show_shortcuts = use_signal(|| false);
+    let mut show_palette = use_signal(|| false);
+    // Feeds shown a page per item or split with the selected item,
+    // instead of as a list
+    let mut layout = use_signal(FeedLayout::default);
+    // Shared by whichever feed is shown, so search results can jump into it
+    let mut controller = use_virtual_list_controller();
+    // Opened on launch: the first feed, or without the demo feeds the first
+    // subscription folder, as seeded on first run
+    let launch_tab = use_hook(|| {
+        let folder = subscriptions.folders().into_iter().next().filter(|_| !setup.demo_feeds());
+        match folder {
+            Some(folder) => ActiveTab::Folder(folder.title),
+            None => ActiveTab::Feed(feeds.iter().copied().find(shows_feed).unwrap_or(feeds[0])),
+        }
+    });
+    let mut active = use_signal(|| launch_tab.clone());
+    let mut import_status = use_signal(|| None::<String>);
+    let work = use_context::<WorkProgress>();
+    let clock = use_clock();
+    let clock = use_hook(|| CopyValue::new(clock));
+
+    // Stores are created the first time their feed is opened and then kept,
+    // so switching back restores the items and scroll position
+    let mut stores = use_signal(|| {
+        let store = match &launch_tab {
+            ActiveTab::Feed(feed) if *feed == feeds[0] => Some(feed.create_store(context, &clock.read(), initial_items)),
+            ActiveTab::Feed(feed) => Some(feed.create_store(context, &clock.read(), None)),
+            ActiveTab::Folder(title) => subscriptions
+                .folder(title)
+                .map(|folder| create_folder_store(&folder, context, &clock.read())),
+            ActiveTab::Account(_) => None,
+        };
+        store.map(|store| (launch_tab.key(), store)).into_iter().collect::<HashMap<_, _>>()
+    });
+
+    let mut open_tab = move |tab: ActiveTab| {
+        let key = tab.key();
+        if !stores.read().contains_key(&key) {
+            let store = match &tab {
+                ActiveTab::Feed(feed) => Some(feed.create_store(context, &clock.read(), None)),
+                ActiveTab::Folder(title) => subscriptions
+                    .folder(title)
+                    .map(|folder| create_folder_store(&folder, context, &clock.read())),
+                ActiveTab::Account(provider) => auth.active_account(*provider).map(|account| {
+                    store_accounts.write().insert(key.clone(), account.key());
+                    pending_restore.set(positions.anchor(&account.key()));
+                    create_account_store(auth, &account, context, &clock.read())
+                }),
+            };
+            if let Some(store) = store {
+                stores.write().insert(key.clone(), store);
+            }
+        }
+        // Returning to a position only makes sense in the feed it was in
+        if active.peek().key() != key {
+            controller.dismiss_return();
+        }
+        active.set(tab);
+    };
+
+    // Read OPML files picked by the user and open the first new folder
+    let import_opml = move |evt: FormEvent| async move {
+        let Some(files) = evt.files() else {
+            return;
+        };
+        for name in files.files() {
+            let Some(xml) = files.read_file_to_string(&name).await else {
+                import_status.set(Some(format!("Couldn't read {}", name)));
+                continue;
+            };
+            let before = subscriptions.folders().len();
+            match subscriptions.import_opml(xml).await {
+                Ok(added) => {
+                    import_status.set(Some(format!("Imported {} feeds", added)));
+                    // Merged folders get new stores the next time they open
+                    for folder in subscriptions.folders() {
+                        stores.write().remove(&ActiveTab::Folder(folder.title).key());
+                    }
+                    match subscriptions.folders().get(before) {
+                        Some(folder) => open_tab(ActiveTab::Folder(folder.title.clone())),
+                        None => open_tab(active()),
+                    }
+                }
+                Err(e) => import_status.set(Some(format!("Couldn't import {}: {}", name, e))),
+            }
+        }
+    };
+
+    // Switch to the result's feed and scroll to it once the list renders
+    let open_hit = move |hit: SearchHit| {
+        match ActiveTab::from_key(feeds, &hit.feed) {
+            Some(tab) => {
+                open_tab(tab);
+                controller.jump_to_item(hit.id);
+            }
+            None => report(FeedError::UnknownFeed(hit.feed)),
+        }
+    };
+    // Follow `myapp://` links clicked in item and article content
+    let mut router = use_context_provider(LinkRouter::new);
+    use_effect(move || {
+        let Some(route) = router.take() else {
+            return;
+        };
+        let (feed, item) = match route {
+            AppRoute::Feed(feed) => (feed, None),
+            AppRoute::Item { feed, id } => (feed, Some(id)),
+        };
+        match ActiveTab::from_key(feeds, &feed) {
+            Some(tab) => {
+                open_tab(tab);
+                if let Some(id) = item {
+                    controller.jump_to_item(id);
+                }
+            }
+            None => report(FeedError::UnknownFeed(feed)),
+        }
+    });
+
+    // Switching accounts swaps the credentials behind a provider's tab: the
+    // old account's place is saved and its store dropped, and the tab is
+    // rebuilt for the new account (or left once no account is connected)
+    use_effect(move || {
+        for provider in Provider::ALL {
+            let tab = ActiveTab::Account(provider);
+            let tab_key = tab.key();
+            let active_key = auth.active_account(provider).map(|account| account.key());
+            let built_for = store_accounts.peek().get(&tab_key).cloned();
+            if built_for == active_key {
+                continue;
+            }
+            // Disconnected accounts have nothing to come back to
+            let previous = built_for.filter(|key| auth.account(key).is_some());
+            if let (Some(previous), Some(store)) = (previous, stores.peek().get(&tab_key).copied()) {
+                if let Some(anchor) = anchor_in(controller, store) {
+                    positions.remember(&previous, &anchor);
+                }
+            }
+            stores.write().remove(&tab_key);
+            store_accounts.write().remove(&tab_key);
+            if *active.peek() == tab {
+                match active_key {
+                    Some(_) => open_tab(tab),
+                    None => open_tab(launch_tab.clone()),
+                }
+            }
+        }
+    });
+
+    // Scroll a rebuilt account list back to its saved position once the
+    // first page is in, giving up if its item isn't among it
+    use_effect(move || {
+        let Some(anchor) = pending_restore() else {
+            return;
+        };
+        let Some(store) = stores.read().get(&active().key()).copied() else {
+            return;
+        };
+        let loaded = store.ordered_items();
+        if loaded.is_empty() || store.status.read().is_loading() {
+            return;
+        }
+        if loaded.iter().any(|item| item.id == anchor.item_id) {
+            controller.restore(anchor);
+        }
+        pending_restore.set(None);
+    });
+
+    // Keep the active account's place as the list scrolls onto another
+    // item; switching accounts saves the exact position
+    use_effect(move || {
+        let tab_key = active().key();
+        let Some(store) = stores.read().get(&tab_key).copied() else {
+            return;
+        };
+        let Some(anchor) = anchor_in(controller, store) else {
+            return;
+        };
+        if pending_restore.read().is_some() {
+            return;
+        }
+        let Some(account) = store_accounts.peek().get(&tab_key).cloned() else {
+            return;
+        };
+        if positions.anchor(&account).is_some_and(|saved| saved.item_id == anchor.item_id) {
+            return;
+        }
+        positions.remember(&account, &anchor);
+    });
+
+    // Refreshes and unread counts requested over the control channel, for
+    // the feed named by its key or the one on screen
+    let read_state = try_use_context::<ReadState>();
+    use_control_handler(move |request| {
+        let feed = match &request {
+            ControlRequest::Refresh { feed } | ControlRequest::UnreadCount { feed } => feed.clone(),
+            ControlRequest::Post { .. } => {
+                return ControlResponse::Error { message: "Posts go to the inbox queue".to_string() };
+            }
+        };
+        let key = feed.unwrap_or_else(|| active.peek().key());
+        let Some(tab) = ActiveTab::from_key(feeds, &key) else {
+            return ControlResponse::Error { message: format!("No feed {}", key) };
+        };
+        let Some(store) = stores.peek().get(&key).copied() else {
+            return ControlResponse::Error { message: format!("{} hasn't been opened yet", tab.title()) };
+        };
+        match request {
+            ControlRequest::Refresh { .. } => {
+                spawn(async move {
+                    store.refresh().await;
+                });
+                ControlResponse::Ok { message: format!("Refreshing {}", tab.title()) }
+            }
+            _ => {
+                // Only named feeds keep track of what was read
+                let ActiveTab::Feed(feed) = tab else {
+                    return ControlResponse::Error { message: format!("{} doesn't track unread items", tab.title()) };
+                };
+                let newest_seen = read_state.and_then(|read_state| read_state.newest_seen(feed.id));
+                let items = store.items.peek();
+                let read = newest_seen
+                    .and_then(|id| items.iter().position(|item| item.id == id))
+                    .map_or(0, |index| index + 1);
+                ControlResponse::UnreadCount { feed: key, count: items.len() - read }
+            }
+        }
+    });
+
+    let refresh_active = move || {
+        if let Some(store) = stores.peek().get(&active.peek().key()).copied() {
+            spawn(async move {
+                store.refresh().await;
+            });
+        }
+    };
+
+    // Keyboard shortcuts act on the feed on screen
+    use_shortcuts(move |action| match action {
+        ShortcutAction::NextItem => controller.scroll_to(ScrollRequest::Step(1)),
+        ShortcutAction::PreviousItem => controller.scroll_to(ScrollRequest::Step(-1)),
+        ShortcutAction::OpenItem => controller.open_current(),
+        ShortcutAction::Refresh => refresh_active(),
+        ShortcutAction::Search => show_search.set(true),
+        ShortcutAction::Help => show_shortcuts.toggle(),
+        ShortcutAction::CommandPalette => show_palette.toggle(),
+    });
+
+    // Commands offered by the palette for the feed on screen and the app
+    let mut theme = use_context::<ThemeSettings>();
+    let mut reading_width = use_context::<ReadingWidthSettings>();
+    use_command(|| Command::new("refresh", "Refresh feed", Callback::new(move |_| refresh_active())));
+    use_command(|| {
+        Command::new(
+            "jump_to_date",
+            "Jump to date",
+            Callback::new(move |date: String| {
+                let Some(store) = stores.peek().get(&active.peek().key()).copied() else {
+                    return;
+                };
+                match item_on_or_before(&store.ordered_items(), &date) {
+                    Ok(id) => controller.jump_to_item(id),
+                    Err(e) => report(e),
+                }
+            }),
+        )
+        .with_argument("Date, e.g. 2024-05-31")
+    });
+    use_command(|| Command::new("search", "Search feeds", Callback::new(move |_| show_search.set(true))));
+    use_command(|| Command::new("settings", "Open settings", Callback::new(move |_| show_settings.set(true))));
+    use_command(|| Command::new("toggle_theme", "Toggle dark theme", Callback::new(move |_| theme.toggle())));
+    use_command(|| Command::new("cycle_reading_width", "Cycle reading width", Callback::new(move |_| reading_width.cycle())));
+    use_command(|| Command::new("shortcuts", "Show keyboard shortcuts", Callback::new(move |_| show_shortcuts.set(true))));
+    use_command(|| Command::new("toggle_paged", "Toggle paged mode", Callback::new(move |_| {
+        let toggled = layout.peek().toggled(FeedLayout::Pages);
+        layout.set(toggled);
+    })));
+    use_command(|| Command::new("toggle_split", "Toggle split view", Callback::new(move |_| {
+        let toggled = layout.peek().toggled(FeedLayout::Split);
+        layout.set(toggled);
+    })));
+
+    // One command per tab, kept in step with the tab bar
+    let mut registry = use_context::<CommandRegistry>();
+    use_effect(move || {
+        let mut tabs: Vec<ActiveTab> = feeds
+            .iter()
+            .copied()
+            .filter(shows_feed)
+            .map(ActiveTab::Feed)
+            .collect();
+        tabs.extend(subscriptions.folders().into_iter().map(|folder| ActiveTab::Folder(folder.title)));
+        tabs.extend(
+            Provider::ALL
+                .into_iter()
+                .filter(|provider| auth.active_account(*provider).is_some())
+                .map(ActiveTab::Account),
+        );
+        let commands = tabs
+            .into_iter()
+            .map(|tab| {
+                let id = format!("switch_feed:{}", tab.key());
+                let label = tab.title();
+                Command::new(id, label, Callback::new(move |_| open_tab(tab.clone())))
+            })
+            .collect();
+        registry.set_group("Switch feed", commands);
+    });
+
+    let feed_title = move |key: String| {
+        ActiveTab::from_key(feeds, &key).map(|tab| tab.title()).unwrap_or(key)
+    };
+
+    let active_tab = active();
+    let active_key = active_tab.key();
+    let active_store = stores.read().get(&active_key).copied();
+    let folders = subscriptions.folders();
+    let account_tabs: Vec<Provider> =
+        Provider::ALL.into_iter().filter(|provider| auth.active_account(*provider).is_some()).collect();
+
+    rsx! {
+        // Tab bar
+        nav {
+            role: "tablist",
+            style: "
+                display: flex;
+                align-items: center;
+                gap: 4px;
+                margin-bottom: 12px;
+                border-bottom: 1px solid #e2e8f0;
+            ",
+            for feed in feeds.iter().copied().filter(shows_feed) {
+                button {
+                    key: "{feed.id}",
+                    role: "tab",
+                    aria_selected: "{active_tab == ActiveTab::Feed(feed)}",
+                    style: tab_style(active_tab == ActiveTab::Feed(feed)),
+                    onclick: move |_| open_tab(ActiveTab::Feed(feed)),
+                    "{feed.title}"
+                }
+            }
+
+            // One tab per imported folder
+            for (title, feed_count) in folders.into_iter().map(|folder| (folder.title, folder.feeds.len())) {
+                button {
+                    key: "folder-{title}",
+                    role: "tab",
+                    aria_selected: "{active_tab == ActiveTab::Folder(title.clone())}",
+                    style: tab_style(active_tab == ActiveTab::Folder(title.clone())),
+                    title: "{feed_count} feeds",
+                    onclick: {
+                        let title = title.clone();
+                        move |_| open_tab(ActiveTab::Folder(title.clone()))
+                    },
+                    "{title}"
+                }
+            }
+
+            // One tab per provider with a connected account, showing the
+            // account picked in the header's profile switcher
+            for provider in account_tabs {
+                button {
+                    key: "account-{provider.id()}",
+                    role: "tab",
+                    aria_selected: "{active_tab == ActiveTab::Account(provider)}",
+                    style: tab_style(active_tab == ActiveTab::Account(provider)),
+                    onclick: move |_| open_tab(ActiveTab::Account(provider)),
+                    "{provider.name()}"
+                }
+            }
+
+            // Search across every item loaded so far
+            button {
+                aria_pressed: "{show_search()}",
+                style: format!("
+                    margin-left: auto;
+                    padding: 6px 12px;
+                    border: none;
+                    background: transparent;
+                    font-size: 13px;
+                    color: {};
+                    cursor: pointer;
+                ", if show_search() { "#0f172a" } else { "#475569" }),
+                onclick: move |_| show_search.toggle(),
+                "Search"
+            }
+
+            // One item per page, the list beside the selected item, or the list
+            for (option, label) in [(FeedLayout::Pages, "Pages"), (FeedLayout::Split, "Split")] {
+                button {
+                    key: "{label}",
+                    aria_pressed: "{layout() == option}",
+                    style: format!("
+                        padding: 6px 12px;
+                        border: none;
+                        background: transparent;
+                        font-size: 13px;
+                        color: {};
+                        cursor: pointer;
+                    ", if layout() == option { "#0f172a" } else { "#475569" }),
+                    onclick: move |_| {
+                        let toggled = layout.peek().toggled(option);
+                        layout.set(toggled);
+                    },
+                    "{label}"
+                }
+            }
+
+            // Diagnostics for every synced source
+            button {
+                aria_pressed: "{show_health()}",
+                style: format!("
+                    padding: 6px 12px;
+                    border: none;
+                    background: transparent;
+                    font-size: 13px;
+                    color: {};
+                    cursor: pointer;
+                ", if show_health() { "#0f172a" } else { "#475569" }),
+                onclick: move |_| show_health.toggle(),
+                "Sources"
+            }
+
+            // App-wide settings
+            button {
+                aria_pressed: "{show_settings()}",
+                style: format!("
+                    padding: 6px 12px;
+                    border: none;
+                    background: transparent;
+                    font-size: 13px;
+                    color: {};
+                    cursor: pointer;
+                ", if show_settings() { "#0f172a" } else { "#475569" }),
+                onclick: move |_| show_settings.toggle(),
+                "Settings"
+            }
+
+            // Subscriptions exported from another reader
+            label {
+                style: "
+                    padding: 6px 12px;
+                    font-size: 13px;
+                    color: #475569;
+                    cursor: pointer;
+                ",
+                "Import OPML"
+                input {
+                    r#type: "file",
+                    accept: ".opml,.xml,text/xml,text/x-opml",
+                    style: "display: none;",
+                    onchange: import_opml,
+                }
+            }
+        }
+
+        if let Some(status) = import_status() {
+            p {
+                role: "status",
+                style: "margin: 0 0 12px; font-size: 13px; color: #64748b;",
+                "{status}"
+            }
+        }
+
+        // Feeds of a large import parse in the background; the list stays usable
+        if let Some((finished, submitted)) = work.pending().filter(|(_, submitted)| *submitted > 1) {
+            p {
+                role: "status",
+                style: "margin: 0 0 12px; font-size: 13px; color: #64748b;",
+                "Parsing {finished} of {submitted}…"
+            }
+        }
+
+        if show_search() {
+            SearchPanel { on_open: open_hit, feed_title }
+        }
+
+        if show_health() {
+            SourceHealthPanel {}
+        }
+
+        if show_settings() {
+            SettingsPanel {}
+        }
+
+        if show_shortcuts() {
+            ShortcutHelp { on_close: move |_| show_shortcuts.set(false) }
+        }
+
+        if show_palette() {
+            CommandPalette { on_close: move |_| show_palette.set(false) }
+        }
+
+        // Stories sit above the home feed only
+        if active_tab.kind() == Some(FeedKind::Home) {
+            StoryRail {}
+        }
+
+        // Active feed, centered within the reading width picked in settings
+        div {
+            style: format!(
+                "width: 100%; max-width: {}; margin: 0 auto;",
+                reading_width.reading_width().max_width().map_or("none".to_string(), |width| format!("{}px", width))
+            ),
+
+            // Keyed so each feed gets its own list instance
+            if let Some(store) = active_store {
+                match active_tab {
+                    ActiveTab::Feed(feed) if feed.kind == FeedKind::Saved => rsx! {
+                        SavedFeed { key: "{active_key}", store }
+                    },
+                    ActiveTab::Feed(feed) if feed.kind == FeedKind::Alerts => rsx! {
+                        AlertsFeed { key: "{active_key}", store }
+                    },
+                    ActiveTab::Feed(feed) if layout() == FeedLayout::Pages => rsx! {
+                        PagedView {
+                            key: "{active_key}-paged",
+                            store,
+                            feed_id: feed.id,
+                            controller,
+                        }
+                    },
+                    ActiveTab::Feed(feed) if layout() == FeedLayout::Split => rsx! {
+                        SplitView {
+                            key: "{active_key}-split",
+                            store,
+                            feed_id: feed.id,
+                            controller,
+                        }
+                    },
+                    ActiveTab::Feed(feed) => rsx! {
+                        VirtualList {
+                            key: "{active_key}",
+                            store,
+                            highlight_new_items: true,
+                            feed_id: feed.id,
+                            controller,
+                            max_items: MAX_LOADED_ITEMS,
+                            interleave: if feed.kind == FeedKind::Home { Some(Callback::new(suggestion_slot)) } else { None },
+                        }
+                    },
+                    ActiveTab::Folder(_) | ActiveTab::Account(_) if layout() == FeedLayout::Pages => rsx! {
+                        PagedView {
+                            key: "{active_key}-paged",
+                            store,
+                            controller,
+                        }
+                    },
+                    ActiveTab::Folder(_) | ActiveTab::Account(_) if layout() == FeedLayout::Split => rsx! {
+                        SplitView {
+                            key: "{active_key}-split",
+                            store,
+                            controller,
+                        }
+                    },
+                    ActiveTab::Folder(_) | ActiveTab::Account(_) => rsx! {
+                        VirtualList {
+                            key: "{active_key}",
+                            store,
+                            highlight_new_items: true,
+                            controller,
+                            max_items: MAX_LOADED_ITEMS,
+                        }
+                    },
+                }
+            }
+        }
+    }
+}
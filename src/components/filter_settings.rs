@@ -0,0 +1,121 @@
+use dioxus::prelude::*;
+
+use crate::persistence::filters::{ContentFilters, FilterLevel};
+
+// Style of a row holding one profile setting
+const ROW_STYLE: &str = "display: flex; align-items: center; gap: 8px; margin: 0 0 8px;";
+
+// Entries of a comma-separated input
+fn split_list(value: &str) -> Vec<String> {
+    value.split(',').map(str::to_string).collect()
+}
+
+// Content filter profiles: which one is in effect and what it filters.
+// Shown as a section of the settings panel.
+#[component]
+pub fn FilterSettings() -> Element {
+    let mut filters = use_context::<ContentFilters>();
+    let active = filters.active();
+    let profiles = filters.profiles();
+    let mut new_profile = use_signal(String::new);
+    let mut status = use_signal(|| None::<String>);
+
+    rsx! {
+        h3 { style: "margin: 8px 0; font-size: 13px; color: #64748b;", "Filter profile" }
+        p {
+            style: "margin: 0 0 8px; color: #64748b;",
+            "Items the active profile doesn't allow are left out of every feed and raise no alerts."
+        }
+
+        div {
+            style: ROW_STYLE,
+            select {
+                aria_label: "Active filter profile",
+                onchange: move |evt| filters.set_active(&evt.value()),
+                for profile in profiles.iter() {
+                    option {
+                        key: "{profile.name}",
+                        value: "{profile.name}",
+                        selected: profile.name == active.name,
+                        "{profile.name}"
+                    }
+                }
+            }
+            if profiles.len() > 1 {
+                button {
+                    onclick: {
+                        let name = active.name.clone();
+                        move |_| filters.remove_profile(&name)
+                    },
+                    "Remove"
+                }
+            }
+        }
+        div {
+            style: ROW_STYLE,
+            input {
+                r#type: "text",
+                placeholder: "Profile name",
+                aria_label: "New profile name",
+                value: "{new_profile}",
+                oninput: move |evt| new_profile.set(evt.value()),
+            }
+            button {
+                disabled: new_profile().trim().is_empty(),
+                onclick: move |_| match filters.add_profile(&new_profile()) {
+                    Ok(()) => {
+                        new_profile.set(String::new());
+                        status.set(None);
+                    }
+                    Err(e) => status.set(Some(e.to_string())),
+                },
+                "Add profile"
+            }
+        }
+        if let Some(status) = status() {
+            p { role: "status", style: "margin: 0 0 8px; color: #64748b;", "{status}" }
+        }
+
+        label {
+            style: ROW_STYLE,
+            title: "Standard leaves out spam, Strict every sensitive or spam item",
+            "Flagged content"
+            select {
+                onchange: move |evt| {
+                    let level = FilterLevel::ALL.into_iter().find(|level| level.label() == evt.value());
+                    if let Some(level) = level {
+                        filters.set_level(level);
+                    }
+                },
+                for level in FilterLevel::ALL {
+                    option {
+                        value: level.label(),
+                        selected: level == active.level,
+                        "{level.label()}"
+                    }
+                }
+            }
+        }
+        label {
+            style: ROW_STYLE,
+            "Muted words"
+            input {
+                style: "flex: 1;",
+                placeholder: "Comma-separated",
+                value: "{active.muted_keywords.join(\", \")}",
+                onchange: move |evt| filters.set_muted_keywords(split_list(&evt.value())),
+            }
+        }
+        label {
+            style: ROW_STYLE,
+            title: "Feed or subscription names; leave empty to allow every source",
+            "Allowed sources"
+            input {
+                style: "flex: 1;",
+                placeholder: "All sources",
+                value: "{active.allowed_sources.join(\", \")}",
+                onchange: move |evt| filters.set_allowed_sources(split_list(&evt.value())),
+            }
+        }
+    }
+}
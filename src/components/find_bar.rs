@@ -0,0 +1,113 @@
+use dioxus::prelude::*;
+use std::ops::Range;
+
+/// Byte ranges of the case-insensitive matches of `query` in `text`
+pub fn match_ranges(text: &str, query: &str) -> Vec<Range<usize>> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let (haystack, needle) = (text.to_lowercase(), query.to_lowercase());
+    // Lowercasing can change byte lengths; match exactly rather than report
+    // ranges that don't line up with the text
+    let (haystack, needle) = if haystack.len() == text.len() {
+        (haystack, needle)
+    } else {
+        (text.to_string(), query.to_string())
+    };
+    haystack
+        .match_indices(&needle)
+        .map(|(start, found)| start..start + found.len())
+        .collect()
+}
+
+/// `text` split into runs, each flagged when it is a match of `query`
+pub fn highlight_segments(text: &str, query: &str) -> Vec<(String, bool)> {
+    let mut segments = Vec::new();
+    let mut position = 0;
+    for range in match_ranges(text, query) {
+        if range.start > position {
+            segments.push((text[position..range.start].to_string(), false));
+        }
+        segments.push((text[range.clone()].to_string(), true));
+        position = range.end;
+    }
+    if position < text.len() || segments.is_empty() {
+        segments.push((text[position..].to_string(), false));
+    }
+    segments
+}
+
+#[derive(PartialEq, Props, Clone)]
+pub struct FindBarProps {
+    pub query: Signal<String>,
+    // Number of items matching the query
+    pub count: usize,
+    // Index of the match scrolled to
+    pub current: usize,
+    pub on_next: EventHandler<()>,
+    pub on_previous: EventHandler<()>,
+    pub on_close: EventHandler<()>,
+}
+
+// Find bar over a feed's loaded items, opened with Ctrl+F
+#[component]
+pub fn FindBar(props: FindBarProps) -> Element {
+    let mut query = props.query;
+    let status = if query.read().trim().is_empty() {
+        String::new()
+    } else if props.count == 0 {
+        "No matches".to_string()
+    } else {
+        format!("{} of {}", props.current + 1, props.count)
+    };
+
+    rsx! {
+        div {
+            role: "search",
+            style: "
+                flex-basis: 100%;
+                display: flex;
+                align-items: center;
+                gap: 8px;
+                font-size: 13px;
+                color: #64748b;
+            ",
+            input {
+                r#type: "search",
+                aria_label: "Find in feed",
+                placeholder: "Find in feed",
+                style: "flex: 1; padding: 6px 8px; border: 1px solid #e2e8f0; border-radius: 6px;",
+                value: "{query}",
+                onmounted: move |evt| async move {
+                    let _ = evt.set_focus(true).await;
+                },
+                oninput: move |evt| query.set(evt.value()),
+                onkeydown: move |evt| match evt.key() {
+                    Key::Enter if evt.modifiers().shift() => props.on_previous.call(()),
+                    Key::Enter => props.on_next.call(()),
+                    Key::Escape => props.on_close.call(()),
+                    _ => {}
+                },
+            }
+            span { aria_live: "polite", style: "min-width: 64px;", "{status}" }
+            for (label, handler) in [("Previous", props.on_previous), ("Next", props.on_next), ("Close", props.on_close)] {
+                button {
+                    key: "{label}",
+                    style: "
+                        padding: 6px 10px;
+                        border-radius: 6px;
+                        border: 1px solid #e2e8f0;
+                        background: white;
+                        color: #0f172a;
+                        font-size: 13px;
+                        cursor: pointer;
+                    ",
+                    disabled: label != "Close" && props.count == 0,
+                    onclick: move |_| handler.call(()),
+                    "{label}"
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use dioxus::html::geometry::PixelsVector2D;
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+use super::virtual_rows::EdgeScroll;
+use super::wheel::wheel_pixels;
+
+// Horizontal list configuration
+const BUFFER_SIZE: usize = 3; // Extra items to render outside the viewport
+const DEFAULT_VIEWPORT_WIDTH: f64 = 760.0; // Used until the first scroll reports a width
+const EDGE_TOLERANCE: f64 = 1.0; // Distance from an end that counts as reaching it
+
+/// Handle for scrolling a `HorizontalList` from outside it.
+///
+/// Independent of any `VirtualListController`, so a strip nested in a row
+/// of a feed scrolls on its own. Create one with
+/// [`use_horizontal_list_controller`] and pass it as the list's
+/// `controller` prop; lists without one create their own.
+#[derive(Clone, Copy, PartialEq)]
+pub struct HorizontalListController {
+    // Index of the item to scroll to
+    request: Signal<Option<usize>>,
+    // Scroll offset of the list in pixels, see `scroll_offset`
+    offset: CopyValue<f64>,
+}
+
+impl HorizontalListController {
+    pub fn new() -> Self {
+        Self {
+            request: Signal::new(None),
+            offset: CopyValue::new(0.0),
+        }
+    }
+
+    /// Scroll the item at `index` to the start of the list
+    pub fn scroll_to_index(&mut self, index: usize) {
+        self.request.set(Some(index));
+    }
+
+    /// Current scroll offset of the list in pixels. Not reactive.
+    pub fn scroll_offset(&self) -> f64 {
+        *self.offset.peek()
+    }
+
+    // Take the pending request. Called by the list rendering it.
+    fn take_request(&mut self) -> Option<usize> {
+        if self.request.read().is_none() {
+            return None;
+        }
+        self.request.write().take()
+    }
+}
+
+impl Default for HorizontalListController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Create a controller owned by the calling component
+pub fn use_horizontal_list_controller() -> HorizontalListController {
+    use_hook(HorizontalListController::new)
+}
+
+#[derive(PartialEq, Props, Clone)]
+pub struct HorizontalListProps {
+    pub item_count: usize,
+    // Width of each item including its gap
+    pub item_width: f64,
+    pub height: f64,
+    // Renders the item at an index; called only for items in the window
+    pub render_item: Callback<usize, Element>,
+    // Handle for scrolling the list from outside; the list creates its own
+    // when none is given
+    pub controller: Option<HorizontalListController>,
+    // Turn vertical wheel scrolls over the list into sideways ones, e.g. for
+    // a media strip inside a feed row. At either end they pass on to the
+    // list around it, unless `edge_scroll` contains them.
+    #[props(default)]
+    pub wheel_scrolls_sideways: bool,
+    // Whether scrolls past the list's ends carry on to what is around it
+    #[props(default)]
+    pub edge_scroll: EdgeScroll,
+}
+
+// Horizontally scrolling list that only renders the items around the
+// viewport, the sideways counterpart of `VirtualList`
+#[component]
+pub fn HorizontalList(props: HorizontalListProps) -> Element {
+    let item_width = props.item_width;
+    let mut scroll_left = use_signal(|| 0.0);
+    let mut client_width = use_signal(|| DEFAULT_VIEWPORT_WIDTH);
+    let mut element = use_signal(|| None::<Rc<MountedData>>);
+    let own_controller = use_horizontal_list_controller();
+    let mut controller = props.controller.unwrap_or(own_controller);
+    let wheel_scrolls_sideways = props.wheel_scrolls_sideways;
+    let edge_scroll = props.edge_scroll;
+
+    let total_width = props.item_count as f64 * item_width;
+    let visible_count = (client_width() / item_width).ceil() as usize;
+    let start_index = ((scroll_left() / item_width) as usize).saturating_sub(BUFFER_SIZE);
+    let end_index = (start_index + visible_count + BUFFER_SIZE * 2).min(props.item_count);
+
+    // Scroll the list itself; the webview stops it at the far end
+    let scroll_list = move |left: f64| {
+        let Some(element) = element.peek().clone() else {
+            return;
+        };
+        let left = left.max(0.0);
+        let mut controller = controller;
+        controller.offset.set(left);
+        spawn(async move {
+            if let Err(e) = element.scroll(PixelsVector2D::new(left, 0.0), ScrollBehavior::Instant).await {
+                eprintln!("Failed to scroll horizontal list: {}", e);
+            }
+        });
+    };
+
+    use_effect(move || {
+        if let Some(index) = controller.take_request() {
+            scroll_list(index as f64 * item_width);
+        }
+    });
+
+    rsx! {
+        div {
+            style: format!("
+                height: {}px;
+                overflow-x: auto;
+                overflow-y: hidden;
+                overscroll-behavior-x: {};
+                position: relative;
+            ", props.height, edge_scroll.overscroll_behavior()),
+            onmounted: move |evt: Event<MountedData>| async move {
+                element.set(Some(evt.data()));
+                if let Ok(rect) = evt.data().get_client_rect().await {
+                    client_width.set(rect.width());
+                }
+            },
+            onscroll: move |evt: Event<ScrollData>| {
+                // Scrolling sideways doesn't move a list this one is nested in
+                evt.stop_propagation();
+                let left = evt.data().scroll_left() as f64;
+                controller.offset.set(left);
+                scroll_left.set(left);
+                client_width.set(evt.data().client_width() as f64);
+            },
+            onwheel: move |evt: Event<WheelData>| {
+                if !wheel_scrolls_sideways {
+                    return;
+                }
+                let (delta_x, delta_y) = wheel_pixels(evt.data().delta(), *client_width.peek());
+                // Sideways wheels and trackpads scroll the list natively
+                if delta_y.abs() <= delta_x.abs() {
+                    return;
+                }
+                // The list's own scrolls may not have reported back yet
+                let left = controller.scroll_offset();
+                let max_left = (total_width - *client_width.peek()).max(0.0);
+                let at_edge = (delta_y < 0.0 && left <= EDGE_TOLERANCE)
+                    || (delta_y > 0.0 && left >= max_left - EDGE_TOLERANCE);
+                if at_edge {
+                    // The list around carries on scrolling, unless contained
+                    if edge_scroll == EdgeScroll::Contain {
+                        evt.prevent_default();
+                        evt.stop_propagation();
+                    }
+                    return;
+                }
+                evt.prevent_default();
+                evt.stop_propagation();
+                scroll_list(left + delta_y);
+            },
+
+            div {
+                style: format!("width: {}px; height: 100%; position: relative;", total_width),
+
+                for index in start_index..end_index {
+                    div {
+                        key: "{index}",
+                        style: format!("
+                            position: absolute;
+                            top: 0;
+                            left: {}px;
+                            width: {}px;
+                            height: 100%;
+                        ", index as f64 * item_width, item_width),
+                        {props.render_item.call(index)}
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+use super::popover::{Anchor, Popover};
+use crate::store::FeedStore;
+
+// Width of the card
+const CARD_WIDTH: f64 = 300.0;
+
+// Tallest the card grows before its item list scrolls
+const CARD_MAX_HEIGHT: f64 = 320.0;
+
+// Recent items listed on the card
+const RECENT_ITEMS: usize = 3;
+
+// Longest excerpt of a recent item shown on the card
+const EXCERPT_CHARS: usize = 90;
+
+// An author name being hovered, and where it is
+#[derive(Clone, PartialEq, Debug)]
+pub struct AuthorHover {
+    pub author: String,
+    pub anchor: Anchor,
+}
+
+#[derive(PartialEq, Props, Clone)]
+pub struct AuthorCardProps {
+    pub hover: AuthorHover,
+    // Feed the author's items are looked up in
+    pub store: FeedStore,
+    // Scroll container of the feed, which the card stays inside
+    pub container: Signal<Option<Rc<MountedData>>>,
+    // Pointer over the card, so leaving the name for the card keeps it open
+    pub on_hover_change: EventHandler<bool>,
+    pub on_close: EventHandler<()>,
+}
+
+// Card shown over the feed while an author's name is hovered: who they are
+// in this feed and their latest items.
+#[component]
+pub fn AuthorCard(props: AuthorCardProps) -> Element {
+    let on_hover_change = props.on_hover_change;
+    let on_close = props.on_close;
+    let AuthorHover { author, anchor } = props.hover;
+
+    let items = props.store.items_by(&author);
+    let mut sources: Vec<&str> = Vec::new();
+    for item in &items {
+        for source in &item.sources {
+            if !sources.contains(&source.as_str()) {
+                sources.push(source);
+            }
+        }
+    }
+    let last_posted = items
+        .first()
+        .and_then(|item| item.published_at)
+        .and_then(chrono::DateTime::from_timestamp_millis)
+        .map(|date| date.format("%b %-d, %Y").to_string());
+
+    rsx! {
+        Popover {
+            anchor,
+            container: props.container,
+            aria_label: format!("About {}", author),
+            on_hover_change: move |hovered| {
+                on_hover_change.call(hovered);
+                // Leaving the card closes it
+                if !hovered {
+                    on_close.call(());
+                }
+            },
+            on_close: move |_| on_close.call(()),
+            div {
+                style: format!("
+                    width: {}px;
+                    max-height: {}px;
+                    box-sizing: border-box;
+                    display: flex;
+                    flex-direction: column;
+                    gap: 8px;
+                    padding: 12px;
+                    border-radius: 8px;
+                    border: 1px solid #e2e8f0;
+                    background: white;
+                    box-shadow: 0 8px 24px rgba(15, 23, 42, 0.15);
+                    font-size: 13px;
+                    color: #334155;
+                ", CARD_WIDTH, CARD_MAX_HEIGHT),
+
+                strong { style: "font-size: 14px; color: #0f172a;", "{author}" }
+                span {
+                    style: "color: #64748b;",
+                    if items.len() == 1 { "1 item in this feed" } else { "{items.len()} items in this feed" }
+                    if let Some(last_posted) = last_posted {
+                        " · last posted {last_posted}"
+                    }
+                }
+                if !sources.is_empty() {
+                    span { style: "color: #64748b;", "From {sources.join(\", \")}" }
+                }
+
+                if !items.is_empty() {
+                    ul {
+                        style: "margin: 0; padding: 0; list-style: none; overflow-y: auto; display: flex; flex-direction: column; gap: 6px;",
+                        for item in items.iter().take(RECENT_ITEMS) {
+                            li {
+                                key: "{item.id}",
+                                style: "padding-top: 6px; border-top: 1px solid #f1f5f9; line-height: 1.4;",
+                                {excerpt(&item.content)}
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Start of `content`, cut at a character boundary
+fn excerpt(content: &str) -> String {
+    let mut excerpt: String = content.chars().take(EXCERPT_CHARS).collect();
+    if excerpt.len() < content.len() {
+        excerpt.push('…');
+    }
+    excerpt
+}
@@ -0,0 +1,125 @@
+use dioxus::prelude::*;
+use dioxus_feed_core::VirtualFeedItem;
+use std::rc::Rc;
+
+use super::popover::{Anchor, Popover};
+use super::selection::copy_to_clipboard;
+use crate::error::{report, FeedError};
+use crate::persistence::bookmarks::Bookmarks;
+use crate::urlcleaner::clean_url;
+
+// Style of a menu entry
+const ENTRY_STYLE: &str = "
+    display: block;
+    width: 100%;
+    padding: 6px 12px;
+    border: none;
+    background: transparent;
+    color: #0f172a;
+    font-size: 13px;
+    text-align: left;
+    cursor: pointer;
+";
+
+#[derive(PartialEq, Props, Clone)]
+pub struct ItemMenuProps {
+    pub item: VirtualFeedItem,
+    // Where the menu was opened
+    pub anchor: Anchor,
+    // Scroll container of the feed, which the menu stays inside
+    pub container: Signal<Option<Rc<MountedData>>>,
+    // Opens the item's link in reader mode
+    pub on_read: EventHandler<String>,
+    pub on_close: EventHandler<()>,
+}
+
+// Context menu of a row, opened with a right click
+#[component]
+pub fn ItemMenu(props: ItemMenuProps) -> Element {
+    let on_close = props.on_close;
+    let on_read = props.on_read;
+    let item = props.item;
+    // Bookmarks are optional so the list can be used without the app shell
+    let bookmarks = try_use_context::<Bookmarks>();
+    let is_saved = bookmarks.is_some_and(|bookmarks| bookmarks.is_saved(&item.id));
+
+    rsx! {
+        Popover {
+            anchor: props.anchor,
+            container: props.container,
+            role: "menu".to_string(),
+            aria_label: "Item actions".to_string(),
+            modal: true,
+            on_close: move |_| on_close.call(()),
+            div {
+                style: "
+                    min-width: 180px;
+                    padding: 4px 0;
+                    border-radius: 8px;
+                    border: 1px solid #e2e8f0;
+                    background: white;
+                    box-shadow: 0 8px 24px rgba(15, 23, 42, 0.15);
+                ",
+                if let Some(link) = item.link.clone() {
+                    button {
+                        role: "menuitem",
+                        style: ENTRY_STYLE,
+                        onclick: {
+                            let link = link.clone();
+                            move |_| {
+                                on_read.call(link.clone());
+                                on_close.call(());
+                            }
+                        },
+                        "Read"
+                    }
+                    button {
+                        role: "menuitem",
+                        style: ENTRY_STYLE,
+                        onclick: move |_| {
+                            let link = clean_url(&link);
+                            spawn(async move {
+                                if let Err(e) = copy_to_clipboard(link).await {
+                                    report(FeedError::Clipboard { what: "link", message: e });
+                                }
+                            });
+                            on_close.call(());
+                        },
+                        "Copy link"
+                    }
+                }
+                button {
+                    role: "menuitem",
+                    style: ENTRY_STYLE,
+                    onclick: {
+                        let content = item.content.clone();
+                        move |_| {
+                            let content = content.clone();
+                            spawn(async move {
+                                if let Err(e) = copy_to_clipboard(content).await {
+                                    report(FeedError::Clipboard { what: "text", message: e });
+                                }
+                            });
+                            on_close.call(());
+                        }
+                    },
+                    "Copy text"
+                }
+                if let Some(mut bookmarks) = bookmarks {
+                    button {
+                        role: "menuitem",
+                        style: ENTRY_STYLE,
+                        onclick: {
+                            let item = item.clone();
+                            move |_| {
+                                bookmarks.toggle(item.clone());
+                                on_close.call(());
+                            }
+                        },
+                        if is_saved { "Unsave" } else { "Save" }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use dioxus::prelude::*;
+
+// An image opened full size, with its alt text
+#[derive(Clone, PartialEq, Debug)]
+pub struct LightboxImage {
+    pub src: String,
+    pub alt: Option<String>,
+}
+
+#[derive(PartialEq, Props, Clone)]
+pub struct LightboxProps {
+    pub image: LightboxImage,
+    pub on_close: EventHandler<()>,
+}
+
+// Full-size view of an item's image over the feed, captioned with its alt
+// text. Escape or a click outside the image closes it.
+#[component]
+pub fn Lightbox(props: LightboxProps) -> Element {
+    let on_close = props.on_close;
+    let LightboxImage { src, alt } = props.image;
+
+    rsx! {
+        div {
+            role: "dialog",
+            aria_modal: "true",
+            aria_label: "Image",
+            tabindex: "0",
+            style: "
+                position: fixed;
+                inset: 0;
+                z-index: 1000;
+                display: flex;
+                align-items: center;
+                justify-content: center;
+                background: rgba(15, 23, 42, 0.85);
+                outline: none;
+            ",
+            onmounted: move |evt| async move {
+                let _ = evt.set_focus(true).await;
+            },
+            onkeydown: move |evt| {
+                if evt.key() == Key::Escape {
+                    on_close.call(());
+                }
+            },
+            onclick: move |_| on_close.call(()),
+            figure {
+                style: "
+                    margin: 0;
+                    display: flex;
+                    flex-direction: column;
+                    align-items: center;
+                    gap: 12px;
+                    max-width: 90vw;
+                ",
+                // Clicks on the image and caption don't close the view
+                onclick: move |evt| evt.stop_propagation(),
+                img {
+                    src: "{src}",
+                    alt: alt.clone().unwrap_or_default(),
+                    style: "max-width: 90vw; max-height: 80vh; object-fit: contain; border-radius: 6px;",
+                }
+                figcaption {
+                    style: "max-width: 640px; color: #e2e8f0; font-size: 14px; line-height: 1.5; text-align: center;",
+                    match &alt {
+                        Some(alt) => rsx! { "{alt}" },
+                        None => rsx! { span { style: "color: #94a3b8;", "No description available" } },
+                    }
+                }
+                button {
+                    style: "
+                        padding: 6px 14px;
+                        border-radius: 6px;
+                        border: 1px solid #475569;
+                        background: transparent;
+                        color: #e2e8f0;
+                        font-size: 13px;
+                        cursor: pointer;
+                    ",
+                    onclick: move |_| on_close.call(()),
+                    "Close"
+                }
+            }
+        }
+    }
+}
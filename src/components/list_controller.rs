@@ -0,0 +1,242 @@
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::clock::Instant;
+
+/// A scroll position as the row at the top of the viewport and how far into
+/// it the viewport starts, so it survives items being added above it and
+/// rows changing height, unlike a pixel offset
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScrollAnchor {
+    pub item_id: String,
+    /// Share of the row above the viewport's top edge, from 0 to 1
+    pub offset_fraction: f64,
+}
+
+impl ScrollAnchor {
+    /// The top of the row of `item_id`
+    pub fn item(item_id: impl Into<String>) -> Self {
+        Self {
+            item_id: item_id.into(),
+            offset_fraction: 0.0,
+        }
+    }
+}
+
+// A scroll the list makes itself, from where the viewport was to its target
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct OwnScroll {
+    from: f64,
+    to: f64,
+    // Forgotten then even if the target was never reached, e.g. past the end
+    until: Instant,
+}
+
+impl OwnScroll {
+    // Whether a viewport at `top` lies on the way
+    fn covers(&self, top: f64) -> bool {
+        top >= self.from.min(self.to) - 1.0 && top <= self.from.max(self.to) + 1.0
+    }
+}
+
+/// Scroll position requested through a [`VirtualListController`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScrollRequest {
+    /// A position within the item's row, if the item is loaded
+    Anchor(ScrollAnchor),
+    Top,
+    /// The item with this id, if it is loaded
+    Item(String),
+    /// This many rows down (or up, when negative) from the row at the top
+    /// of the viewport
+    Step(i64),
+    /// This many pixels from the top of the list, e.g. a recorded scroll
+    /// position
+    Offset(f64),
+}
+
+/// Handle for driving a `VirtualList` from outside it.
+///
+/// Create one with [`use_virtual_list_controller`] and pass it as the list's
+/// `controller` prop; lists without one create their own. Jumps remember the
+/// position they started from, which the list offers to return to, and the
+/// list keeps the controller's [`ScrollAnchor`] current as it scrolls.
+#[derive(Clone, Copy, PartialEq)]
+pub struct VirtualListController {
+    request: Signal<Option<ScrollRequest>>,
+    // Whether the pending request should record where it started
+    remember_origin: Signal<bool>,
+    // Whether opening the row at the top of the viewport was requested
+    open_requested: Signal<bool>,
+    /// Position the last jump started from, while returning is offered
+    pub return_to: Signal<Option<ScrollAnchor>>,
+    // Position of the list, updated by the list as it scrolls
+    anchor: Signal<Option<ScrollAnchor>>,
+    // Scroll offset of the list in pixels, see `scroll_offset`
+    offset: CopyValue<f64>,
+    // Load triggers are held off until then while the list scrolls itself
+    triggers_held_until: CopyValue<Option<Instant>>,
+    // The list's own scrolls whose events may still arrive
+    own_scrolls: CopyValue<Vec<OwnScroll>>,
+}
+
+impl VirtualListController {
+    pub fn new() -> Self {
+        Self {
+            request: Signal::new(None),
+            remember_origin: Signal::new(false),
+            open_requested: Signal::new(false),
+            return_to: Signal::new(None),
+            anchor: Signal::new(None),
+            offset: CopyValue::new(0.0),
+            triggers_held_until: CopyValue::new(None),
+            own_scrolls: CopyValue::new(Vec::new()),
+        }
+    }
+
+    /// Jump to `request`, remembering the current position
+    pub fn jump(&mut self, request: ScrollRequest) {
+        self.remember_origin.set(true);
+        self.request.set(Some(request));
+    }
+
+    /// Scroll to `request` without offering to return, e.g. when stepping
+    /// through find matches
+    pub fn scroll_to(&mut self, request: ScrollRequest) {
+        self.remember_origin.set(false);
+        self.request.set(Some(request));
+    }
+
+    pub fn jump_to_top(&mut self) {
+        self.jump(ScrollRequest::Top);
+    }
+
+    pub fn jump_to_item(&mut self, id: impl Into<String>) {
+        self.jump(ScrollRequest::Item(id.into()));
+    }
+
+    /// Scroll to a position taken from [`current_anchor`](Self::current_anchor),
+    /// e.g. one persisted in an earlier session, without offering to return
+    pub fn restore(&mut self, anchor: ScrollAnchor) {
+        self.scroll_to(ScrollRequest::Anchor(anchor));
+    }
+
+    /// Position of the list, `None` until it has items
+    pub fn current_anchor(&self) -> Option<ScrollAnchor> {
+        self.anchor.read().clone()
+    }
+
+    /// Current scroll offset of the list in pixels, without waiting for the
+    /// next scroll event: updated by the user's scrolls as they are reported
+    /// and by the list's own as soon as it makes them, so right after a jump
+    /// or restore it is already the target. Not reactive; follow
+    /// [`current_anchor`](Self::current_anchor) to re-render on scrolls.
+    pub fn scroll_offset(&self) -> f64 {
+        *self.offset.peek()
+    }
+
+    /// Open the link of the row at the top of the viewport in reader mode
+    pub fn open_current(&mut self) {
+        self.open_requested.set(true);
+    }
+
+    /// Scroll back to where the last jump started
+    pub fn return_to_origin(&mut self) {
+        if let Some(origin) = self.return_to.take() {
+            self.remember_origin.set(false);
+            self.request.set(Some(ScrollRequest::Anchor(origin)));
+        }
+    }
+
+    /// Stop offering to return to the last jump's origin
+    pub fn dismiss_return(&mut self) {
+        self.return_to.set(None);
+    }
+
+    /// Whether the list is scrolling itself or has only just finished:
+    /// restoring a position, jumping, or keeping rows in place as items are
+    /// inserted. Load triggers are held off meanwhile, so landing within
+    /// their threshold doesn't start another load.
+    pub fn is_programmatic_scroll(&self, now: Instant) -> bool {
+        self.triggers_held_until.peek().is_some_and(|until| now < until)
+    }
+
+    /// Hold off load triggers until at least `until`. Called by the list
+    /// around its own scrolls.
+    pub(crate) fn hold_triggers_until(&mut self, until: Instant) {
+        if !self.triggers_held_until.peek().is_some_and(|held| held >= until) {
+            self.triggers_held_until.set(Some(until));
+        }
+    }
+
+    /// Tag the scroll events of a scroll the list is about to make from
+    /// `from` to `to` as its own, until they reach `to` or `until` passes.
+    /// Triggers are held off meanwhile too.
+    pub(crate) fn begin_own_scroll(&mut self, from: f64, to: f64, until: Instant) {
+        self.own_scrolls.write().push(OwnScroll { from, to, until });
+        self.hold_triggers_until(until);
+    }
+
+    /// Whether a scroll event that left the viewport at `top` comes from
+    /// one of the list's own scrolls rather than the user. Called by the
+    /// list for every scroll event.
+    pub(crate) fn is_own_scroll_event(&mut self, top: f64, now: Instant) -> bool {
+        if self.own_scrolls.peek().is_empty() {
+            return false;
+        }
+        let mut scrolls = self.own_scrolls.write();
+        scrolls.retain(|scroll| now < scroll.until);
+        let Some(index) = scrolls.iter().position(|scroll| scroll.covers(top)) else {
+            return false;
+        };
+        // Nothing more comes from a scroll that has arrived
+        if (top - scrolls[index].to).abs() <= 1.0 {
+            scrolls.remove(index);
+        }
+        true
+    }
+
+    /// Take the pending request and whether it should record its origin.
+    /// Called by the list rendering it.
+    pub(crate) fn take_request(&mut self) -> Option<(ScrollRequest, bool)> {
+        if self.request.read().is_none() {
+            return None;
+        }
+        let request = self.request.write().take()?;
+        Some((request, *self.remember_origin.peek()))
+    }
+
+    /// Record the list's position. Called by the list rendering it.
+    pub(crate) fn set_anchor(&mut self, anchor: Option<ScrollAnchor>) {
+        if *self.anchor.peek() != anchor {
+            self.anchor.set(anchor);
+        }
+    }
+
+    /// Record the list's scroll offset. Called by the list for scroll events
+    /// and its own scrolls.
+    pub(crate) fn set_offset(&mut self, offset: f64) {
+        self.offset.set(offset);
+    }
+
+    /// Take a pending request to open the current row. Called by the list
+    /// rendering it.
+    pub(crate) fn take_open(&mut self) -> bool {
+        if !*self.open_requested.read() {
+            return false;
+        }
+        self.open_requested.set(false);
+        true
+    }
+}
+
+impl Default for VirtualListController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Create a controller owned by the calling component
+pub fn use_virtual_list_controller() -> VirtualListController {
+    use_hook(VirtualListController::new)
+}
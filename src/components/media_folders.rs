@@ -0,0 +1,94 @@
+use dioxus::prelude::*;
+
+#[cfg(feature = "desktop")]
+use crate::persistence::setup::SetupConfig;
+#[cfg(feature = "desktop")]
+use crate::protocol::myprotocol::{grantable_directory, ProtocolPolicy};
+
+// Style of a row holding one folder
+#[cfg(feature = "desktop")]
+const ROW_STYLE: &str = "display: flex; align-items: center; gap: 8px; margin: 0 0 8px;";
+
+// Folders the asset protocol may serve images from, besides the bundled
+// assets. "Add folder…" opens the system folder picker; access is granted
+// as soon as a folder is picked and saved for later launches. Used in the
+// settings panel and on first run.
+#[cfg(feature = "desktop")]
+#[component]
+pub fn MediaFolders() -> Element {
+    let mut setup = use_context::<SetupConfig>();
+    let policy = use_context::<ProtocolPolicy>();
+    let mut status = use_signal(|| None::<String>);
+    let mut picking = use_signal(|| false);
+
+    let add_folder = {
+        let policy = policy.clone();
+        move |_| {
+            let policy = policy.clone();
+            async move {
+                picking.set(true);
+                let folder = rfd::AsyncFileDialog::new()
+                    .set_title("Choose a folder with images")
+                    .pick_folder()
+                    .await;
+                picking.set(false);
+                let Some(folder) = folder else {
+                    return;
+                };
+                match grantable_directory(folder.path()) {
+                    Ok(directory) => {
+                        let directory = directory.display().to_string();
+                        policy.allow(&directory);
+                        if setup.add_media_directory(&directory) {
+                            status.set(None);
+                        } else {
+                            status.set(Some(format!("{} is already allowed", directory)));
+                        }
+                    }
+                    Err(e) => status.set(Some(e.to_string())),
+                }
+            }
+        }
+    };
+
+    rsx! {
+        for directory in setup.media_directories() {
+            div {
+                key: "{directory}",
+                style: ROW_STYLE,
+                code { style: "flex: 1; word-break: break-all;", "{directory}" }
+                button {
+                    onclick: {
+                        let policy = policy.clone();
+                        let directory = directory.clone();
+                        move |_| {
+                            policy.revoke(&directory);
+                            setup.remove_media_directory(&directory);
+                        }
+                    },
+                    "Remove"
+                }
+            }
+        }
+        if let Some(status) = status() {
+            p { role: "status", style: "margin: 0 0 8px; color: #64748b;", "{status}" }
+        }
+        button {
+            disabled: picking(),
+            onclick: add_folder,
+            "Add folder…"
+        }
+    }
+}
+
+// Other platforms load images through the browser, without the protocol
+#[cfg(not(feature = "desktop"))]
+#[component]
+pub fn MediaFolders() -> Element {
+    rsx! {
+        p {
+            style: "margin: 0 0 8px; color: #64748b;",
+            "Local images are only available in the desktop app"
+        }
+    }
+}
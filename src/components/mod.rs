@@ -1,3 +1,75 @@
+// The minimal preset builds the virtual rows core and what it scrolls
+// with; the feed's components need the full app
+#[cfg(feature = "full")]
 pub mod virtual_list;
+pub mod virtual_rows;
+pub mod list_controller;
+pub mod scroll_fallback;
+pub mod scroll_parent;
+#[cfg(feature = "full")]
+pub mod wheel;
+#[cfg(feature = "full")]
 pub mod feed_item;
-pub mod feed;
\ No newline at end of file
+#[cfg(feature = "full")]
+pub mod feed;
+#[cfg(feature = "full")]
+pub mod read_aloud;
+#[cfg(feature = "full")]
+pub mod article_view;
+#[cfg(feature = "full")]
+pub mod saved_feed;
+#[cfg(feature = "full")]
+pub mod alerts_feed;
+#[cfg(feature = "full")]
+pub mod session_export;
+#[cfg(feature = "full")]
+pub mod feed_tabs;
+#[cfg(feature = "full")]
+pub mod paged_view;
+#[cfg(feature = "full")]
+pub mod split_pane;
+#[cfg(feature = "full")]
+pub mod split_view;
+#[cfg(feature = "full")]
+pub mod find_bar;
+#[cfg(feature = "full")]
+pub mod lightbox;
+#[cfg(feature = "full")]
+pub mod hover_card;
+#[cfg(feature = "full")]
+pub mod item_menu;
+#[cfg(feature = "full")]
+pub mod popover;
+#[cfg(feature = "full")]
+pub mod summary_view;
+#[cfg(feature = "full")]
+pub mod horizontal_list;
+#[cfg(feature = "full")]
+pub mod story_rail;
+#[cfg(feature = "full")]
+pub mod source_health;
+#[cfg(feature = "full")]
+pub mod search_panel;
+#[cfg(feature = "full")]
+pub mod settings_panel;
+#[cfg(feature = "full")]
+pub mod account_settings;
+#[cfg(feature = "full")]
+pub mod filter_settings;
+#[cfg(feature = "full")]
+pub mod shortcut_settings;
+#[cfg(feature = "full")]
+pub mod shortcut_help;
+#[cfg(feature = "full")]
+pub mod command_palette;
+#[cfg(feature = "full")]
+pub mod onboarding;
+#[cfg(feature = "full")]
+pub mod media_folders;
+pub mod snackbar;
+#[cfg(feature = "full")]
+pub mod profile_switcher;
+#[cfg(feature = "full")]
+pub mod selection;
+#[cfg(all(feature = "full", debug_assertions))]
+pub mod recorder_panel;
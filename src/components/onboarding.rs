@@ -0,0 +1,196 @@
+use dioxus::prelude::*;
+
+use super::media_folders::MediaFolders;
+use crate::persistence::setup::SetupConfig;
+use crate::persistence::subscriptions::Subscriptions;
+use crate::persistence::theme::{Theme, ThemeSettings};
+
+// Style of a row holding one choice
+const ROW_STYLE: &str = "display: flex; align-items: center; gap: 8px; margin: 0 0 8px;";
+
+// Style of the buttons moving between steps
+const NAV_BUTTON_STYLE: &str = "
+    padding: 6px 14px;
+    border-radius: 6px;
+    border: 1px solid #cbd5e1;
+    background: white;
+    color: #0f172a;
+    font-size: 13px;
+    cursor: pointer;
+";
+
+// A page of the first-run flow
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Step {
+    Sources,
+    // Folders the asset protocol may read images from, desktop only
+    Media,
+    Theme,
+}
+
+impl Step {
+    // Steps shown on this platform, in order
+    fn all() -> Vec<Step> {
+        if cfg!(feature = "desktop") {
+            vec![Step::Sources, Step::Media, Step::Theme]
+        } else {
+            vec![Step::Sources, Step::Theme]
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Step::Sources => "Where should your feed come from?",
+            Step::Media => "Show images from your computer",
+            Step::Theme => "Pick a theme",
+        }
+    }
+}
+
+// Feed URL typed in the sources step, `Ok(None)` when left empty
+fn parse_feed_url(input: &str) -> Result<Option<url::Url>, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(None);
+    }
+    let url = url::Url::parse(input).map_err(|e| format!("That isn't a valid URL: {}", e))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err("Feed URLs start with http:// or https://".to_string());
+    }
+    Ok(Some(url))
+}
+
+// First-run flow picking the sources, media folders and theme, shown in
+// place of the feeds until it is finished. Folders are granted as they're
+// picked; finishing persists the other choices and seeds the feed: the demo
+// feeds or the subscribed feed open next.
+#[component]
+pub fn Onboarding() -> Element {
+    let mut setup = use_context::<SetupConfig>();
+    let mut subscriptions = use_context::<Subscriptions>();
+    let mut theme = use_context::<ThemeSettings>();
+    let steps = Step::all();
+    let mut step_index = use_signal(|| 0usize);
+    let mut demo_feeds = use_signal(|| true);
+    let mut feed_url = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+
+    let step = steps[step_index().min(steps.len() - 1)];
+    let is_last = step_index() + 1 >= steps.len();
+    let current_theme = theme.theme();
+
+    // Check the current step and move on, persisting everything after the last
+    let mut next = move || {
+        if step == Step::Sources {
+            match parse_feed_url(&feed_url.peek()) {
+                Ok(None) if !demo_feeds() => {
+                    error.set(Some("Pick the demo data or enter a feed URL".to_string()));
+                    return;
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    error.set(Some(e));
+                    return;
+                }
+            }
+        }
+        error.set(None);
+        if !is_last {
+            step_index += 1;
+            return;
+        }
+
+        if let Ok(Some(url)) = parse_feed_url(&feed_url.peek()) {
+            let title = url.host_str().unwrap_or("Feed").to_string();
+            subscriptions.subscribe(&title, url.as_str());
+        }
+        setup.set_demo_feeds(demo_feeds());
+        setup.complete();
+    };
+
+    rsx! {
+        section {
+            aria_label: "Welcome",
+            style: "font-size: 14px; color: #334155;",
+            p {
+                style: "margin: 0 0 4px; font-size: 12px; color: #64748b;",
+                "Step {step_index() + 1} of {steps.len()}"
+            }
+            h2 { style: "margin: 0 0 12px; font-size: 18px; color: #0f172a;", "{step.title()}" }
+
+            match step {
+                Step::Sources => rsx! {
+                    label {
+                        style: ROW_STYLE,
+                        input {
+                            r#type: "checkbox",
+                            checked: demo_feeds(),
+                            onchange: move |evt| demo_feeds.set(evt.checked()),
+                        }
+                        "Demo data: Home and Media feeds filled with generated items"
+                    }
+                    label {
+                        style: ROW_STYLE,
+                        "RSS or Atom feed"
+                        input {
+                            r#type: "url",
+                            style: "flex: 1;",
+                            placeholder: "https://example.com/feed.xml",
+                            value: "{feed_url}",
+                            oninput: move |evt| feed_url.set(evt.value()),
+                        }
+                    }
+                    p {
+                        style: "margin: 0 0 8px; color: #64748b;",
+                        "More feeds can be added later with Import OPML."
+                    }
+                },
+                Step::Media => rsx! {
+                    p {
+                        style: "margin: 0 0 8px; color: #64748b;",
+                        "Items can show images stored on this computer. The app only reads from folders you choose here; everything else stays off limits."
+                    }
+                    MediaFolders {}
+                },
+                Step::Theme => rsx! {
+                    for option_theme in Theme::ALL {
+                        label {
+                            key: "{option_theme.label()}",
+                            style: ROW_STYLE,
+                            input {
+                                r#type: "radio",
+                                name: "theme",
+                                checked: option_theme == current_theme,
+                                onchange: move |_| theme.set_theme(option_theme),
+                            }
+                            "{option_theme.label()}"
+                        }
+                    }
+                },
+            }
+
+            if let Some(error) = error() {
+                p { role: "alert", style: "margin: 8px 0 0; color: #b91c1c;", "{error}" }
+            }
+
+            div {
+                style: "display: flex; justify-content: flex-end; gap: 8px; margin-top: 16px;",
+                if step_index() > 0 {
+                    button {
+                        style: NAV_BUTTON_STYLE,
+                        onclick: move |_| {
+                            error.set(None);
+                            step_index -= 1;
+                        },
+                        "Back"
+                    }
+                }
+                button {
+                    style: "{NAV_BUTTON_STYLE} background: #0f172a; color: white;",
+                    onclick: move |_| next(),
+                    if is_last { "Start reading" } else { "Next" }
+                }
+            }
+        }
+    }
+}
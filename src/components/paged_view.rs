@@ -0,0 +1,277 @@
+use dioxus::prelude::*;
+
+use super::article_view::ArticleView;
+use super::lightbox::{Lightbox, LightboxImage};
+use super::list_controller::{use_virtual_list_controller, ScrollAnchor, ScrollRequest, VirtualListController};
+use super::virtual_list::{predecode_images, VirtualFeedItemComponent};
+use crate::persistence::read_state::ReadState;
+use crate::store::ranking::FeedRanking;
+use crate::store::{FeedStore, Sequence};
+
+// Paged view configuration
+const PAGE_HEIGHT: f64 = 600.0; // Same viewport as the list
+const PAGE_CHROME_HEIGHT: f64 = 64.0; // Navigation bar and padding around the item
+const PRELOAD_NEIGHBORS: usize = 2; // Pages either side whose images are decoded ahead
+const LOAD_AHEAD_PAGES: usize = 3; // Pages from an end at which the next batch is requested
+
+#[derive(PartialEq, Props, Clone)]
+pub struct PagedViewProps {
+    // Store holding the items, the same one the list shows
+    pub store: FeedStore,
+    // Key for the persisted read state, as on the list
+    #[props(default)]
+    pub feed_id: Option<&'static str>,
+    // Steps, jumps and opening the current item go through the same
+    // controller as the list; the view creates its own when none is given
+    pub controller: Option<VirtualListController>,
+}
+
+// One item at a time filling the viewport, stepped through with the
+// buttons or the arrow keys: a reader over the feed's items. Neighbours'
+// images are decoded ahead and more items are loaded near either end.
+#[component]
+pub fn PagedView(props: PagedViewProps) -> Element {
+    let store = props.store;
+    let feed_id = props.feed_id;
+    let items = use_memo(move || store.ordered_items());
+    let status = store.status;
+    let own_controller = use_virtual_list_controller();
+    let mut controller = props.controller.unwrap_or(own_controller);
+
+    // The item shown, by id so items loading above it don't move it; it
+    // opens where the previous session's reading ended, or at the start
+    let read_state = try_use_context::<ReadState>();
+    let mut current = use_signal(|| feed_id.zip(read_state).and_then(|(id, read_state)| read_state.last_visit(id)));
+    let index = use_memo(move || {
+        let items = items.read();
+        current
+            .read()
+            .as_ref()
+            .and_then(|id| items.iter().position(|item| &item.id == id))
+            .unwrap_or(0)
+    });
+    let count = items.read().len();
+
+    // The first page, when the view opens on a feed that isn't loaded yet
+    use_future(move || async move {
+        store.initialize().await;
+    });
+
+    // Show the page at `target` (the last page for targets past the end),
+    // loading the next batch when nearing either end
+    let mut go_to = move |target: usize| {
+        let count = items.peek().len();
+        if count == 0 {
+            return;
+        }
+        let target = target.min(count - 1);
+        let id = items.peek()[target].id.clone();
+        current.set(Some(id));
+        if target + LOAD_AHEAD_PAGES >= count && status.peek().can_load_bottom() {
+            spawn(async move {
+                store.load_newer().await;
+            });
+        }
+        if target < LOAD_AHEAD_PAGES && store.can_load_top() {
+            spawn(async move {
+                store.load_older().await;
+            });
+        }
+    };
+    let mut step = move |pages: i64| {
+        let target = (*index.peek() as i64 + pages).max(0) as usize;
+        go_to(target);
+    };
+
+    // Carry out steps and jumps requested through the controller
+    use_effect(move || {
+        let Some((request, remember_origin)) = controller.take_request() else {
+            return;
+        };
+        let origin = current.peek().clone();
+        let position = |id: &str| items.peek().iter().position(|item| item.id == id);
+        let target = match request {
+            ScrollRequest::Top => Some(0),
+            ScrollRequest::Item(id) => position(&id),
+            ScrollRequest::Anchor(anchor) => position(&anchor.item_id),
+            ScrollRequest::Step(pages) => Some((*index.peek() as i64 + pages).max(0) as usize),
+            // Pages have no pixel offsets
+            ScrollRequest::Offset(_) => None,
+        };
+        let Some(target) = target else {
+            return;
+        };
+        // A page away is a step, not a jump worth returning from
+        if remember_origin && target.abs_diff(*index.peek()) > 1 {
+            controller.return_to.set(origin.map(ScrollAnchor::item));
+        }
+        go_to(target);
+    });
+
+    // Keep the controller's position on the page shown, and remember the
+    // newest item read for the next session
+    let mut newest_seen = use_hook(|| CopyValue::new(None::<Sequence>));
+    use_effect(move || {
+        let index = index();
+        let Some(item) = items.read().get(index).cloned() else {
+            return;
+        };
+        controller.set_anchor(Some(ScrollAnchor::item(item.id.clone())));
+        let (Some(feed_id), Some(mut read_state)) = (feed_id, read_state) else {
+            return;
+        };
+        let seq = store.sequence_at(index);
+        if *store.ranking.peek() != FeedRanking::Latest || newest_seen.peek().is_some_and(|seen| seen >= seq) {
+            return;
+        }
+        newest_seen.set(Some(seq));
+        read_state.mark_newest_seen(feed_id, &item.id);
+    });
+
+    // Decode the neighbours' images so stepping shows them at once
+    use_effect(move || {
+        let index = index();
+        let items = items.read();
+        let urls: Vec<String> = (index.saturating_sub(PRELOAD_NEIGHBORS)..=index + PRELOAD_NEIGHBORS)
+            .filter(|neighbor| *neighbor != index)
+            .filter_map(|neighbor| items.get(neighbor))
+            .map(|item| item.image_url.clone())
+            .filter(|url| !url.is_empty())
+            .collect();
+        predecode_images(urls);
+    });
+
+    // Article opened in reader mode, and the image opened from the page
+    let mut reader_url = use_signal(|| None::<String>);
+    let mut lightbox = use_signal(|| None::<LightboxImage>);
+    use_effect(move || {
+        if !controller.take_open() {
+            return;
+        }
+        let link = items.peek().get(*index.peek()).and_then(|item| item.link.clone());
+        if let Some(link) = link {
+            reader_url.set(Some(link));
+        }
+    });
+
+    let item = items.read().get(index()).cloned();
+    let at_first = index() == 0 && !store.can_load_top();
+    let at_last = index() + 1 >= count && !status.read().can_load_bottom();
+    let nav_button = |disabled: bool| {
+        format!(
+            "
+                padding: 6px 14px;
+                border-radius: 6px;
+                border: 1px solid #e2e8f0;
+                background: white;
+                color: {};
+                font-size: 13px;
+                cursor: {};
+            ",
+            if disabled { "#cbd5e1" } else { "#0f172a" },
+            if disabled { "default" } else { "pointer" }
+        )
+    };
+
+    rsx! {
+        div {
+            style: format!("
+                height: {}px;
+                display: flex;
+                flex-direction: column;
+                background: white;
+                outline: none;
+            ", PAGE_HEIGHT),
+            "data-paged-view": "true",
+            tabindex: "0",
+            onmounted: move |evt| async move {
+                let _ = evt.data().set_focus(true).await;
+            },
+            onkeydown: move |evt: Event<KeyboardData>| {
+                let pages = match evt.key() {
+                    Key::ArrowRight | Key::ArrowDown | Key::PageDown => 1,
+                    Key::ArrowLeft | Key::ArrowUp | Key::PageUp => -1,
+                    Key::Home => {
+                        evt.prevent_default();
+                        go_to(0);
+                        return;
+                    }
+                    _ => return,
+                };
+                evt.prevent_default();
+                step(pages);
+            },
+
+            // Page navigation
+            div {
+                style: "
+                    display: flex;
+                    align-items: center;
+                    gap: 8px;
+                    padding: 8px;
+                    border-bottom: 1px solid #e2e8f0;
+                    font-size: 13px;
+                    color: #64748b;
+                ",
+                button {
+                    style: nav_button(at_first),
+                    disabled: at_first,
+                    aria_label: "Previous item",
+                    onclick: move |_| step(-1),
+                    "← Previous"
+                }
+                span {
+                    style: "flex: 1; text-align: center; font-variant-numeric: tabular-nums;",
+                    if count > 0 {
+                        "{index() + 1} of {count}"
+                    }
+                    if status.read().is_loading() {
+                        " · Loading…"
+                    }
+                }
+                button {
+                    style: nav_button(at_last),
+                    disabled: at_last,
+                    aria_label: "Next item",
+                    onclick: move |_| step(1),
+                    "Next →"
+                }
+            }
+
+            // The page itself; long items scroll within it
+            div {
+                style: "flex: 1; overflow-y: auto; padding: 8px;",
+                if let Some(item) = item {
+                    VirtualFeedItemComponent {
+                        key: "{item.id}",
+                        item: item.clone(),
+                        row_height: PAGE_HEIGHT - PAGE_CHROME_HEIGHT,
+                        stacked: true,
+                        state: store.item_state(&item.id),
+                        on_read: move |url| reader_url.set(Some(url)),
+                        on_open_image: move |image| lightbox.set(Some(image)),
+                    }
+                } else {
+                    p {
+                        style: "margin: 40px 0; text-align: center; color: #64748b;",
+                        if status.read().is_loading() { "Loading feed..." } else { "Nothing here yet" }
+                    }
+                }
+            }
+
+            if let Some(image) = lightbox() {
+                Lightbox {
+                    image,
+                    on_close: move |_| lightbox.set(None),
+                }
+            }
+
+            if let Some(url) = reader_url() {
+                ArticleView {
+                    url,
+                    on_close: move |_| reader_url.set(None),
+                }
+            }
+        }
+    }
+}
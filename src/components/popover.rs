@@ -0,0 +1,213 @@
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+// Space between a popover and its anchor
+const ANCHOR_GAP: f64 = 8.0;
+
+// Space kept between a popover and the edges of its bounds
+const EDGE_MARGIN: f64 = 8.0;
+
+const WINDOW_SIZE_SCRIPT: &str = "return [window.innerWidth, window.innerHeight];";
+
+// A rectangle in window coordinates, e.g. the element a popover points at
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Anchor {
+    pub left: f64,
+    pub top: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Anchor {
+    // Zero-size anchor at a point, e.g. where a context menu was opened
+    pub fn point(x: f64, y: f64) -> Self {
+        Self { left: x, top: y, width: 0.0, height: 0.0 }
+    }
+
+    // Where `element` is laid out right now
+    pub async fn of(element: &MountedData) -> Option<Self> {
+        let rect = element.get_client_rect().await.ok()?;
+        Some(Self {
+            left: rect.origin.x,
+            top: rect.origin.y,
+            width: rect.width(),
+            height: rect.height(),
+        })
+    }
+
+    fn right(&self) -> f64 {
+        self.left + self.width
+    }
+
+    fn bottom(&self) -> f64 {
+        self.top + self.height
+    }
+
+    // The part of this rectangle also inside `other`
+    fn intersect(&self, other: Anchor) -> Anchor {
+        let left = self.left.max(other.left);
+        let top = self.top.max(other.top);
+        Anchor {
+            left,
+            top,
+            width: (self.right().min(other.right()) - left).max(0.0),
+            height: (self.bottom().min(other.bottom()) - top).max(0.0),
+        }
+    }
+}
+
+// Side of the anchor a popover opens on
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum Side {
+    #[default]
+    Below,
+    Above,
+}
+
+// Where a popover of `size` goes: on `side` of `anchor`, flipped to the other
+// side when it doesn't fit and that side has more room, then shifted to stay
+// inside `bounds`
+pub fn place(anchor: Anchor, size: (f64, f64), bounds: Anchor, side: Side) -> (f64, f64) {
+    let (width, height) = size;
+    let room_below = bounds.bottom() - anchor.bottom() - ANCHOR_GAP - EDGE_MARGIN;
+    let room_above = anchor.top - bounds.top - ANCHOR_GAP - EDGE_MARGIN;
+    let side = match side {
+        Side::Below if height > room_below && room_above > room_below => Side::Above,
+        Side::Above if height > room_above && room_below > room_above => Side::Below,
+        side => side,
+    };
+    let top = match side {
+        Side::Below => anchor.bottom() + ANCHOR_GAP,
+        Side::Above => anchor.top - ANCHOR_GAP - height,
+    };
+
+    // Shifted back inside, keeping the start visible when it can't fit
+    let top = top
+        .min(bounds.bottom() - height - EDGE_MARGIN)
+        .max(bounds.top + EDGE_MARGIN);
+    let left = anchor
+        .left
+        .min(bounds.right() - width - EDGE_MARGIN)
+        .max(bounds.left + EDGE_MARGIN);
+    (left, top)
+}
+
+#[derive(PartialEq, Props, Clone)]
+pub struct PopoverProps {
+    pub anchor: Anchor,
+    // Element the popover stays inside, e.g. the scroll container of the
+    // feed; the window when unset
+    #[props(default)]
+    pub container: Option<Signal<Option<Rc<MountedData>>>>,
+    #[props(default)]
+    pub side: Side,
+    #[props(default = "dialog".to_string())]
+    pub role: String,
+    #[props(default)]
+    pub aria_label: Option<String>,
+    // Takes focus and closes on a click outside, for menus
+    #[props(default)]
+    pub modal: bool,
+    // The pointer entered or left the popover
+    #[props(default)]
+    pub on_hover_change: Option<EventHandler<bool>>,
+    pub on_close: EventHandler<()>,
+    pub children: Element,
+}
+
+// Content floating next to an anchor, for hover cards, menus and tooltips.
+//
+// The webview has no popover management, so the popover measures itself,
+// its container and the window once mounted and is only shown once placed.
+// It is positioned `fixed`, so it must be rendered outside rows with
+// `content-visibility`, whose containment would make it scroll with the row.
+#[component]
+pub fn Popover(props: PopoverProps) -> Element {
+    let anchor = props.anchor;
+    let side = props.side;
+    let container = props.container;
+    let on_close = props.on_close;
+    let on_hover_change = props.on_hover_change;
+    let modal = props.modal;
+    let mut position = use_signal(|| None::<(f64, f64)>);
+
+    // Measure once mounted, and again when the anchor moves
+    let mut element = use_signal(|| None::<Rc<MountedData>>);
+    use_effect(use_reactive!(|anchor| {
+        let Some(popover) = element() else {
+            return;
+        };
+        spawn(async move {
+            let Some(own) = Anchor::of(&popover).await else {
+                return;
+            };
+            let window = match document::eval(WINDOW_SIZE_SCRIPT).join::<(f64, f64)>().await {
+                Ok((width, height)) => Anchor { left: 0.0, top: 0.0, width, height },
+                Err(e) => {
+                    eprintln!("Failed to read the window size: {}", e);
+                    return;
+                }
+            };
+            let container = container.and_then(|container| container.peek().clone());
+            let bounds = match container {
+                Some(container) => match Anchor::of(&container).await {
+                    Some(rect) => window.intersect(rect),
+                    None => window,
+                },
+                None => window,
+            };
+            position.set(Some(place(anchor, (own.width, own.height), bounds, side)));
+        });
+    }));
+
+    let (left, top) = position().unwrap_or_default();
+
+    rsx! {
+        if modal {
+            // Catches clicks outside the popover
+            div {
+                style: "position: fixed; inset: 0; z-index: 899;",
+                onclick: move |_| on_close.call(()),
+                oncontextmenu: move |evt| {
+                    evt.prevent_default();
+                    on_close.call(());
+                },
+            }
+        }
+        div {
+            role: "{props.role}",
+            aria_label: props.aria_label.clone(),
+            tabindex: modal.then_some("-1"),
+            style: format!("
+                position: fixed;
+                left: {}px;
+                top: {}px;
+                z-index: 900;
+                visibility: {};
+                outline: none;
+            ", left, top, if position().is_some() { "visible" } else { "hidden" }),
+            onmounted: move |evt| async move {
+                element.set(Some(evt.data()));
+                if modal {
+                    let _ = evt.set_focus(true).await;
+                }
+            },
+            onmouseenter: move |_| {
+                if let Some(on_hover_change) = on_hover_change {
+                    on_hover_change.call(true);
+                }
+            },
+            onmouseleave: move |_| {
+                if let Some(on_hover_change) = on_hover_change {
+                    on_hover_change.call(false);
+                }
+            },
+            onkeydown: move |evt| {
+                if evt.key() == Key::Escape {
+                    on_close.call(());
+                }
+            },
+            {props.children}
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use dioxus::prelude::*;
+
+use crate::auth::{AuthManager, Provider};
+
+// Picker in the header choosing which connected account backs each
+// provider's feed. Providers with a single account show it without a picker.
+#[component]
+pub fn ProfileSwitcher() -> Element {
+    let mut auth = use_context::<AuthManager>();
+    let accounts = auth.accounts();
+
+    rsx! {
+        div {
+            style: "display: flex; align-items: center; gap: 8px; font-size: 13px; color: #475569;",
+            for provider in Provider::ALL {
+                {
+                    let connected: Vec<_> = accounts.iter().filter(|account| account.provider == provider).cloned().collect();
+                    let active_key = auth.active_account(provider).map(|account| account.key());
+                    match connected.as_slice() {
+                        [] => rsx! {},
+                        [account] => rsx! {
+                            span { key: "{provider.id()}", title: "{provider.name()}", "{account.label()}" }
+                        },
+                        _ => rsx! {
+                            select {
+                                key: "{provider.id()}",
+                                aria_label: "{provider.name()} account",
+                                onchange: move |evt| auth.set_active(&evt.value()),
+                                for account in connected.iter() {
+                                    option {
+                                        value: "{account.key()}",
+                                        selected: active_key.as_deref() == Some(account.key().as_str()),
+                                        "{account.label()}"
+                                    }
+                                }
+                            }
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use dioxus::prelude::*;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+
+use crate::error::{report, FeedError};
+use crate::store::FeedItems;
+
+/// Boxed future returned by speech backends
+pub type SpeechFuture = Pin<Box<dyn Future<Output = Result<(), SpeechError>>>>;
+
+/// Errors reported by a speech backend
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpeechError {
+    Unavailable,
+    Interrupted,
+    Backend(String),
+}
+
+impl std::fmt::Display for SpeechError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpeechError::Unavailable => write!(f, "Speech synthesis unavailable"),
+            SpeechError::Interrupted => write!(f, "Speech interrupted"),
+            SpeechError::Backend(msg) => write!(f, "Speech backend error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for SpeechError {}
+
+/// Pluggable text-to-speech backend used by the read aloud mode.
+///
+/// Provide a custom backend to the component tree with
+/// `use_context_provider(|| ReadAloudBackend::new(MyBackend))`; the webview
+/// `speechSynthesis` API is used when none is provided.
+pub trait SpeechBackend {
+    /// Speak `text`, resolving once the utterance has finished
+    fn speak(&self, text: String) -> SpeechFuture;
+
+    /// Stop the utterance currently being spoken, if any
+    fn cancel(&self);
+}
+
+/// Shared handle to the active speech backend
+#[derive(Clone)]
+pub struct ReadAloudBackend(Rc<dyn SpeechBackend>);
+
+impl ReadAloudBackend {
+    pub fn new(backend: impl SpeechBackend + 'static) -> Self {
+        Self(Rc::new(backend))
+    }
+}
+
+/// Speech backend built on the webview's `speechSynthesis` API
+pub struct WebSpeechBackend;
+
+// Speaks the text sent from Rust and reports how the utterance ended
+const SPEAK_SCRIPT: &str = r#"
+    const text = await dioxus.recv();
+    if (!("speechSynthesis" in window)) {
+        dioxus.send("unavailable");
+        return;
+    }
+    const utterance = new SpeechSynthesisUtterance(text);
+    utterance.onend = () => dioxus.send("done");
+    utterance.onerror = (event) => {
+        const interrupted = event.error === "interrupted" || event.error === "canceled";
+        dioxus.send(interrupted ? "interrupted" : "error: " + event.error);
+    };
+    window.speechSynthesis.speak(utterance);
+"#;
+
+const CANCEL_SCRIPT: &str = r#"
+    if ("speechSynthesis" in window) {
+        window.speechSynthesis.cancel();
+    }
+"#;
+
+impl SpeechBackend for WebSpeechBackend {
+    fn speak(&self, text: String) -> SpeechFuture {
+        Box::pin(async move {
+            let mut eval = document::eval(SPEAK_SCRIPT);
+            eval.send(text)
+                .map_err(|e| SpeechError::Backend(e.to_string()))?;
+
+            match eval.recv::<String>().await {
+                Ok(status) if status == "done" => Ok(()),
+                Ok(status) if status == "unavailable" => Err(SpeechError::Unavailable),
+                Ok(status) if status == "interrupted" => Err(SpeechError::Interrupted),
+                Ok(status) => Err(SpeechError::Backend(status)),
+                Err(e) => Err(SpeechError::Backend(e.to_string())),
+            }
+        })
+    }
+
+    fn cancel(&self) {
+        let _ = document::eval(CANCEL_SCRIPT);
+    }
+}
+
+/// Read aloud state shared with the list rendering the items
+#[derive(Clone, Copy)]
+pub struct ReadAloud {
+    /// Id of the item currently being read
+    pub reading_id: Signal<Option<String>>,
+    /// Whether the read aloud session is running
+    pub active: Signal<bool>,
+    /// Start reading from the given item index
+    pub start: Callback<usize>,
+    /// Stop reading and cancel the current utterance
+    pub stop: Callback<()>,
+}
+
+/// Read items sequentially through the speech backend.
+///
+/// `scroll_to_index` is called before each item is spoken so the list can
+/// keep the item being read in view.
+pub fn use_read_aloud(
+    items: Memo<FeedItems>,
+    scroll_to_index: Callback<usize>,
+) -> ReadAloud {
+    let backend = use_hook(|| {
+        try_consume_context::<ReadAloudBackend>()
+            .unwrap_or_else(|| ReadAloudBackend::new(WebSpeechBackend))
+    });
+
+    let mut reading_id = use_signal(|| None::<String>);
+    let mut active = use_signal(|| false);
+    // Bumped on every start/stop so a stale session loop exits on its own
+    let mut session = use_signal(|| 0u64);
+
+    let stop_backend = backend.clone();
+    let stop = use_callback(move |_| {
+        session += 1;
+        active.set(false);
+        reading_id.set(None);
+        stop_backend.0.cancel();
+    });
+
+    let start = use_callback(move |start_index: usize| {
+        backend.0.cancel();
+        session += 1;
+        let current_session = session();
+        active.set(true);
+
+        let backend = backend.clone();
+        spawn(async move {
+            let mut index = start_index;
+            loop {
+                if session() != current_session {
+                    return;
+                }
+
+                let Some(item) = items.read().get(index).cloned() else {
+                    break;
+                };
+
+                reading_id.set(Some(item.id.clone()));
+                scroll_to_index.call(index);
+
+                match backend.0.speak(item.content).await {
+                    Ok(()) => index += 1,
+                    Err(SpeechError::Interrupted) => return,
+                    Err(e) => {
+                        report(FeedError::Speech(e));
+                        break;
+                    }
+                }
+            }
+
+            if session() == current_session {
+                active.set(false);
+                reading_id.set(None);
+            }
+        });
+    });
+
+    ReadAloud { reading_id, active, start, stop }
+}
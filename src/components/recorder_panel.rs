@@ -0,0 +1,109 @@
+use dioxus::prelude::*;
+
+use crate::store::FeedStore;
+
+// Time of day a snapshot was recorded at, to the millisecond
+const TIME_FORMAT: &str = "%H:%M:%S%.3f";
+
+#[derive(PartialEq, Props, Clone)]
+pub struct RecorderPanelProps {
+    // Store whose recorded history is shown
+    pub store: FeedStore,
+    // Called with the scroll position of the snapshot travelled to
+    pub on_travel: EventHandler<f64>,
+}
+
+// Debug panel for stepping backward and forward through a store's snapshots,
+// filling the pane of the debug dock it sits in
+#[component]
+pub fn RecorderPanel(props: RecorderPanelProps) -> Element {
+    let mut store = props.store;
+    let recorder = store.recorder();
+    let mut expanded = use_signal(|| false);
+
+    let mut travel = move |index: usize| {
+        if let Some(scroll_top) = store.time_travel(index) {
+            props.on_travel.call(scroll_top);
+        }
+    };
+
+    let len = recorder.len();
+    let position = recorder.position();
+    let snapshot = position.and_then(|index| recorder.snapshot(index));
+    let button_style = "
+        padding: 4px 8px;
+        border-radius: 4px;
+        border: 1px solid #475569;
+        background: transparent;
+        color: white;
+        font-size: 12px;
+        cursor: pointer;
+    ";
+
+    rsx! {
+        div {
+            style: "
+                height: 100%;
+                overflow-y: auto;
+                box-sizing: border-box;
+                background: rgba(15, 23, 42, 0.9);
+                color: white;
+                padding: 10px;
+                font-size: 12px;
+                font-family: monospace;
+            ",
+
+            div {
+                style: "display: flex; align-items: center; justify-content: space-between;",
+                span {
+                    if recorder.is_travelling() { "History (paused)" } else { "History" }
+                }
+                button {
+                    style: button_style,
+                    onclick: move |_| expanded.toggle(),
+                    if expanded() { "Hide" } else { "Show" }
+                }
+            }
+
+            if expanded() {
+                if let (Some(index), Some(snapshot)) = (position, snapshot) {
+                    div {
+                        style: "margin: 8px 0; display: flex; flex-direction: column; gap: 2px;",
+                        div { "Snapshot {index + 1}/{len}" }
+                        div { "{snapshot.recorded_at.format(TIME_FORMAT)} {snapshot.event}" }
+                        div { "Items: {snapshot.items.len()} from seq {snapshot.first_seq}" }
+                        div { "Scroll: {snapshot.scroll_top:.0}px" }
+                        div { "Status: {snapshot.status:?}" }
+                    }
+
+                    div {
+                        style: "display: flex; gap: 6px;",
+                        button {
+                            style: button_style,
+                            disabled: index == 0,
+                            onclick: move |_| travel(index.saturating_sub(1)),
+                            "Back"
+                        }
+                        button {
+                            style: button_style,
+                            disabled: index + 1 >= len,
+                            onclick: move |_| travel(index + 1),
+                            "Forward"
+                        }
+                        button {
+                            style: button_style,
+                            disabled: index + 1 >= len,
+                            onclick: move |_| travel(len - 1),
+                            "Live"
+                        }
+                    }
+                } else {
+                    div {
+                        style: "margin-top: 8px; color: #94a3b8;",
+                        "No mutations recorded yet"
+                    }
+                }
+            }
+        }
+    }
+}
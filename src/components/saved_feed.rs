@@ -0,0 +1,90 @@
+use dioxus::prelude::*;
+
+use super::virtual_list::VirtualList;
+use crate::persistence::bookmarks::{Bookmarks, ExportFormat};
+use crate::store::FeedStore;
+
+#[derive(PartialEq, Props, Clone)]
+pub struct SavedFeedProps {
+    // Store over the bookmarked items
+    pub store: FeedStore,
+}
+
+// Saved items view rendered through the same virtual list as the main feed
+#[component]
+pub fn SavedFeed(props: SavedFeedProps) -> Element {
+    let bookmarks = use_context::<Bookmarks>();
+    let store = props.store;
+    let items = store.items;
+    let mut export_status = use_signal(|| None::<String>);
+
+    let mut export = move |format: ExportFormat| {
+        let status = match bookmarks.export(format) {
+            Ok(path) => format!("Exported to {}", path.display()),
+            Err(e) => format!("Export failed: {}", e),
+        };
+        export_status.set(Some(status));
+    };
+
+    rsx! {
+        // Export toolbar
+        div {
+            style: "
+                display: flex;
+                align-items: center;
+                gap: 8px;
+                padding: 8px 0;
+                font-size: 13px;
+                color: #64748b;
+            ",
+            span {
+                style: "flex: 1;",
+                "{items.read().len()} saved"
+            }
+            if let Some(status) = export_status() {
+                span { "{status}" }
+            }
+            button {
+                style: "
+                    padding: 6px 12px;
+                    border-radius: 6px;
+                    border: 1px solid #e2e8f0;
+                    background: white;
+                    color: #0f172a;
+                    cursor: pointer;
+                ",
+                disabled: items.read().is_empty(),
+                onclick: move |_| export(ExportFormat::Json),
+                "Export JSON"
+            }
+            button {
+                style: "
+                    padding: 6px 12px;
+                    border-radius: 6px;
+                    border: 1px solid #e2e8f0;
+                    background: white;
+                    color: #0f172a;
+                    cursor: pointer;
+                ",
+                disabled: items.read().is_empty(),
+                onclick: move |_| export(ExportFormat::Html),
+                "Export HTML"
+            }
+        }
+
+        if items.read().is_empty() {
+            div {
+                style: "
+                    text-align: center;
+                    padding: 60px 20px;
+                    color: #64748b;
+                    border: 1px dashed #e2e8f0;
+                    border-radius: 8px;
+                ",
+                "No saved items yet. Use \"Save\" on any item to keep it here."
+            }
+        } else {
+            VirtualList { store, show_progress: true, editable: true }
+        }
+    }
+}
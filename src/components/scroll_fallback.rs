@@ -0,0 +1,117 @@
+//! Scrolling lists from code when `MountedData::scroll` isn't available.
+//!
+//! Some webviews reject the native scroll call. [`scroll_with_fallback`]
+//! then sets `scrollTop` on the list through `eval`, and counts both kinds of
+//! failure in the app's [`ScrollDiagnostics`]. After a few fallbacks in a row
+//! also fail, the user is told once that positions can't be restored.
+
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+use crate::components::snackbar::Snackbar;
+
+/// Fallback failures in a row after which the user is told scroll positions
+/// can't be restored
+pub const FAILURES_BEFORE_HINT: u32 = 3;
+
+const SCROLL_HINT: &str = "Scroll positions can't be restored in this window; lists may open at a different place";
+
+/// Counts of scrolls from code that didn't go through, for the debug panel
+/// and the hint.
+///
+/// Provided once at the app root with `use_context_provider(ScrollDiagnostics::new)`;
+/// without it scrolls still fall back, uncounted and without the hint.
+#[derive(Clone, Copy, PartialEq)]
+pub struct ScrollDiagnostics {
+    /// Native scroll calls that failed
+    pub native_failures: Signal<u32>,
+    /// Fallbacks that scrolled the list
+    pub fallbacks: Signal<u32>,
+    /// Fallbacks that failed too
+    pub fallback_failures: Signal<u32>,
+    // Fallback failures since the last scroll that went through
+    failing_in_row: CopyValue<u32>,
+    hinted: CopyValue<bool>,
+}
+
+impl ScrollDiagnostics {
+    pub fn new() -> Self {
+        Self {
+            native_failures: Signal::new(0),
+            fallbacks: Signal::new(0),
+            fallback_failures: Signal::new(0),
+            failing_in_row: CopyValue::new(0),
+            hinted: CopyValue::new(false),
+        }
+    }
+
+    fn scrolled(&mut self) {
+        self.failing_in_row.set(0);
+    }
+
+    // Count a fallback failure, showing the hint once they keep happening
+    fn fallback_failed(&mut self) {
+        self.fallback_failures += 1;
+        let in_row = *self.failing_in_row.peek() + 1;
+        self.failing_in_row.set(in_row);
+        if in_row >= FAILURES_BEFORE_HINT && !*self.hinted.peek() {
+            self.hinted.set(true);
+            if let Some(mut snackbar) = try_consume_context::<Snackbar>() {
+                snackbar.show(SCROLL_HINT);
+            }
+        }
+    }
+}
+
+impl Default for ScrollDiagnostics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Scroll `element` to `top`, setting `scrollTop` on the element matching
+/// `selector` when the native call fails. Returns whether the list scrolled.
+pub async fn scroll_with_fallback(
+    element: Rc<MountedData>,
+    selector: &str,
+    top: f64,
+    behavior: ScrollBehavior,
+) -> bool {
+    let mut diagnostics = try_consume_context::<ScrollDiagnostics>();
+    let native = element.scroll(dioxus::html::geometry::PixelsVector2D::new(0.0, top), behavior).await;
+    let Err(e) = native else {
+        if let Some(diagnostics) = diagnostics.as_mut() {
+            diagnostics.scrolled();
+        }
+        return true;
+    };
+    eprintln!("Native scroll failed, setting scrollTop instead: {}", e);
+    if let Some(diagnostics) = diagnostics.as_mut() {
+        diagnostics.native_failures += 1;
+    }
+
+    let script = format!(
+        r#"
+        const list = document.querySelector('{selector}');
+        if (!list) {{ return false; }}
+        list.scrollTop = {top};
+        return true;
+        "#
+    );
+    let scrolled = match document::eval(&script).join::<bool>().await {
+        Ok(found) => found,
+        Err(e) => {
+            eprintln!("Fallback scroll failed: {}", e);
+            false
+        }
+    };
+    if let Some(diagnostics) = diagnostics.as_mut() {
+        if scrolled {
+            diagnostics.fallbacks += 1;
+            diagnostics.scrolled();
+        } else {
+            diagnostics.fallback_failed();
+        }
+    }
+    scrolled
+}
@@ -0,0 +1,194 @@
+//! Scroll containers outside a virtual list.
+//!
+//! A list normally scrolls in a box of its own. Given a [`ScrollParent`] it
+//! grows to its full height instead and takes its viewport from the parent,
+//! so a feed can sit inside a page that scrolls as a whole. The parent is
+//! either an element of the page, wired up with [`ScrollParent::mounted`] and
+//! [`ScrollParent::scrolled`], or the window itself.
+
+use dioxus::prelude::*;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::scroll_fallback::scroll_with_fallback;
+
+// Id of the next parent element, so scripts can find it
+static NEXT_PARENT_ID: AtomicUsize = AtomicUsize::new(0);
+
+// Streams the window's scroll metrics whenever it scrolls or resizes
+const WINDOW_METRICS_SCRIPT: &str = r#"
+    const send = () => dioxus.send([
+        window.scrollY,
+        document.documentElement.scrollHeight,
+        window.innerHeight,
+    ]);
+    window.addEventListener('scroll', send, { passive: true });
+    window.addEventListener('resize', send);
+    send();
+    await new Promise(() => {});
+"#;
+
+// Scrolls the window, receiving the top and whether to animate
+const WINDOW_SCROLL_SCRIPT: &str = r#"
+    const [top, smooth] = await dioxus.recv();
+    window.scrollTo({ top, behavior: smooth ? 'smooth' : 'instant' });
+    return true;
+"#;
+
+// Reports how far below the top of the parent's content the list starts,
+// receiving the list's and the parent's selectors (none for the window)
+const LIST_OFFSET_SCRIPT: &str = r#"
+    const [listSelector, parentSelector] = await dioxus.recv();
+    const list = document.querySelector(listSelector);
+    if (!list) { return null; }
+    const top = list.getBoundingClientRect().top;
+    if (parentSelector === null) {
+        return top + window.scrollY;
+    }
+    const parent = document.querySelector(parentSelector);
+    if (!parent) { return null; }
+    return top - parent.getBoundingClientRect().top + parent.scrollTop;
+"#;
+
+/// Scroll position and size of a parent, as its last scroll event gave them
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScrollMetrics {
+    pub scroll_top: f64,
+    pub scroll_height: f64,
+    pub client_height: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ParentKind {
+    Element(usize),
+    Window,
+}
+
+/// A scroll container a virtual list attaches to instead of scrolling
+/// itself, passed to the list's `scroll_parent` prop.
+///
+/// Create one with [`use_scroll_parent`] for an element of the page, or
+/// [`use_window_scroll_parent`] for the window.
+#[derive(Clone, Copy, PartialEq)]
+pub struct ScrollParent {
+    kind: ParentKind,
+    metrics: Signal<Option<ScrollMetrics>>,
+    element: Signal<Option<Rc<MountedData>>>,
+}
+
+impl ScrollParent {
+    /// Attribute value identifying the parent element; set it as its
+    /// `data-scroll-parent` attribute
+    pub fn id(&self) -> String {
+        match self.kind {
+            ParentKind::Element(id) => id.to_string(),
+            ParentKind::Window => String::new(),
+        }
+    }
+
+    /// Pass the parent element's `onmounted` event
+    pub fn mounted(&mut self, event: Event<MountedData>) {
+        self.element.set(Some(event.data()));
+    }
+
+    /// Pass the parent element's `onscroll` events
+    pub fn scrolled(&mut self, event: Event<ScrollData>) {
+        let data = event.data();
+        self.metrics.set(Some(ScrollMetrics {
+            scroll_top: data.scroll_top() as f64,
+            scroll_height: data.scroll_height() as f64,
+            client_height: data.client_height() as f64,
+        }));
+    }
+
+    /// Position and size from the last scroll, or none before the first
+    pub fn metrics(&self) -> Option<ScrollMetrics> {
+        (self.metrics)()
+    }
+
+    fn selector(&self) -> Option<String> {
+        match self.kind {
+            ParentKind::Element(id) => Some(format!("[data-scroll-parent=\"{}\"]", id)),
+            ParentKind::Window => None,
+        }
+    }
+
+    /// Scroll the parent to `top`. Returns whether it scrolled.
+    pub async fn scroll_to(&self, top: f64, behavior: ScrollBehavior) -> bool {
+        let Some(selector) = self.selector() else {
+            let eval = document::eval(WINDOW_SCROLL_SCRIPT);
+            if eval.send((top, matches!(behavior, ScrollBehavior::Smooth))).is_err() {
+                return false;
+            }
+            return eval.join::<bool>().await.unwrap_or(false);
+        };
+        let Some(element) = self.element.peek().clone() else {
+            return false;
+        };
+        scroll_with_fallback(element, &selector, top, behavior).await
+    }
+
+    /// Distance from the top of the parent's content to the element matching
+    /// `list_selector`, which lists subtract to get their own scroll position
+    pub(crate) async fn offset_of(&self, list_selector: &str) -> Option<f64> {
+        let eval = document::eval(LIST_OFFSET_SCRIPT);
+        eval.send((list_selector.to_string(), self.selector())).ok()?;
+        eval.join::<Option<f64>>().await.ok().flatten()
+    }
+}
+
+/// A scroll parent for an element of the page. Give the element the
+/// parent's `data-scroll-parent` id and pass it the `onmounted` and
+/// `onscroll` events:
+///
+/// ```ignore
+/// let mut parent = use_scroll_parent();
+/// rsx! {
+///     div {
+///         style: "height: 100vh; overflow-y: auto;",
+///         "data-scroll-parent": parent.id(),
+///         onmounted: move |evt| parent.mounted(evt),
+///         onscroll: move |evt| parent.scrolled(evt),
+///         PageHeader {}
+///         VirtualList { store, scroll_parent: parent }
+///     }
+/// }
+/// ```
+pub fn use_scroll_parent() -> ScrollParent {
+    let metrics = use_signal(|| None);
+    let element = use_signal(|| None);
+    let id = use_hook(|| NEXT_PARENT_ID.fetch_add(1, Ordering::Relaxed));
+    ScrollParent {
+        kind: ParentKind::Element(id),
+        metrics,
+        element,
+    }
+}
+
+/// A scroll parent for the window, following its scroll events through the
+/// webview
+pub fn use_window_scroll_parent() -> ScrollParent {
+    let mut metrics = use_signal(|| None);
+    let element = use_signal(|| None);
+    use_future(move || async move {
+        let mut eval = document::eval(WINDOW_METRICS_SCRIPT);
+        loop {
+            match eval.recv::<(f64, f64, f64)>().await {
+                Ok((scroll_top, scroll_height, client_height)) => metrics.set(Some(ScrollMetrics {
+                    scroll_top,
+                    scroll_height,
+                    client_height,
+                })),
+                Err(e) => {
+                    eprintln!("Stopped following the window's scroll: {}", e);
+                    return;
+                }
+            }
+        }
+    });
+    ScrollParent {
+        kind: ParentKind::Window,
+        metrics,
+        element,
+    }
+}
@@ -0,0 +1,109 @@
+use dioxus::prelude::*;
+use std::time::Duration;
+
+use crate::clock::{use_clock, Debounce};
+use crate::persistence::search_index::{SearchHit, SearchIndex};
+
+// Most results listed for a query
+const MAX_RESULTS: usize = 50;
+
+// Pause in typing after which the query is searched for
+const SEARCH_DEBOUNCE: Duration = Duration::from_millis(150);
+
+#[derive(PartialEq, Props, Clone)]
+pub struct SearchPanelProps {
+    // Called with the result the user picked
+    pub on_open: EventHandler<SearchHit>,
+    // Title of the feed a result belongs to, from its feed key
+    pub feed_title: Callback<String, String>,
+}
+
+// Search box over every item ever loaded, listing matches to jump to
+#[component]
+pub fn SearchPanel(props: SearchPanelProps) -> Element {
+    let index = use_context::<SearchIndex>();
+    let clock = use_clock();
+    let debounce = use_hook(|| Debounce::new(SEARCH_DEBOUNCE));
+    let mut query = use_signal(String::new);
+    // The query as it was when typing last paused
+    let mut searched = use_signal(String::new);
+
+    // Rerun as the searched query changes and as new items are indexed
+    let results = use_memo(move || {
+        let _ = (index.revision)();
+        index.search(&searched.read(), MAX_RESULTS)
+    });
+
+    rsx! {
+        section {
+            style: "
+                margin-bottom: 12px;
+                padding: 12px;
+                border-radius: 8px;
+                border: 1px solid #e2e8f0;
+                background: white;
+                font-size: 13px;
+                color: #334155;
+            ",
+            input {
+                r#type: "search",
+                aria_label: "Search all items",
+                placeholder: "Search {index.indexed_count()} items",
+                style: "width: 100%; box-sizing: border-box; padding: 8px; border: 1px solid #e2e8f0; border-radius: 6px;",
+                value: "{query}",
+                oninput: move |evt| {
+                    let value = evt.value();
+                    query.set(value.clone());
+                    let settled = debounce.settle(&clock);
+                    spawn(async move {
+                        if settled.await {
+                            searched.set(value);
+                        }
+                    });
+                },
+            }
+
+            if !searched.read().trim().is_empty() {
+                if results.read().is_empty() {
+                    p { style: "margin: 8px 0 0; color: #64748b;", "No matches" }
+                } else {
+                    ul {
+                        style: "
+                            list-style: none;
+                            margin: 8px 0 0;
+                            padding: 0;
+                            max-height: 240px;
+                            overflow-y: auto;
+                        ",
+                        for hit in results() {
+                            li {
+                                key: "{hit.id}",
+                                button {
+                                    style: "
+                                        width: 100%;
+                                        padding: 6px 8px;
+                                        border: none;
+                                        border-radius: 4px;
+                                        background: transparent;
+                                        text-align: left;
+                                        color: #334155;
+                                        cursor: pointer;
+                                    ",
+                                    onclick: {
+                                        let hit = hit.clone();
+                                        move |_| props.on_open.call(hit.clone())
+                                    },
+                                    span {
+                                        style: "margin-right: 6px; color: #64748b; font-size: 12px;",
+                                        {props.feed_title.call(hit.feed.clone())}
+                                    }
+                                    "{hit.snippet}"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
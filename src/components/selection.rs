@@ -0,0 +1,71 @@
+use dioxus::prelude::*;
+
+// Reports the rows the document selection starts and ends in, as the ids of
+// their items, whenever the selection changes; null when nothing is selected
+// or either end is outside a row
+const SELECTION_SCRIPT: &str = r#"
+    const row = (node) => {
+        const element = node && (node.nodeType === 1 ? node : node.parentElement);
+        return element ? element.closest('[data-item-id]') : null;
+    };
+    const report = () => {
+        const selection = document.getSelection();
+        if (!selection || selection.isCollapsed) {
+            dioxus.send(null);
+            return;
+        }
+        const anchor = row(selection.anchorNode);
+        const focus = row(selection.focusNode);
+        dioxus.send(anchor && focus ? [anchor.dataset.itemId, focus.dataset.itemId] : null);
+    };
+    document.addEventListener('selectionchange', report);
+    await new Promise(() => {});
+"#;
+
+// Writes the text sent from Rust to the clipboard
+const COPY_SCRIPT: &str = r#"
+    const text = await dioxus.recv();
+    await navigator.clipboard.writeText(text);
+    return true;
+"#;
+
+/// Ids of the items the text selection starts and ends in, in selection
+/// order (the end may be above the start), while a selection spans rows
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowSelection {
+    pub anchor_id: String,
+    pub focus_id: String,
+}
+
+/// Follow the document's text selection across rows.
+///
+/// Lists use it to keep the selected rows rendered while they scroll out of
+/// view, so the webview doesn't lose the selection when they are recycled.
+pub fn use_row_selection() -> Signal<Option<RowSelection>> {
+    let mut selection = use_signal(|| None::<RowSelection>);
+    use_future(move || async move {
+        let mut eval = document::eval(SELECTION_SCRIPT);
+        loop {
+            match eval.recv::<Option<(String, String)>>().await {
+                Ok(ends) => {
+                    let next = ends.map(|(anchor_id, focus_id)| RowSelection { anchor_id, focus_id });
+                    if *selection.peek() != next {
+                        selection.set(next);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Stopped following the text selection: {}", e);
+                    return;
+                }
+            }
+        }
+    });
+    selection
+}
+
+/// Put `text` on the system clipboard through the webview
+pub async fn copy_to_clipboard(text: String) -> Result<(), String> {
+    let eval = document::eval(COPY_SCRIPT);
+    eval.send(text).map_err(|e| e.to_string())?;
+    eval.join::<bool>().await.map(|_| ()).map_err(|e| e.to_string())
+}
@@ -0,0 +1,122 @@
+use dioxus::prelude::*;
+use std::collections::HashSet;
+
+use crate::assets::fetch_data_urls;
+use crate::persistence::session_export::{render_session_html, save_session_html};
+use crate::store::media::{use_media_cache, MediaStatus};
+use crate::store::FeedStore;
+
+// Prints the document sent from Rust from a hidden frame, so the webview's
+// print dialog (which can save as PDF) shows the export rather than the app
+const PRINT_SCRIPT: &str = r#"
+    const html = await dioxus.recv();
+    const frame = document.createElement('iframe');
+    frame.style.cssText = 'position: fixed; width: 0; height: 0; border: 0;';
+    document.body.appendChild(frame);
+    await new Promise(resolve => { frame.onload = resolve; frame.srcdoc = html; });
+    frame.contentWindow.focus();
+    frame.contentWindow.print();
+    setTimeout(() => frame.remove(), 1000);
+    return true;
+"#;
+
+// Export target for a reading session
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum SessionExport {
+    Html,
+    Pdf,
+}
+
+#[derive(PartialEq, Props, Clone)]
+pub struct SessionExportProps {
+    pub store: FeedStore,
+    // Heading of the exported document
+    #[props(default = "Feed".to_string())]
+    pub title: String,
+}
+
+// Exports every loaded item, not just the rendered window, as HTML with the
+// images inlined, or prints it to PDF
+#[component]
+pub fn SessionExportButtons(props: SessionExportProps) -> Element {
+    let store = props.store;
+    let title = props.title;
+    let media_cache = use_media_cache();
+    let mut exporting = use_signal(|| false);
+    let mut export_status = use_signal(|| None::<String>);
+
+    let export = use_callback(move |target: SessionExport| {
+        if exporting() {
+            return;
+        }
+        exporting.set(true);
+
+        let items: Vec<_> = store.items.peek().iter().cloned().collect();
+        let title = title.clone();
+        // Inline the candidate each row managed to show
+        let urls: HashSet<String> = items
+            .iter()
+            .filter_map(|item| {
+                item.image_candidates()
+                    .find(|url| media_cache.status(url) == MediaStatus::Loaded)
+                    .or_else(|| item.image_candidates().next())
+                    .map(str::to_string)
+            })
+            .collect();
+
+        spawn(async move {
+            let images = fetch_data_urls(urls.into_iter().collect()).await;
+            let html = render_session_html(&title, &items, &images);
+            match target {
+                SessionExport::Html => {
+                    let status = match save_session_html(&html) {
+                        Ok(path) => format!("Exported to {}", path.display()),
+                        Err(e) => format!("Export failed: {}", e),
+                    };
+                    export_status.set(Some(status));
+                }
+                SessionExport::Pdf => {
+                    let eval = document::eval(PRINT_SCRIPT);
+                    let printed = match eval.send(html) {
+                        Ok(()) => eval.join::<bool>().await.map(|_| ()),
+                        Err(e) => Err(e),
+                    };
+                    if let Err(e) = printed {
+                        export_status.set(Some(format!("Print failed: {}", e)));
+                    }
+                }
+            }
+            exporting.set(false);
+        });
+    });
+
+    rsx! {
+        if let Some(status) = export_status() {
+            span {
+                style: "
+                    align-self: center;
+                    font-size: 12px;
+                    color: #64748b;
+                ",
+                "{status}"
+            }
+        }
+        for (target, label) in [(SessionExport::Html, "Export HTML"), (SessionExport::Pdf, "Print PDF")] {
+            button {
+                key: "{label}",
+                style: "
+                    padding: 6px 12px;
+                    border-radius: 6px;
+                    border: 1px solid #e2e8f0;
+                    background: white;
+                    color: #0f172a;
+                    font-size: 13px;
+                    cursor: pointer;
+                ",
+                disabled: exporting(),
+                onclick: move |_| export.call(target),
+                "{label}"
+            }
+        }
+    }
+}
@@ -0,0 +1,337 @@
+use dioxus::prelude::*;
+
+use super::account_settings::AccountSettings;
+use super::filter_settings::FilterSettings;
+use super::media_folders::MediaFolders;
+use super::shortcut_settings::ShortcutSettings;
+use crate::ingest::control::CONTROL_ENV_VAR;
+use crate::ingest::webhook::WEBHOOK_ENV_VAR;
+use crate::ingest::Integrations;
+use crate::persistence::encryption;
+use crate::persistence::gating::ContentGating;
+use crate::persistence::polling::PollingSettings;
+use crate::persistence::privacy::{PrivacySettings, RemoteContent};
+use crate::persistence::reading_width::{ReadingWidth, ReadingWidthSettings};
+use crate::persistence::setup::SetupConfig;
+use crate::persistence::summarizer::SummarizerSettings;
+use crate::persistence::theme::{Theme, ThemeSettings};
+use crate::persistence::data_dir;
+use crate::processors::{wasm, Processors, PLUGINS_DIR};
+use crate::summarizer::http::API_KEY_ENV_VAR;
+
+// Style of a row holding one setting
+const ROW_STYLE: &str = "display: flex; align-items: center; gap: 8px; margin: 0 0 8px;";
+
+// App-wide settings, opened from the tab bar
+#[component]
+pub fn SettingsPanel() -> Element {
+    let mut privacy = use_context::<PrivacySettings>();
+    let remote_content = privacy.remote_content();
+    let mut encryption_status = use_signal(|| None::<String>);
+    let mut polling = use_context::<PollingSettings>();
+    let mut summarizer = use_context::<SummarizerSettings>();
+    let mut gating = use_context::<ContentGating>();
+    let mut theme = use_context::<ThemeSettings>();
+    let current_theme = theme.theme();
+    let mut reading_width = use_context::<ReadingWidthSettings>();
+    let current_width = reading_width.reading_width();
+    let mut setup = use_context::<SetupConfig>();
+    let integrations = use_context::<Integrations>();
+    let processors = use_context::<Processors>();
+    let plugin_names = processors.names();
+
+    rsx! {
+        section {
+            style: "
+                margin-bottom: 12px;
+                padding: 12px;
+                border-radius: 8px;
+                border: 1px solid #e2e8f0;
+                background: white;
+                font-size: 13px;
+                color: #334155;
+            ",
+            h2 { style: "margin: 0 0 8px; font-size: 14px;", "Settings" }
+
+            label {
+                style: ROW_STYLE,
+                "Theme"
+                select {
+                    onchange: move |evt| {
+                        let picked = Theme::ALL.into_iter().find(|theme| theme.label() == evt.value());
+                        if let Some(picked) = picked {
+                            theme.set_theme(picked);
+                        }
+                    },
+                    for option_theme in Theme::ALL {
+                        option {
+                            value: option_theme.label(),
+                            selected: option_theme == current_theme,
+                            "{option_theme.label()}"
+                        }
+                    }
+                }
+            }
+            label {
+                style: ROW_STYLE,
+                "Reading width"
+                select {
+                    onchange: move |evt| {
+                        let picked = ReadingWidth::ALL.into_iter().find(|width| width.label() == evt.value());
+                        if let Some(picked) = picked {
+                            reading_width.set_reading_width(picked);
+                        }
+                    },
+                    for option_width in ReadingWidth::ALL {
+                        option {
+                            value: option_width.label(),
+                            selected: option_width == current_width,
+                            "{option_width.label()}"
+                        }
+                    }
+                }
+            }
+            label {
+                style: ROW_STYLE,
+                input {
+                    r#type: "checkbox",
+                    checked: setup.demo_feeds(),
+                    onchange: move |evt| setup.set_demo_feeds(evt.checked()),
+                }
+                "Show the demo feeds (Home and Media)"
+            }
+
+            h3 { style: "margin: 8px 0; font-size: 13px; color: #64748b;", "Privacy" }
+            label {
+                style: ROW_STYLE,
+                "Load remote content"
+                select {
+                    onchange: move |evt| {
+                        let setting = RemoteContent::ALL
+                            .into_iter()
+                            .find(|setting| setting.label() == evt.value());
+                        if let Some(setting) = setting {
+                            privacy.set_remote_content(setting);
+                        }
+                    },
+                    for setting in RemoteContent::ALL {
+                        option {
+                            value: setting.label(),
+                            selected: setting == remote_content,
+                            "{setting.label()}"
+                        }
+                    }
+                }
+            }
+            label {
+                style: ROW_STYLE,
+                input {
+                    r#type: "checkbox",
+                    checked: privacy.resolve_redirects(),
+                    onchange: move |evt| privacy.set_resolve_redirects(evt.checked()),
+                }
+                "Resolve link redirects before opening"
+            }
+            label {
+                style: ROW_STYLE,
+                title: if encryption::is_supported() { "The key is kept in the system keychain" } else { "This build has no encryption support" },
+                input {
+                    r#type: "checkbox",
+                    disabled: !encryption::is_supported(),
+                    checked: privacy.encrypt_at_rest(),
+                    onchange: move |evt| {
+                        let status = match privacy.set_encrypt_at_rest(evt.checked()) {
+                            Ok(rewritten) => format!("Rewrote {} saved documents", rewritten),
+                            Err(e) => format!("Couldn't change encryption: {}", e),
+                        };
+                        encryption_status.set(Some(status));
+                    },
+                }
+                "Encrypt saved data"
+            }
+            if let Some(status) = encryption_status() {
+                p { role: "status", style: "margin: 0; color: #64748b;", "{status}" }
+            }
+
+            h3 { style: "margin: 8px 0; font-size: 13px; color: #64748b;", "Content" }
+            label {
+                style: ROW_STYLE,
+                title: "Flagged by the source or a plugin. Each item can still be revealed.",
+                input {
+                    r#type: "checkbox",
+                    checked: gating.blur_flagged(),
+                    onchange: move |evt| gating.set_blur_flagged(evt.checked()),
+                }
+                "Blur sensitive and spam content"
+            }
+
+            FilterSettings {}
+
+            h3 { style: "margin: 8px 0; font-size: 13px; color: #64748b;", "Local images" }
+            p {
+                style: "margin: 0 0 8px; color: #64748b;",
+                "Images on this computer are only read from the bundled assets and the folders listed here."
+            }
+            MediaFolders {}
+
+            h3 { style: "margin: 8px 0; font-size: 13px; color: #64748b;", "Polling" }
+            p {
+                style: "margin: 0 0 8px; color: #64748b;",
+                "Feeds are checked more often while new items keep arriving and less often when they're quiet, between these bounds."
+            }
+            label {
+                style: ROW_STYLE,
+                "Check at most every"
+                input {
+                    r#type: "number",
+                    min: "1",
+                    style: "width: 72px;",
+                    value: "{polling.min_secs()}",
+                    onchange: move |evt| {
+                        if let Ok(secs) = evt.value().parse() {
+                            polling.set_min_secs(secs);
+                        }
+                    },
+                }
+                "seconds"
+            }
+            label {
+                style: ROW_STYLE,
+                "Check at least every"
+                input {
+                    r#type: "number",
+                    min: "1",
+                    style: "width: 72px;",
+                    value: "{polling.max_secs()}",
+                    onchange: move |evt| {
+                        if let Ok(secs) = evt.value().parse() {
+                            polling.set_max_secs(secs);
+                        }
+                    },
+                }
+                "seconds"
+            }
+
+            h3 { style: "margin: 8px 0; font-size: 13px; color: #64748b;", "Summaries and alt text" }
+            p {
+                style: "margin: 0 0 8px; color: #64748b;",
+                "Long items and the newest items of a feed can be summarized by an OpenAI-compatible chat completions endpoint, such as a local Ollama. Leave the endpoint empty to turn summaries off."
+            }
+            label {
+                style: ROW_STYLE,
+                "Endpoint"
+                input {
+                    r#type: "url",
+                    style: "flex: 1;",
+                    placeholder: "http://localhost:11434/v1/chat/completions",
+                    value: "{summarizer.endpoint()}",
+                    onchange: move |evt| summarizer.set_endpoint(&evt.value()),
+                }
+            }
+            label {
+                style: ROW_STYLE,
+                "Model"
+                input {
+                    style: "width: 160px;",
+                    value: "{summarizer.model()}",
+                    onchange: move |evt| summarizer.set_model(&evt.value()),
+                }
+            }
+            label {
+                style: ROW_STYLE,
+                "Alt-text model"
+                input {
+                    style: "width: 160px;",
+                    placeholder: "e.g. llava",
+                    value: "{summarizer.caption_model()}",
+                    onchange: move |evt| summarizer.set_caption_model(&evt.value()),
+                }
+            }
+            p {
+                style: "margin: 0 0 8px; color: #64748b;",
+                "With a vision model set, images their source didn't describe get generated alt text once they load."
+            }
+            p {
+                style: "margin: 0 0 8px; color: #64748b;",
+                "An API key, if the endpoint needs one, is read from "
+                code { "{API_KEY_ENV_VAR}" }
+                " at launch."
+            }
+
+            ShortcutSettings {}
+
+            AccountSettings {}
+
+            h3 { style: "margin: 8px 0; font-size: 13px; color: #64748b;", "Integrations" }
+            match &integrations.webhook {
+                Some(endpoint) => rsx! {
+                    p {
+                        style: "margin: 0 0 8px;",
+                        "Webhook listening at "
+                        code { "{endpoint.url()}" }
+                    }
+                    p {
+                        style: "margin: 0 0 8px; word-break: break-all;",
+                        "Token "
+                        code { "{endpoint.token}" }
+                    }
+                },
+                None => rsx! {
+                    p {
+                        style: "margin: 0 0 8px; color: #64748b;",
+                        "Webhook off. Launch with "
+                        code { "{WEBHOOK_ENV_VAR}=1" }
+                        " to accept items pushed from local scripts."
+                    }
+                },
+            }
+            match &integrations.control {
+                Some(path) => rsx! {
+                    p {
+                        style: "margin: 0 0 8px; word-break: break-all;",
+                        "dioxus-feed-ctl connects through "
+                        code { "{path.display()}" }
+                    }
+                },
+                None => rsx! {
+                    p {
+                        style: "margin: 0 0 8px; color: #64748b;",
+                        "Control channel off"
+                        if std::env::var(CONTROL_ENV_VAR).is_ok_and(|value| value == "0") {
+                            " ("
+                            code { "{CONTROL_ENV_VAR}=0" }
+                            ")"
+                        }
+                    }
+                },
+            }
+
+            h3 { style: "margin: 8px 0; font-size: 13px; color: #64748b;", "Plugins" }
+            if !wasm::is_supported() {
+                p {
+                    style: "margin: 0 0 8px; color: #64748b;",
+                    "This build can't run plugins"
+                }
+            } else if plugin_names.is_empty() {
+                p {
+                    style: "margin: 0 0 8px; color: #64748b; word-break: break-all;",
+                    "No plugins. Put WASI modules in "
+                    code { "{data_dir().join(PLUGINS_DIR).display()}" }
+                    " and restart to load them."
+                }
+            } else {
+                p {
+                    style: "margin: 0 0 8px;",
+                    "Loaded: {plugin_names.join(\", \")}"
+                }
+            }
+            for failure in processors.failures() {
+                p {
+                    key: "{failure.plugin}",
+                    style: "margin: 0 0 8px; color: #b91c1c;",
+                    "{failure.plugin}: {failure.error}"
+                }
+            }
+        }
+    }
+}
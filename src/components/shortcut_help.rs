@@ -0,0 +1,84 @@
+use dioxus::prelude::*;
+
+use crate::persistence::shortcuts::ShortcutMap;
+use crate::shortcuts::ShortcutAction;
+
+// Keys the feed list handles itself, listed after the configurable ones
+const LIST_KEYS: &[(&str, &str)] = &[
+    ("Ctrl+F", "Find in feed"),
+    ("Ctrl+Z", "Undo an edit"),
+    ("Ctrl+Y", "Redo an edit"),
+];
+
+#[derive(PartialEq, Props, Clone)]
+pub struct ShortcutHelpProps {
+    pub on_close: EventHandler<()>,
+}
+
+// Overlay listing every keyboard shortcut, opened with "?" by default.
+// Escape or a click outside closes it.
+#[component]
+pub fn ShortcutHelp(props: ShortcutHelpProps) -> Element {
+    let on_close = props.on_close;
+    let shortcuts = use_context::<ShortcutMap>();
+
+    rsx! {
+        div {
+            role: "dialog",
+            aria_modal: "true",
+            aria_label: "Keyboard shortcuts",
+            tabindex: "0",
+            style: "
+                position: fixed;
+                inset: 0;
+                z-index: 1000;
+                display: flex;
+                align-items: center;
+                justify-content: center;
+                background: rgba(15, 23, 42, 0.5);
+                outline: none;
+            ",
+            onmounted: move |evt| async move {
+                let _ = evt.set_focus(true).await;
+            },
+            onkeydown: move |evt| {
+                if evt.key() == Key::Escape {
+                    on_close.call(());
+                }
+            },
+            onclick: move |_| on_close.call(()),
+            section {
+                style: "
+                    min-width: 320px;
+                    padding: 16px 20px;
+                    border-radius: 8px;
+                    background: white;
+                    color: #334155;
+                    font-size: 13px;
+                ",
+                onclick: move |evt| evt.stop_propagation(),
+                h2 { style: "margin: 0 0 12px; font-size: 15px; color: #0f172a;", "Keyboard shortcuts" }
+                table {
+                    style: "width: 100%; border-collapse: collapse;",
+                    tbody {
+                        for action in ShortcutAction::ALL {
+                            tr {
+                                key: "{action.label()}",
+                                td { style: "padding: 4px 0;", "{action.label()}" }
+                                td { style: "padding: 4px 0; text-align: right;", kbd { "{shortcuts.key(action)}" } }
+                            }
+                        }
+                        for (key, label) in LIST_KEYS.iter().copied() {
+                            tr {
+                                key: "{label}",
+                                td { style: "padding: 4px 0; color: #64748b;", "{label}" }
+                                td { style: "padding: 4px 0; text-align: right;", kbd { "{key}" } }
+                            }
+                        }
+                    }
+                }
+                p { style: "margin: 12px 0 0; color: #64748b;", "Shortcuts can be changed under Settings." }
+            }
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use dioxus::prelude::*;
+
+use crate::persistence::shortcuts::ShortcutMap;
+use crate::shortcuts::{key_name, ShortcutAction};
+
+// Style of a row holding one binding
+const ROW_STYLE: &str = "display: flex; align-items: center; gap: 8px; margin: 0 0 8px;";
+
+// Key bound to each shortcut action, rebound by pressing a new key in its
+// field. Shown as a section of the settings panel.
+#[component]
+pub fn ShortcutSettings() -> Element {
+    let mut shortcuts = use_context::<ShortcutMap>();
+    let conflicts = shortcuts.conflicts();
+    let mut status = use_signal(|| None::<String>);
+
+    rsx! {
+        h3 { style: "margin: 8px 0; font-size: 13px; color: #64748b;", "Keyboard shortcuts" }
+
+        for action in ShortcutAction::ALL {
+            label {
+                key: "{action.label()}",
+                style: ROW_STYLE,
+                span { style: "flex: 1;", "{action.label()}" }
+                input {
+                    r#type: "text",
+                    readonly: true,
+                    style: format!("width: 96px; text-align: center; {}", if conflicts.contains(&action) { "border-color: #ef4444;" } else { "" }),
+                    title: "Press the new key",
+                    value: "{shortcuts.key(action)}",
+                    onkeydown: move |evt| {
+                        // Tab still moves between fields
+                        if evt.key() == Key::Tab {
+                            return;
+                        }
+                        evt.prevent_default();
+                        let Some(key) = key_name(&evt.key(), evt.modifiers()) else {
+                            return;
+                        };
+                        match shortcuts.set_key(action, &key) {
+                            Ok(()) => status.set(None),
+                            Err(e) => status.set(Some(e.to_string())),
+                        }
+                    },
+                }
+            }
+        }
+        if !conflicts.is_empty() {
+            p {
+                style: "margin: 0 0 8px; color: #ef4444;",
+                "Some actions share a key; only the first of them runs."
+            }
+        }
+        if let Some(status) = status() {
+            p { role: "status", style: "margin: 0 0 8px; color: #64748b;", "{status}" }
+        }
+        button {
+            onclick: move |_| {
+                shortcuts.reset();
+                status.set(None);
+            },
+            "Restore default shortcuts"
+        }
+    }
+}
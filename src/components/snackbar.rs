@@ -0,0 +1,133 @@
+use dioxus::prelude::*;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::clock::use_clock;
+
+// How long a message stays up unless dismissed
+const SNACKBAR_DURATION: Duration = Duration::from_secs(6);
+
+// Most messages shown at once; older ones are dropped first
+const MAX_MESSAGES: usize = 3;
+
+// A message shown in the snackbar
+#[derive(Clone, PartialEq, Debug)]
+struct SnackbarMessage {
+    id: u64,
+    text: String,
+}
+
+/// Short messages shown at the bottom of the window, e.g. errors the user
+/// can act on (see `error::report`).
+///
+/// Provided once at the app root with `use_context_provider(Snackbar::new)`
+/// and rendered by [`SnackbarHost`].
+#[derive(Clone, Copy, PartialEq)]
+pub struct Snackbar {
+    messages: Signal<VecDeque<SnackbarMessage>>,
+    next_id: Signal<u64>,
+}
+
+impl Snackbar {
+    pub fn new() -> Self {
+        Self {
+            messages: Signal::new(VecDeque::new()),
+            next_id: Signal::new(0),
+        }
+    }
+
+    /// Show `text`; the same text already on screen isn't repeated
+    pub fn show(&mut self, text: impl Into<String>) {
+        let text = text.into();
+        if self.messages.peek().iter().any(|message| message.text == text) {
+            return;
+        }
+        let id = *self.next_id.peek();
+        self.next_id.set(id + 1);
+        let mut messages = self.messages.write();
+        messages.push_back(SnackbarMessage { id, text });
+        while messages.len() > MAX_MESSAGES {
+            messages.pop_front();
+        }
+    }
+
+    fn dismiss(&mut self, id: u64) {
+        if !self.messages.peek().iter().any(|message| message.id == id) {
+            return;
+        }
+        self.messages.write().retain(|message| message.id != id);
+    }
+}
+
+impl Default for Snackbar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Messages of the `Snackbar` in context, each dismissed after a while or
+// with its close button. Rendered once at the app root.
+#[component]
+pub fn SnackbarHost() -> Element {
+    let mut snackbar = use_context::<Snackbar>();
+    let clock = use_clock();
+    let messages = snackbar.messages.read().clone();
+
+    // Time out each message once, when it first shows up
+    let mut scheduled = use_hook(|| CopyValue::new(0u64));
+    use_effect(move || {
+        for message in snackbar.messages.read().iter() {
+            if message.id < *scheduled.peek() {
+                continue;
+            }
+            scheduled.set(message.id + 1);
+            let id = message.id;
+            let clock = clock.clone();
+            spawn(async move {
+                clock.sleep(SNACKBAR_DURATION).await;
+                snackbar.dismiss(id);
+            });
+        }
+    });
+
+    rsx! {
+        div {
+            role: "status",
+            aria_live: "polite",
+            style: "
+                position: fixed;
+                left: 50%;
+                bottom: 24px;
+                transform: translateX(-50%);
+                z-index: 1100;
+                display: flex;
+                flex-direction: column;
+                gap: 8px;
+                max-width: calc(100vw - 32px);
+            ",
+            for message in messages {
+                div {
+                    key: "{message.id}",
+                    style: "
+                        display: flex;
+                        align-items: center;
+                        gap: 12px;
+                        padding: 10px 14px;
+                        border-radius: 8px;
+                        background: #0f172a;
+                        color: white;
+                        font-size: 13px;
+                        box-shadow: 0 8px 24px rgba(15, 23, 42, 0.25);
+                    ",
+                    span { style: "flex: 1;", "{message.text}" }
+                    button {
+                        aria_label: "Dismiss",
+                        style: "border: none; background: transparent; color: #cbd5e1; font-size: 16px; cursor: pointer;",
+                        onclick: move |_| snackbar.dismiss(message.id),
+                        "×"
+                    }
+                }
+            }
+        }
+    }
+}
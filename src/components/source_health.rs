@@ -0,0 +1,99 @@
+use chrono::{DateTime, Utc};
+use dioxus::prelude::*;
+
+use crate::clock::use_clock;
+use crate::sources::health::{SourceHealth, SourceStats};
+
+// Refresh interval for relative times and rate-limit countdowns
+const TICK_INTERVAL_SECS: u64 = 5;
+
+// Time since `at`, e.g. "12s ago"
+fn time_ago(at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let seconds = (now - at).num_seconds().max(0);
+    match seconds {
+        0..=59 => format!("{}s ago", seconds),
+        60..=3599 => format!("{}m ago", seconds / 60),
+        _ => format!("{}h ago", seconds / 3600),
+    }
+}
+
+// Rate-limit column for a source
+fn rate_limit_label(stats: &SourceStats, now: DateTime<Utc>) -> String {
+    match stats.rate_limited_until {
+        Some(until) if until > now => format!("Limited for {}s", (until - now).num_seconds()),
+        _ => "OK".to_string(),
+    }
+}
+
+// Diagnostics table of every attached source's sync history
+#[component]
+pub fn SourceHealthPanel() -> Element {
+    let health = use_context::<SourceHealth>();
+    let clock = use_clock();
+    let mut now = use_signal(|| clock.utc_now());
+
+    // Keep relative times current while the panel is open
+    use_hook(move || {
+        spawn(async move {
+            loop {
+                clock.sleep(std::time::Duration::from_secs(TICK_INTERVAL_SECS)).await;
+                now.set(clock.utc_now());
+            }
+        })
+    });
+
+    let sources = health.sources();
+    let now = now();
+    let cell_style = "padding: 6px 8px; border-bottom: 1px solid #e2e8f0; text-align: left;";
+
+    rsx! {
+        section {
+            style: "
+                margin-bottom: 12px;
+                padding: 12px;
+                border-radius: 8px;
+                border: 1px solid #e2e8f0;
+                background: white;
+                font-size: 13px;
+                color: #334155;
+                overflow-x: auto;
+            ",
+            h2 { style: "margin: 0 0 8px; font-size: 14px;", "Sources" }
+
+            if sources.is_empty() {
+                p { style: "margin: 0; color: #64748b;", "No source has synced yet" }
+            } else {
+                table {
+                    style: "width: 100%; border-collapse: collapse;",
+                    thead {
+                        tr {
+                            th { style: cell_style, "Source" }
+                            th { style: cell_style, "Last sync" }
+                            th { style: cell_style, "Items" }
+                            th { style: cell_style, "Rate limit" }
+                            th { style: cell_style, "Last error" }
+                        }
+                    }
+                    tbody {
+                        for stats in sources {
+                            tr {
+                                key: "{stats.name}",
+                                td { style: cell_style, "{stats.name}" }
+                                td {
+                                    style: cell_style,
+                                    {stats.last_sync.map(|at| time_ago(at, now)).unwrap_or_else(|| "Never".to_string())}
+                                }
+                                td { style: cell_style, "{stats.items_fetched}" }
+                                td { style: cell_style, {rate_limit_label(&stats, now)} }
+                                td {
+                                    style: format!("{} color: {};", cell_style, if stats.last_error.is_some() { "#b91c1c" } else { "#64748b" }),
+                                    {stats.last_error.clone().unwrap_or_else(|| "—".to_string())}
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use dioxus::html::geometry::ClientPoint;
+use dioxus::prelude::*;
+use std::rc::Rc;
+
+use crate::persistence::pane_layouts::{PaneLayout, PaneLayouts};
+
+// Split pane configuration
+const HANDLE_SIZE: f64 = 6.0;
+const KEY_STEP: f64 = 24.0; // Pixels an arrow key moves the handle
+
+/// Direction the panes of a `SplitPane` are laid out in
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SplitAxis {
+    /// Side by side, with a vertical handle between them
+    #[default]
+    Horizontal,
+    /// One above the other, with a horizontal handle between them
+    Vertical,
+}
+
+impl SplitAxis {
+    fn flex_direction(self) -> &'static str {
+        match self {
+            SplitAxis::Horizontal => "row",
+            SplitAxis::Vertical => "column",
+        }
+    }
+
+    // CSS property sized along the axis
+    fn dimension(self) -> &'static str {
+        match self {
+            SplitAxis::Horizontal => "width",
+            SplitAxis::Vertical => "height",
+        }
+    }
+
+    fn cursor(self) -> &'static str {
+        match self {
+            SplitAxis::Horizontal => "col-resize",
+            SplitAxis::Vertical => "row-resize",
+        }
+    }
+
+    // Orientation of the handle, as ARIA describes separators
+    fn handle_orientation(self) -> &'static str {
+        match self {
+            SplitAxis::Horizontal => "vertical",
+            SplitAxis::Vertical => "horizontal",
+        }
+    }
+
+    fn along(self, point: ClientPoint) -> f64 {
+        match self {
+            SplitAxis::Horizontal => point.x,
+            SplitAxis::Vertical => point.y,
+        }
+    }
+
+    // Keys moving the handle back and forward
+    fn step(self, key: &Key) -> Option<f64> {
+        match (self, key) {
+            (SplitAxis::Horizontal, Key::ArrowLeft) | (SplitAxis::Vertical, Key::ArrowUp) => Some(-KEY_STEP),
+            (SplitAxis::Horizontal, Key::ArrowRight) | (SplitAxis::Vertical, Key::ArrowDown) => Some(KEY_STEP),
+            _ => None,
+        }
+    }
+}
+
+#[derive(PartialEq, Props, Clone)]
+pub struct SplitPaneProps {
+    // The pane sized by the handle, left or on top
+    pub first: Element,
+    // The pane taking the rest of the space
+    pub second: Element,
+    #[props(default)]
+    pub axis: SplitAxis,
+    // Size of the first pane until the user resizes it
+    pub default_size: f64,
+    // Bounds of the first pane's size
+    #[props(default)]
+    pub min_size: f64,
+    pub max_size: Option<f64>,
+    // Space the handle always leaves the second pane
+    #[props(default)]
+    pub min_second_size: f64,
+    // Let the first pane be folded away by double-clicking the handle or
+    // pressing Enter on it
+    #[props(default)]
+    pub collapsible: bool,
+    #[props(default)]
+    pub default_collapsed: bool,
+    // Key the size and collapsed state are persisted under; panes without
+    // one start at their defaults every time
+    pub storage_key: Option<&'static str>,
+    // Accessible name of the handle
+    #[props(default = "Resize panes".to_string())]
+    pub label: String,
+}
+
+// Two panes with a handle between them that is dragged, or moved with the
+// arrow keys, to resize the first. The webview has no resizable panes of its
+// own, so the pane tracks the drag itself and sizes the first pane in pixels.
+#[component]
+pub fn SplitPane(props: SplitPaneProps) -> Element {
+    let axis = props.axis;
+    let min_size = props.min_size;
+    let max_size = props.max_size;
+    let min_second_size = props.min_second_size;
+    let collapsible = props.collapsible;
+    let storage_key = props.storage_key;
+
+    let layouts = try_use_context::<PaneLayouts>();
+    let saved = use_hook(|| storage_key.zip(layouts).and_then(|(key, layouts)| layouts.layout(key)));
+    let mut size = use_signal(|| saved.map_or(props.default_size, |layout| layout.size));
+    let mut collapsed = use_signal(|| collapsible && saved.map_or(props.default_collapsed, |layout| layout.collapsed));
+    let mut dragging = use_signal(|| false);
+
+    // Where the container starts along the axis and how long it is, so a
+    // drag can be turned into a size
+    let mut bounds = use_signal(|| None::<(f64, f64)>);
+    let mut container = use_signal(|| None::<Rc<MountedData>>);
+    let measure = move || {
+        let Some(element) = container.peek().clone() else {
+            return;
+        };
+        spawn(async move {
+            if let Ok(rect) = element.get_client_rect().await {
+                bounds.set(Some(match axis {
+                    SplitAxis::Horizontal => (rect.origin.x, rect.width()),
+                    SplitAxis::Vertical => (rect.origin.y, rect.height()),
+                }));
+            }
+        });
+    };
+
+    // Largest size the first pane can take in the container as it is now
+    let largest = move || {
+        let fits = bounds().map_or(f64::INFINITY, |(_, length)| length - HANDLE_SIZE - min_second_size);
+        max_size.unwrap_or(f64::INFINITY).min(fits).max(min_size)
+    };
+    let clamp = move |value: f64| value.clamp(min_size, largest());
+
+    let persist = move || {
+        let (Some(key), Some(mut layouts)) = (storage_key, layouts) else {
+            return;
+        };
+        layouts.remember(key, PaneLayout {
+            size: *size.peek(),
+            collapsed: *collapsed.peek(),
+        });
+    };
+    let mut resize_to = move |value: f64| {
+        collapsed.set(false);
+        size.set(clamp(value));
+    };
+    let mut toggle_collapsed = move || {
+        if collapsible {
+            collapsed.toggle();
+            persist();
+        }
+    };
+    let mut end_drag = move || {
+        if *dragging.peek() {
+            dragging.set(false);
+            persist();
+        }
+    };
+
+    // Shown clamped, so a container that shrank never hides the second pane
+    let shown_size = if collapsed() { 0.0 } else { clamp(size()) };
+    let dimension = axis.dimension();
+
+    rsx! {
+        div {
+            style: format!("
+                display: flex;
+                flex-direction: {};
+                width: 100%;
+                height: 100%;
+                min-width: 0;
+                min-height: 0;
+                user-select: {};
+            ", axis.flex_direction(), if dragging() { "none" } else { "auto" }),
+            onmounted: move |evt| {
+                container.set(Some(evt.data()));
+                measure();
+            },
+            onresize: move |_| measure(),
+            onmousemove: move |evt: Event<MouseData>| {
+                if !dragging() {
+                    return;
+                }
+                if let Some((start, _)) = bounds() {
+                    resize_to(axis.along(evt.client_coordinates()) - start - HANDLE_SIZE / 2.0);
+                }
+            },
+            onmouseup: move |_| end_drag(),
+            onmouseleave: move |_| end_drag(),
+
+            // Kept mounted while collapsed, so its state survives
+            div {
+                style: format!("
+                    flex: none;
+                    {}: {}px;
+                    min-width: 0;
+                    min-height: 0;
+                    overflow: hidden;
+                    display: {};
+                ", dimension, shown_size, if collapsed() { "none" } else { "block" }),
+                {props.first}
+            }
+
+            div {
+                role: "separator",
+                tabindex: "0",
+                aria_orientation: axis.handle_orientation(),
+                aria_label: "{props.label}",
+                aria_valuenow: format!("{:.0}", shown_size),
+                aria_valuemin: format!("{:.0}", min_size),
+                aria_expanded: collapsible.then(|| if collapsed() { "false" } else { "true" }),
+                style: format!("
+                    flex: none;
+                    {}: {}px;
+                    cursor: {};
+                    background: {};
+                ", dimension, HANDLE_SIZE, axis.cursor(), if dragging() { "#94a3b8" } else { "#e2e8f0" }),
+                onmousedown: move |evt| {
+                    evt.prevent_default();
+                    measure();
+                    dragging.set(true);
+                },
+                ondoubleclick: move |_| toggle_collapsed(),
+                onkeydown: move |evt: Event<KeyboardData>| {
+                    let key = evt.key();
+                    let target = match key {
+                        Key::Enter => {
+                            evt.prevent_default();
+                            toggle_collapsed();
+                            return;
+                        }
+                        Key::Home => min_size,
+                        Key::End => largest(),
+                        _ => match axis.step(&key) {
+                            Some(step) => shown_size + step,
+                            None => return,
+                        },
+                    };
+                    evt.prevent_default();
+                    evt.stop_propagation();
+                    resize_to(target);
+                    persist();
+                },
+            }
+
+            div {
+                style: "flex: 1; min-width: 0; min-height: 0; overflow: hidden;",
+                {props.second}
+            }
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use dioxus::prelude::*;
+use dioxus_feed_core::VirtualFeedItem;
+
+use super::article_view::ArticleView;
+use super::list_controller::{use_virtual_list_controller, ScrollRequest, VirtualListController};
+use super::split_pane::SplitPane;
+use super::virtual_list::{VirtualFeedItemComponent, VirtualList};
+use crate::store::FeedStore;
+
+// Split view configuration
+const PANE_HEIGHT: f64 = 600.0; // Same viewport as the list
+const DEFAULT_LIST_WIDTH: f64 = 420.0; // Width of the list until it is resized
+const MIN_PANE_WIDTH: f64 = 280.0; // Narrowest either pane can be dragged to
+
+#[derive(PartialEq, Props, Clone)]
+pub struct SplitViewProps {
+    // Store holding the items, the same one the list shows alone
+    pub store: FeedStore,
+    // Key for the persisted read state, passed on to the list
+    #[props(default)]
+    pub feed_id: Option<&'static str>,
+    // Handle for scrolling the list; the view creates its own when none is given
+    pub controller: Option<VirtualListController>,
+}
+
+// The feed's list on the left and the selected item in full on the right,
+// split by a handle that resizes them and remembers the size, or folds the
+// list away. Clicking a row selects it; the up and down arrows move the
+// selection.
+#[component]
+pub fn SplitView(props: SplitViewProps) -> Element {
+    let store = props.store;
+    let items = use_memo(move || store.ordered_items());
+    let own_controller = use_virtual_list_controller();
+    let mut controller = props.controller.unwrap_or(own_controller);
+
+    // Selected item, shown while it is loaded
+    let mut selected = use_signal(|| None::<String>);
+    let detail = use_memo(move || {
+        let id = selected.read().clone()?;
+        items.read().iter().find(|item| item.id == id).cloned()
+    });
+
+    // Move the selection `step` items, bringing it into view. Without a
+    // selection it starts from the row at the top of the list.
+    let mut move_selection = move |step: i64| {
+        let count = items.peek().len();
+        if count == 0 {
+            return;
+        }
+        let from = selected
+            .peek()
+            .clone()
+            .or_else(|| controller.current_anchor().map(|anchor| anchor.item_id));
+        let current = from.and_then(|id| items.peek().iter().position(|item| item.id == id));
+        let target = match current {
+            Some(index) => (index as i64 + step).clamp(0, count as i64 - 1) as usize,
+            None => 0,
+        };
+        let id = items.peek()[target].id.clone();
+        selected.set(Some(id.clone()));
+        controller.scroll_to(ScrollRequest::Item(id));
+    };
+
+    // Article opened from the detail pane
+    let mut reader_url = use_signal(|| None::<String>);
+
+    rsx! {
+        div {
+            style: format!("height: {}px;", PANE_HEIGHT),
+            onkeydown: move |evt: Event<KeyboardData>| {
+                let step = match evt.key() {
+                    Key::ArrowDown => 1,
+                    Key::ArrowUp => -1,
+                    Key::Enter => {
+                        if let Some(link) = detail().and_then(|item| item.link) {
+                            reader_url.set(Some(link));
+                        }
+                        return;
+                    }
+                    _ => return,
+                };
+                evt.prevent_default();
+                move_selection(step);
+            },
+
+            SplitPane {
+                default_size: DEFAULT_LIST_WIDTH,
+                min_size: MIN_PANE_WIDTH,
+                min_second_size: MIN_PANE_WIDTH,
+                collapsible: true,
+                storage_key: "split_view",
+                label: "Resize list and detail".to_string(),
+                first: rsx! {
+                    VirtualList {
+                        store,
+                        feed_id: props.feed_id,
+                        controller,
+                        highlight_new_items: true,
+                        selected_id: selected(),
+                        on_select: move |item: VirtualFeedItem| selected.set(Some(item.id)),
+                    }
+                },
+                second: rsx! {
+                    div {
+                        style: "height: 100%; overflow-y: auto; padding: 8px 12px; box-sizing: border-box; background: white;",
+                        if let Some(item) = detail() {
+                            VirtualFeedItemComponent {
+                                key: "{item.id}",
+                                item: item.clone(),
+                                row_height: PANE_HEIGHT - 16.0,
+                                stacked: true,
+                                state: store.item_state(&item.id),
+                                on_read: move |url| reader_url.set(Some(url)),
+                            }
+                        } else {
+                            p {
+                                style: "margin: 40px 0; text-align: center; color: #64748b; font-size: 14px;",
+                                "Select an item to read it here"
+                            }
+                        }
+                    }
+                },
+            }
+
+            if let Some(url) = reader_url() {
+                ArticleView {
+                    url,
+                    on_close: move |_| reader_url.set(None),
+                }
+            }
+        }
+    }
+}
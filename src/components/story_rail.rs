@@ -0,0 +1,117 @@
+use dioxus::prelude::*;
+
+use super::horizontal_list::HorizontalList;
+use crate::clock::use_clock;
+use crate::persistence::seen_stories::SeenStories;
+use crate::sources::stories::{DemoStories, StorySource};
+use crate::startup::StartupTrace;
+
+// Story rail configuration
+const STORY_WIDTH: f64 = 84.0; // Width per story including the gap
+const RAIL_HEIGHT: f64 = 96.0;
+const AVATAR_SIZE: f64 = 60.0;
+
+// Horizontally scrolling stories above the feed. Unseen stories get a
+// colored ring; opening one marks it seen.
+#[component]
+pub fn StoryRail() -> Element {
+    let mut seen = use_context::<SeenStories>();
+    let clock = use_clock();
+    let startup = try_use_context::<StartupTrace>();
+    // The rail and its avatars can wait until the feed is on screen
+    let stories = use_resource(move || {
+        let clock = clock.clone();
+        let source = DemoStories::new().with_clock(clock.clone());
+        async move {
+            if let Some(startup) = startup {
+                startup.after_first_paint(&clock).await;
+            }
+            source.load_stories().await
+        }
+    });
+
+    let stories = match &*stories.read() {
+        Some(Ok(stories)) => stories.clone(),
+        Some(Err(e)) => {
+            eprintln!("Failed to load stories: {}", e);
+            return rsx! {};
+        }
+        None => Vec::new(),
+    };
+    if stories.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        nav {
+            aria_label: "Stories",
+            style: "
+                margin-bottom: 12px;
+                border-bottom: 1px solid #e2e8f0;
+            ",
+            HorizontalList {
+                item_count: stories.len(),
+                item_width: STORY_WIDTH,
+                height: RAIL_HEIGHT,
+                render_item: move |index: usize| {
+                    let story = stories[index].clone();
+                    let is_seen = seen.is_seen(&story.id);
+                    let story_id = story.id.clone();
+                    rsx! {
+                        button {
+                            style: "
+                                display: flex;
+                                flex-direction: column;
+                                align-items: center;
+                                gap: 4px;
+                                width: 100%;
+                                padding: 4px 0;
+                                border: none;
+                                background: transparent;
+                                cursor: pointer;
+                            ",
+                            aria_label: if is_seen { "{story.author}'s story" } else { "{story.author}'s story, unseen" },
+                            onclick: move |_| seen.mark_seen(&story_id),
+                            div {
+                                style: format!("
+                                    width: {}px;
+                                    height: {}px;
+                                    border-radius: 50%;
+                                    padding: 2px;
+                                    background: {};
+                                ", AVATAR_SIZE, AVATAR_SIZE,
+                                    if is_seen { "#e2e8f0" } else { "linear-gradient(45deg, #f59e0b, #ec4899)" }
+                                ),
+                                img {
+                                    src: "{story.image_url}",
+                                    alt: "",
+                                    loading: "lazy",
+                                    style: "
+                                        width: 100%;
+                                        height: 100%;
+                                        border-radius: 50%;
+                                        border: 2px solid white;
+                                        box-sizing: border-box;
+                                        object-fit: cover;
+                                        background: #f8fafc;
+                                    ",
+                                }
+                            }
+                            span {
+                                style: format!("
+                                    font-size: 12px;
+                                    color: {};
+                                    max-width: 100%;
+                                    overflow: hidden;
+                                    text-overflow: ellipsis;
+                                    white-space: nowrap;
+                                ", if is_seen { "#64748b" } else { "#0f172a" }),
+                                "{story.author}"
+                            }
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use dioxus::prelude::*;
+
+use crate::store::summaries::{Summary, SummaryStatus};
+
+#[derive(PartialEq, Props, Clone)]
+pub struct SummaryViewProps {
+    pub summary: Signal<Summary>,
+    // Heading shown above the summary, e.g. for the feed digest
+    pub title: Option<String>,
+    // Offers a "Summarize" action while there is no summary yet
+    pub on_summarize: Option<EventHandler<()>>,
+    // Offers dismissing the summary once it has started
+    pub on_close: Option<EventHandler<()>>,
+}
+
+// Summary of an item or digest, shown as it streams in. Reading the summary
+// here keeps each arriving piece from re-rendering the row around it.
+#[component]
+pub fn SummaryView(props: SummaryViewProps) -> Element {
+    let Summary { text, status } = props.summary.read().clone();
+    let action_style = "
+        align-self: flex-start;
+        padding: 0;
+        border: none;
+        background: transparent;
+        color: #2563eb;
+        font-size: 12px;
+        cursor: pointer;
+    ";
+
+    rsx! {
+        match status {
+            SummaryStatus::Idle => rsx! {
+                if let Some(on_summarize) = props.on_summarize {
+                    button {
+                        style: action_style,
+                        onclick: move |_| on_summarize.call(()),
+                        "Summarize"
+                    }
+                }
+            },
+            status => rsx! {
+                div {
+                    role: "note",
+                    aria_live: "polite",
+                    aria_busy: "{status == SummaryStatus::Streaming}",
+                    style: "
+                        flex-basis: 100%;
+                        display: flex;
+                        flex-direction: column;
+                        gap: 4px;
+                        max-height: 120px;
+                        overflow-y: auto;
+                        padding: 8px 10px;
+                        border-radius: 6px;
+                        background: #f8fafc;
+                        border-left: 3px solid #6366f1;
+                        font-size: 13px;
+                        line-height: 1.5;
+                        color: #334155;
+                    ",
+                    div {
+                        style: "display: flex; align-items: center; gap: 8px;",
+                        strong {
+                            style: "font-size: 12px; color: #4f46e5;",
+                            {props.title.clone().unwrap_or_else(|| "Summary".to_string())}
+                        }
+                        if let Some(on_close) = props.on_close {
+                            button {
+                                aria_label: "Dismiss summary",
+                                style: "margin-left: auto; border: none; background: transparent; color: #64748b; cursor: pointer;",
+                                onclick: move |_| on_close.call(()),
+                                "×"
+                            }
+                        }
+                    }
+                    match &status {
+                        SummaryStatus::Failed(error) => rsx! {
+                            span { style: "color: #b91c1c;", "{error}" }
+                            if let Some(on_summarize) = props.on_summarize {
+                                button {
+                                    style: action_style,
+                                    onclick: move |_| on_summarize.call(()),
+                                    "Try again"
+                                }
+                            }
+                        },
+                        SummaryStatus::Streaming if text.is_empty() => rsx! {
+                            span { style: "color: #64748b;", "Summarizing…" }
+                        },
+                        SummaryStatus::Streaming => rsx! { span { "{text}…" } },
+                        _ => rsx! { span { "{text}" } },
+                    }
+                }
+            },
+        }
+    }
+}
@@ -1,467 +1,2348 @@
 use dioxus::prelude::*;
-use dioxus::html::geometry::PixelsVector2D;
+use dioxus_feed_core::{ContentFlag, VirtualFeedItem};
 
-// Feed item data structure for virtual list
-#[derive(Clone, PartialEq, Debug)]
-pub struct VirtualFeedItem {
-    pub id: String,
-    pub content: String,
-    pub image_url: String,
-}
+use super::article_view::ArticleView;
+use super::find_bar::{highlight_segments, match_ranges, FindBar};
+use super::hover_card::{AuthorCard, AuthorHover};
+use super::item_menu::ItemMenu;
+use super::lightbox::{Lightbox, LightboxImage};
+use super::list_controller::{use_virtual_list_controller, ScrollRequest, VirtualListController};
+use super::popover::{Anchor, Popover, Side};
+use super::read_aloud::use_read_aloud;
+#[cfg(debug_assertions)]
+use super::scroll_fallback::ScrollDiagnostics;
+use super::scroll_parent::ScrollParent;
+use super::selection::{copy_to_clipboard, use_row_selection};
+use super::session_export::SessionExportButtons;
+use super::summary_view::SummaryView;
+use super::virtual_rows::{
+    EdgeScroll, LayoutMode, Row, Viewport, VirtualRows, DEFAULT_INTERLEAVE_EVERY, DEFAULT_INTERLEAVE_HEIGHT,
+};
+use super::wheel::{wheel_pixels, WheelVelocity};
+#[cfg(debug_assertions)]
+use super::recorder_panel::RecorderPanel;
+#[cfg(debug_assertions)]
+use super::split_pane::{SplitAxis, SplitPane};
+use crate::assets::{probe_status, reload_url};
+use crate::captions::use_alt_text;
+use crate::clock::{use_clock, Instant};
+use crate::error::{report, FeedError};
+use crate::links::{link_segments, use_link_policy};
+use crate::persistence::bookmarks::Bookmarks;
+use crate::persistence::gating::ContentGating;
+use crate::persistence::polling::PollingSettings;
+use crate::persistence::read_state::ReadState;
+use crate::persistence::row_heights::RowHeightCache;
+use crate::persistence::summarizer::SummarizerSettings;
+use crate::startup::StartupTrace;
+use crate::store::media::{
+    is_transient_failure, media_retry_delay, use_media_cache, use_media_dimensions, use_media_slot,
+    MediaStatus, MAX_MEDIA_RETRIES,
+};
+use crate::store::edits::EditAction;
+use crate::store::item_state::ItemState;
+use crate::store::polling::AdaptiveInterval;
+use crate::store::progress::use_feed_progress;
+use crate::store::ranking::FeedRanking;
+use crate::store::summaries::{Summary, SummaryStatus};
+use crate::store::{Eviction, FeedStatus, FeedStore, Sequence, StoreEvent, FRESH_HIGHLIGHT_DURATION, ITEMS_PER_LOAD, PREPEND_CHUNK_SIZE};
+use crate::summarizer::is_long;
+use crate::urlcleaner::clean_url;
 
-impl VirtualFeedItem {
-    pub fn new(id: String, content: String, image_name: &str) -> Self {
-        Self {
-            id,
-            content,
-            image_url: format!("myprotocol/assets/images/{}", image_name),
-        }
+// Virtual list configuration
+pub const ITEM_HEIGHT: f64 = 320.0; // Height per item including padding
+const STACKED_ITEM_HEIGHT: f64 = 460.0; // Height per item when the image sits above the text
+const STACKED_IMAGE_HEIGHT: f64 = 160.0; // Image height in stacked items
+const MIN_ESTIMATED_HEIGHT: f64 = 48.0; // Smallest height an estimator may give a row
+const ROW_CHROME_HEIGHT: f64 = 58.0; // Padding, border and gap around a row's content
+const ROW_HEADER_HEIGHT: f64 = 45.0; // Header of a row, with its rule and the space below
+const DEFAULT_STACK_BELOW_WIDTH: f64 = 480.0; // Container width below which items stack
+const CONTAINER_HEIGHT: f64 = 600.0; // Viewport height
+const BUFFER_SIZE: usize = 5; // Extra items to render outside viewport
+const CONTENT_VISIBILITY_BUFFER: usize = 15; // Further rows rendered when the webview skips offscreen ones
+const CONTENT_VISIBILITY_MAX_ITEMS: usize = 2000; // Largest list given the larger buffer
+const FLING_VELOCITY: f64 = 2.0; // Upward speed in px/ms above which older items are held back
+const FLING_SETTLE_MS: u64 = 150; // Time without scroll events after which a fling has ended
+const REFRESH_PULL_DISTANCE: f64 = 160.0; // Normalized wheel distance pulled past the top that refreshes
+const PULL_RESET_MS: u64 = 200; // Time without wheel events after which a pull is let go
+const FRAME_MS: u64 = 16; // Time between chunks of staged older items
+const IDLE_MS: u64 = 300; // Time without scroll events before deferred work runs
+const PREDECODE_ROWS: usize = 10; // Rows past the viewport whose images are decoded while idle
+const DEFAULT_IMAGE_LEAD_DISTANCE: f64 = 300.0; // Start loading images this far outside the viewport
+const CAUGHT_UP_HEIGHT: f64 = 48.0; // Height of the "caught up" divider
+#[cfg(debug_assertions)]
+const DEBUG_DOCK_HEIGHT: f64 = 320.0; // Height of the debug panels' dock
+#[cfg(debug_assertions)]
+const DEBUG_STATS_HEIGHT: f64 = 150.0; // Height of the scroll statistics once shown
+#[cfg(debug_assertions)]
+const DEBUG_PANEL_MIN_HEIGHT: f64 = 48.0;
+const IMAGE_BOX_WIDTH: f64 = 120.0; // Width of the image column in each row
+const IMAGE_BOX_MAX_HEIGHT: f64 = 200.0; // Tall images are cropped to fit the row
+const AUTHOR_CARD_OPEN_DELAY_MS: u64 = 400; // Hover time before an author card opens
+const AUTHOR_CARD_CLOSE_DELAY_MS: u64 = 250; // Time to move from a name onto its card
+
+// Fetches and decodes the images at the URLs it receives, so they show
+// without a decode when their rows scroll in
+const PREDECODE_SCRIPT: &str = r#"
+    const urls = await dioxus.recv();
+    await Promise.all(urls.map((url) => {
+        const image = new Image();
+        image.src = url;
+        return image.decode().catch(() => {});
+    }));
+    return true;
+"#;
+
+// Fetch and decode the images at `urls` in the webview, so they show
+// without a decode once their rows come into view
+pub(crate) fn predecode_images(urls: Vec<String>) {
+    if urls.is_empty() {
+        return;
     }
-    
-    pub fn new_with_random_image(id: String, content: String) -> Self {
-        let image = get_random_image_for_id(&id);
-        Self::new(id, content, image)
+    let eval = document::eval(PREDECODE_SCRIPT);
+    if let Err(e) = eval.send(urls) {
+        eprintln!("Failed to decode upcoming images: {}", e);
     }
 }
 
-// Available images for random selection
-const AVAILABLE_IMAGES: &[&str] = &[
-    "sample1.svg",
-    "sample2.svg", 
-    "sample3.svg",
-    "sample4.svg",
-    "sample5.svg",
-    "sample6.svg",
-    "sample7.avif",
-    "sample8.avif",
-    "sample9.avif",
-    "sample10.avif",
-    "sample11.avif",
-    "sample12.avif",
-    "sample13.avif"
-];
-
-// Random image selector with better uniqueness
-fn get_random_image_for_id(id: &str) -> &'static str {
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-    
-    let mut hasher = DefaultHasher::new();
-    id.hash(&mut hasher);
-    
-    let hash = hasher.finish();
-    let index = (hash as usize) % AVAILABLE_IMAGES.len();
-    AVAILABLE_IMAGES[index]
+// Reports whether the webview supports skipping offscreen rendering
+const CONTENT_VISIBILITY_SCRIPT: &str = r#"
+    return typeof CSS !== 'undefined' && CSS.supports('content-visibility', 'auto');
+"#;
+
+// Height of the image box beside the text for an image of the given shape;
+// unknown shapes get a square box
+fn image_box_height(aspect: Option<f64>) -> f64 {
+    aspect.map_or(IMAGE_BOX_WIDTH, |aspect| (IMAGE_BOX_WIDTH / aspect).min(IMAGE_BOX_MAX_HEIGHT))
 }
 
-// Virtual list configuration
-const ITEM_HEIGHT: f64 = 320.0; // Height per item including padding
-const CONTAINER_HEIGHT: f64 = 600.0; // Viewport height
-const BUFFER_SIZE: usize = 5; // Extra items to render outside viewport
-const LOAD_THRESHOLD: f64 = 200.0; // Distance from edge to trigger loading
-const ITEMS_PER_LOAD: usize = 5; // Items to load at once
-const POLLING_INTERVAL_MS: u64 = 5000; // 5 seconds for new items
+// Side-by-side row height of an item whose declared image hints settle it:
+// an image with nothing beside it. These rows are laid out from the hints
+// and not measured.
+fn hinted_row_height(item: &VirtualFeedItem) -> Option<f64> {
+    let image_only = item.content.trim().is_empty()
+        && item.sources.len() <= 1
+        && item.annotations.is_empty()
+        && item.also_shared.is_empty();
+    let aspect = item.image_aspect().filter(|_| image_only)?;
+    Some(ROW_HEADER_HEIGHT + image_box_height(Some(aspect)) + ROW_CHROME_HEIGHT)
+}
 
 #[derive(PartialEq, Props, Clone)]
 pub struct VirtualListProps {
+    // Store holding the items, loading state and scroll position of the feed
+    pub store: FeedStore,
+    // Briefly highlight items inserted by polling or refresh
+    #[props(default)]
+    pub highlight_new_items: bool,
+    // How far outside the viewport rows start loading their images; rows
+    // further out in the buffer keep a placeholder
+    #[props(default = DEFAULT_IMAGE_LEAD_DISTANCE)]
+    pub image_lead_distance: f64,
+    // Non-feed content (suggestions, markers) rendered in a slot after every
+    // `interleave_every` rows, called with the sequence of the row above.
+    // Slots reserve `interleave_height` even when it returns `None`, so row
+    // positions never depend on what a slot renders.
+    pub interleave: Option<Callback<Sequence, Option<Element>>>,
+    #[props(default = DEFAULT_INTERLEAVE_EVERY)]
+    pub interleave_every: usize,
+    #[props(default = DEFAULT_INTERLEAVE_HEIGHT)]
+    pub interleave_height: f64,
+    // Key for the persisted read state; lists with one mark where the
+    // previous session's reading ended with a "caught up" divider
+    #[props(default)]
+    pub feed_id: Option<&'static str>,
+    // Handle for scrolling the list from outside; the list creates its own
+    // when none is given
+    pub controller: Option<VirtualListController>,
+    // Scroll container outside the list to attach to, such as the page or the
+    // window; the list then grows to its full height inside it and takes its
+    // viewport from the parent's scrolls instead of scrolling itself
+    pub scroll_parent: Option<ScrollParent>,
+    // Whether scrolls past the list's ends carry on to the list around it,
+    // for lists nested in another list's rows
+    #[props(default)]
+    pub edge_scroll: EdgeScroll,
+    // Thin bar at the top showing how far through the loaded items the
+    // reader is, for finite feeds
+    #[props(default)]
+    pub show_progress: bool,
+    // Container width below which each item's image stacks above its text
+    // instead of beside it; observed on the list itself, not the window
+    #[props(default = DEFAULT_STACK_BELOW_WIDTH)]
+    pub stack_below_width: f64,
+    // Curated list whose items can be reordered, hidden, pinned and deleted
+    // in edit mode, with undo (Ctrl+Z) and redo (Ctrl+Y)
+    #[props(default)]
+    pub editable: bool,
+    // Mark rows `content-visibility: auto` with their height as intrinsic
+    // size. Where the webview supports it, medium-sized lists also render a
+    // larger buffer, leaving its offscreen layout and paint to the webview.
+    #[props(default = true)]
+    pub content_visibility: bool,
+    // How rows are positioned; both modes share the same range math
+    #[props(default)]
+    pub layout_mode: LayoutMode,
+    // Height of an item's row including the gap below it, for side-by-side
+    // rows; stacked rows add the image above. Rows are laid out at
+    // `ITEM_HEIGHT` when no estimator is given.
+    pub estimate_height: Option<fn(&VirtualFeedItem) -> f64>,
+    // Shown above the first row once the source has nothing older, in
+    // place of "This is the beginning"
+    pub beginning_header: Option<Element>,
+    // Most items to keep loaded. Past it, items are evicted from the edge
+    // furthest from the viewport, never the rendered rows, and load again
+    // when scrolled back to.
+    #[props(default)]
+    pub max_items: Option<usize>,
+    // Called with each range evicted to stay within `max_items`
+    pub on_evict: Option<EventHandler<Eviction>>,
+    // Item whose detail is open beside the list, outlined in its row
+    #[props(default)]
+    pub selected_id: Option<String>,
+    // Called with the item of a row clicked, for a split view
+    pub on_select: Option<EventHandler<VirtualFeedItem>>,
     pub on_load_more_top: Option<EventHandler<()>>,
+    // Called when the user reaches the top and keeps pulling with a wheel or
+    // touchpad; the list then refreshes the newest items. Approaching the top
+    // loads older ones through `on_load_more_top` instead.
+    pub on_refresh: Option<EventHandler<()>>,
     pub on_load_more_bottom: Option<EventHandler<()>>,
 }
 
+// The feed's list: `VirtualRows` over the store's items, with loading at
+// both ends, read state, find, editing and the popovers of its rows
 #[component]
 pub fn VirtualList(props: VirtualListProps) -> Element {
     // Core state
-    let mut items = use_signal(|| vec![
-        VirtualFeedItem::new_with_random_image("initial_1".to_string(), "Welcome to the feed! This is item 1".to_string()),
-        VirtualFeedItem::new_with_random_image("initial_2".to_string(), "Here's another item in your feed".to_string()),
-        VirtualFeedItem::new_with_random_image("initial_3".to_string(), "Scroll up or down to load more content".to_string()),
-        VirtualFeedItem::new_with_random_image("initial_4".to_string(), "Images load asynchronously via custom protocol".to_string()),
-        VirtualFeedItem::new_with_random_image("initial_5".to_string(), "Infinite scrolling in both directions".to_string()),
-    ]);
-    
-    // Scroll tracking
-    let mut scroll_top = use_signal(|| 0.0);
-    let mut scroll_height = use_signal(|| 0.0);
-    let mut client_height = use_signal(|| CONTAINER_HEIGHT);
-    let mut last_scroll_top = use_signal(|| 0.0);
-    let mut scroll_direction = use_signal(|| 0i8); // -1 = up, 0 = none, 1 = down
-    
-    // Loading states
-    let mut is_loading_top = use_signal(|| false);
-    let mut is_loading_bottom = use_signal(|| false);
-    
-    // Scroll element reference
+    let mut store = props.store;
+    // Items in the order shown, ranked by the store
+    let items = use_memo(move || store.ordered_items());
+    let ranking = store.ranking;
+    let highlight_new_items = props.highlight_new_items;
+    let image_lead_distance = props.image_lead_distance;
+    let feed_id = props.feed_id;
+    let show_progress = props.show_progress;
+    let progress = use_feed_progress(store);
+    let own_controller = use_virtual_list_controller();
+    let mut controller = props.controller.unwrap_or(own_controller);
+
+    // Newest item seen in the previous session, captured when the feed loads;
+    // the divider follows it once it is among the loaded items
+    let read_state = try_use_context::<ReadState>();
+    let last_visit = use_hook(|| feed_id.zip(read_state).and_then(|(id, read_state)| read_state.last_visit(id)));
+    let visited_before = last_visit.is_some();
+    let caught_up_after = use_memo(move || {
+        // Only arrival order has a point where the last visit ended
+        if ranking() != FeedRanking::Latest {
+            return None;
+        }
+        let items = items.read();
+        let index = items.iter().position(|item| Some(&item.id) == last_visit.as_ref())?;
+        // Nothing newer has arrived since the last visit
+        (index + 1 < items.len()).then(|| store.sequence_at(index))
+    });
+
+    // Row height follows the container width (see `on_resize` below)
+    let row_height = (store.row_height)();
+    let stacked = row_height != ITEM_HEIGHT;
+    let stack_below_width = props.stack_below_width;
+
+    // Heights of rows until they are measured: settled by their image hints,
+    // cached for the feed or else estimated, for side-by-side rows; stacked
+    // rows add the image above
+    let estimate_height = props.estimate_height;
+    let height_cache = try_use_context::<RowHeightCache>().filter(|_| feed_id.is_some());
+    let estimate_row = use_callback(move |index: usize| {
+        let items = items.peek();
+        let item = items.get(index)?;
+        let height = hinted_row_height(item)
+            .or_else(|| {
+                height_cache
+                    .zip(feed_id)
+                    .and_then(|(cache, feed_id)| cache.height(feed_id, &item.id))
+            })
+            .or_else(|| estimate_height.map(|estimate| estimate(item).max(MIN_ESTIMATED_HEIGHT)))?;
+        Some(height + (store.row_height)() - ITEM_HEIGHT)
+    });
+    // Heights rows measured at since mount, kept here to be cached; they
+    // win over the cache and estimates
+    let mut measured = use_signal(std::collections::HashMap::<String, f64>::new);
+
+    // Items read in previous sessions can be folded into a pill above the
+    // unread ones for this session; the pill expands them in place
+    let mut hide_read = use_signal(|| false);
+    let mut show_read = use_signal(|| false);
+    let read_folded = move || caught_up_after().is_some() && hide_read() && !show_read();
+
+    // Whether the webview skips rendering offscreen rows, checked on mount
+    let content_visibility = props.content_visibility;
+    let mut offscreen_skipped = use_signal(|| false);
+    use_future(move || async move {
+        if !content_visibility {
+            return;
+        }
+        match document::eval(CONTENT_VISIBILITY_SCRIPT).join::<bool>().await {
+            Ok(supported) => offscreen_skipped.set(supported),
+            Err(e) => eprintln!("Failed to check content-visibility support: {}", e),
+        }
+    });
+
+    // Rows the text selection spans, as indices into `items`
+    let selection = use_row_selection();
+    let selected_range = use_memo(move || {
+        let selection = selection()?;
+        let items = items.read();
+        let anchor = items.iter().position(|item| item.id == selection.anchor_id)?;
+        let focus = items.iter().position(|item| item.id == selection.focus_id)?;
+        Some((anchor.min(focus), anchor.max(focus)))
+    });
+
+    // Find in feed (Ctrl+F) over every loaded item, not just the rendered
+    // rows, stepping through matching items by scrolling to them
+    let mut find_open = use_signal(|| false);
+    let mut find_query = use_signal(String::new);
+    let mut find_current = use_signal(|| 0usize);
+    let find_matches = use_memo(move || {
+        let query = find_query.read();
+        items
+            .read()
+            .iter()
+            .filter(|item| !match_ranges(&item.content, &query).is_empty())
+            .map(|item| item.id.clone())
+            .collect::<Vec<_>>()
+    });
+    let mut show_match = move |index: usize| {
+        let matches = find_matches.peek();
+        if matches.is_empty() {
+            return;
+        }
+        let index = index % matches.len();
+        find_current.set(index);
+        controller.scroll_to(ScrollRequest::Item(matches[index].clone()));
+    };
+    // A new query starts from its first match; items arriving later don't move it
+    let mut last_query = use_hook(|| CopyValue::new(String::new()));
+    use_effect(move || {
+        let query = find_query();
+        let _ = find_matches();
+        if *last_query.peek() != query {
+            last_query.set(query);
+            show_match(0);
+        }
+    });
+    let find_highlight = find_open().then(|| find_query()).filter(|query| !query.trim().is_empty());
+    let current_match = find_matches.read().get(find_current()).cloned();
+
+    // Edit mode of a curated list
+    let editable = props.editable;
+    let mut editing = use_signal(|| false);
+
+    // Scroll position, kept in the store so it survives remounts and can be
+    // read outside the list
+    let scroll_top = store.scroll_top;
+    let client_height = store.viewport_height;
+    // Last scroll event of the list, and the rows in its viewport
+    let mut viewport = use_hook(|| CopyValue::new(None::<Viewport>));
+    let mut rows_in_view = use_hook(|| CopyValue::new(0..0));
+
+    // Feed status (loading, error and end-of-feed state)
+    let status = store.status;
+
+    // Scroll element reference, which popovers stay inside
     let mut scroll_element = use_signal(|| None::<std::rc::Rc<MountedData>>);
-    
-    // Calculate virtual list parameters
+    #[cfg(debug_assertions)]
+    let scroll_diagnostics = try_use_context::<ScrollDiagnostics>();
+
+    // Remember the newest item scrolled into view for the next session
+    let mut newest_seen = use_hook(|| CopyValue::new(None::<Sequence>));
+    let mut mark_newest_seen = move |last_in_view: usize| {
+        let (Some(feed_id), Some(mut read_state)) = (feed_id, read_state) else {
+            return;
+        };
+        if *ranking.peek() != FeedRanking::Latest {
+            return;
+        }
+        let bottom_seq = store.sequence_at(last_in_view);
+        if newest_seen.peek().is_some_and(|seen| seen >= bottom_seq) {
+            return;
+        }
+        newest_seen.set(Some(bottom_seq));
+        if let Some(item) = items.peek().get(last_in_view) {
+            read_state.mark_newest_seen(feed_id, &item.id);
+        }
+    };
+
+    // Article opened in reader mode
+    let mut reader_url = use_signal(|| None::<String>);
+    // Image opened from a row
+    let mut lightbox = use_signal(|| None::<LightboxImage>);
+
+    // Open the row at the top of the viewport when the controller asks
+    use_effect(move || {
+        if !controller.take_open() {
+            return;
+        }
+        let index = rows_in_view.peek().start;
+        let link = items.peek().get(index).and_then(|item| item.link.clone());
+        if let Some(link) = link {
+            reader_url.set(Some(link));
+        }
+    });
+    // Author name under the pointer, the card shown for it and whether the
+    // pointer is over that card
+    let mut author_hover = use_signal(|| None::<AuthorHover>);
+    let mut author_card = use_signal(|| None::<AuthorHover>);
+    let mut author_card_hovered = use_signal(|| false);
+    // Full date of the hovered timestamp, and the row whose menu is open
+    let mut tooltip = use_signal(|| None::<Tooltip>);
+    let mut item_menu = use_signal(|| None::<(Anchor, VirtualFeedItem)>);
+    // Cards open after a short hover and stay open while the pointer moves
+    // from the name onto the card
+    let hover_author = use_callback(move |hover: Option<AuthorHover>| {
+        author_hover.set(hover.clone());
+        let (delay, hover) = match hover {
+            Some(hover) => (AUTHOR_CARD_OPEN_DELAY_MS, Some(hover)),
+            None => (AUTHOR_CARD_CLOSE_DELAY_MS, None),
+        };
+        spawn(async move {
+            store.clock().sleep(std::time::Duration::from_millis(delay)).await;
+            if *author_hover.peek() != hover {
+                return;
+            }
+            match hover {
+                Some(hover) => author_card.set(Some(hover)),
+                None if !*author_card_hovered.peek() => author_card.set(None),
+                None => {}
+            }
+        });
+    });
+
+    // Keep a given item in view (used by read aloud mode); rows wholly in
+    // the viewport stay where they are
+    let scroll_to_index = use_callback(move |index: usize| {
+        let in_view = rows_in_view.peek().clone();
+        if in_view.start < index && index + 1 < in_view.end {
+            return;
+        }
+        if let Some(item) = items.peek().get(index) {
+            controller.scroll_to(ScrollRequest::Item(item.id.clone()));
+        }
+    });
+
+    // Text-to-speech read aloud mode
+    let read_aloud = use_read_aloud(items, scroll_to_index);
+
     let total_items = items().len();
-    let total_height = total_items as f64 * ITEM_HEIGHT;
-    let visible_count = (client_height() / ITEM_HEIGHT).ceil() as usize;
-    
-    // Calculate visible range with buffer
-    let start_index = ((scroll_top() / ITEM_HEIGHT) as usize).saturating_sub(BUFFER_SIZE);
-    let end_index = (start_index + visible_count + (BUFFER_SIZE * 2)).min(total_items);
-    
-    // Load more items at top
-    let load_more_top = use_callback(move |_| {
-        if is_loading_top() {
+    let buffer = if offscreen_skipped() && total_items <= CONTENT_VISIBILITY_MAX_ITEMS {
+        BUFFER_SIZE + CONTENT_VISIBILITY_BUFFER
+    } else {
+        BUFFER_SIZE
+    };
+    // Read items folded into the pill
+    let hidden_count = caught_up_after()
+        .filter(|_| read_folded())
+        .and_then(|after| store.index_of(after + 1))
+        .unwrap_or(0);
+
+    // Fold or unfold the read items; the list keeps the row at the top of
+    // the viewport in place
+    let mut set_read_hidden = move |hide: bool, show: bool| {
+        hide_read.set(hide);
+        show_read.set(show);
+    };
+
+    // Copy the selected items whole, stitched together from the store
+    let copy_selection = move |_| {
+        let Some((first, last)) = selected_range() else {
+            return;
+        };
+        let text = items
+            .read()
+            .iter()
+            .skip(first)
+            .take(last + 1 - first)
+            .map(|item| match &item.link {
+                Some(link) => format!("{}\n{}", item.content, clean_url(link)),
+                None => item.content.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        spawn(async move {
+            if let Err(e) = copy_to_clipboard(text).await {
+                report(FeedError::Clipboard { what: "selection", message: e });
+            }
+        });
+    };
+
+    // Evict past `max_items` whenever items arrive, keeping the rows in and
+    // around the viewport and the selected ones; the list keeps the rows in
+    // view in place
+    let max_items = props.max_items;
+    let on_evict = props.on_evict;
+    use_effect(move || {
+        let Some(max_items) = max_items else {
+            return;
+        };
+        if store.items.read().len() <= max_items {
+            return;
+        }
+        let in_view = rows_in_view.peek().clone();
+        let mut keep = in_view.start.saturating_sub(BUFFER_SIZE)..in_view.end + BUFFER_SIZE;
+        if let Some((first, last)) = *selected_range.peek() {
+            keep = keep.start.min(first)..keep.end.max(last + 1);
+        }
+        // Rows are indices into the shown items, eviction goes by the loaded order
+        let keep = {
+            let shown = items.peek();
+            let end = keep.end.min(shown.len());
+            let count = end.saturating_sub(keep.start);
+            store.loaded_span(shown.iter().skip(keep.start).take(count).map(|item| item.id.as_str()))
+        };
+
+        let evictions = store.evict_outside(max_items, keep);
+        if let Some(handler) = on_evict {
+            for eviction in evictions {
+                handler.call(eviction);
+            }
+        }
+    });
+
+    // Width the rows were last laid out at, which cached heights must match
+    let mut laid_out_width = use_hook(|| CopyValue::new(None::<f64>));
+
+    // Insert the staged older items (held back during a fling, or a large
+    // page) a chunk per frame, so long backfills stream in rather than
+    // blocking a frame; the list keeps the rows in view in place after each
+    let mut draining = use_hook(|| CopyValue::new(false));
+    let mut prepend_staged = move || {
+        if *draining.peek() || store.staged_count() == 0 {
             return;
         }
-        
-        is_loading_top.set(true);
-        
+        draining.set(true);
         spawn(async move {
-            // Simulate loading delay
-            tokio::time::sleep(std::time::Duration::from_millis(800)).await;
-            
-            let mut current_items = items();
-            let mut new_items = Vec::new();
-            
-            // Add items at the beginning
-            for i in 1..=ITEMS_PER_LOAD {
-                let item_id = format!("older_{}_{}", current_items.len() + i, chrono::Utc::now().timestamp_millis());
-                let content = format!("Older content item {} - loaded from top", current_items.len() + i);
-                new_items.push(VirtualFeedItem::new_with_random_image(item_id, content));
+            while store.prepend_staged(PREPEND_CHUNK_SIZE) > 0 {
+                store.clock().sleep(std::time::Duration::from_millis(FRAME_MS)).await;
             }
-            
-            // Prepend new items
-            new_items.extend(current_items);
-            
-            // Preserve scroll position by adjusting scroll_top
-            let added_height = ITEMS_PER_LOAD as f64 * ITEM_HEIGHT;
-            if let Some(element) = scroll_element() {
-                let new_scroll_top = scroll_top() + added_height;
-                let _ = spawn(async move {
-                    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
-                    let _ = element.scroll(
-                        PixelsVector2D::new(0.0, new_scroll_top),
-                        ScrollBehavior::Instant
-                    ).await;
-                });
-                scroll_top.set(new_scroll_top);
+            draining.set(false);
+        });
+    };
+
+    // Hold older items back while the user flings upward, and insert them
+    // once no scroll event has come for a moment. Scroll events slowing
+    // down or reaching the top insert them earlier (see `follow_scroll`).
+    let mut last_user_scroll = use_hook(|| CopyValue::new(None::<(Instant, f64)>));
+    let mut wheel_velocity = use_hook(|| CopyValue::new(WheelVelocity::default()));
+    let mut watching_fling = use_hook(|| CopyValue::new(false));
+    let mut hold_for_fling = move || {
+        store.hold_prepends(true);
+        if *watching_fling.peek() {
+            return;
+        }
+        watching_fling.set(true);
+        spawn(async move {
+            let settle = std::time::Duration::from_millis(FLING_SETTLE_MS);
+            loop {
+                store.clock().sleep(settle).await;
+                let now = store.clock().now();
+                let last = (*last_user_scroll.peek()).map(|(at, _)| at);
+                if !last.is_some_and(|at| now.saturating_duration_since(at) < settle) {
+                    break;
+                }
             }
-            
-            items.set(new_items);
-            is_loading_top.set(false);
+            watching_fling.set(false);
+            store.hold_prepends(false);
+            prepend_staged();
         });
+    };
+
+    // Work put off until no scroll event has come for a moment, so it doesn't
+    // compete with scrolling: caching the heights rows were laid out at,
+    // so the next session lays them out the same, and decoding the images
+    // of the rows about to scroll in
+    let media_cache = use_media_cache();
+    let media_cache = use_hook(|| CopyValue::new(media_cache));
+    let gating = try_use_context::<ContentGating>();
+    // Idle work and polling wait for the first frame; the first rendered
+    // rows close the startup trace
+    let startup = try_use_context::<StartupTrace>();
+    let mut last_scroll_event = use_hook(|| CopyValue::new(None::<Instant>));
+    let mut watching_idle = use_hook(|| CopyValue::new(false));
+    let mut predecoded = use_hook(|| CopyValue::new(std::collections::HashSet::<String>::new()));
+    let mut work_when_idle = move || {
+        if *watching_idle.peek() {
+            return;
+        }
+        watching_idle.set(true);
+        spawn(async move {
+            let idle = std::time::Duration::from_millis(IDLE_MS);
+            loop {
+                store.clock().sleep(idle).await;
+                let now = store.clock().now();
+                let last = *last_scroll_event.peek();
+                if !last.is_some_and(|at| now.saturating_duration_since(at) < idle) {
+                    break;
+                }
+            }
+            watching_idle.set(false);
+            if let Some(startup) = startup {
+                startup.after_first_paint(&store.clock()).await;
+            }
+
+            let items = items.peek().clone();
+            // Measured rows replace what the cache had for them. When heights
+            // cached at another width are dropped instead, the rows measured
+            // so far were laid out at that width too; they are measured again
+            // and cached on a later pass.
+            if let (Some(mut cache), Some(feed_id)) = (height_cache, feed_id) {
+                let stale = (*laid_out_width.peek()).is_some_and(|width| cache.measured_at(feed_id, width));
+                if stale {
+                    measured.write().clear();
+                } else {
+                    let heights: Vec<(String, f64)> = measured
+                        .peek()
+                        .iter()
+                        .map(|(id, height)| (id.clone(), *height))
+                        .collect();
+                    cache.update(feed_id, heights);
+                }
+            }
+            if let (Some(mut cache), Some(feed_id), Some(estimate)) = (height_cache, feed_id, estimate_height) {
+                let heights = items
+                    .iter()
+                    .filter(|item| cache.height(feed_id, &item.id).is_none())
+                    .map(|item| (item.id.clone(), estimate(item).max(MIN_ESTIMATED_HEIGHT)))
+                    .collect::<Vec<_>>();
+                cache.fill(feed_id, heights);
+            }
+
+            // Rows past the viewport in the direction last scrolled
+            let count = items.len();
+            let in_view = rows_in_view.peek().clone();
+            let upcoming: Vec<usize> = if (*viewport.peek()).is_some_and(|viewport| viewport.direction < 0) {
+                (in_view.start.saturating_sub(PREDECODE_ROWS)..in_view.start).rev().collect()
+            } else {
+                (in_view.end..(in_view.end + PREDECODE_ROWS).min(count)).collect()
+            };
+            let urls: Vec<String> = upcoming
+                .into_iter()
+                .filter_map(|index| items.get(index))
+                .filter(|item| !gating.is_some_and(|gating| gating.is_gated(item)))
+                .filter_map(|item| item.image_candidates().next().map(str::to_string))
+                .filter(|url| media_cache.peek().status(url) == MediaStatus::Loading)
+                .filter(|url| !predecoded.peek().contains(url))
+                .collect();
+            if urls.is_empty() {
+                return;
+            }
+            predecoded.write().extend(urls.iter().cloned());
+            predecode_images(urls);
+        });
+    };
+    use_effect(move || {
+        let _ = items.read();
+        work_when_idle();
     });
-    
+    use_effect(move || {
+        if let Some(mut startup) = startup {
+            if !items.read().is_empty() {
+                startup.first_item_rendered();
+            }
+        }
+    });
+
+    // Load more items at top
+    let load_more_top = use_callback(move |_| {
+        if let Some(handler) = props.on_load_more_top {
+            handler.call(());
+        }
+
+        if !store.can_load_top() {
+            return;
+        }
+
+        spawn(async move {
+            store.load_older().await;
+            // Large pages are staged instead
+            prepend_staged();
+        });
+    });
+
     // Load more items at bottom
     let load_more_bottom = use_callback(move |_| {
-        if is_loading_bottom() {
+        if let Some(handler) = props.on_load_more_bottom {
+            handler.call(());
+        }
+
+        if !status.read().can_load_bottom() {
             return;
         }
-        
-        is_loading_bottom.set(true);
-        
+
         spawn(async move {
-            // Simulate loading delay
-            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
-            
-            let mut current_items = items();
-            
-            // Add items at the end
-            for i in 1..=ITEMS_PER_LOAD {
-                let item_id = format!("newer_{}_{}", current_items.len() + i, chrono::Utc::now().timestamp_millis());
-                let content = format!("Newer content item {} - loaded from bottom", current_items.len() + i);
-                current_items.push(VirtualFeedItem::new_with_random_image(item_id, content));
+            store.load_newer().await;
+        });
+    });
+
+    // Manual refresh, keeping current items visible while fetching
+    let refresh = use_callback(move |_| {
+        if status.read().is_loading() {
+            return;
+        }
+
+        spawn(async move {
+            if store.refresh().await > 0 && highlight_new_items {
+                expire_highlights(store).await;
             }
-            
-            items.set(current_items);
-            is_loading_bottom.set(false);
         });
     });
-    
-    // Auto-polling for new content
+
+    // Refresh from pulling past the top, see `pull_past_top`
+    let refresh_from_pull = use_callback(move |_| {
+        if let Some(handler) = props.on_refresh {
+            handler.call(());
+        }
+        refresh.call(());
+    });
+
+    // Wheel distance pulled past the top in the current gesture. Only pulls
+    // begun at the top count: a fling that carries the list there doesn't
+    // refresh, however far its momentum goes on. One refresh per gesture.
+    let mut pulled = use_signal(|| 0.0);
+    let mut pull_spent = use_hook(|| CopyValue::new(false));
+    let mut watching_pull = use_hook(|| CopyValue::new(false));
+    let mut pull_past_top = move |delta: f64, new_gesture: bool| {
+        let now = store.clock().now();
+        if new_gesture {
+            pull_spent.set(false);
+        }
+        if *scroll_top.peek() > 0.5 || delta >= 0.0 {
+            if *pulled.peek() > 0.0 {
+                pulled.set(0.0);
+            }
+            return;
+        }
+        if *pulled.peek() == 0.0 && wheel_velocity.peek().current(now).is_some_and(|velocity| velocity.abs() > FLING_VELOCITY) {
+            pull_spent.set(true);
+        }
+        if *pull_spent.peek() {
+            return;
+        }
+        let distance = *pulled.peek() - delta;
+        if distance >= REFRESH_PULL_DISTANCE {
+            pull_spent.set(true);
+            pulled.set(0.0);
+            refresh_from_pull.call(());
+            return;
+        }
+        pulled.set(distance);
+
+        // Let go of a pull the user stops short of refreshing
+        if *watching_pull.peek() {
+            return;
+        }
+        watching_pull.set(true);
+        spawn(async move {
+            let settle = std::time::Duration::from_millis(PULL_RESET_MS);
+            loop {
+                store.clock().sleep(settle).await;
+                if wheel_velocity.peek().current(store.clock().now()).is_none() {
+                    break;
+                }
+            }
+            watching_pull.set(false);
+            pulled.set(0.0);
+        });
+    };
+
+    // Summaries are offered once a summarizer is set up under Settings
+    let summarizer = try_use_context::<SummarizerSettings>()
+        .map(|settings| settings.summarizer())
+        .filter(|summarizer| summarizer.is_available());
+
+    // Initial page and auto-polling for new content. With polling settings
+    // provided, the interval starts at the source's and then follows how
+    // many items recent polls brought in, within the configured bounds.
+    let polling = try_use_context::<PollingSettings>();
     use_future(move || async move {
+        store.initialize().await;
+
+        let Some(source_interval) = store.source().and_then(|source| source.poll_interval()) else {
+            return;
+        };
+
+        let clock = store.clock();
+        if let Some(startup) = startup {
+            startup.after_first_paint(&clock).await;
+        }
+        let mut interval = AdaptiveInterval::new(source_interval);
         loop {
-            tokio::time::sleep(std::time::Duration::from_millis(POLLING_INTERVAL_MS)).await;
-            
-            let mut current_items = items();
-            let next_num = current_items.len() + 1;
-            let item_id = format!("auto_{}_{}", next_num, chrono::Utc::now().timestamp_millis());
-            let content = format!("Auto-generated item {} - real-time update", next_num);
-            current_items.push(VirtualFeedItem::new_with_random_image(item_id, content));
-            
-            items.set(current_items);
+            let wait = match polling {
+                Some(polling) => interval.current(polling.bounds()),
+                None => source_interval,
+            };
+            clock.sleep(wait).await;
+            let count = store.poll().await;
+            if let Some(polling) = polling {
+                interval.record(count, ITEMS_PER_LOAD, polling.bounds());
+            }
+            if count > 0 && highlight_new_items {
+                spawn(expire_highlights(store));
+            }
         }
     });
-    
-    // Handle scroll events
-    let handle_scroll = move |evt: Event<ScrollData>| {
-        let current_scroll_top = evt.data().scroll_top() as f64;
-        let current_scroll_height = evt.data().scroll_height() as f64;
-        let current_client_height = evt.data().client_height() as f64;
-        
-        // Determine scroll direction
-        let previous_scroll = last_scroll_top();
-        let direction = if current_scroll_top > previous_scroll {
-            1i8 // scrolling down
-        } else if current_scroll_top < previous_scroll {
-            -1i8 // scrolling up
-        } else {
-            0i8 // no change
-        };
-        
-        // Update state
-        scroll_top.set(current_scroll_top);
-        scroll_height.set(current_scroll_height);
-        client_height.set(current_client_height);
-        last_scroll_top.set(current_scroll_top);
-        scroll_direction.set(direction);
-        
-        // Check if we need to load more items at top (only when scrolling UP)
-        if current_scroll_top <= LOAD_THRESHOLD && direction == -1 && !is_loading_top() {
-            load_more_top.call(());
-        }
-        
-        // Check if we need to load more items at bottom (only when scrolling DOWN)
-        let distance_from_bottom = current_scroll_height - current_scroll_top - current_client_height;
-        if distance_from_bottom <= LOAD_THRESHOLD && direction == 1 && !is_loading_bottom() {
-            load_more_bottom.call(());
+
+    // Follow the list's scroll events, the list's own included
+    let follow_scroll = move |current: Viewport| {
+        let now = store.clock().now();
+        last_scroll_event.set(Some(now));
+        work_when_idle();
+
+        // Older items arriving mid-fling wait until it slows down or reaches
+        // the top, rather than shifting the rows while they fly by
+        if !current.own_scroll {
+            // Wheel and trackpad gestures are measured from their normalized
+            // deltas; touch, scrollbar and keyboard scrolls from the position
+            let velocity = match (wheel_velocity.peek().current(now), *last_user_scroll.peek()) {
+                (Some(velocity), _) => velocity.abs(),
+                (None, Some((at, top))) => {
+                    (current.scroll_top - top).abs() / now.saturating_duration_since(at).as_millis().max(1) as f64
+                }
+                (None, None) => 0.0,
+            };
+            last_user_scroll.set(Some((now, current.scroll_top)));
+            if current.direction == -1 && velocity > FLING_VELOCITY && current.scroll_top > 0.0 {
+                hold_for_fling();
+            } else {
+                store.hold_prepends(false);
+                prepend_staged();
+            }
         }
+
+        // Popovers are placed for where their anchor was
+        if author_card.peek().is_some() {
+            author_card.set(None);
+            author_card_hovered.set(false);
+        }
+        if tooltip.peek().is_some() {
+            tooltip.set(None);
+        }
+        if item_menu.peek().is_some() {
+            item_menu.set(None);
+        }
+        viewport.set(Some(current));
     };
 
-    rsx! {
+    // Scroll statistics above the time-travel panel over the store's recorded
+    // snapshots, in a resizable dock whose statistics start folded away
+    // (debug builds only)
+    #[cfg(debug_assertions)]
+    let (scroll_height, direction) = (*viewport.peek()).map_or((0.0, 0), |viewport| (viewport.scroll_height, viewport.direction));
+    #[cfg(debug_assertions)]
+    let debug_panels = rsx! {
         div {
             style: format!("
+                position: fixed;
+                bottom: 10px;
+                right: 10px;
+                width: 280px;
                 height: {}px;
-                overflow-y: auto;
-                background: white;
-                position: relative;
-                scroll-behavior: smooth;
-            ", CONTAINER_HEIGHT),
+                border-radius: 6px;
+                overflow: hidden;
+                font-size: 12px;
+                font-family: monospace;
+                color: white;
+                z-index: 1000;
+            ", DEBUG_DOCK_HEIGHT),
+            SplitPane {
+                axis: SplitAxis::Vertical,
+                default_size: DEBUG_STATS_HEIGHT,
+                min_size: DEBUG_PANEL_MIN_HEIGHT,
+                min_second_size: DEBUG_PANEL_MIN_HEIGHT,
+                collapsible: true,
+                default_collapsed: true,
+                storage_key: "debug_panels",
+                label: "Resize debug panels (double-click to show scroll statistics)".to_string(),
+                first: rsx! {
+                    div {
+                        style: "
+                            height: 100%;
+                            overflow-y: auto;
+                            box-sizing: border-box;
+                            background: rgba(0, 0, 0, 0.8);
+                            padding: 10px;
+                        ",
+                            div { "Items: {total_items}" }
+                            div { "Visible: {rows_in_view.peek().start}-{rows_in_view.peek().end}" }
+                            div { "Buffer: {buffer} (content-visibility: {offscreen_skipped})" }
+                            div { "Scroll: {scroll_top:.0}px" }
+                            div { "Height: {scroll_height:.0}px" }
+                            div { 
+                                if direction == -1 { "Direction: UP" }
+                                else if direction == 1 { "Direction: DOWN" }
+                                else { "Direction: NONE" }
+                            }
+                            div { "Status: {status:?}" }
+                            if let Some(diagnostics) = scroll_diagnostics {
+                                div {
+                                    "Scroll failures: {diagnostics.native_failures} native, {diagnostics.fallback_failures} fallback ({diagnostics.fallbacks} recovered)"
+                                }
+                            }
+                    }
+                },
+                second: rsx! {
+                    RecorderPanel {
+                        store,
+                        on_travel: move |target: f64| controller.scroll_to(ScrollRequest::Offset(target)),
+                    }
+                },
+            }
+        }
+    };
+    #[cfg(not(debug_assertions))]
+    let debug_panels = rsx! {};
+
+    rsx! {
+        div {
+            style: "background: white; position: relative;",
+
+            // Hook for the end-to-end harness
+            "data-feed-list": "true",
+
+            onwheel: move |evt: Event<WheelData>| {
+                let (_, delta) = wheel_pixels(evt.data().delta(), *client_height.peek());
+                let now = store.clock().now();
+                let new_gesture = wheel_velocity.peek().current(now).is_none();
+                wheel_velocity.write().record(now, delta);
+                pull_past_top(delta, new_gesture);
+            },
+            // The rows' scroll box is focusable and takes the find and edit shortcuts
+            onkeydown: move |evt: Event<KeyboardData>| {
+                let modifiers = evt.modifiers();
+                if !(modifiers.ctrl() || modifiers.meta()) {
+                    return;
+                }
+                let Key::Character(key) = evt.key() else {
+                    return;
+                };
+                let handled = match key.to_lowercase().as_str() {
+                    // Native find can't see rows that aren't rendered
+                    "f" => {
+                        find_open.set(true);
+                        true
+                    }
+                    "z" if editing() && modifiers.shift() => store.redo(),
+                    "z" if editing() => store.undo(),
+                    "y" if editing() => store.redo(),
+                    _ => return,
+                };
+                if handled {
+                    evt.prevent_default();
+                    // Only the innermost of nested lists takes the shortcut
+                    evt.stop_propagation();
+                }
+            },
+
+            VirtualRows {
+                items,
+                render_row: {
+                    let summarizer = summarizer.clone();
+                    let find_highlight = find_highlight.clone();
+                    let current_match = current_match.clone();
+                    let selected_id = props.selected_id.clone();
+                    let on_select = props.on_select;
+                    move |row: Row<VirtualFeedItem>| {
+                        let item = row.item;
+                        rsx! {
+                            VirtualFeedItemComponent {
+                                item: item.clone(),
+                                row_height: row.height,
+                                stacked,
+                                load_media: row.viewport_distance <= image_lead_distance,
+                                media_priority: (row.viewport_distance / row_height).ceil() as u32,
+                                is_reading: (read_aloud.reading_id)().as_deref() == Some(item.id.as_str()),
+                                state: store.item_state(&item.id),
+                                content_visibility,
+                                find_highlight: find_highlight.clone(),
+                                is_find_current: current_match.as_deref() == Some(item.id.as_str()),
+                                highlight_fresh: highlight_new_items,
+                                on_read: move |url| reader_url.set(Some(url)),
+                                on_open_image: move |image| lightbox.set(Some(image)),
+                                on_author_hover: move |hover| hover_author.call(hover),
+                                on_tooltip: move |tip| tooltip.set(tip),
+                                on_context_menu: {
+                                    let item = item.clone();
+                                    move |anchor| item_menu.set(Some((anchor, item.clone())))
+                                },
+                                summary: is_long(&item).then(|| store.summary(&item.id)),
+                                on_summarize: summarizer.clone().filter(|_| is_long(&item)).map(|summarizer| {
+                                    let id = item.id.clone();
+                                    EventHandler::new(move |_| {
+                                        spawn(store.summarize_item(summarizer.clone(), id.clone()));
+                                    })
+                                }),
+                                on_edit: editing().then(|| {
+                                    let id = item.id.clone();
+                                    EventHandler::new(move |action| store.edit(&id, action))
+                                }),
+                                is_selected: selected_id.as_deref() == Some(item.id.as_str()),
+                                on_select: on_select.map(|on_select| {
+                                    let item = item.clone();
+                                    EventHandler::new(move |_| on_select.call(item.clone()))
+                                }),
+                                // Rows settled by their image hints only need
+                                // measuring when stacked
+                                on_measure: (stacked || hinted_row_height(&item).is_none()).then_some(row.on_measure),
+                            }
+                        }
+                    }
+                },
+                estimate_height: estimate_row,
+                row_height,
+                // Rows observe their own content, below their fixed-height box
+                measure_rows: false,
+                measured,
+                height: CONTAINER_HEIGHT,
+                buffer,
+                // Keep the selected rows rendered as they scroll away, as
+                // recycling them would drop the selection
+                keep_rendered: selected_range(),
+                layout_mode: props.layout_mode,
+                first_seq: store.first_sequence(),
+                interleave: props.interleave,
+                interleave_every: props.interleave_every,
+                interleave_height: props.interleave_height,
+                // Divider below the newest item seen last session
+                divider_after: caught_up_after(),
+                divider: rsx! {
+                    div {
+                        role: "separator",
+                        style: format!("
+                            height: {}px;
+                            margin-bottom: 16px;
+                            display: flex;
+                            align-items: center;
+                            gap: 12px;
+                            color: #64748b;
+                            font-size: 12px;
+                            font-weight: 500;
+                        ", CAUGHT_UP_HEIGHT - 16.0),
+                        div { style: "flex: 1; height: 1px; background: #cbd5e1;" }
+                        "You're all caught up — last visit here"
+                        div { style: "flex: 1; height: 1px; background: #cbd5e1;" }
+                    }
+                },
+                divider_height: CAUGHT_UP_HEIGHT,
+                // Read items folded into a pill in the divider's place
+                fold_above_divider: read_folded(),
+                folded: rsx! {
+                    div {
+                        style: format!("
+                            height: {}px;
+                            margin-bottom: 16px;
+                            display: flex;
+                            align-items: center;
+                            justify-content: center;
+                        ", CAUGHT_UP_HEIGHT - 16.0),
+                        button {
+                            style: "
+                                padding: 6px 14px;
+                                border-radius: 999px;
+                                border: 1px solid #cbd5e1;
+                                background: #f8fafc;
+                                color: #475569;
+                                font-size: 12px;
+                                font-weight: 500;
+                                cursor: pointer;
+                            ",
+                            onclick: move |_| set_read_hidden(true, true),
+                            if hidden_count == 1 { "1 read item hidden" } else { "{hidden_count} read items hidden" }
+                        }
+                    }
+                },
+                on_unfold: move |_| show_read.set(true),
+                // Anything older than folded read items is read too
+                on_reach_start: move |_| {
+                    if store.can_load_top() && !read_folded() {
+                        load_more_top.call(());
+                    }
+                },
+                on_reach_end: move |_| {
+                    if status.peek().can_load_bottom() {
+                        load_more_bottom.call(());
+                    }
+                },
+                on_scroll: follow_scroll,
+                on_rows_in_view: move |in_view: std::ops::Range<usize>| {
+                    if !in_view.is_empty() {
+                        mark_newest_seen(in_view.end - 1);
+                    }
+                    rows_in_view.set(in_view);
+                },
+                on_keep_in_place: move |top: f64| store.record(StoreEvent::ScrollAnchor { scroll_top: top }),
+                // Switch between side-by-side and stacked rows as the
+                // container resizes; the list keeps the row at the top of the
+                // viewport in place
+                on_resize: move |width: f64| {
+                    laid_out_width.set(Some(width));
+                    let new_height = if width < stack_below_width { STACKED_ITEM_HEIGHT } else { ITEM_HEIGHT };
+                    if new_height != *store.row_height.peek() {
+                        store.row_height.set(new_height);
+                    }
+                },
+                on_mounted: move |element| scroll_element.set(Some(element)),
+                controller,
+                scroll_parent: props.scroll_parent,
+                edge_scroll: props.edge_scroll,
+                scroll_top,
+                viewport_height: client_height,
+                header: rsx! {
+                    // Read aloud toolbar
+                    div {
+                        style: "
+                            position: sticky;
+                            top: 0;
+                            z-index: 110;
+                            display: flex;
+                            flex-wrap: wrap;
+                            justify-content: flex-end;
+                            gap: 8px;
+                            padding: 8px;
+                            background: white;
+                            border-bottom: 1px solid #e2e8f0;
+                        ",
+                
+                        // Reading progress along the toolbar's bottom edge
+                        if show_progress {
+                            div {
+                                role: "progressbar",
+                                aria_valuemin: "0",
+                                aria_valuemax: "100",
+                                aria_valuenow: format!("{:.0}", progress().fraction() * 100.0),
+                                title: if progress().complete { format!("{} items remaining", progress().remaining()) } else { String::new() },
+                                style: format!("
+                                    position: absolute;
+                                    left: 0;
+                                    bottom: -1px;
+                                    height: 3px;
+                                    width: {:.2}%;
+                                    background: #3b82f6;
+                                    transition: width 0.2s ease;
+                                ", progress().fraction() * 100.0),
+                            }
+                        }
+                
+                        // How far a pull past the top has to go to refresh
+                        if pulled() > 0.0 {
+                            div {
+                                role: "status",
+                                style: format!("
+                                    position: absolute;
+                                    left: 0;
+                                    right: 0;
+                                    top: 100%;
+                                    padding: 6px;
+                                    text-align: center;
+                                    font-size: 12px;
+                                    color: #475569;
+                                    background: rgba(241, 245, 249, {:.2});
+                                    pointer-events: none;
+                                ", pulled() / REFRESH_PULL_DISTANCE),
+                                "Keep pulling to refresh"
+                            }
+                        }
+                
+                        // Latest/Top ordering
+                        div {
+                            role: "group",
+                            aria_label: "Order",
+                            style: "display: flex; margin-right: auto;",
+                            for mode in FeedRanking::ALL {
+                                button {
+                                    aria_pressed: "{ranking() == mode}",
+                                    style: format!("
+                                        padding: 6px 12px;
+                                        border: 1px solid #e2e8f0;
+                                        background: {};
+                                        color: {};
+                                        font-size: 13px;
+                                        cursor: pointer;
+                                    ",
+                                        if ranking() == mode { "#0f172a" } else { "white" },
+                                        if ranking() == mode { "white" } else { "#0f172a" }
+                                    ),
+                                    onclick: move |_| store.ranking.set(mode),
+                                    "{mode.label()}"
+                                }
+                            }
+                        }
+                        if visited_before {
+                            button {
+                                style: "
+                                    padding: 6px 12px;
+                                    border-radius: 6px;
+                                    border: 1px solid #e2e8f0;
+                                    background: white;
+                                    color: #0f172a;
+                                    font-size: 13px;
+                                    cursor: pointer;
+                                ",
+                                aria_pressed: "{hide_read()}",
+                                onclick: move |_| set_read_hidden(!hide_read(), false),
+                                if hide_read() { "Show read" } else { "Hide read" }
+                            }
+                        }
+                        if let Some((first, last)) = selected_range() {
+                            button {
+                                style: "
+                                    padding: 6px 12px;
+                                    border-radius: 6px;
+                                    border: 1px solid #e2e8f0;
+                                    background: white;
+                                    color: #0f172a;
+                                    font-size: 13px;
+                                    cursor: pointer;
+                                ",
+                                // Keep the selection from collapsing on press
+                                onmousedown: move |evt| evt.prevent_default(),
+                                onclick: copy_selection,
+                                if first == last { "Copy selection" } else { {format!("Copy {} items", last + 1 - first)} }
+                            }
+                        }
+                        if editing() {
+                            button {
+                                style: "
+                                    padding: 6px 12px;
+                                    border-radius: 6px;
+                                    border: 1px solid #e2e8f0;
+                                    background: white;
+                                    color: #0f172a;
+                                    font-size: 13px;
+                                    cursor: pointer;
+                                ",
+                                title: "Ctrl+Z",
+                                disabled: !store.can_undo(),
+                                onclick: move |_| {
+                                    store.undo();
+                                },
+                                "Undo"
+                            }
+                            button {
+                                style: "
+                                    padding: 6px 12px;
+                                    border-radius: 6px;
+                                    border: 1px solid #e2e8f0;
+                                    background: white;
+                                    color: #0f172a;
+                                    font-size: 13px;
+                                    cursor: pointer;
+                                ",
+                                title: "Ctrl+Y",
+                                disabled: !store.can_redo(),
+                                onclick: move |_| {
+                                    store.redo();
+                                },
+                                "Redo"
+                            }
+                        }
+                        if editable {
+                            button {
+                                style: format!("
+                                    padding: 6px 12px;
+                                    border-radius: 6px;
+                                    border: 1px solid #e2e8f0;
+                                    background: {};
+                                    color: {};
+                                    font-size: 13px;
+                                    cursor: pointer;
+                                ",
+                                    if editing() { "#0f172a" } else { "white" },
+                                    if editing() { "white" } else { "#0f172a" }
+                                ),
+                                aria_pressed: "{editing()}",
+                                onclick: move |_| editing.toggle(),
+                                if editing() { "Done" } else { "Edit" }
+                            }
+                        }
+                        SessionExportButtons { store }
+                        button {
+                            style: "
+                                padding: 6px 12px;
+                                border-radius: 6px;
+                                border: 1px solid #e2e8f0;
+                                background: white;
+                                color: #0f172a;
+                                font-size: 13px;
+                                cursor: pointer;
+                            ",
+                            onclick: move |_| controller.jump_to_top(),
+                            "Top"
+                        }
+                        if store.source().is_some() {
+                            button {
+                                style: "
+                                    padding: 6px 12px;
+                                    border-radius: 6px;
+                                    border: 1px solid #e2e8f0;
+                                    background: white;
+                                    color: #0f172a;
+                                    font-size: 13px;
+                                    cursor: pointer;
+                                ",
+                                disabled: status().is_loading(),
+                                onclick: move |_| refresh.call(()),
+                                "Refresh"
+                            }
+                        }
+                        button {
+                            style: "
+                                padding: 6px 12px;
+                                border-radius: 6px;
+                                border: 1px solid #e2e8f0;
+                                background: white;
+                                color: #0f172a;
+                                font-size: 13px;
+                                cursor: pointer;
+                            ",
+                            aria_pressed: "{(read_aloud.active)()}",
+                            onclick: move |_| {
+                                if (read_aloud.active)() {
+                                    read_aloud.stop.call(());
+                                } else {
+                                    read_aloud.start.call(rows_in_view.peek().start);
+                                }
+                            },
+                            if (read_aloud.active)() { "Stop reading" } else { "Read aloud" }
+                        }
+                        if let Some(summarizer) = summarizer.clone() {
+                            button {
+                                style: "
+                                    padding: 6px 12px;
+                                    border-radius: 6px;
+                                    border: 1px solid #e2e8f0;
+                                    background: white;
+                                    color: #0f172a;
+                                    font-size: 13px;
+                                    cursor: pointer;
+                                ",
+                                title: "Summarize the newest items",
+                                disabled: store.digest().read().status == SummaryStatus::Streaming,
+                                onclick: move |_| {
+                                    spawn(store.summarize_digest(summarizer.clone()));
+                                },
+                                "Summarize feed"
+                            }
+                        }
+                
+                        // Find bar, on its own line below the buttons
+                        if find_open() {
+                            FindBar {
+                                query: find_query,
+                                count: find_matches.read().len(),
+                                current: find_current(),
+                                on_next: move |_| show_match(find_current() + 1),
+                                on_previous: move |_| {
+                                    let count = find_matches.peek().len();
+                                    show_match(find_current() + count.max(1) - 1);
+                                },
+                                on_close: move |_| {
+                                    find_open.set(false);
+                                    find_query.set(String::new());
+                                },
+                            }
+                        }
+
+                        // Digest of the newest items, below the buttons
+                        SummaryView {
+                            summary: store.digest(),
+                            title: "Digest of the newest items".to_string(),
+                            on_close: move |_| store.clear_digest(),
+                        }
+                    }
+            
+                    // Loading indicator at top
+                    if matches!(status(), FeedStatus::LoadingTop | FeedStatus::Refreshing) {
+                        div {
+                            style: "
+                                position: sticky;
+                                top: 0;
+                                z-index: 100;
+                                background: white;
+                                color: #0f172a;
+                                text-align: center;
+                                padding: 15px;
+                                border-bottom: 1px solid #e2e8f0;
+                                font-weight: 500;
+                            ",
+                            if status() == FeedStatus::Refreshing { "Refreshing..." } else { "Loading older items..." }
+                        }
+                    }
             
-            onscroll: handle_scroll,
-            onmounted: move |event| scroll_element.set(Some(event.data())),
+                    // Error banner
+                    if let FeedStatus::Error(error) = status() {
+                        div {
+                            role: "alert",
+                            style: "
+                                position: sticky;
+                                top: 0;
+                                z-index: 100;
+                                display: flex;
+                                justify-content: space-between;
+                                align-items: center;
+                                gap: 12px;
+                                background: #fef2f2;
+                                color: #b91c1c;
+                                padding: 12px 15px;
+                                border-bottom: 1px solid #fecaca;
+                                font-size: 13px;
+                            ",
+                            span { "Couldn't load items: {error}" }
+                            button {
+                                style: "
+                                    padding: 4px 10px;
+                                    border-radius: 6px;
+                                    border: 1px solid #fecaca;
+                                    background: white;
+                                    color: #b91c1c;
+                                    cursor: pointer;
+                                ",
+                                onclick: move |_| store.clear_error(),
+                                "Dismiss"
+                            }
+                        }
+                    }
             
-            // Loading indicator at top
-            if is_loading_top() {
-                div {
-                    style: "
-                        position: sticky;
-                        top: 0;
-                        z-index: 100;
-                        background: white;
-                        color: #0f172a;
-                        text-align: center;
-                        padding: 15px;
-                        border-bottom: 1px solid #e2e8f0;
-                        font-weight: 500;
-                    ",
-                    "Loading older items..."
+                    // Initial load of an empty feed
+                    if status() == FeedStatus::Initializing {
+                        div {
+                            style: "
+                                text-align: center;
+                                padding: 40px 15px;
+                                color: #64748b;
+                            ",
+                            "Loading feed..."
+                        }
+                    }
+            
+                    // Start of the feed, once the source has nothing older; other
+                    // orders don't start with the oldest item
+                    if store.at_beginning() && ranking() == FeedRanking::Latest {
+                        div {
+                            role: "note",
+                            style: "
+                                text-align: center;
+                                padding: 24px 15px 8px;
+                                color: #64748b;
+                                font-size: 13px;
+                            ",
+                            if let Some(header) = props.beginning_header.clone() {
+                                {header}
+                            } else {
+                                "This is the beginning"
+                            }
+                        }
+                    }
+                },
+                footer: rsx! {
+                    // Return to where the last jump started
+                    if (controller.return_to)().is_some() {
+                        div {
+                            style: "
+                                position: sticky;
+                                bottom: 0;
+                                height: 0;
+                                z-index: 120;
+                            ",
+                            button {
+                                style: "
+                                    position: absolute;
+                                    bottom: 16px;
+                                    left: 50%;
+                                    transform: translateX(-50%);
+                                    padding: 8px 16px;
+                                    border-radius: 999px;
+                                    border: 1px solid #e2e8f0;
+                                    background: #0f172a;
+                                    color: white;
+                                    font-size: 13px;
+                                    cursor: pointer;
+                                    white-space: nowrap;
+                                ",
+                                onclick: move |_| controller.return_to_origin(),
+                                "Return to previous position"
+                            }
+                        }
+                    }
+            
+                    // Loading indicator at bottom
+                    if status() == FeedStatus::LoadingBottom {
+                        div {
+                            style: "
+                                position: sticky;
+                                bottom: 0;
+                                z-index: 100;
+                                background: white;
+                                color: #0f172a;
+                                text-align: center;
+                                padding: 15px;
+                                border-top: 1px solid #e2e8f0;
+                                font-weight: 500;
+                            ",
+                            "Loading newer items..."
+                        }
+                    }
+                },
+            }
+
+            // Profile card for the hovered author
+            if let Some(hover) = author_card() {
+                AuthorCard {
+                    hover,
+                    store,
+                    container: scroll_element,
+                    on_hover_change: move |hovered| author_card_hovered.set(hovered),
+                    on_close: move |_| {
+                        author_card.set(None);
+                        author_card_hovered.set(false);
+                    },
                 }
             }
             
-            // Virtual content container
-            div {
-                style: format!("height: {}px; position: relative;", total_height),
-                
-                // Render only visible items
-                for i in start_index..end_index {
-                    if i < items().len() {
-                        VirtualFeedItemComponent {
-                            key: "{items()[i].id}",
-                            item: items()[i].clone(),
-                            top_position: i as f64 * ITEM_HEIGHT,
-                        }
+            // Full date of the hovered timestamp
+            if let Some(Tooltip { anchor, text }) = tooltip() {
+                Popover {
+                    anchor,
+                    container: scroll_element,
+                    side: Side::Above,
+                    role: "tooltip".to_string(),
+                    on_close: move |_| tooltip.set(None),
+                    div {
+                        style: "
+                            padding: 4px 8px;
+                            border-radius: 4px;
+                            background: #0f172a;
+                            color: white;
+                            font-size: 12px;
+                            white-space: nowrap;
+                            pointer-events: none;
+                        ",
+                        "{text}"
                     }
                 }
             }
             
-            // Loading indicator at bottom
-            if is_loading_bottom() {
-                div {
-                    style: "
-                        position: sticky;
-                        bottom: 0;
-                        z-index: 100;
-                        background: white;
-                        color: #0f172a;
-                        text-align: center;
-                        padding: 15px;
-                        border-top: 1px solid #e2e8f0;
-                        font-weight: 500;
-                    ",
-                    "Loading newer items..."
+            // Actions of the row that was right-clicked
+            if let Some((anchor, item)) = item_menu() {
+                ItemMenu {
+                    item,
+                    anchor,
+                    container: scroll_element,
+                    on_read: move |url| reader_url.set(Some(url)),
+                    on_close: move |_| item_menu.set(None),
                 }
             }
             
-            // Debug info (hidden by default)
-            div {
-                style: "
-                    position: fixed;
-                    top: 10px;
-                    right: 10px;
-                    background: rgba(0, 0, 0, 0.8);
-                    color: white;
-                    padding: 10px;
-                    border-radius: 5px;
-                    font-size: 12px;
-                    font-family: monospace;
-                    z-index: 1000;
-                    display: none;
-                ",
-                div { "Items: {total_items}" }
-                div { "Visible: {start_index}-{end_index}" }
-                div { "Scroll: {scroll_top:.0}px" }
-                div { "Height: {total_height:.0}px" }
-                div { 
-                    if scroll_direction() == -1 { "Direction: UP" }
-                    else if scroll_direction() == 1 { "Direction: DOWN" }
-                    else { "Direction: NONE" }
+            // Full-size image with its alt text
+            if let Some(image) = lightbox() {
+                Lightbox {
+                    image,
+                    on_close: move |_| lightbox.set(None),
+                }
+            }
+            
+            // Reader mode detail view
+            if let Some(url) = reader_url() {
+                ArticleView {
+                    url,
+                    on_close: move |_| reader_url.set(None),
                 }
-                div { "Loading T:{is_loading_top()} B:{is_loading_bottom()}" }
             }
+            
+            // Scroll statistics and recorded store history (debug builds only)
+            {debug_panels}
         }
     }
 }
 
+// Drop highlights once they have run their course; only the rows whose
+// item state changes re-render
+// A short text shown over the list, anchored to what it describes
+#[derive(Clone, PartialEq, Debug)]
+pub struct Tooltip {
+    pub anchor: Anchor,
+    pub text: String,
+}
+
+// How long ago `date` was, e.g. "5m", or the date itself after a week
+fn item_age(date: chrono::DateTime<chrono::Utc>, now: chrono::DateTime<chrono::Utc>) -> String {
+    let minutes = (now - date).num_minutes().max(0);
+    match minutes {
+        0 => "now".to_string(),
+        1..=59 => format!("{}m", minutes),
+        60..=1439 => format!("{}h", minutes / 60),
+        1440..=10079 => format!("{}d", minutes / 1440),
+        _ => date.format("%b %-d").to_string(),
+    }
+}
+
+async fn expire_highlights(mut store: FeedStore) {
+    store.clock().sleep(FRESH_HIGHLIGHT_DURATION).await;
+    store.expire_fresh();
+}
+
 #[derive(PartialEq, Props, Clone)]
 pub struct VirtualFeedItemProps {
     pub item: VirtualFeedItem,
-    pub top_position: f64,
+    #[props(default = ITEM_HEIGHT)]
+    pub row_height: f64,
+    // Image above the text rather than beside it, for narrow containers
+    #[props(default)]
+    pub stacked: bool,
+    #[props(default)]
+    pub is_reading: bool,
+    // Fresh and pinned state of the item, read here so changes to it only
+    // re-render this row
+    pub state: Option<Signal<ItemState>>,
+    // Highlight the row while its item is fresh
+    #[props(default)]
+    pub highlight_fresh: bool,
+    // Let the webview skip rendering the row while it is offscreen
+    #[props(default)]
+    pub content_visibility: bool,
+    // Query whose matches are marked in the content, while finding
+    #[props(default)]
+    pub find_highlight: Option<String>,
+    // The find match currently stepped to
+    #[props(default)]
+    pub is_find_current: bool,
+    // Near enough to the viewport to load its image
+    #[props(default = true)]
+    pub load_media: bool,
+    // Rows between this one and the viewport; nearer rows load first
+    #[props(default)]
+    pub media_priority: u32,
+    pub on_read: Option<EventHandler<String>>,
+    // Cached summary of a long item, with the action generating it when a
+    // summarizer is configured
+    pub summary: Option<Signal<Summary>>,
+    pub on_summarize: Option<EventHandler<()>>,
+    // Opens the loaded image full size
+    pub on_open_image: Option<EventHandler<LightboxImage>>,
+    // The pointer or focus entered (`Some`) or left (`None`) the author's name
+    pub on_author_hover: Option<EventHandler<Option<AuthorHover>>>,
+    // Same for the timestamp, with its full date
+    pub on_tooltip: Option<EventHandler<Option<Tooltip>>>,
+    // Right click on the row, at the pointer
+    pub on_context_menu: Option<EventHandler<Anchor>>,
+    // Edit controls are shown when set, for a curated list in edit mode
+    pub on_edit: Option<EventHandler<EditAction>>,
+    // Called with the row's natural height, including the gap below it,
+    // whenever its content resizes (translation, text wrapping, images)
+    pub on_measure: Option<EventHandler<f64>>,
+    // Shown as the row whose detail is open, in a split view
+    #[props(default)]
+    pub is_selected: bool,
+    // Clicking the row selects it when set
+    pub on_select: Option<EventHandler<()>>,
 }
 
 #[component]
 pub fn VirtualFeedItemComponent(props: VirtualFeedItemProps) -> Element {
     let item = &props.item;
-    let top_position = props.top_position;
+    let state = props.state.map(|state| state()).unwrap_or_default();
+    let is_fresh = props.highlight_fresh && state.fresh;
+    let content_visibility = if props.content_visibility {
+        format!("content-visibility: auto; contain-intrinsic-size: auto {}px;", props.row_height - 16.0)
+    } else {
+        String::new()
+    };
+    let (border_color, background) = if props.is_reading {
+        ("#3b82f6", "#eff6ff")
+    } else if props.is_selected {
+        ("#0f172a", "#f8fafc")
+    } else {
+        ("#e2e8f0", "white")
+    };
+    
+    // Whether the near-duplicates collapsed into this item are listed
+    let mut shared_expanded = use_signal(|| false);
+    
+    // Image loading state, seeded from the media cache so images loaded
+    // before the row was virtualized away render without flicker
+    let media_cache = use_media_cache();
+    // Index of the image candidate being shown, skipping candidates already
+    // known to fail
+    let candidates: Vec<String> = item.image_candidates().map(str::to_string).collect();
+    let last_candidate = candidates.len() - 1;
+    let mut candidate = use_signal(|| {
+        candidates
+            .iter()
+            .position(|url| media_cache.status(url) != MediaStatus::Failed)
+            .unwrap_or(last_candidate)
+    });
+    let image_url = candidates[candidate().min(last_candidate)].clone();
+    let mut media_status = use_signal(|| media_cache.status(&image_url));
+    
+    // Flagged items are blurred until revealed, and their media isn't
+    // fetched, measured or captioned meanwhile. Without gating settings in
+    // context nothing is gated.
+    let gating = try_use_context::<ContentGating>();
+    let mut flags: Vec<ContentFlag> = Vec::new();
+    for flag in item.content_flags() {
+        if !flags.contains(&flag) {
+            flags.push(flag);
+        }
+    }
+    let gated = gating.is_some_and(|gating| gating.is_gated(&item));
+    let concealable = !gated
+        && !flags.is_empty()
+        && gating.is_some_and(|gating| gating.blur_flagged() && gating.is_revealed(&item.id));
+    
+    // Set once the row comes within the lead distance and never cleared, so
+    // a load in progress isn't cancelled by scrolling away
+    let mut media_requested = use_hook(|| CopyValue::new(false));
+    if !gated && (props.load_media || media_status() == MediaStatus::Loaded) {
+        media_requested.set(true);
+    }
+    let media_requested = *media_requested.read() && !gated;
     
-    // Image loading state
-    let mut image_loaded = use_signal(|| false);
-    let mut image_error = use_signal(|| false);
+    // Wait for a slot in the app-wide loader queue before fetching
+    let slot_granted = use_media_slot(media_requested && media_status() == MediaStatus::Loading, props.media_priority);
+    
+    // Failed loads are retried with backoff unless the protocol reports the
+    // image as missing; `reloads` changes the URL so the webview refetches
+    let clock = use_clock();
+    let mut retry_attempts = use_signal(|| 0u32);
+    let mut reloads = use_signal(|| 0u32);
+    let image_src = reload_url(&image_url, reloads());
+    
+    // Reserve the image's aspect ratio so the row doesn't shift when it
+    // loads: the shape the item declares, else the size from the metadata
+    // endpoint, which hinted items skip
+    // Item hints describe the main image only
+    let hinted_aspect = if candidate() == 0 { item.image_aspect() } else { None };
+    let known_size = if candidate() == 0 { item.image_size() } else { None };
+    let lookup_url = if gated || hinted_aspect.is_some() { String::new() } else { image_url.clone() };
+    let dimensions = use_media_dimensions(lookup_url, known_size);
+    let image_box_height = image_box_height(hinted_aspect.or_else(|| {
+        dimensions
+            .filter(|(width, height)| *width > 0 && *height > 0)
+            .map(|(width, height)| width as f64 / height as f64)
+    }));
+    let (image_width, image_height) = dimensions.unzip();
+    
+    // The source's description of the main image, or generated alt text
+    // once the image has loaded
+    let source_alt = if candidate() == 0 { item.image_alt.clone() } else { None };
+    let alt_text = use_alt_text(image_url.clone(), source_alt, !gated && media_status() == MediaStatus::Loaded);
+    
+    // Stacked items show the image full width above the text
+    let (image_box_width, image_box_height) = if props.stacked {
+        ("100%".to_string(), STACKED_IMAGE_HEIGHT)
+    } else {
+        (format!("{}px", IMAGE_BOX_WIDTH), image_box_height)
+    };
+    
+    // Bookmarks are optional so the list can be used without the app shell
+    let bookmarks = try_use_context::<Bookmarks>();
+    let is_saved = bookmarks.is_some_and(|bookmarks| bookmarks.is_saved(&item.id));
+    let save_item = item.clone();
+    // Links in the content are opened through the link policy
+    let links = use_link_policy();
+    // Author's name, measured when hovered to anchor their card
+    let mut author_element = use_signal(|| None::<std::rc::Rc<MountedData>>);
+    let author_name = item.author.clone();
+    let hover_author = use_callback(move |entered: bool| {
+        let Some(on_author_hover) = props.on_author_hover else {
+            return;
+        };
+        let Some(author) = author_name.clone().filter(|_| entered) else {
+            on_author_hover.call(None);
+            return;
+        };
+        let Some(element) = author_element() else {
+            return;
+        };
+        spawn(async move {
+            if let Some(anchor) = Anchor::of(&element).await {
+                on_author_hover.call(Some(AuthorHover { author, anchor }));
+            }
+        });
+    });
+    // Publication time, measured when hovered to anchor its tooltip
+    let mut time_element = use_signal(|| None::<std::rc::Rc<MountedData>>);
+    let published_at = item
+        .published_at
+        .and_then(chrono::DateTime::from_timestamp_millis);
+    let show_full_date = move |entered: bool| {
+        let Some(on_tooltip) = props.on_tooltip else {
+            return;
+        };
+        let (Some(date), true) = (published_at, entered) else {
+            on_tooltip.call(None);
+            return;
+        };
+        let Some(element) = time_element() else {
+            return;
+        };
+        let text = date.with_timezone(&chrono::Local).format("%A, %B %-d, %Y at %H:%M").to_string();
+        spawn(async move {
+            if let Some(anchor) = Anchor::of(&element).await {
+                on_tooltip.call(Some(Tooltip { anchor, text }));
+            }
+        });
+    };
     
     rsx! {
         article {
             style: format!("
-                position: absolute;
-                top: {}px;
+                position: relative;
                 width: 100%;
                 height: {}px;
-                background: white;
+                background: {};
                 border-radius: 8px;
-                border: 1px solid #e2e8f0;
+                border: 1px solid {};
                 margin-bottom: 16px;
                 padding: 20px;
                 box-sizing: border-box;
                 display: flex;
                 flex-direction: column;
-                transition: border-color 0.2s ease;
-            ", top_position, ITEM_HEIGHT - 16.0),
+                transition: border-color 0.2s ease, background 0.2s ease;
+                {}
+            ", props.row_height - 16.0, background, border_color, content_visibility),
+            class: if is_fresh { "virtual-feed-item-fresh" } else { "" },
+            "data-item-id": "{item.id}",
+            aria_current: if props.is_reading { "true" } else { "false" },
+            aria_selected: props.on_select.map(|_| if props.is_selected { "true" } else { "false" }),
+            onclick: move |_| {
+                if let Some(on_select) = props.on_select {
+                    on_select.call(());
+                }
+            },
+            oncontextmenu: move |evt| {
+                if let Some(on_context_menu) = props.on_context_menu {
+                    evt.prevent_default();
+                    let point = evt.client_coordinates();
+                    on_context_menu.call(Anchor::point(point.x, point.y));
+                }
+            },
             
             onmouseenter: |_| {
                 // Add hover effect via CSS-in-JS
             },
             
-            // Header with timestamp
-            header {
-                style: "
-                    display: flex;
-                    align-items: center;
-                    margin-bottom: 12px;
-                    padding-bottom: 8px;
-                    border-bottom: 1px solid #e2e8f0;
-                ",
-                h3 {
+            // Header and content at their natural height, observed so the
+            // list can lay the row out at its measured height
+            div {
+                style: "display: flex; flex-direction: column; flex: none;",
+                onresize: move |evt: Event<ResizeData>| {
+                    let Some(on_measure) = props.on_measure else {
+                        return;
+                    };
+                    // Rows the webview skips while offscreen report nothing useful
+                    if let Ok(size) = evt.data().get_border_box_size() {
+                        if size.height > 0.0 {
+                            on_measure.call(size.height + ROW_CHROME_HEIGHT);
+                        }
+                    }
+                },
+
+                // Header with timestamp
+                header {
                     style: "
-                        margin: 0;
-                        font-size: 16px;
-                        font-weight: 600;
-                        color: #0f172a;
+                        display: flex;
+                        align-items: center;
+                        justify-content: space-between;
+                        margin-bottom: 12px;
+                        padding-bottom: 8px;
+                        border-bottom: 1px solid #e2e8f0;
                     ",
-                    "Item {item.id}"
+                    h3 {
+                        style: "
+                            margin: 0;
+                            font-size: 16px;
+                            font-weight: 600;
+                            color: #0f172a;
+                        ",
+                        "Item {item.id}"
+                    }
+                
+                    if let Some(author) = item.author.clone() {
+                        span {
+                            tabindex: if props.on_author_hover.is_some() { "0" } else { "-1" },
+                            style: "margin-left: 8px; font-size: 13px; color: #475569; cursor: default;",
+                            onmounted: move |evt| author_element.set(Some(evt.data())),
+                            onmouseenter: move |_| hover_author.call(true),
+                            onmouseleave: move |_| hover_author.call(false),
+                            onfocus: move |_| hover_author.call(true),
+                            onblur: move |_| hover_author.call(false),
+                            "{author}"
+                        }
+                    }
+                
+                    if let Some(date) = published_at {
+                        time {
+                            datetime: date.to_rfc3339(),
+                            tabindex: if props.on_tooltip.is_some() { "0" } else { "-1" },
+                            style: "margin-left: 8px; font-size: 12px; color: #94a3b8;",
+                            onmounted: move |evt| time_element.set(Some(evt.data())),
+                            onmouseenter: move |_| show_full_date(true),
+                            onmouseleave: move |_| show_full_date(false),
+                            onfocus: move |_| show_full_date(true),
+                            onblur: move |_| show_full_date(false),
+                            {item_age(date, clock.utc_now())}
+                        }
+                    }
+                
+                    if let Some(on_edit) = props.on_edit {
+                        div {
+                            style: "display: flex; gap: 4px; margin-left: auto; margin-right: 8px;",
+                            for (action, label) in [
+                                (EditAction::MoveUp, "↑"),
+                                (EditAction::MoveDown, "↓"),
+                                (EditAction::TogglePin, if state.pinned { "Unpin" } else { "Pin" }),
+                                (EditAction::Hide, "Hide"),
+                                (EditAction::Delete, "Delete"),
+                            ] {
+                                button {
+                                    key: "{label}",
+                                    style: "
+                                        padding: 4px 8px;
+                                        border-radius: 6px;
+                                        border: 1px solid #e2e8f0;
+                                        background: white;
+                                        color: #0f172a;
+                                        font-size: 12px;
+                                        cursor: pointer;
+                                    ",
+                                    onclick: move |_| on_edit.call(action),
+                                    "{label}"
+                                }
+                            }
+                        }
+                    } else if state.pinned {
+                        span {
+                            style: "margin-left: auto; margin-right: 8px; font-size: 12px; color: #64748b;",
+                            "Pinned"
+                        }
+                    }
+                
+                    if let Some(mut bookmarks) = bookmarks {
+                        button {
+                            style: format!("
+                                padding: 4px 10px;
+                                border-radius: 6px;
+                                border: 1px solid #e2e8f0;
+                                background: {};
+                                color: #0f172a;
+                                font-size: 12px;
+                                cursor: pointer;
+                            ", if is_saved { "#f1f5f9" } else { "white" }),
+                            aria_pressed: "{is_saved}",
+                            onclick: move |_| bookmarks.toggle(save_item.clone()),
+                            if is_saved { "Unsave" } else { "Save" }
+                        }
+                    }
                 }
-            }
             
-            // Main content area
-            div {
-                style: "
-                    display: flex;
-                    gap: 16px;
-                    flex: 1;
-                    align-items: flex-start;
-                ",
-                
-                // Image container
+                // Main content area
                 div {
-                    style: "
-                        flex-shrink: 0;
-                        width: 120px;
-                        height: 120px;
-                        border-radius: 6px;
-                        overflow: hidden;
-                        background: #f8fafc;
+                    style: format!("
                         display: flex;
-                        align-items: center;
-                        justify-content: center;
-                        border: 1px solid #e2e8f0;
+                        flex-direction: {};
+                        gap: 16px;
+                        flex: 1;
+                        align-items: {};
                         position: relative;
                     ",
+                        if props.stacked { "column" } else { "row" },
+                        if props.stacked { "stretch" } else { "flex-start" }
+                    ),
+                
+                    // Image container
+                    div {
+                        style: format!("
+                            flex-shrink: 0;
+                            width: {};
+                            height: {}px;
+                            border-radius: 6px;
+                            overflow: hidden;
+                            background: #f8fafc;
+                            display: flex;
+                            align-items: center;
+                            justify-content: center;
+                            border: 1px solid #e2e8f0;
+                            position: relative;
+                        ", image_box_width, image_box_height),
                     
-                    if !image_loaded() && !image_error() {
-                        div {
-                            style: "
-                                color: #64748b;
-                                font-size: 12px;
-                                text-align: center;
-                                padding: 10px;
-                            ",
-                            "Loading..."
+                        if gated {
+                            div {
+                                style: "
+                                    color: #64748b;
+                                    font-size: 12px;
+                                    text-align: center;
+                                    padding: 10px;
+                                ",
+                                "Sensitive media"
+                            }
+                        }
+                    
+                        if !gated && media_status() == MediaStatus::Loading {
+                            div {
+                                style: "
+                                    color: #64748b;
+                                    font-size: 12px;
+                                    text-align: center;
+                                    padding: 10px;
+                                ",
+                                "Loading..."
+                            }
+                        }
+                    
+                        if !gated && media_status() == MediaStatus::Failed {
+                            div {
+                                style: "
+                                    color: #ef4444;
+                                    font-size: 12px;
+                                    text-align: center;
+                                    padding: 10px;
+                                ",
+                                "Failed to load"
+                                button {
+                                    style: "
+                                        display: block;
+                                        margin: 6px auto 0;
+                                        padding: 2px 8px;
+                                        border-radius: 6px;
+                                        border: 1px solid #fecaca;
+                                        background: white;
+                                        color: #b91c1c;
+                                        font-size: 12px;
+                                        cursor: pointer;
+                                    ",
+                                    // Start the chain again from the main image
+                                    onclick: {
+                                        let media_cache = media_cache.clone();
+                                        let candidates = candidates.clone();
+                                        move |_| {
+                                            for url in &candidates {
+                                                media_cache.set_status(url, MediaStatus::Loading);
+                                            }
+                                            candidate.set(0);
+                                            media_status.set(MediaStatus::Loading);
+                                            retry_attempts.set(0);
+                                            reloads += 1;
+                                        }
+                                    },
+                                    "Retry"
+                                }
+                            }
+                        }
+                    
+                        if !gated && (media_status() == MediaStatus::Loaded || (media_status() == MediaStatus::Loading && slot_granted)) {
+                            img {
+                                src: "{image_src}",
+                                alt: alt_text.clone().unwrap_or_else(|| "Feed item image".to_string()),
+                                loading: "lazy",
+                                tabindex: if props.on_open_image.is_some() { "0" } else { "-1" },
+                                width: image_width.map(|width| width.to_string()),
+                                height: image_height.map(|height| height.to_string()),
+                                // Laid over the placeholder and hidden with opacity rather
+                                // than `display: none`, which would stop lazy loading
+                                style: format!("
+                                    position: absolute;
+                                    inset: 0;
+                                    width: 100%;
+                                    height: 100%;
+                                    object-fit: cover;
+                                    opacity: {};
+                                    cursor: {};
+                                ",
+                                    if media_status() == MediaStatus::Loaded { 1 } else { 0 },
+                                    if props.on_open_image.is_some() { "zoom-in" } else { "default" }
+                                ),
+                            
+                                onclick: {
+                                    let image = LightboxImage { src: image_src.clone(), alt: alt_text.clone() };
+                                    move |_| {
+                                        if let Some(on_open_image) = props.on_open_image {
+                                            on_open_image.call(image.clone());
+                                        }
+                                    }
+                                },
+                                onkeydown: {
+                                    let image = LightboxImage { src: image_src.clone(), alt: alt_text.clone() };
+                                    move |evt: KeyboardEvent| {
+                                        if evt.key() == Key::Enter {
+                                            if let Some(on_open_image) = props.on_open_image {
+                                                on_open_image.call(image.clone());
+                                            }
+                                        }
+                                    }
+                                },
+                            
+                                onload: {
+                                    let media_cache = media_cache.clone();
+                                    let image_url = image_url.clone();
+                                    move |_| {
+                                        media_cache.set_status(&image_url, MediaStatus::Loaded);
+                                        media_status.set(MediaStatus::Loaded);
+                                    }
+                                },
+                            
+                                onerror: {
+                                    let image_url = image_url.clone();
+                                    move |_| {
+                                        let media_cache = media_cache.clone();
+                                        let image_url = image_url.clone();
+                                        let clock = clock.clone();
+                                        spawn(async move {
+                                            let attempt = retry_attempts();
+                                            if attempt < MAX_MEDIA_RETRIES
+                                                && is_transient_failure(probe_status(&image_url).await)
+                                            {
+                                                clock.sleep(media_retry_delay(attempt)).await;
+                                                retry_attempts.set(attempt + 1);
+                                                reloads += 1;
+                                                return;
+                                            }
+                                            media_cache.set_status(&image_url, MediaStatus::Failed);
+                                        
+                                            // Move on to the next candidate, if any
+                                            let current = candidate();
+                                            if current < last_candidate {
+                                                candidate.set(current + 1);
+                                                retry_attempts.set(0);
+                                                reloads.set(0);
+                                                media_status.set(MediaStatus::Loading);
+                                            } else {
+                                                media_status.set(MediaStatus::Failed);
+                                            }
+                                        });
+                                    }
+                                },
+                            }
                         }
                     }
+                
+                    // Text content, blurred and unreachable while gated
+                    div {
+                        style: format!("
+                            flex: 1;
+                            display: flex;
+                            flex-direction: column;
+                            gap: 8px;
+                            {}
+                        ", if gated { "filter: blur(6px); user-select: none;" } else { "" }),
+                        aria_hidden: if gated { "true" } else { "false" },
+                        "inert": gated.then_some("true"),
                     
-                    if image_error() {
-                        div {
+                        p {
                             style: "
-                                color: #ef4444;
-                                font-size: 12px;
-                                text-align: center;
-                                padding: 10px;
+                                margin: 0;
+                                font-size: 14px;
+                                line-height: 1.5;
+                                color: #475569;
                             ",
-                            "Failed to load"
+                            match &props.find_highlight {
+                                Some(query) => rsx! {
+                                    for (index, (segment, found)) in highlight_segments(&item.content, query).into_iter().enumerate() {
+                                        if found {
+                                            mark {
+                                                key: "{index}",
+                                                style: if props.is_find_current { "background: #f97316; color: white;" } else { "background: #fde68a;" },
+                                                "{segment}"
+                                            }
+                                        } else {
+                                            span { key: "{index}", "{segment}" }
+                                        }
+                                    }
+                                },
+                                None => rsx! {
+                                    for (index, (segment, href)) in link_segments(&item.content).into_iter().enumerate() {
+                                        if let Some(href) = href {
+                                            a {
+                                                key: "{index}",
+                                                href: "{href}",
+                                                style: "color: #2563eb; word-break: break-all;",
+                                                onclick: move |evt| {
+                                                    evt.prevent_default();
+                                                    evt.stop_propagation();
+                                                    links.follow(&href, None);
+                                                },
+                                                "{segment}"
+                                            }
+                                        } else {
+                                            span { key: "{index}", "{segment}" }
+                                        }
+                                    }
+                                },
+                            }
+                        }
+
+                        // Badges for items merged from several sources
+                        if item.sources.len() > 1 {
+                            div {
+                                style: "display: flex; flex-wrap: wrap; gap: 4px;",
+                                for source in item.sources.iter() {
+                                    span {
+                                        key: "{source}",
+                                        style: "
+                                            padding: 2px 6px;
+                                            border-radius: 4px;
+                                            background: #f1f5f9;
+                                            color: #475569;
+                                            font-size: 11px;
+                                        ",
+                                        "{source}"
+                                    }
+                                }
+                            }
+                        }
+
+                        // Labels from item processors
+                        if !item.annotations.is_empty() {
+                            div {
+                                style: "display: flex; flex-wrap: wrap; gap: 4px;",
+                                for (index, annotation) in item.annotations.iter().enumerate() {
+                                    span {
+                                        key: "{index}",
+                                        title: annotation.detail.clone().unwrap_or_else(|| annotation.processor.clone()),
+                                        style: "
+                                            padding: 2px 6px;
+                                            border-radius: 4px;
+                                            background: #ede9fe;
+                                            color: #5b21b6;
+                                            font-size: 11px;
+                                        ",
+                                        "{annotation.label}"
+                                    }
+                                }
+                            }
+                        }
+
+                        // Near-duplicates from other sources, listed on demand
+                        if !item.also_shared.is_empty() {
+                            button {
+                                aria_expanded: "{shared_expanded()}",
+                                style: "
+                                    align-self: flex-start;
+                                    padding: 0;
+                                    border: none;
+                                    background: transparent;
+                                    color: #2563eb;
+                                    font-size: 12px;
+                                    cursor: pointer;
+                                ",
+                                onclick: move |_| shared_expanded.toggle(),
+                                if item.also_shared.len() == 1 {
+                                    "Also shared by 1 other"
+                                } else {
+                                    "Also shared by {item.also_shared.len()} others"
+                                }
+                            }
+                            if shared_expanded() {
+                                ul {
+                                    style: "
+                                        margin: 0;
+                                        padding: 0 0 0 16px;
+                                        max-height: 96px;
+                                        overflow-y: auto;
+                                        font-size: 12px;
+                                        color: #64748b;
+                                    ",
+                                    for copy in item.also_shared.iter() {
+                                        li {
+                                            key: "{copy.id}",
+                                            strong { "{copy.sources.join(\", \")}: " }
+                                            "{copy.content}"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        // Revealed flagged items can be blurred again
+                        if let (true, Some(mut gating)) = (concealable, gating) {
+                            button {
+                                style: "
+                                    align-self: flex-start;
+                                    padding: 0;
+                                    border: none;
+                                    background: transparent;
+                                    color: #64748b;
+                                    font-size: 12px;
+                                    cursor: pointer;
+                                ",
+                                onclick: {
+                                    let id = item.id.clone();
+                                    move |_| gating.conceal(&id)
+                                },
+                                "Hide again"
+                            }
+                        }
+
+                        // Summary of long content, generated on demand
+                        if let Some(summary) = props.summary {
+                            SummaryView { summary, on_summarize: props.on_summarize }
+                        }
+
+                        // Reader mode action for linked articles
+                        if let Some(link) = item.link.clone() {
+                            button {
+                                style: "
+                                    align-self: flex-start;
+                                    padding: 4px 10px;
+                                    border-radius: 6px;
+                                    border: 1px solid #e2e8f0;
+                                    background: white;
+                                    color: #0f172a;
+                                    font-size: 12px;
+                                    cursor: pointer;
+                                ",
+                                onclick: move |_| {
+                                    if let Some(on_read) = props.on_read {
+                                        on_read.call(link.clone());
+                                    }
+                                },
+                                "Read"
+                            }
                         }
                     }
-                    
-                    img {
-                        src: "{item.image_url}",
-                        alt: "Feed item image",
-                        style: format!("
-                            width: 100%;
-                            height: 100%;
-                            object-fit: cover;
-                            display: {};
-                        ", if image_loaded() { "block" } else { "none" }),
-                        
-                        onload: move |_| {
-                            image_loaded.set(true);
-                            image_error.set(false);
-                        },
-                        
-                        onerror: move |_| {
-                            image_error.set(true);
-                            image_loaded.set(false);
-                        },
-                    }
-                }
                 
-                // Text content
-                div {
-                    style: "
-                        flex: 1;
-                        display: flex;
-                        flex-direction: column;
-                        gap: 8px;
-                    ",
-                    
-                    p {
-                        style: "
-                            margin: 0;
-                            font-size: 14px;
-                            line-height: 1.5;
-                            color: #475569;
-                        ",
-                        "{item.content}"
+                    // Reason for the gate and the way past it
+                    if let (true, Some(mut gating)) = (gated, gating) {
+                        div {
+                            style: "
+                                position: absolute;
+                                inset: 0;
+                                display: flex;
+                                flex-direction: column;
+                                align-items: center;
+                                justify-content: center;
+                                gap: 8px;
+                                background: rgba(255, 255, 255, 0.4);
+                            ",
+                            span {
+                                style: "font-size: 13px; font-weight: 600; color: #0f172a;",
+                                {flags.iter().map(|flag| flag.label()).collect::<Vec<_>>().join(" · ")}
+                            }
+                            button {
+                                style: "
+                                    padding: 6px 14px;
+                                    border-radius: 6px;
+                                    border: 1px solid #cbd5e1;
+                                    background: white;
+                                    color: #0f172a;
+                                    font-size: 13px;
+                                    cursor: pointer;
+                                ",
+                                onclick: {
+                                    let id = item.id.clone();
+                                    move |_| gating.reveal(&id)
+                                },
+                                "Show sensitive content"
+                            }
+                        }
                     }
                 }
             }
@@ -0,0 +1,1064 @@
+//! Virtual scrolling over any kind of item.
+//!
+//! [`VirtualRows`] renders only the rows around the viewport, each through
+//! a render prop, for any `T: Keyed + Clone + PartialEq`. It lays the rows out at
+//! their measured or estimated heights, with optional interleaved slots and
+//! a divider, keeps the row at the top of the viewport in place as items
+//! are added above it or rows change height, and carries out the scrolls
+//! requested through a `VirtualListController`. It knows nothing of feeds:
+//! `VirtualList` is the feed's list built on it, and the gallery's messages
+//! example shows it over a type of its own.
+
+use dioxus::prelude::*;
+use dioxus_feed_core::{Sequence, VirtualFeedItem};
+use std::collections::HashMap;
+use std::ops::Range;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use super::list_controller::{use_virtual_list_controller, ScrollAnchor, ScrollRequest, VirtualListController};
+use super::scroll_fallback::scroll_with_fallback;
+use super::scroll_parent::{ScrollMetrics, ScrollParent};
+use crate::clock::use_clock;
+
+// Virtual rows configuration
+const DEFAULT_HEIGHT: f64 = 600.0; // Viewport height
+const DEFAULT_ROW_HEIGHT: f64 = 48.0; // Height of rows without a measured or estimated one
+const DEFAULT_BUFFER: usize = 5; // Extra rows to render outside the viewport
+pub(crate) const DEFAULT_INTERLEAVE_EVERY: usize = 10; // Rows between interleaved slots
+pub(crate) const DEFAULT_INTERLEAVE_HEIGHT: f64 = 120.0; // Height reserved per interleaved slot
+const DEFAULT_DIVIDER_HEIGHT: f64 = 48.0;
+const MAX_KEEP_RENDERED: usize = 200; // Most rows kept rendered outside the viewport's range
+const EDGE_THRESHOLD: f64 = 200.0; // Distance from an end at which it counts as reached
+const HEIGHT_TOLERANCE: f64 = 1.0; // Change below which a row isn't laid out again or the list moved
+const SCROLL_RESTORE_DELAY_MS: u64 = 50; // Wait for a scroll parent's layout before restoring
+const PROGRAMMATIC_SCROLL_SETTLE_MS: u64 = 600; // Time a smooth scroll of the list's own takes to settle
+const RETURN_OFFER_MS: u64 = 8000; // How long returning to a jump's origin is offered
+
+// Id of the next list mounted, so the scroll fallback can find its element
+static NEXT_LIST_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// How a virtual list positions its rendered rows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LayoutMode {
+    /// Every row absolutely positioned in a container of the full height
+    #[default]
+    Absolute,
+    /// Rows in normal flow between spacers for the rows above and below
+    /// them, so text selection and find-in-page follow the rows
+    Spacers,
+}
+
+/// What a wheel or touch scroll does once a nested list reaches its edge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EdgeScroll {
+    /// Carry on scrolling the list or page around it
+    #[default]
+    Chain,
+    /// Stop at the edge, leaving whatever is around the list in place
+    Contain,
+}
+
+impl EdgeScroll {
+    /// Value of the `overscroll-behavior` properties for this rule
+    pub fn overscroll_behavior(self) -> &'static str {
+        match self {
+            EdgeScroll::Chain => "auto",
+            EdgeScroll::Contain => "contain",
+        }
+    }
+}
+
+/// An item of a [`VirtualRows`] list, with the stable key its row and
+/// measured height follow
+pub trait Keyed {
+    fn key(&self) -> String;
+}
+
+// Rows of a feed follow their item's id
+impl Keyed for VirtualFeedItem {
+    fn key(&self) -> String {
+        self.id.clone()
+    }
+}
+
+/// A row of a [`VirtualRows`] list, as its `render_row` prop receives it
+#[derive(Clone, PartialEq)]
+pub struct Row<T: 'static> {
+    pub item: T,
+    /// Position of the item in the list's items
+    pub index: usize,
+    /// Height the row is laid out at
+    pub height: f64,
+    /// Distance in pixels between the row and the viewport, 0 when they overlap
+    pub viewport_distance: f64,
+    /// Reports the row's height, for rows that observe their own content
+    /// instead of being measured by the list (see `measure_rows`)
+    pub on_measure: EventHandler<f64>,
+}
+
+/// Where the viewport of a [`VirtualRows`] list is, passed to `on_scroll`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport {
+    pub scroll_top: f64,
+    pub scroll_height: f64,
+    pub client_height: f64,
+    /// -1 up, 1 down, 0 none. The list's own scrolls keep the direction the
+    /// user was last heading in.
+    pub direction: i8,
+    /// Whether the list scrolled itself, e.g. for a jump or to keep its
+    /// rows in place, rather than the user
+    pub own_scroll: bool,
+}
+
+#[derive(Props, Clone, PartialEq)]
+pub struct VirtualRowsProps<T: Keyed + Clone + PartialEq + 'static> {
+    // Items in display order, e.g. a signal the caller appends to
+    pub items: ReadSignal<im::Vector<T>>,
+    // Renders a row; called only for rows around the viewport
+    pub render_row: Callback<Row<T>, Element>,
+    // Height of the row at an index until it is measured, when rows differ
+    // in size; rows it gives none for are laid out at `row_height`
+    pub estimate_height: Option<Callback<usize, Option<f64>>>,
+    #[props(default = DEFAULT_ROW_HEIGHT)]
+    pub row_height: f64,
+    // Measure each row as it renders. Rows that observe their own content
+    // report through `Row::on_measure` instead.
+    #[props(default = true)]
+    pub measure_rows: bool,
+    // Heights rows measured at, by key, when the caller keeps them, e.g. to
+    // cache them; the list keeps its own otherwise
+    pub measured: Option<Signal<HashMap<String, f64>>>,
+    #[props(default = DEFAULT_HEIGHT)]
+    pub height: f64,
+    #[props(default = DEFAULT_BUFFER)]
+    pub buffer: usize,
+    // First and last index of rows kept rendered while they are out of
+    // view, e.g. those a text selection spans, unless that renders too many
+    #[props(default)]
+    pub keep_rendered: Option<(usize, usize)>,
+    // How rows are positioned; both modes share the same range math
+    #[props(default)]
+    pub layout_mode: LayoutMode,
+    // Sequence of the first item. Slots and the divider follow sequences, so
+    // they keep their places relative to the rows as items are added above.
+    #[props(default)]
+    pub first_seq: Sequence,
+    // Content rendered in a slot after every `interleave_every` rows, called
+    // with the sequence of the row above. Slots reserve `interleave_height`
+    // even when it returns `None`, so row positions never depend on what a
+    // slot renders.
+    pub interleave: Option<Callback<Sequence, Option<Element>>>,
+    #[props(default = DEFAULT_INTERLEAVE_EVERY)]
+    pub interleave_every: usize,
+    #[props(default = DEFAULT_INTERLEAVE_HEIGHT)]
+    pub interleave_height: f64,
+    // Row the divider is rendered below, after any slot below that row
+    #[props(default)]
+    pub divider_after: Option<Sequence>,
+    pub divider: Option<Element>,
+    #[props(default = DEFAULT_DIVIDER_HEIGHT)]
+    pub divider_height: f64,
+    // Fold the rows up to the divider away, rendering `folded` in the
+    // divider's place. Scroll requests for a folded row call `on_unfold`.
+    #[props(default)]
+    pub fold_above_divider: bool,
+    pub folded: Option<Element>,
+    pub on_unfold: Option<EventHandler<()>>,
+    // Open at the end and keep it in view as items arrive while the list is
+    // scrolled to it, as chats and logs do
+    #[props(default)]
+    pub follow_end: bool,
+    // Called as the user scrolls toward the start or end within reach of
+    // it, e.g. to load more items; the list's own scrolls don't call them
+    pub on_reach_start: Option<EventHandler<()>>,
+    pub on_reach_end: Option<EventHandler<()>>,
+    // Called for every scroll event, the list's own included
+    pub on_scroll: Option<EventHandler<Viewport>>,
+    // Called with the indices of the rows in the viewport as they change
+    pub on_rows_in_view: Option<EventHandler<Range<usize>>>,
+    // Called with the scroll offset the list moved to to keep its rows in
+    // place
+    pub on_keep_in_place: Option<EventHandler<f64>>,
+    // Called with the list's width as it resizes
+    pub on_resize: Option<EventHandler<f64>>,
+    // Called with the list's element once it is mounted
+    pub on_mounted: Option<EventHandler<Rc<MountedData>>>,
+    // Handle for scrolling the list from outside, with items addressed by
+    // key; the list creates its own when none is given
+    pub controller: Option<VirtualListController>,
+    // Scroll container outside the list to attach to, such as the page or the
+    // window; the list then grows to its full height inside it and takes its
+    // viewport from the parent's scrolls instead of scrolling itself
+    pub scroll_parent: Option<ScrollParent>,
+    // Whether scrolls past the list's ends carry on to what is around it
+    #[props(default)]
+    pub edge_scroll: EdgeScroll,
+    // Scroll offset and viewport height, when the caller keeps them so they
+    // survive remounts; the list keeps its own otherwise
+    pub scroll_top: Option<Signal<f64>>,
+    pub viewport_height: Option<Signal<f64>>,
+    // Rendered above and below the rows inside the scroll box, e.g.
+    // toolbars, banners and loading indicators
+    pub header: Option<Element>,
+    pub footer: Option<Element>,
+}
+
+// Vertically scrolling list over any item type, rendering each row around
+// the viewport through `render_row`
+#[component]
+pub fn VirtualRows<T: Keyed + Clone + PartialEq + 'static>(props: VirtualRowsProps<T>) -> Element {
+    let items = props.items;
+    let estimate_height = props.estimate_height;
+    let measure_rows = props.measure_rows;
+    let height = props.height;
+    let follow_end = props.follow_end;
+    let on_unfold = props.on_unfold;
+    let on_reach_start = props.on_reach_start;
+    let on_reach_end = props.on_reach_end;
+    let on_scroll = props.on_scroll;
+    let on_rows_in_view = props.on_rows_in_view;
+    let on_keep_in_place = props.on_keep_in_place;
+    let on_resize = props.on_resize;
+    let on_mounted = props.on_mounted;
+    let scroll_parent = props.scroll_parent;
+    let clock = use_clock();
+    let clock = use_hook(|| CopyValue::new(clock));
+    let own_controller = use_virtual_list_controller();
+    let mut controller = props.controller.unwrap_or(own_controller);
+
+    // Heights rows measured at, by key; they win over the estimates
+    let own_measured = use_signal(HashMap::<String, f64>::new);
+    let mut measured = props.measured.unwrap_or(own_measured);
+    let row_heights = use_memo(move || {
+        let items = items.read();
+        let measured = measured.read();
+        RowHeights::new(items.iter().enumerate().map(|(index, item)| {
+            measured
+                .get(&item.key())
+                .copied()
+                .or_else(|| estimate_height.and_then(|estimate| estimate.call(index)))
+        }))
+    });
+
+    let layout = ListLayout {
+        row_height: props.row_height,
+        heights: Some(row_heights),
+        first_seq: props.first_seq,
+        slot_every: props.interleave.map(|_| props.interleave_every as i64).filter(|every| *every > 0),
+        slot_height: props.interleave_height,
+        divider_after: props.divider_after,
+        divider_height: props.divider_height,
+        collapsed_through: props.divider_after.filter(|_| props.fold_above_divider),
+    };
+    // Effects and handlers are kept from the first render, so they read the
+    // layout through a value updated on every render
+    let mut current_layout = use_hook(|| CopyValue::new(layout));
+    current_layout.set(layout);
+
+    let own_scroll_top = use_signal(|| 0.0);
+    let mut scroll_top = props.scroll_top.unwrap_or(own_scroll_top);
+    let own_viewport_height = use_signal(|| height);
+    let mut client_height = props.viewport_height.unwrap_or(own_viewport_height);
+    use_hook(move || {
+        if *client_height.peek() == 0.0 {
+            client_height.set(height);
+        }
+    });
+    let mut scroll_height = use_hook(|| CopyValue::new(0.0));
+    let mut last_scroll_top = use_hook(|| CopyValue::new(*scroll_top.peek()));
+    let mut direction = use_hook(|| CopyValue::new(0i8));
+    // Row at the top of the viewport, by key, and how far below its top the
+    // viewport starts; kept there as the layout changes
+    let mut anchor = use_hook(|| CopyValue::new(None::<(String, f64)>));
+    // Whether the list is scrolled to its end, which it then follows
+    let mut at_end = use_hook(|| CopyValue::new(follow_end));
+    let mut laid_out_width = use_hook(|| CopyValue::new(None::<f64>));
+
+    // Scroll element reference, and the id the scroll fallback finds it by
+    let mut element = use_signal(|| None::<Rc<MountedData>>);
+    let list_id = use_hook(|| NEXT_LIST_ID.fetch_add(1, Ordering::Relaxed));
+
+    // Index of the first item with each key, for the anchors and scrolls
+    // that address items by key
+    let key_index = use_memo(move || {
+        let items = items.read();
+        let mut index_by_key = HashMap::with_capacity(items.len());
+        for (index, item) in items.iter().enumerate() {
+            index_by_key.entry(item.key()).or_insert(index);
+        }
+        index_by_key
+    });
+    let index_of = move |key: &str| key_index.read().get(key).copied();
+    // Row at `top` in `layout`, as its key and how far below its top `top` is
+    let anchor_in = move |layout: &ListLayout, top: f64| {
+        let items = items.peek();
+        if items.is_empty() {
+            return None;
+        }
+        let (seq, within) = layout.anchor_at(top, items.len());
+        Some((items.get(layout.index_of(seq))?.key(), within))
+    };
+    // The same as the controller shares it, with the share of the row above
+    let scroll_anchor_at = move |top: f64| {
+        let layout = *current_layout.peek();
+        let items = items.peek();
+        if items.is_empty() {
+            return None;
+        }
+        let seq = layout.sequence_at(top, items.len());
+        let item_id = items.get(layout.index_of(seq))?.key();
+        let offset_fraction = ((top - layout.offset(seq)) / layout.height_of(seq)).clamp(0.0, 1.0);
+        Some(ScrollAnchor { item_id, offset_fraction })
+    };
+
+    // With a scroll parent, where the list starts in the parent's content.
+    // It is measured when the list mounts or resizes, so content above the
+    // list changing height on its own isn't followed until then.
+    let mut parent_offset = use_hook(|| CopyValue::new(0.0));
+    let measure_parent_offset = move || {
+        let Some(parent) = scroll_parent else {
+            return;
+        };
+        spawn(async move {
+            let selector = format!("[data-list-id=\"{}\"]", list_id);
+            if let Some(offset) = parent.offset_of(&selector).await {
+                parent_offset.set(offset);
+            }
+        });
+    };
+
+    // Scroll the list from code, after `delay_ms` when the layout needs to
+    // settle first. Its scroll events are tagged as the list's own, and the
+    // edges aren't reported as reached until it has settled, so landing
+    // near one doesn't start a load.
+    let mut scroll_list = move |top: f64, behavior: ScrollBehavior, delay_ms: u64| {
+        let Some(element) = element.peek().clone() else {
+            return;
+        };
+        let clock = clock.peek().clone();
+        let settle = Duration::from_millis(PROGRAMMATIC_SCROLL_SETTLE_MS);
+        let until = clock.now() + Duration::from_millis(delay_ms) + settle;
+        let from = *scroll_top.peek();
+        controller.begin_own_scroll(from, top, until);
+        // Where the list is headed, clamped like the webview will
+        let max_top = *scroll_height.peek() - *client_height.peek();
+        let target = if max_top > 0.0 { top.clamp(0.0, max_top) } else { top.max(0.0) };
+        controller.set_offset(target);
+        anchor.set(anchor_in(&current_layout.peek(), top.max(0.0)));
+        // Instant scrolls take the rendered rows there at once; smooth ones
+        // follow their scroll events
+        if matches!(behavior, ScrollBehavior::Instant) {
+            scroll_top.set(top.max(0.0));
+        }
+        spawn(async move {
+            if delay_ms > 0 {
+                clock.sleep(Duration::from_millis(delay_ms)).await;
+            }
+            let scrolled = match scroll_parent {
+                Some(parent) => parent.scroll_to(top + *parent_offset.peek(), behavior).await,
+                None => {
+                    let selector = format!("[data-list-id=\"{}\"]", list_id);
+                    scroll_with_fallback(element, &selector, top, behavior).await
+                }
+            };
+            // The list stayed put, unless the user scrolled meanwhile
+            if !scrolled && controller.scroll_offset() == target {
+                controller.set_offset(from);
+            }
+            controller.hold_triggers_until(clock.now() + settle);
+        });
+    };
+
+    // Keep the row at the top of the viewport in place when items are added
+    // above it, rows above it change height or fold away, or follow the end
+    // while the list is scrolled to it
+    use_effect(use_reactive!(|layout| {
+        let count = items.read().len();
+        let target = if follow_end && *at_end.peek() {
+            Some(layout.end_top(count, *client_height.peek()))
+        } else {
+            let kept = anchor.peek().clone();
+            kept.and_then(|(key, within)| {
+                let seq = layout.sequence_of(index_of(&key)?);
+                // Rows folded away leave the viewport at the top
+                Some(if seq < layout.first_shown() { 0.0 } else { layout.offset(seq) + within })
+            })
+        };
+        if let Some(target) = target {
+            if (target - *scroll_top.peek()).abs() >= HEIGHT_TOLERANCE {
+                scroll_list(target, ScrollBehavior::Instant, 0);
+                if let Some(handler) = on_keep_in_place {
+                    handler.call(target);
+                }
+            }
+        }
+        // Forget rows long gone, without pruning on every change
+        if measured.peek().len() > count * 2 {
+            let keys = key_index.read();
+            measured.write().retain(|key, _| keys.contains_key(key));
+        }
+    }));
+
+    // Keep the controller's anchor on the row at the top of the viewport
+    use_effect(move || {
+        let top = scroll_top();
+        let _ = items.read();
+        controller.set_anchor(scroll_anchor_at(top));
+    });
+
+    // Carry out scrolls requested through the controller
+    use_effect(move || {
+        let Some((request, remember_origin)) = controller.take_request() else {
+            return;
+        };
+        // Offset of an anchor's position in `layout`, once its item is loaded
+        let anchor_offset = move |layout: &mut ListLayout, anchor: &ScrollAnchor| {
+            let seq = layout.sequence_of(index_of(&anchor.item_id)?);
+            // Rows folded away are unfolded to scroll to them
+            if seq < layout.first_shown() {
+                if let Some(handler) = on_unfold {
+                    handler.call(());
+                }
+                layout.collapsed_through = None;
+            }
+            Some(layout.offset(seq) + anchor.offset_fraction.clamp(0.0, 1.0) * layout.height_of(seq))
+        };
+        // Restored positions are taken up directly, jumps are animated
+        let behavior = match request {
+            ScrollRequest::Anchor(_) | ScrollRequest::Offset(_) => ScrollBehavior::Instant,
+            _ => ScrollBehavior::Smooth,
+        };
+        let mut layout = *current_layout.peek();
+        let top = *scroll_top.peek();
+        let target = match request {
+            ScrollRequest::Top => Some(0.0),
+            ScrollRequest::Offset(offset) => Some(offset),
+            ScrollRequest::Item(key) => anchor_offset(&mut layout, &ScrollAnchor::item(key)),
+            ScrollRequest::Anchor(anchor) => anchor_offset(&mut layout, &anchor),
+            ScrollRequest::Step(rows) => {
+                let count = items.peek().len();
+                (count > 0).then(|| {
+                    // Half a pixel in, so a row scrolled exactly to the top counts as current
+                    let current = layout.sequence_at(top + 0.5, count);
+                    let last = layout.first_seq + count as i64 - 1;
+                    layout.offset((current + rows).clamp(layout.first_shown(), last))
+                })
+            }
+        };
+        let Some(target) = target else {
+            return;
+        };
+
+        // Only jumps further than a screen are worth returning from
+        if remember_origin && (target - top).abs() > *client_height.peek() {
+            controller.return_to.set(scroll_anchor_at(top));
+        }
+        at_end.set(false);
+        scroll_list(target, behavior, 0);
+        // Anchored in the layout the target was found in, with any folded
+        // rows shown
+        anchor.set(anchor_in(&layout, target));
+    });
+
+    // Offer to return for a limited time only
+    use_effect(move || {
+        let Some(origin) = (controller.return_to)() else {
+            return;
+        };
+        let clock = clock.peek().clone();
+        spawn(async move {
+            clock.sleep(Duration::from_millis(RETURN_OFFER_MS)).await;
+            if *controller.return_to.peek() == Some(origin) {
+                controller.dismiss_return();
+            }
+        });
+    });
+
+    // Lay a row out at the height it measured, when that differs from the
+    // one it was laid out at; the anchor keeps the rows in view in place
+    let mut remeasure = move |key: String, laid_out: f64, height: f64| {
+        // Rows the webview skips while offscreen report nothing useful
+        if height <= 0.0 || (height - laid_out).abs() < HEIGHT_TOLERANCE {
+            return;
+        }
+        if measured.peek().get(&key).is_some_and(|known| (known - height).abs() < HEIGHT_TOLERANCE) {
+            return;
+        }
+        measured.write().insert(key, height);
+    };
+
+    // Follow the viewport to a new position, from the list's scroll events
+    // or its scroll parent's
+    let mut update_viewport = move |top: f64, total: f64, viewport: f64| {
+        let now = clock.peek().now();
+        // Determine scroll direction from the user's scrolls only
+        let own_scroll = controller.is_own_scroll_event(top, now);
+        let previous = *last_scroll_top.peek();
+        let heading = if own_scroll {
+            *direction.peek()
+        } else if top > previous {
+            1
+        } else if top < previous {
+            -1
+        } else {
+            0
+        };
+
+        controller.set_offset(top);
+        scroll_top.set(top);
+        scroll_height.set(total);
+        client_height.set(viewport);
+        last_scroll_top.set(top);
+        direction.set(heading);
+        let layout = *current_layout.peek();
+        anchor.set(anchor_in(&layout, top));
+        at_end.set(top + viewport >= layout.total_height(items.peek().len()) - HEIGHT_TOLERANCE);
+        if let Some(handler) = on_scroll {
+            handler.call(Viewport {
+                scroll_top: top,
+                scroll_height: total,
+                client_height: viewport,
+                direction: heading,
+                own_scroll,
+            });
+        }
+
+        // The list's own scrolls don't reach an end; landing near one after
+        // restoring a position would start a load, and that load another restore
+        let user_scroll = !own_scroll && !controller.is_programmatic_scroll(now);
+        if user_scroll && heading == -1 && top <= EDGE_THRESHOLD {
+            if let Some(handler) = on_reach_start {
+                handler.call(());
+            }
+        }
+        if user_scroll && heading == 1 && total - top - viewport <= EDGE_THRESHOLD {
+            if let Some(handler) = on_reach_end {
+                handler.call(());
+            }
+        }
+    };
+
+    // Attached to a scroll parent, the viewport is the part of the parent's
+    // that the list spans, as the list's own scroll position
+    let mut applied_metrics = use_hook(|| CopyValue::new(None::<ScrollMetrics>));
+    use_effect(move || {
+        let Some(metrics) = scroll_parent.and_then(|parent| parent.metrics()) else {
+            return;
+        };
+        // Reads in the update don't run it again for the same scroll
+        if *applied_metrics.peek() == Some(metrics) {
+            return;
+        }
+        applied_metrics.set(Some(metrics));
+        let offset = *parent_offset.peek();
+        update_viewport(
+            (metrics.scroll_top - offset).max(0.0),
+            metrics.scroll_height - offset,
+            metrics.client_height,
+        );
+    });
+
+    // Rows around the viewport, in sequences so each row's offset follows
+    // its item rather than its index
+    let count = items.read().len();
+    let top = scroll_top();
+    let viewport = client_height();
+    let rendered = rendered_range(&layout, count, top, viewport, props.buffer, props.keep_rendered);
+    let in_view = layout.in_view(count, top, viewport);
+    use_effect(use_reactive!(|in_view| {
+        if let Some(handler) = on_rows_in_view {
+            handler.call(in_view);
+        }
+    }));
+    let rows: Vec<(usize, Sequence, String, T)> = {
+        let items = items.read();
+        rendered
+            .clone()
+            .filter_map(|index| {
+                let item = items.get(index)?;
+                Some((index, layout.sequence_of(index), item.key(), item.clone()))
+            })
+            .collect()
+    };
+
+    // Spacers standing in for the rows outside the range when rows flow; the
+    // folded rows keep their place at the top
+    let in_flow = props.layout_mode == LayoutMode::Spacers;
+    let total_height = layout.total_height(count);
+    let folded = layout.first_shown() > layout.first_seq && count > 0;
+    let folded_height = if folded { layout.divider_height } else { 0.0 };
+    let top_spacer = (layout.offset(layout.sequence_of(rendered.start)) - folded_height).max(0.0);
+    let bottom_spacer = (total_height - layout.offset(layout.sequence_of(rendered.end))).max(0.0);
+
+    // The list is a scroll box of its own unless a parent scrolls it
+    let scroll_box_style = if scroll_parent.is_some() {
+        String::new()
+    } else {
+        format!(
+            "height: {}px; overflow-y: auto; scroll-behavior: smooth; overscroll-behavior-y: {};",
+            height,
+            props.edge_scroll.overscroll_behavior()
+        )
+    };
+
+    rsx! {
+        div {
+            style: "{scroll_box_style} position: relative;",
+            "data-list-id": "{list_id}",
+            // Focusable, so the keyboard scrolls it and shortcuts reach the
+            // component around it
+            tabindex: "0",
+            onmounted: move |evt: Event<MountedData>| {
+                let data = evt.data();
+                element.set(Some(data.clone()));
+                measure_parent_offset();
+                if let Some(handler) = on_mounted {
+                    handler.call(data);
+                }
+
+                // Restore the position the list was left at, once its place
+                // in a scroll parent is known, or open at the end
+                let target = if follow_end {
+                    current_layout.peek().end_top(items.peek().len(), *client_height.peek())
+                } else {
+                    *scroll_top.peek()
+                };
+                if target > 0.0 {
+                    let delay_ms = if scroll_parent.is_some() { SCROLL_RESTORE_DELAY_MS } else { 0 };
+                    scroll_list(target, ScrollBehavior::Instant, delay_ms);
+                }
+            },
+            onscroll: move |evt: Event<ScrollData>| {
+                // Scrolling this list doesn't move a list it is nested in
+                evt.stop_propagation();
+                let data = evt.data();
+                update_viewport(data.scroll_top() as f64, data.scroll_height() as f64, data.client_height() as f64);
+            },
+            // Heights measured at another width no longer hold: the rendered
+            // rows report their new ones, and the anchor keeps the row at the
+            // top in place meanwhile
+            onresize: move |evt: Event<ResizeData>| {
+                measure_parent_offset();
+                let Ok(size) = evt.data().get_content_box_size() else {
+                    return;
+                };
+                let width_changed = laid_out_width
+                    .peek()
+                    .is_some_and(|width| (width - size.width).abs() >= HEIGHT_TOLERANCE);
+                laid_out_width.set(Some(size.width));
+                if width_changed && !measured.peek().is_empty() {
+                    measured.write().clear();
+                }
+                if let Some(handler) = on_resize {
+                    handler.call(size.width);
+                }
+            },
+
+            if let Some(header) = props.header.clone() {
+                {header}
+            }
+
+            div {
+                style: if in_flow {
+                    "position: relative;".to_string()
+                } else {
+                    format!("height: {}px; position: relative;", total_height)
+                },
+
+                // Folded rows, in the divider's place
+                if folded {
+                    if let Some(content) = props.folded.clone() {
+                        div {
+                            style: format!("{} width: 100%; height: {}px;", placement(in_flow, 0.0), layout.divider_height),
+                            {content}
+                        }
+                    } else if in_flow {
+                        div { style: "height: {layout.divider_height}px;" }
+                    }
+                }
+
+                // Space taken by the rows above the rendered ones
+                if in_flow {
+                    div { style: "height: {top_spacer}px;" }
+                }
+
+                // Each rendered row, followed by what sits below it in the
+                // layout's order: its slot, then the divider
+                for (index, seq, key, item) in rows {
+                    div {
+                        key: "{key}",
+                        style: format!("{} width: 100%;", placement(in_flow, layout.offset(seq))),
+                        onresize: {
+                            let key = key.clone();
+                            let laid_out = layout.height_of(seq);
+                            move |evt: Event<ResizeData>| {
+                                if !measure_rows {
+                                    return;
+                                }
+                                if let Ok(size) = evt.data().get_border_box_size() {
+                                    remeasure(key.clone(), laid_out, size.height);
+                                }
+                            }
+                        },
+                        {props.render_row.call(Row {
+                            item,
+                            index,
+                            height: layout.height_of(seq),
+                            viewport_distance: layout.viewport_distance(seq, top, viewport),
+                            on_measure: {
+                                let key = key.clone();
+                                let laid_out = layout.height_of(seq);
+                                EventHandler::new(move |height| remeasure(key.clone(), laid_out, height))
+                            },
+                        })}
+                    }
+
+                    // Interleaved slot below the row, when one falls here.
+                    // Flowing rows need the slot's space even when it is empty.
+                    if layout.has_slot_after(seq) {
+                        if let Some(content) = props.interleave.and_then(|interleave| interleave.call(seq)) {
+                            div {
+                                key: "slot-{seq}",
+                                style: format!("
+                                    {}
+                                    width: 100%;
+                                    height: {}px;
+                                    box-sizing: border-box;
+                                    padding-bottom: 16px;
+                                ", placement(in_flow, layout.offset(seq) + layout.height_of(seq)), layout.slot_height),
+                                {content}
+                            }
+                        } else if in_flow {
+                            div { key: "slot-{seq}", style: "height: {layout.slot_height}px;" }
+                        }
+                    }
+
+                    if layout.divider_after == Some(seq) {
+                        if let Some(content) = props.divider.clone() {
+                            div {
+                                style: format!(
+                                    "{} width: 100%; height: {}px;",
+                                    placement(in_flow, layout.divider_offset().unwrap_or_default()),
+                                    layout.divider_height
+                                ),
+                                {content}
+                            }
+                        } else if in_flow {
+                            div { style: "height: {layout.divider_height}px;" }
+                        }
+                    }
+                }
+
+                // Space taken by the rows below the rendered ones
+                if in_flow {
+                    div { style: "height: {bottom_spacer}px;" }
+                }
+            }
+
+            if let Some(footer) = props.footer.clone() {
+                {footer}
+            }
+        }
+    }
+}
+
+// Indices of the rows rendered for a viewport `height` high at `top`: those
+// in it and `buffer` more on either side, after any folded rows, extended to
+// the `keep` rows unless that renders too many
+fn rendered_range(
+    layout: &ListLayout,
+    count: usize,
+    top: f64,
+    height: f64,
+    buffer: usize,
+    keep: Option<(usize, usize)>,
+) -> Range<usize> {
+    if count == 0 {
+        return 0..0;
+    }
+    let hidden = layout.index_of(layout.first_shown()).min(count);
+    let start = layout.index_of(layout.sequence_at(top, count)).saturating_sub(buffer).max(hidden);
+    let end = (layout.index_of(layout.sequence_at(top + height, count)) + 1 + buffer)
+        .min(count)
+        .max(start);
+    match keep {
+        Some((first, last)) => {
+            let kept = start.min(first.max(hidden))..end.max(last + 1).min(count);
+            if kept.len() <= MAX_KEEP_RENDERED {
+                kept
+            } else {
+                start..end
+            }
+        }
+        None => start..end,
+    }
+}
+
+// Vertical layout of rows, interleaved slots and the divider. Slots follow
+// rows whose sequence + 1 is a multiple of `slot_every`, so they keep their
+// place relative to the rows when items are added above.
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct ListLayout {
+    // Height of rows without a height of their own
+    row_height: f64,
+    // Measured and estimated heights of the loaded rows
+    heights: Option<Memo<RowHeights>>,
+    first_seq: Sequence,
+    slot_every: Option<i64>,
+    slot_height: f64,
+    // Row the divider follows, below any slot after that row
+    divider_after: Option<Sequence>,
+    divider_height: f64,
+    // Rows up to and including this one are folded away; the divider's
+    // space at the top holds what shows them
+    collapsed_through: Option<Sequence>,
+}
+
+impl ListLayout {
+    // Offset of the row with sequence `seq`
+    fn offset(&self, seq: Sequence) -> f64 {
+        let divider = match self.divider_after {
+            Some(after) if seq > after => self.divider_height,
+            _ => 0.0,
+        };
+        self.rows_height(self.first_shown(), seq) + self.slots_before(seq) as f64 * self.slot_height + divider
+    }
+
+    // Height of the rows from `from` up to, not including, `to`; negative
+    // when `to` comes first
+    fn rows_height(&self, from: Sequence, to: Sequence) -> f64 {
+        let (from_index, to_index) = (from - self.first_seq, to - self.first_seq);
+        match self.heights {
+            Some(heights) => heights.read().height(from_index, to_index, self.row_height),
+            None => (to - from) as f64 * self.row_height,
+        }
+    }
+
+    // Height of the row with sequence `seq`
+    fn height_of(&self, seq: Sequence) -> f64 {
+        self.rows_height(seq, seq + 1)
+    }
+
+    // Sequence of the first row laid out, after any folded rows
+    fn first_shown(&self) -> Sequence {
+        self.collapsed_through.map_or(self.first_seq, |through| through + 1)
+    }
+
+    // Offset of the divider, directly above the row after it
+    fn divider_offset(&self) -> Option<f64> {
+        self.divider_after.map(|after| self.offset(after + 1) - self.divider_height)
+    }
+
+    // Number of slots between the first row and the row with sequence `seq`
+    fn slots_before(&self, seq: Sequence) -> i64 {
+        self.slot_every
+            .map_or(0, |every| seq.div_euclid(every) - self.first_shown().div_euclid(every))
+    }
+
+    fn has_slot_after(&self, seq: Sequence) -> bool {
+        self.slot_every.is_some_and(|every| (seq + 1).rem_euclid(every) == 0)
+    }
+
+    // Height of `count` rows and the slots among them
+    fn total_height(&self, count: usize) -> f64 {
+        self.offset(self.first_seq + count as i64)
+    }
+
+    // Scroll offset showing the end of `count` rows in a viewport `height` high
+    fn end_top(&self, count: usize, height: f64) -> f64 {
+        (self.total_height(count) - height).max(0.0)
+    }
+
+    // Distance in pixels between the row with sequence `seq` and the
+    // viewport, 0 when they overlap
+    fn viewport_distance(&self, seq: Sequence, viewport_top: f64, viewport_height: f64) -> f64 {
+        let top = self.offset(seq);
+        (viewport_top - (top + self.height_of(seq))).max(top - (viewport_top + viewport_height)).max(0.0)
+    }
+
+    // Sequence of the last row starting at or above `y`, among `count` rows
+    fn sequence_at(&self, y: f64, count: usize) -> Sequence {
+        let (mut low, mut high) = (self.first_shown(), self.first_seq + count as i64);
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            if self.offset(mid) <= y {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+
+    // The row at `y` among `count` rows, and how far below its top `y` is
+    fn anchor_at(&self, y: f64, count: usize) -> (Sequence, f64) {
+        let seq = self.sequence_at(y, count);
+        (seq, y - self.offset(seq))
+    }
+
+    // Indices of the rows at least partly in a viewport `height` high at `top`
+    fn in_view(&self, count: usize, top: f64, height: f64) -> Range<usize> {
+        if count == 0 {
+            return 0..0;
+        }
+        let first = self.index_of(self.sequence_at(top, count));
+        let last = self.index_of(self.sequence_at(top + height - 1.0, count));
+        first.min(count)..(last + 1).min(count)
+    }
+
+    fn sequence_of(&self, index: usize) -> Sequence {
+        self.first_seq + index as i64
+    }
+
+    // Index of the row with sequence `seq`, 0 for rows before the first
+    fn index_of(&self, seq: Sequence) -> usize {
+        (seq - self.first_seq).max(0) as usize
+    }
+}
+
+// Heights of the loaded rows that have one, measured or estimated, summed
+// up in order so offsets stay a lookup. Rows without one, or not loaded,
+// are laid out at the row height.
+#[derive(Clone, PartialEq, Debug)]
+struct RowHeights {
+    // Sum of the known heights of the rows before each loaded row, then of all
+    known_before: Vec<f64>,
+    // Number of rows with a known height before each loaded row, then of all
+    counted_before: Vec<usize>,
+}
+
+impl RowHeights {
+    fn new(heights: impl Iterator<Item = Option<f64>>) -> Self {
+        let mut known_before = vec![0.0];
+        let mut counted_before = vec![0];
+        let (mut sum, mut counted) = (0.0, 0);
+        for height in heights {
+            if let Some(height) = height {
+                sum += height.max(0.0);
+                counted += 1;
+            }
+            known_before.push(sum);
+            counted_before.push(counted);
+        }
+        Self { known_before, counted_before }
+    }
+
+    // Height of the rows from index `from` up to, not including, `to`, with
+    // `row_height` for each row without one; negative when `to` comes first
+    fn height(&self, from: i64, to: i64, row_height: f64) -> f64 {
+        let before = |index: i64| {
+            let index = index.clamp(0, self.known_before.len() as i64 - 1) as usize;
+            (self.known_before[index], self.counted_before[index] as i64)
+        };
+        let ((known_from, counted_from), (known_to, counted_to)) = (before(from), before(to));
+        let unknown = (to - from) - (counted_to - counted_from);
+        known_to - known_from + unknown as f64 * row_height
+    }
+}
+
+// Positioning of an element in the content container: absolute at `top`,
+// or in normal flow between the spacers
+fn placement(in_flow: bool, top: f64) -> String {
+    if in_flow {
+        "position: relative;".to_string()
+    } else {
+        format!("position: absolute; top: {}px;", top)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(row_height: f64) -> ListLayout {
+        ListLayout {
+            row_height,
+            heights: None,
+            first_seq: 0,
+            slot_every: None,
+            slot_height: 0.0,
+            divider_after: None,
+            divider_height: DEFAULT_DIVIDER_HEIGHT,
+            collapsed_through: None,
+        }
+    }
+
+    #[test]
+    fn row_heights_fill_in_unknown_rows() {
+        let heights = RowHeights::new([Some(100.0), None, Some(20.0), None].into_iter());
+        assert_eq!(heights.height(0, 4, 50.0), 220.0);
+        assert_eq!(heights.height(1, 3, 50.0), 70.0);
+        assert_eq!(heights.height(3, 1, 50.0), -70.0);
+        // Rows past the loaded ones are laid out at the row height
+        assert_eq!(heights.height(2, 6, 50.0), 170.0);
+        assert_eq!(heights.height(-2, 1, 50.0), 200.0);
+    }
+
+    #[test]
+    fn slots_and_divider_push_later_rows_down() {
+        let layout = ListLayout {
+            slot_every: Some(2),
+            slot_height: 30.0,
+            divider_after: Some(2),
+            ..layout(100.0)
+        };
+        assert_eq!(layout.offset(1), 100.0);
+        // A slot follows rows 1 and 3, the divider row 2
+        assert_eq!(layout.offset(2), 230.0);
+        assert_eq!(layout.offset(3), 330.0 + DEFAULT_DIVIDER_HEIGHT);
+        assert_eq!(layout.divider_offset(), Some(330.0));
+        assert_eq!(layout.total_height(4), 460.0 + DEFAULT_DIVIDER_HEIGHT);
+    }
+
+    #[test]
+    fn folded_rows_take_no_space() {
+        let layout = ListLayout {
+            divider_after: Some(2),
+            collapsed_through: Some(2),
+            ..layout(100.0)
+        };
+        assert_eq!(layout.first_shown(), 3);
+        assert_eq!(layout.offset(3), DEFAULT_DIVIDER_HEIGHT);
+        assert_eq!(layout.divider_offset(), Some(0.0));
+        assert_eq!(layout.sequence_at(0.0, 5), 3);
+        assert_eq!(rendered_range(&layout, 5, 0.0, 100.0, 5, None), 3..5);
+    }
+
+    #[test]
+    fn sequence_at_finds_the_row_under_an_offset() {
+        let layout = layout(100.0);
+        assert_eq!(layout.sequence_at(0.0, 10), 0);
+        assert_eq!(layout.sequence_at(99.0, 10), 0);
+        assert_eq!(layout.sequence_at(100.0, 10), 1);
+        // Clamped to the rows there are
+        assert_eq!(layout.sequence_at(5000.0, 10), 9);
+        assert_eq!(layout.anchor_at(250.0, 10), (2, 50.0));
+    }
+
+    #[test]
+    fn anchor_survives_rows_added_above() {
+        let before = layout(100.0);
+        let (seq, within) = before.anchor_at(250.0, 10);
+        // Five older rows arrive; sequences stay with their items
+        let after = ListLayout { first_seq: -5, ..before };
+        assert_eq!(after.offset(seq) + within, 750.0);
+    }
+
+    #[test]
+    fn rendered_range_buffers_the_viewport() {
+        let layout = layout(100.0);
+        assert_eq!(rendered_range(&layout, 100, 1000.0, 300.0, 2, None), 8..16);
+        assert_eq!(rendered_range(&layout, 100, 0.0, 300.0, 2, None), 0..6);
+        assert_eq!(rendered_range(&layout, 0, 0.0, 300.0, 2, None), 0..0);
+        assert_eq!(layout.in_view(100, 1000.0, 300.0), 10..13);
+    }
+
+    #[test]
+    fn kept_rows_stay_rendered_within_a_limit() {
+        let layout = layout(100.0);
+        assert_eq!(rendered_range(&layout, 1000, 1000.0, 300.0, 2, Some((3, 4))), 3..16);
+        assert_eq!(rendered_range(&layout, 1000, 1000.0, 300.0, 2, Some((20, 900))), 8..16);
+    }
+}
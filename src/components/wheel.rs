@@ -0,0 +1,75 @@
+//! Normalizing wheel and trackpad input.
+//!
+//! Platforms report wheel deltas in lines, pages or pixels, and even pixel
+//! deltas differ several times over between them for the same gesture.
+//! [`wheel_pixels`] turns a delta into pixels on a common scale, and
+//! [`WheelVelocity`] estimates how fast the user is scrolling from those, so
+//! fling detection means the same on every platform.
+
+use dioxus::html::geometry::WheelDelta;
+
+use crate::clock::Instant;
+
+/// Pixels per line for deltas given in lines
+pub const LINE_HEIGHT: f64 = 16.0;
+
+/// Largest delta a single event may contribute, so a burst from a
+/// misbehaving driver doesn't read as a fling
+pub const MAX_EVENT_DELTA: f64 = 400.0;
+
+// Wheel velocity configuration
+const SMOOTHING: f64 = 0.5; // Weight of the newest event in the velocity
+const GAP_MS: u64 = 100; // Time without wheel events after which a gesture has ended
+const MIN_INTERVAL_MS: f64 = 4.0; // Shortest interval counted between events, so bursts stay finite
+
+/// Scale of the platform's pixel deltas relative to the common scale. The
+/// webviews on Windows and Linux report a notch of a mouse wheel as about
+/// twice the distance macOS reports for a comparable trackpad swipe.
+#[cfg(target_os = "macos")]
+pub const PLATFORM_FACTOR: f64 = 1.0;
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+pub const PLATFORM_FACTOR: f64 = 0.5;
+#[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+pub const PLATFORM_FACTOR: f64 = 1.0;
+
+/// Horizontal and vertical distance of a wheel event in pixels on the common
+/// scale. Page deltas count as `page_size` pixels each.
+pub fn wheel_pixels(delta: WheelDelta, page_size: f64) -> (f64, f64) {
+    let (x, y) = match delta {
+        WheelDelta::Pixels(pixels) => (pixels.x * PLATFORM_FACTOR, pixels.y * PLATFORM_FACTOR),
+        WheelDelta::Lines(lines) => (lines.x * LINE_HEIGHT, lines.y * LINE_HEIGHT),
+        WheelDelta::Pages(pages) => (pages.x * page_size, pages.y * page_size),
+    };
+    (
+        x.clamp(-MAX_EVENT_DELTA, MAX_EVENT_DELTA),
+        y.clamp(-MAX_EVENT_DELTA, MAX_EVENT_DELTA),
+    )
+}
+
+/// Speed of the current wheel or trackpad gesture, smoothed over its events
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct WheelVelocity {
+    // When the last event came and the velocity after it, in px/ms
+    last: Option<(Instant, f64)>,
+}
+
+impl WheelVelocity {
+    /// Record a vertical delta in normalized pixels (see [`wheel_pixels`])
+    pub fn record(&mut self, now: Instant, delta: f64) {
+        let velocity = match self.last {
+            Some((at, velocity)) if now.saturating_duration_since(at).as_millis() < GAP_MS as u128 => {
+                let interval = (now.saturating_duration_since(at).as_secs_f64() * 1000.0).max(MIN_INTERVAL_MS);
+                SMOOTHING * (delta / interval) + (1.0 - SMOOTHING) * velocity
+            }
+            // The first event of a gesture has no interval of its own
+            _ => delta / GAP_MS as f64,
+        };
+        self.last = Some((now, velocity));
+    }
+
+    /// Velocity in px/ms, negative upward, while a gesture is under way
+    pub fn current(&self, now: Instant) -> Option<f64> {
+        let (at, velocity) = self.last?;
+        (now.saturating_duration_since(at).as_millis() < GAP_MS as u128).then_some(velocity)
+    }
+}
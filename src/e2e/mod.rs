@@ -0,0 +1,265 @@
+//! Scripted end-to-end checks of scroll behaviour.
+//!
+//! Launching the app with `DIOXUS_FEED_E2E=1` mounts [`E2eRunner`], which
+//! drives the first feed list through `eval` (synthetic scrolls and DOM
+//! queries), prints one line per scenario and exits with a non-zero status if
+//! any scenario failed. `tests/scroll_e2e.rs` runs the binary in this mode.
+//! The soak run in [`soak`] checks long polling sessions the same way.
+
+mod scenarios;
+pub mod soak;
+
+use dioxus::prelude::*;
+use serde::Deserialize;
+use std::time::Duration;
+
+use crate::clock::{use_clock, ClockHandle};
+use crate::startup::StartupTrace;
+
+pub use scenarios::{default_scenarios, Scenario, Step};
+
+/// Environment variable enabling the end-to-end mode
+pub const E2E_ENV_VAR: &str = "DIOXUS_FEED_E2E";
+
+/// How often DOM state is re-queried while waiting
+const POLL_INTERVAL_MS: u64 = 50;
+
+/// Whether the app was launched to run the end-to-end scenarios
+pub fn enabled() -> bool {
+    std::env::var(E2E_ENV_VAR).is_ok_and(|value| value == "1")
+}
+
+/// Custom error type for failed scenario steps
+#[derive(Debug, Clone, PartialEq)]
+pub enum E2eError {
+    /// The script could not run or returned unexpected data
+    Eval(String),
+    /// A wait step's condition didn't hold before its deadline
+    Timeout(String),
+    Assertion(String),
+}
+
+impl std::fmt::Display for E2eError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            E2eError::Eval(msg) => write!(f, "Eval failed: {}", msg),
+            E2eError::Timeout(msg) => write!(f, "Timed out waiting for {}", msg),
+            E2eError::Assertion(msg) => write!(f, "Assertion failed: {}", msg),
+        }
+    }
+}
+
+/// Snapshot of the feed list as rendered in the webview
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DomState {
+    pub scroll_top: f64,
+    pub scroll_height: f64,
+    /// Number of rows currently in the DOM
+    pub item_count: usize,
+    /// Id of the first row intersecting the viewport
+    pub anchor_id: Option<String>,
+    /// Offset of that row from the top of the viewport
+    pub anchor_offset: f64,
+    /// Ids of the first and last rendered rows
+    pub first_id: Option<String>,
+    pub last_id: Option<String>,
+}
+
+impl DomState {
+    fn range(&self) -> (Option<&str>, Option<&str>) {
+        (self.first_id.as_deref(), self.last_id.as_deref())
+    }
+}
+
+const QUERY_SCRIPT: &str = r#"
+    const list = document.querySelector('[data-feed-list] [data-list-id]');
+    if (!list) { return null; }
+    const rows = Array.from(list.querySelectorAll('[data-item-id]'))
+        .sort((a, b) => a.getBoundingClientRect().top - b.getBoundingClientRect().top);
+    const top = list.getBoundingClientRect().top;
+    const anchor = rows.find(row => row.getBoundingClientRect().bottom > top);
+    return {
+        scroll_top: list.scrollTop,
+        scroll_height: list.scrollHeight,
+        item_count: rows.length,
+        anchor_id: anchor ? anchor.dataset.itemId : null,
+        anchor_offset: anchor ? anchor.getBoundingClientRect().top - top : 0,
+        first_id: rows.length ? rows[0].dataset.itemId : null,
+        last_id: rows.length ? rows[rows.length - 1].dataset.itemId : null,
+    };
+"#;
+
+/// Read the current state of the feed list
+pub async fn query_dom() -> Result<DomState, E2eError> {
+    document::eval(QUERY_SCRIPT)
+        .join::<Option<DomState>>()
+        .await
+        .map_err(|e| E2eError::Eval(e.to_string()))?
+        .ok_or_else(|| E2eError::Eval("feed list not found".to_string()))
+}
+
+/// Scroll the feed list to `top`, firing a native scroll event
+pub async fn scroll_to(top: f64) -> Result<(), E2eError> {
+    let script = format!(
+        r#"
+        const list = document.querySelector('[data-feed-list] [data-list-id]');
+        if (!list) {{ return false; }}
+        list.scrollTo({{ top: {top}, behavior: 'instant' }});
+        return true;
+        "#
+    );
+    let found = document::eval(&script)
+        .join::<bool>()
+        .await
+        .map_err(|e| E2eError::Eval(e.to_string()))?;
+    if found {
+        Ok(())
+    } else {
+        Err(E2eError::Eval("feed list not found".to_string()))
+    }
+}
+
+// Re-query the DOM until `condition` holds or the timeout passes
+async fn wait_for(
+    clock: &ClockHandle,
+    timeout: Duration,
+    description: &str,
+    condition: impl Fn(&DomState) -> bool,
+) -> Result<DomState, E2eError> {
+    let deadline = clock.now() + timeout;
+    loop {
+        let state = query_dom().await?;
+        if condition(&state) {
+            return Ok(state);
+        }
+        if clock.now() >= deadline {
+            return Err(E2eError::Timeout(description.to_string()));
+        }
+        clock.sleep(Duration::from_millis(POLL_INTERVAL_MS)).await;
+    }
+}
+
+/// Run a scenario's steps in order, stopping at the first failure
+pub async fn run_scenario(scenario: &Scenario, clock: &ClockHandle) -> Result<(), E2eError> {
+    let mut state = wait_for(clock, Duration::from_secs(5), "feed to render", |state| state.item_count > 0).await?;
+    // Anchor captured by `Step::RememberAnchor`
+    let mut remembered: Option<(String, f64)> = None;
+
+    for step in &scenario.steps {
+        match step {
+            Step::ScrollTo(top) => {
+                scroll_to(*top).await?;
+                state = query_dom().await?;
+            }
+            Step::ScrollToBottom => {
+                scroll_to(state.scroll_height).await?;
+                state = query_dom().await?;
+            }
+            Step::Wait(duration) => {
+                clock.sleep(*duration).await;
+                state = query_dom().await?;
+            }
+            Step::WaitForRangeChange(timeout) => {
+                let previous = state.clone();
+                state = wait_for(clock, *timeout, "rendered range to change", |current| {
+                    current.range() != previous.range()
+                })
+                .await?;
+            }
+            Step::WaitForGrowth(timeout) => {
+                let previous_height = state.scroll_height;
+                state = wait_for(clock, *timeout, "feed to grow", |current| {
+                    current.scroll_height > previous_height
+                })
+                .await?;
+            }
+            Step::RememberAnchor => {
+                state = query_dom().await?;
+                let id = state
+                    .anchor_id
+                    .clone()
+                    .ok_or_else(|| E2eError::Assertion("no row in the viewport".to_string()))?;
+                remembered = Some((id, state.anchor_offset));
+            }
+            Step::AssertAnchorKept { tolerance } => {
+                let (id, offset) = remembered
+                    .clone()
+                    .ok_or_else(|| E2eError::Assertion("no anchor remembered".to_string()))?;
+                let position = format!(
+                    r#"
+                    const list = document.querySelector('[data-feed-list] [data-list-id]');
+                    const row = list && list.querySelector('[data-item-id="{id}"]');
+                    if (!row) {{ return null; }}
+                    return row.getBoundingClientRect().top - list.getBoundingClientRect().top;
+                    "#
+                );
+                let current = document::eval(&position)
+                    .join::<Option<f64>>()
+                    .await
+                    .map_err(|e| E2eError::Eval(e.to_string()))?
+                    .ok_or_else(|| E2eError::Assertion(format!("anchor {} is no longer rendered", id)))?;
+                if (current - offset).abs() > *tolerance {
+                    return Err(E2eError::Assertion(format!(
+                        "anchor {} moved from {:.0}px to {:.0}px",
+                        id, offset, current
+                    )));
+                }
+            }
+            Step::AssertItemCount { min, max } => {
+                state = query_dom().await?;
+                if state.item_count < *min || state.item_count > *max {
+                    return Err(E2eError::Assertion(format!(
+                        "expected {}..={} rendered rows, found {}",
+                        min, max, state.item_count
+                    )));
+                }
+            }
+            Step::AssertScrollTop { expected, tolerance } => {
+                state = query_dom().await?;
+                if (state.scroll_top - expected).abs() > *tolerance {
+                    return Err(E2eError::Assertion(format!(
+                        "expected scroll_top {:.0}px, found {:.0}px",
+                        expected, state.scroll_top
+                    )));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Runs the scenarios once the feed has mounted, then exits the app
+#[component]
+pub fn E2eRunner() -> Element {
+    let clock = use_clock();
+    let startup = try_use_context::<StartupTrace>();
+    use_future(move || {
+        let clock = clock.clone();
+        async move {
+            let mut failures = 0;
+            for scenario in default_scenarios() {
+                match run_scenario(&scenario, &clock).await {
+                    Ok(()) => println!("e2e: PASS {}", scenario.name),
+                    Err(e) => {
+                        failures += 1;
+                        println!("e2e: FAIL {}: {}", scenario.name, e);
+                    }
+                }
+            }
+            // The first scenario waited for the feed, so the trace is closed
+            match startup.and_then(|startup| startup.report()) {
+                Some(report) if report.within_budget() => println!("e2e: PASS startup: {}", report),
+                Some(report) => {
+                    failures += 1;
+                    println!("e2e: FAIL startup: {}", report);
+                }
+                None => println!("e2e: SKIP startup: not traced"),
+            }
+            println!("e2e: {} failed", failures);
+            std::process::exit(if failures == 0 { 0 } else { 1 });
+        }
+    });
+
+    rsx! {}
+}
@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+use crate::components::virtual_list::ITEM_HEIGHT;
+
+/// Action or check performed against the feed list
+#[derive(Debug, Clone, PartialEq)]
+pub enum Step {
+    ScrollTo(f64),
+    ScrollToBottom,
+    Wait(Duration),
+    /// Wait until the first or last rendered row changes
+    WaitForRangeChange(Duration),
+    /// Wait until the scrollable height grows, i.e. a page was inserted
+    WaitForGrowth(Duration),
+    /// Remember the row at the top of the viewport and its offset
+    RememberAnchor,
+    /// Check the remembered row is still at its offset
+    AssertAnchorKept { tolerance: f64 },
+    AssertItemCount { min: usize, max: usize },
+    AssertScrollTop { expected: f64, tolerance: f64 },
+}
+
+/// Named sequence of steps, run from the state the previous scenario left
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scenario {
+    pub name: &'static str,
+    pub steps: Vec<Step>,
+}
+
+/// Scenarios run by the end-to-end mode
+pub fn default_scenarios() -> Vec<Scenario> {
+    vec![
+        Scenario {
+            name: "renders a bounded window of rows",
+            steps: vec![Step::AssertItemCount { min: 1, max: 20 }],
+        },
+        Scenario {
+            name: "scrolling to the bottom loads newer items",
+            steps: vec![
+                Step::ScrollToBottom,
+                Step::WaitForGrowth(Duration::from_secs(3)),
+                Step::ScrollToBottom,
+                Step::WaitForGrowth(Duration::from_secs(3)),
+            ],
+        },
+        Scenario {
+            name: "scrolling moves the rendered range",
+            steps: vec![
+                // Stay clear of the top load threshold
+                Step::ScrollTo(ITEM_HEIGHT),
+                Step::Wait(Duration::from_millis(100)),
+                Step::ScrollTo(ITEM_HEIGHT * 8.0),
+                Step::WaitForRangeChange(Duration::from_secs(2)),
+                Step::AssertItemCount { min: 1, max: 20 },
+            ],
+        },
+        Scenario {
+            name: "prepending older items keeps the viewport anchored",
+            steps: vec![
+                Step::ScrollTo(400.0),
+                Step::Wait(Duration::from_millis(100)),
+                Step::ScrollTo(100.0),
+                Step::RememberAnchor,
+                Step::WaitForGrowth(Duration::from_secs(3)),
+                // Let the deferred scroll adjustment land
+                Step::Wait(Duration::from_millis(300)),
+                Step::AssertAnchorKept { tolerance: 2.0 },
+            ],
+        },
+        Scenario {
+            name: "scrolling away from the edges sets scroll_top",
+            steps: vec![
+                Step::ScrollTo(ITEM_HEIGHT * 4.0),
+                Step::Wait(Duration::from_millis(100)),
+                Step::AssertScrollTop { expected: ITEM_HEIGHT * 4.0, tolerance: 1.0 },
+            ],
+        },
+    ]
+}
@@ -0,0 +1,200 @@
+//! Soak run: hours of polling on an accelerated clock, checking that a
+//! long-running feed stays bounded.
+//!
+//! Launching the app with `DIOXUS_FEED_SOAK=<hours>` mounts [`SoakRunner`]
+//! instead of the feeds. It polls a demo feed on an [`AcceleratedClock`]
+//! running [`SOAK_SPEEDUP`] times faster than real time, scrolls it between
+//! samples, and checks every sample: the items held stay under
+//! [`SOAK_MAX_ITEMS`], per-item state and highlight expiries don't outlive
+//! their items, requests don't pile up, the rendered rows stay a window and
+//! the scroll anchor names a loaded item. `tests/soak.rs` runs the binary in
+//! this mode.
+//!
+//! [`AcceleratedClock`]: crate::clock::AcceleratedClock
+
+use dioxus::prelude::*;
+use std::time::Duration;
+
+use super::{query_dom, scroll_to, DomState, E2eError};
+use crate::clock::{use_clock, ClockHandle};
+use crate::components::list_controller::{use_virtual_list_controller, VirtualListController};
+use crate::components::virtual_list::{VirtualList, ITEM_HEIGHT};
+use crate::sources::demo::DemoSource;
+use crate::store::{FeedStore, StoreFootprint};
+
+/// Environment variable enabling the soak run, set to the hours to simulate
+pub const SOAK_ENV_VAR: &str = "DIOXUS_FEED_SOAK";
+
+/// How much faster than real time the soak clock runs
+pub const SOAK_SPEEDUP: u32 = 240;
+
+/// Most items a soaked feed may hold once warmed up
+pub const SOAK_MAX_ITEMS: usize = 500;
+
+// Soak check configuration
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(5 * 60); // Simulated time between samples
+const WARM_UP_SAMPLES: usize = 3; // Samples before the item bound applies
+const MAX_RENDERED_ROWS: usize = 50; // Rows in the DOM at once
+const MAX_IN_FLIGHT: usize = 2; // One request per edge
+
+const HEAP_SCRIPT: &str = r#"
+    return performance.memory ? performance.memory.usedJSHeapSize : null;
+"#;
+
+/// Simulated duration of the soak run, if the app was launched for one
+pub fn soak_duration() -> Option<Duration> {
+    let hours: u64 = std::env::var(SOAK_ENV_VAR).ok()?.trim().parse().ok()?;
+    (hours > 0).then(|| Duration::from_secs(hours * 60 * 60))
+}
+
+/// State of the soaked feed at one point of the run
+#[derive(Debug, Clone, PartialEq)]
+pub struct SoakSample {
+    /// Simulated time since the run started
+    pub at: Duration,
+    pub footprint: StoreFootprint,
+    pub dom: DomState,
+    /// JS heap in use, where the webview reports it
+    pub heap_bytes: Option<f64>,
+}
+
+impl std::fmt::Display for SoakSample {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let minutes = self.at.as_secs() / 60;
+        write!(
+            f,
+            "{:02}:{:02} items={} states={} fresh={} staged={} in_flight={} rows={}",
+            minutes / 60,
+            minutes % 60,
+            self.footprint.items,
+            self.footprint.item_states,
+            self.footprint.fresh,
+            self.footprint.staged,
+            self.footprint.in_flight,
+            self.dom.item_count
+        )?;
+        if let Some(heap) = self.heap_bytes {
+            write!(f, " heap={:.1}MB", heap / 1_000_000.0)?;
+        }
+        Ok(())
+    }
+}
+
+// Check a sample against the bounds; `warmed_up` once the feed had time to
+// fill its cap
+fn check(sample: &SoakSample, store: FeedStore, controller: VirtualListController, warmed_up: bool) -> Result<(), E2eError> {
+    let footprint = sample.footprint;
+    if warmed_up && footprint.items > SOAK_MAX_ITEMS {
+        return Err(E2eError::Assertion(format!(
+            "{} items held, expected at most {}",
+            footprint.items, SOAK_MAX_ITEMS
+        )));
+    }
+    if footprint.item_states > footprint.items {
+        return Err(E2eError::Assertion(format!(
+            "{} item states for {} items",
+            footprint.item_states, footprint.items
+        )));
+    }
+    if footprint.fresh > footprint.items {
+        return Err(E2eError::Assertion(format!(
+            "{} highlight expiries pending for {} items",
+            footprint.fresh, footprint.items
+        )));
+    }
+    if footprint.in_flight > MAX_IN_FLIGHT {
+        return Err(E2eError::Assertion(format!("{} requests in flight", footprint.in_flight)));
+    }
+    if sample.dom.item_count > MAX_RENDERED_ROWS {
+        return Err(E2eError::Assertion(format!(
+            "{} rows rendered, expected at most {}",
+            sample.dom.item_count, MAX_RENDERED_ROWS
+        )));
+    }
+
+    let loaded = |id: &str| store.items.peek().iter().any(|item| item.id == id);
+    match sample.dom.anchor_id.as_deref() {
+        Some(id) if loaded(id) => {}
+        Some(id) => return Err(E2eError::Assertion(format!("row {} in view is not in the store", id))),
+        None => return Err(E2eError::Assertion("no row in the viewport".to_string())),
+    }
+    if let Some(anchor) = controller.current_anchor() {
+        if !loaded(&anchor.item_id) {
+            return Err(E2eError::Assertion(format!(
+                "scroll anchor {} is not in the store",
+                anchor.item_id
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Poll and scroll the feed for `duration` of simulated time, printing each
+/// sample and stopping at the first one out of bounds
+pub async fn run_soak(
+    store: FeedStore,
+    controller: VirtualListController,
+    clock: &ClockHandle,
+    duration: Duration,
+) -> Result<SoakSample, E2eError> {
+    let start = clock.now();
+    let mut samples = 0;
+    loop {
+        clock.sleep(SAMPLE_INTERVAL).await;
+        // Alternate between two positions clear of the top edge, so rows
+        // keep entering and leaving the rendered window
+        let rows = if samples % 2 == 0 { 3.0 } else { 6.0 };
+        scroll_to(ITEM_HEIGHT * rows).await?;
+
+        let heap_bytes = document::eval(HEAP_SCRIPT).join::<Option<f64>>().await.ok().flatten();
+        let sample = SoakSample {
+            at: clock.now().duration_since(start),
+            footprint: store.footprint(),
+            dom: query_dom().await?,
+            heap_bytes,
+        };
+        println!("soak: {}", sample);
+        samples += 1;
+        check(&sample, store, controller, samples > WARM_UP_SAMPLES)?;
+
+        if sample.at >= duration {
+            return Ok(sample);
+        }
+    }
+}
+
+// Soaks a demo feed of its own, then exits the app
+#[component]
+pub fn SoakRunner(duration: Duration) -> Element {
+    let clock = use_clock();
+    let store = use_hook(|| {
+        let source = DemoSource::new().with_clock(clock.clone());
+        FeedStore::new(source.initial_items(), source).with_clock(clock.clone())
+    });
+    let controller = use_virtual_list_controller();
+
+    use_future(move || {
+        let clock = clock.clone();
+        async move {
+            match run_soak(store, controller, &clock, duration).await {
+                Ok(sample) => {
+                    println!("soak: PASS after {} simulated minutes", sample.at.as_secs() / 60);
+                    std::process::exit(0);
+                }
+                Err(e) => {
+                    println!("soak: FAIL {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+    });
+
+    rsx! {
+        VirtualList {
+            store,
+            controller,
+            highlight_new_items: true,
+            max_items: SOAK_MAX_ITEMS,
+        }
+    }
+}
@@ -0,0 +1,169 @@
+//! Crate-wide error type and how errors reach the user.
+//!
+//! Module errors (persistence, links, speech, ...) are wrapped in a
+//! [`FeedError`] with what was being done when they happened. [`report`]
+//! logs every error and shows those the user can act on in the
+//! [`Snackbar`], so failures aren't swallowed.
+
+use dioxus::prelude::*;
+
+use crate::auth::AuthError;
+use crate::components::read_aloud::SpeechError;
+use crate::components::snackbar::Snackbar;
+use crate::links::LinkBlocked;
+use crate::persistence::PersistenceError;
+#[cfg(feature = "desktop")]
+use crate::protocol::myprotocol::ProtocolError;
+use crate::reader::ReaderError;
+use crate::sources::SourceError;
+
+/// Errors of the feed app, with the context they happened in
+#[derive(Debug, Clone, PartialEq)]
+pub enum FeedError {
+    /// A persisted document couldn't be read, so its defaults are used
+    Load { what: &'static str, source: PersistenceError },
+    /// A change couldn't be written and will be lost on restart
+    Save { what: &'static str, source: PersistenceError },
+    Clipboard { what: &'static str, message: String },
+    OpenLink { url: String, message: String },
+    LinkBlocked { href: String, source: LinkBlocked },
+    /// A link or search result named a feed that doesn't exist
+    UnknownFeed(String),
+    InvalidDate(String),
+    NoItemsBefore(String),
+    Speech(SpeechError),
+    /// The list couldn't be kept in place while items were inserted
+    Scroll(ScrollError),
+    Trim { len: usize },
+    Source(SourceError),
+    Reader(ReaderError),
+    Auth(AuthError),
+    /// A bundled asset or remote image couldn't be served to the webview
+    #[cfg(feature = "desktop")]
+    Protocol(ProtocolError),
+}
+
+impl std::fmt::Display for FeedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FeedError::Load { what, source } => write!(f, "Couldn't load {}: {}", what, source),
+            FeedError::Save { what, source } => write!(f, "Couldn't save {}: {}", what, source),
+            FeedError::Clipboard { what, message } => write!(f, "Couldn't copy the {}: {}", what, message),
+            FeedError::OpenLink { url, message } => write!(f, "Couldn't open {}: {}", url, message),
+            FeedError::LinkBlocked { href, source } => write!(f, "Didn't open {}: {}", href, source),
+            FeedError::UnknownFeed(feed) => write!(f, "No feed {}", feed),
+            FeedError::InvalidDate(date) => write!(f, "{} isn't a date, use YYYY-MM-DD", date),
+            FeedError::NoItemsBefore(date) => write!(f, "No items on or before {}", date),
+            FeedError::Speech(e) => write!(f, "Read aloud stopped: {}", e),
+            FeedError::Scroll(e) => write!(f, "Couldn't keep the scroll position: {}", e),
+            FeedError::Trim { len } => write!(f, "Couldn't trim {} items to the limit", len),
+            FeedError::Source(e) => write!(f, "Couldn't load the feed: {}", e),
+            FeedError::Reader(e) => write!(f, "Couldn't open the article: {}", e),
+            FeedError::Auth(e) => write!(f, "Account error: {}", e),
+            #[cfg(feature = "desktop")]
+            FeedError::Protocol(e) => write!(f, "Couldn't load media: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for FeedError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FeedError::Load { source, .. } | FeedError::Save { source, .. } => Some(source),
+            FeedError::LinkBlocked { source, .. } => Some(source),
+            FeedError::Speech(e) => Some(e),
+            FeedError::Scroll(e) => Some(e),
+            FeedError::Source(e) => Some(e),
+            FeedError::Reader(e) => Some(e),
+            FeedError::Auth(e) => Some(e),
+            #[cfg(feature = "desktop")]
+            FeedError::Protocol(e) => Some(e),
+            FeedError::Clipboard { .. }
+            | FeedError::OpenLink { .. }
+            | FeedError::UnknownFeed(_)
+            | FeedError::InvalidDate(_)
+            | FeedError::NoItemsBefore(_)
+            | FeedError::Trim { .. } => None,
+        }
+    }
+}
+
+impl From<SourceError> for FeedError {
+    fn from(error: SourceError) -> Self {
+        FeedError::Source(error)
+    }
+}
+
+impl From<ReaderError> for FeedError {
+    fn from(error: ReaderError) -> Self {
+        FeedError::Reader(error)
+    }
+}
+
+impl From<AuthError> for FeedError {
+    fn from(error: AuthError) -> Self {
+        FeedError::Auth(error)
+    }
+}
+
+#[cfg(feature = "desktop")]
+impl From<ProtocolError> for FeedError {
+    fn from(error: ProtocolError) -> Self {
+        FeedError::Protocol(error)
+    }
+}
+
+/// Why a list couldn't be scrolled to where it should be
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScrollError {
+    /// The list's element isn't mounted, or was unmounted meanwhile
+    NotMounted,
+    /// The webview refused to scroll the element
+    Failed(String),
+    /// Every retry failed, with the last failure
+    GaveUp { attempts: usize, last: String },
+}
+
+impl std::fmt::Display for ScrollError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScrollError::NotMounted => write!(f, "the list isn't mounted"),
+            ScrollError::Failed(msg) => write!(f, "{}", msg),
+            ScrollError::GaveUp { attempts, last } => write!(f, "gave up after {} attempts: {}", attempts, last),
+        }
+    }
+}
+
+impl std::error::Error for ScrollError {}
+
+impl From<dioxus::html::MountedError> for ScrollError {
+    fn from(error: dioxus::html::MountedError) -> Self {
+        ScrollError::Failed(error.to_string())
+    }
+}
+
+impl FeedError {
+    /// Whether the user can do something about the error, e.g. free disk
+    /// space or fix what they typed. Layout hiccups only go to the log.
+    pub fn is_actionable(&self) -> bool {
+        match self {
+            FeedError::Scroll(_) | FeedError::Trim { .. } => false,
+            // Media that failed to load shows its fallback in place
+            #[cfg(feature = "desktop")]
+            FeedError::Protocol(_) => false,
+            _ => true,
+        }
+    }
+}
+
+/// Log `error`, and show it in the snackbar when the user can act on it and
+/// a [`Snackbar`] is provided
+pub fn report(error: FeedError) {
+    eprintln!("{}", error);
+    if !error.is_actionable() {
+        return;
+    }
+    if let Some(mut snackbar) = try_consume_context::<Snackbar>() {
+        snackbar.show(error.to_string());
+    }
+}
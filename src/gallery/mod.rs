@@ -0,0 +1,491 @@
+//! Example gallery: the same list component configured for other kinds of
+//! feeds than the app's own.
+//!
+//! Launching with `--scenario <name>` (e.g. `dx serve -- --scenario chat`)
+//! shows the gallery instead of the feed tabs, opened on that example. Each
+//! example pairs a source with the `VirtualList` options that suit it, and
+//! the gallery's tab row switches between them. The messages example shows
+//! `VirtualRows`, the list's core, over a type of its own instead.
+
+mod sources;
+
+use dioxus::prelude::*;
+use dioxus_feed_core::VirtualFeedItem;
+
+use crate::assets::BundledImages;
+use crate::clock::use_clock;
+use crate::components::horizontal_list::{use_horizontal_list_controller, HorizontalList};
+use crate::components::list_controller::{use_virtual_list_controller, ScrollRequest};
+use crate::components::scroll_parent::use_scroll_parent;
+use crate::components::virtual_list::VirtualList;
+use crate::components::virtual_rows::{Keyed, LayoutMode, Row, VirtualRows};
+use crate::sources::demo::DemoSource;
+use crate::sources::rss::RssSource;
+use crate::store::{FeedStore, Sequence};
+use sources::{ChatSource, LogSource, StressSource, STRESS_ITEMS};
+
+/// Command-line flag naming the example to open
+pub const SCENARIO_FLAG: &str = "--scenario";
+
+/// Command-line flag giving the feed the RSS example reads
+pub const FEED_FLAG: &str = "--feed";
+
+/// Feed the RSS example reads without `--feed`
+const DEFAULT_FEED_URL: &str = "https://blog.rust-lang.org/feed.xml";
+
+// Nested example configuration
+const STRIP_EVERY: usize = 4; // Rows between photo strips
+const STRIP_PHOTOS: usize = 24; // Photos per strip
+const STRIP_PHOTO_WIDTH: f64 = 112.0; // Width per photo including the gap
+const STRIP_HEIGHT: f64 = 96.0;
+
+// Messages example configuration
+const MESSAGES_PAGE: u64 = 50; // Earlier messages loaded at a time
+const FIRST_MESSAGE: u64 = 500; // Id of the first message shown; earlier ones load on scrolling up
+const MESSAGE_AUTHORS: [&str; 3] = ["Ada", "Grace", "Linus"];
+
+// Stress example configuration
+const STRESS_PAGE_SIZE: usize = 100; // Items per edge load
+const STRESS_WINDOW: usize = 1000; // Items held at once, whatever was scrolled through
+
+/// An example the gallery can show
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Example {
+    Chat,
+    Logs,
+    Photos,
+    Rss,
+    Stress,
+    Embedded,
+    Nested,
+    Messages,
+}
+
+impl Example {
+    pub const ALL: [Example; 8] = [
+        Example::Chat,
+        Example::Logs,
+        Example::Photos,
+        Example::Rss,
+        Example::Stress,
+        Example::Embedded,
+        Example::Nested,
+        Example::Messages,
+    ];
+
+    /// Name given to `--scenario`
+    pub fn name(self) -> &'static str {
+        match self {
+            Example::Chat => "chat",
+            Example::Logs => "logs",
+            Example::Photos => "photos",
+            Example::Rss => "rss",
+            Example::Stress => "stress",
+            Example::Embedded => "embedded",
+            Example::Nested => "nested",
+            Example::Messages => "messages",
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Example::Chat => "Chat",
+            Example::Logs => "Log viewer",
+            Example::Photos => "Photos",
+            Example::Rss => "RSS reader",
+            Example::Stress => "Stress test",
+            Example::Embedded => "Embedded",
+            Example::Nested => "Nested lists",
+            Example::Messages => "Custom data",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Example::Chat => "History loads above, new messages arrive at the bottom and are highlighted briefly.",
+            Example::Logs => "Lines are tailed in bursts and laid out in normal flow, so they can be selected and copied.",
+            Example::Photos => "Photo items with every image stacked above its text, loading well ahead of the viewport.",
+            Example::Rss => "A single RSS or Atom feed (--feed <url>), with reading progress along the toolbar.",
+            Example::Stress => "A million-item keyspace served instantly in both directions, for profiling scroll performance.",
+            Example::Embedded => "A feed inside a page that scrolls as a whole, below an introduction of its own.",
+            Example::Nested => "Photo strips between the rows, scrolled sideways by the wheel until their ends, then the feed moves on.",
+            Example::Messages => "Chat messages of the example's own type in the generic list: it opens at the end, follows new messages and loads earlier ones above without moving.",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|example| example.name() == name)
+    }
+
+    /// Example named by `--scenario` on the command line. Unknown names are
+    /// reported and open the first example.
+    pub fn from_args() -> Option<Self> {
+        let name = arg_value(SCENARIO_FLAG)?;
+        Some(Self::from_name(&name).unwrap_or_else(|| {
+            let names: Vec<&str> = Self::ALL.iter().map(|example| example.name()).collect();
+            eprintln!("Unknown scenario {}, expected one of: {}", name, names.join(", "));
+            Self::ALL[0]
+        }))
+    }
+}
+
+// Value following `flag` on the command line
+fn arg_value(flag: &str) -> Option<String> {
+    let mut args = std::env::args().skip_while(|arg| arg != flag);
+    args.next()?;
+    args.next()
+}
+
+// Tab row of the examples above the one shown
+#[component]
+pub fn Gallery(initial: Example) -> Element {
+    let mut example = use_signal(|| initial);
+    let shown = example();
+
+    rsx! {
+        nav {
+            role: "tablist",
+            aria_label: "Examples",
+            style: "
+                display: flex;
+                flex-wrap: wrap;
+                gap: 4px;
+                margin-bottom: 8px;
+                border-bottom: 1px solid #e2e8f0;
+            ",
+            for option in Example::ALL {
+                button {
+                    key: "{option.name()}",
+                    role: "tab",
+                    aria_selected: "{option == shown}",
+                    style: format!("
+                        padding: 8px 16px;
+                        border: none;
+                        border-bottom: 2px solid {};
+                        background: transparent;
+                        color: {};
+                        font-size: 14px;
+                        font-weight: 500;
+                        cursor: pointer;
+                    ",
+                        if option == shown { "#0f172a" } else { "transparent" },
+                        if option == shown { "#0f172a" } else { "#64748b" }
+                    ),
+                    onclick: move |_| example.set(option),
+                    "{option.title()}"
+                }
+            }
+        }
+        p {
+            style: "margin: 0 0 12px; font-size: 13px; color: #64748b;",
+            "{shown.description()}"
+        }
+        // Keyed in a block of its own, where the key is allowed, so
+        // switching examples mounts a fresh store
+        {rsx! { ExampleView { key: "{shown.name()}", example: shown } }}
+    }
+}
+
+// One example's list, with a store of its own created on mount
+#[component]
+fn ExampleView(example: Example) -> Element {
+    let clock = use_clock();
+    let store = use_hook(|| {
+        let store = match example {
+            Example::Chat => FeedStore::new(Vec::new(), ChatSource::default().with_clock(clock.clone())),
+            Example::Logs => FeedStore::new(Vec::new(), LogSource::default().with_clock(clock.clone())),
+            Example::Photos => {
+                let source = DemoSource::photos_only().with_clock(clock.clone());
+                FeedStore::new(source.initial_items(), source)
+            }
+            Example::Rss => {
+                let url = arg_value(FEED_FLAG).unwrap_or_else(|| DEFAULT_FEED_URL.to_string());
+                FeedStore::new(Vec::new(), RssSource::new(url))
+            }
+            Example::Stress => FeedStore::new(Vec::new(), StressSource)
+                .with_page_size(STRESS_PAGE_SIZE)
+                .with_window(STRESS_WINDOW),
+            Example::Embedded | Example::Nested => {
+                let source = DemoSource::new().with_clock(clock.clone());
+                FeedStore::new(source.initial_items(), source)
+            }
+            // Holds its messages itself, not in a store
+            Example::Messages => return None,
+        };
+        Some(store.with_clock(clock.clone()))
+    });
+
+    match (example, store) {
+        (Example::Chat, Some(store)) => rsx! {
+            VirtualList {
+                store,
+                highlight_new_items: true,
+                beginning_header: rsx! {
+                    p {
+                        style: "margin: 0; padding: 12px; text-align: center; font-size: 13px; color: #94a3b8;",
+                        "Start of the conversation"
+                    }
+                },
+            }
+        },
+        (Example::Logs, Some(store)) => rsx! {
+            VirtualList {
+                store,
+                layout_mode: LayoutMode::Spacers,
+            }
+        },
+        (Example::Photos, Some(store)) => rsx! {
+            VirtualList {
+                store,
+                stack_below_width: f64::INFINITY,
+                image_lead_distance: 1200.0,
+            }
+        },
+        (Example::Rss, Some(store)) => rsx! {
+            VirtualList {
+                store,
+                highlight_new_items: true,
+                show_progress: true,
+            }
+        },
+        (Example::Stress, Some(store)) => rsx! {
+            StressExample { store }
+        },
+        (Example::Embedded, Some(store)) => rsx! {
+            EmbeddedExample { store }
+        },
+        (Example::Nested, Some(store)) => rsx! {
+            VirtualList {
+                store,
+                interleave: move |seq: Sequence| Some(rsx! { PhotoStrip { seq } }),
+                interleave_every: STRIP_EVERY,
+            }
+        },
+        // Only the messages example has no store
+        (Example::Messages, _) | (_, None) => rsx! {
+            MessagesExample {}
+        },
+    }
+}
+
+// A virtualized strip of photos in a slot of the nested example, with a
+// controller of its own to return to its start
+#[component]
+fn PhotoStrip(seq: Sequence) -> Element {
+    let mut controller = use_horizontal_list_controller();
+
+    rsx! {
+        div {
+            style: "position: relative; padding: 8px 0;",
+            HorizontalList {
+                item_count: STRIP_PHOTOS,
+                item_width: STRIP_PHOTO_WIDTH,
+                height: STRIP_HEIGHT,
+                controller,
+                wheel_scrolls_sideways: true,
+                render_item: move |index: usize| {
+                    let photo = VirtualFeedItem::new_with_random_photo(format!("strip-{}-{}", seq, index), String::new());
+                    rsx! {
+                        img {
+                            src: "{photo.image_url}",
+                            alt: "",
+                            loading: "lazy",
+                            style: "width: calc(100% - 8px); height: 100%; object-fit: cover; border-radius: 6px; background: #f8fafc;",
+                        }
+                    }
+                },
+            }
+            button {
+                style: "position: absolute; top: 12px; right: 4px; padding: 2px 8px; border: 1px solid #cbd5e1; border-radius: 4px; background: white; font-size: 12px; cursor: pointer;",
+                onclick: move |_| controller.scroll_to_index(0),
+                "Start"
+            }
+        }
+    }
+}
+
+// The list attached to the scroll of a page around it, so the page's own
+// content scrolls away above the feed
+#[component]
+fn EmbeddedExample(store: FeedStore) -> Element {
+    let mut page = use_scroll_parent();
+
+    rsx! {
+        div {
+            style: "height: 600px; overflow-y: auto; border: 1px solid #e2e8f0; border-radius: 8px;",
+            "data-scroll-parent": page.id(),
+            onmounted: move |evt| page.mounted(evt),
+            onscroll: move |evt| page.scrolled(evt),
+            section {
+                style: "padding: 24px; background: #f8fafc; border-bottom: 1px solid #e2e8f0;",
+                h2 { style: "margin: 0 0 8px; font-size: 20px; color: #0f172a;", "Around the community" }
+                p {
+                    style: "margin: 0; font-size: 14px; line-height: 1.6; color: #475569;",
+                    "This introduction belongs to the page. Scroll past it and the feed below keeps going: \
+                     the page is the scroll container, and the list renders only the rows in its viewport."
+                }
+            }
+            VirtualList {
+                store,
+                scroll_parent: page,
+                highlight_new_items: true,
+            }
+        }
+    }
+}
+
+// The stress list with the items held and the range they cover, which stay
+// within the window however far it is scrolled, and a jump to any item
+#[component]
+fn StressExample(store: FeedStore) -> Element {
+    let mut store = store;
+    let mut controller = use_virtual_list_controller();
+    let mut target = use_signal(|| StressSource::START.to_string());
+
+    let (held, first, last) = {
+        let items = store.items.read();
+        let index = |item: Option<&VirtualFeedItem>| {
+            item.and_then(|item| StressSource::index_of(&item.id)).unwrap_or_default()
+        };
+        (items.len(), index(items.front()), index(items.back()))
+    };
+
+    let mut jump = move || {
+        let Ok(index) = target.peek().trim().parse::<usize>() else {
+            return;
+        };
+        let index = index.min(STRESS_ITEMS - 1);
+        store.reset(StressSource::page_from(index, STRESS_PAGE_SIZE));
+        controller.scroll_to(ScrollRequest::Item(StressSource::id(index)));
+    };
+
+    rsx! {
+        div {
+            style: "display: flex; align-items: center; gap: 8px; margin: 0 0 8px; font-size: 12px; color: #64748b;",
+            span {
+                style: "flex: 1; font-variant-numeric: tabular-nums;",
+                "{held} items held (window {STRESS_WINDOW}), items {first}–{last} of {STRESS_ITEMS}"
+            }
+            input {
+                r#type: "number",
+                min: "0",
+                max: "{STRESS_ITEMS - 1}",
+                aria_label: "Item to jump to",
+                value: "{target}",
+                style: "width: 96px; padding: 4px 6px; border: 1px solid #cbd5e1; border-radius: 4px; font-size: 12px;",
+                oninput: move |evt| target.set(evt.value()),
+                onkeydown: move |evt| {
+                    if evt.key() == Key::Enter {
+                        jump();
+                    }
+                },
+            }
+            button {
+                style: "padding: 4px 10px; border: 1px solid #cbd5e1; border-radius: 4px; background: white; font-size: 12px; cursor: pointer;",
+                onclick: move |_| jump(),
+                "Go"
+            }
+        }
+        VirtualList {
+            store,
+            controller,
+        }
+    }
+}
+
+// A message of the messages example, unrelated to feed items
+#[derive(Debug, Clone, PartialEq)]
+struct Message {
+    id: u64,
+    author: &'static str,
+    text: String,
+}
+
+impl Message {
+    // Generated message `id`, of a length varying with the id
+    fn generated(id: u64) -> Self {
+        let words = 3 + (id * 7) % 40;
+        let text = (0..words)
+            .map(|word| if word == 0 { format!("Message {}:", id) } else { "lorem".to_string() })
+            .collect::<Vec<_>>()
+            .join(" ");
+        Self {
+            id,
+            author: MESSAGE_AUTHORS[(id % MESSAGE_AUTHORS.len() as u64) as usize],
+            text,
+        }
+    }
+
+}
+
+impl Keyed for Message {
+    fn key(&self) -> String {
+        self.id.to_string()
+    }
+}
+
+// Messages held in a signal of the example's own, shown by the list's core,
+// with a box for sending more
+#[component]
+fn MessagesExample() -> Element {
+    let mut messages = use_signal(|| {
+        (FIRST_MESSAGE..FIRST_MESSAGE + MESSAGES_PAGE)
+            .map(Message::generated)
+            .collect::<im::Vector<_>>()
+    });
+    let mut draft = use_signal(String::new);
+
+    let load_earlier = move |_| {
+        let first = messages.peek().front().map_or(0, |message| message.id);
+        if first == 0 {
+            return;
+        }
+        let mut earlier: im::Vector<Message> = (first.saturating_sub(MESSAGES_PAGE)..first).map(Message::generated).collect();
+        earlier.append(messages.peek().clone());
+        messages.set(earlier);
+    };
+    let mut send = move || {
+        let text = draft.peek().trim().to_string();
+        if text.is_empty() {
+            return;
+        }
+        let id = messages.peek().back().map_or(0, |message| message.id + 1);
+        messages.write().push_back(Message { id, author: "You", text });
+        draft.set(String::new());
+    };
+
+    rsx! {
+        VirtualRows {
+            items: messages,
+            render_row: move |Row { item: message, .. }: Row<Message>| rsx! {
+                div {
+                    style: "padding: 6px 12px; font-size: 14px; line-height: 1.5; color: #0f172a;",
+                    strong { style: "margin-right: 6px;", "{message.author}" }
+                    span { "{message.text}" }
+                }
+            },
+            follow_end: true,
+            on_reach_start: load_earlier,
+        }
+        div {
+            style: "display: flex; gap: 8px; margin-top: 8px;",
+            input {
+                aria_label: "Message",
+                placeholder: "Write a message",
+                value: "{draft}",
+                style: "flex: 1; padding: 6px 8px; border: 1px solid #cbd5e1; border-radius: 4px; font-size: 13px;",
+                oninput: move |evt| draft.set(evt.value()),
+                onkeydown: move |evt| {
+                    if evt.key() == Key::Enter {
+                        send();
+                    }
+                },
+            }
+            button {
+                style: "padding: 6px 12px; border: 1px solid #cbd5e1; border-radius: 4px; background: white; font-size: 13px; cursor: pointer;",
+                onclick: move |_| send(),
+                "Send"
+            }
+        }
+    }
+}
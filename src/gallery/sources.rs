@@ -0,0 +1,188 @@
+use dioxus_feed_core::VirtualFeedItem;
+use std::time::Duration;
+
+use crate::assets::BundledImages;
+use crate::clock::ClockHandle;
+use crate::sources::{FeedSource, PageRequest, SourceFuture};
+
+// Gallery source configuration
+const CHAT_LOAD_DELAY_MS: u64 = 200; // Simulated latency of the chat history
+const CHAT_POLL_INTERVAL_MS: u64 = 2000; // A new message every couple of seconds
+const LOG_POLL_INTERVAL_MS: u64 = 500; // Log lines arrive in quick bursts
+const MAX_LOG_BURST: usize = 3; // Most lines a log poll brings in
+
+/// Items in the stress example's keyspace
+pub const STRESS_ITEMS: usize = 1_000_000;
+
+const CHAT_AUTHORS: &[&str] = &["Ada", "Grace", "Ken", "Barbara"];
+
+const CHAT_LINES: &[&str] = &[
+    "Morning! Did the release go out?",
+    "Yes, tagged it last night",
+    "Nice. Any reports so far?",
+    "One about scrolling on older webviews, looking into it",
+    "Can you share the repro?",
+    "Sure, give me a minute",
+    "Lunch at noon?",
+    "Works for me",
+];
+
+const LOG_TARGETS: &[&str] = &["scheduler", "http", "store", "sync", "cache"];
+
+const LOG_LEVELS: &[&str] = &["INFO", "INFO", "INFO", "DEBUG", "WARN", "ERROR"];
+
+// Stable pseudo-random number for `index`, so reloaded pages match
+fn spread(index: usize) -> usize {
+    index.wrapping_mul(2_654_435_761) >> 7
+}
+
+/// Chat-like source: short messages from a few people, history above and
+/// new messages arriving at the bottom
+#[derive(Clone, Default)]
+pub struct ChatSource {
+    clock: ClockHandle,
+}
+
+impl ChatSource {
+    pub fn with_clock(mut self, clock: ClockHandle) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn message(&self, kind: &str, number: usize) -> VirtualFeedItem {
+        let author = CHAT_AUTHORS[spread(number) % CHAT_AUTHORS.len()];
+        let line = CHAT_LINES[number % CHAT_LINES.len()];
+        let id = format!("chat_{}_{}_{}", kind, number, self.clock.utc_now().timestamp_millis());
+        VirtualFeedItem::new_with_random_image(id, line.to_string())
+            .with_author(author)
+            .with_published_at(self.clock.utc_now().timestamp_millis())
+    }
+}
+
+impl FeedSource for ChatSource {
+    fn load_older(&self, request: PageRequest) -> SourceFuture {
+        let source = self.clone();
+        Box::pin(async move {
+            source.clock.sleep(Duration::from_millis(CHAT_LOAD_DELAY_MS)).await;
+            Ok((1..=request.limit).map(|i| source.message("history", request.loaded + i)).collect())
+        })
+    }
+
+    fn load_newer(&self, request: PageRequest) -> SourceFuture {
+        let source = self.clone();
+        Box::pin(async move {
+            source.clock.sleep(Duration::from_millis(CHAT_LOAD_DELAY_MS)).await;
+            Ok((1..=request.limit).map(|i| source.message("recent", request.loaded + i)).collect())
+        })
+    }
+
+    fn poll(&self, request: PageRequest) -> SourceFuture {
+        let source = self.clone();
+        Box::pin(async move { Ok(vec![source.message("live", request.loaded + 1)]) })
+    }
+
+    fn poll_interval(&self) -> Option<Duration> {
+        Some(Duration::from_millis(CHAT_POLL_INTERVAL_MS))
+    }
+}
+
+/// Log-viewer source: timestamped lines tailed in bursts
+#[derive(Clone, Default)]
+pub struct LogSource {
+    clock: ClockHandle,
+}
+
+impl LogSource {
+    pub fn with_clock(mut self, clock: ClockHandle) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    fn line(&self, number: usize) -> VirtualFeedItem {
+        let now = self.clock.utc_now();
+        let level = LOG_LEVELS[spread(number) % LOG_LEVELS.len()];
+        let target = LOG_TARGETS[spread(number + 1) % LOG_TARGETS.len()];
+        let content = format!(
+            "{} {:<5} {}: request {} handled in {} ms",
+            now.format("%H:%M:%S%.3f"),
+            level,
+            target,
+            number,
+            spread(number) % 900 + 1
+        );
+        let id = format!("log_{}_{}", number, now.timestamp_millis());
+        VirtualFeedItem::new_with_random_image(id, content).with_published_at(now.timestamp_millis())
+    }
+}
+
+impl FeedSource for LogSource {
+    // Logs are tailed; there is nothing before the first line shown
+    fn load_older(&self, _request: PageRequest) -> SourceFuture {
+        Box::pin(async { Ok(Vec::new()) })
+    }
+
+    fn load_newer(&self, request: PageRequest) -> SourceFuture {
+        let source = self.clone();
+        Box::pin(async move { Ok((1..=request.limit).map(|i| source.line(request.loaded + i)).collect()) })
+    }
+
+    fn poll(&self, request: PageRequest) -> SourceFuture {
+        let source = self.clone();
+        Box::pin(async move {
+            let burst = spread(request.loaded) % MAX_LOG_BURST + 1;
+            Ok((1..=burst).map(|i| source.line(request.loaded + i)).collect())
+        })
+    }
+
+    fn poll_interval(&self) -> Option<Duration> {
+        Some(Duration::from_millis(LOG_POLL_INTERVAL_MS))
+    }
+}
+
+/// Source over a fixed keyspace of [`STRESS_ITEMS`] items, served instantly
+/// in both directions from the middle, for profiling scroll performance.
+///
+/// Items are a function of their index alone, so every run scrolls through
+/// the same content.
+#[derive(Clone, Default)]
+pub struct StressSource;
+
+impl StressSource {
+    /// Index of the first item shown
+    pub const START: usize = STRESS_ITEMS / 2;
+
+    pub fn id(index: usize) -> String {
+        format!("stress_{}", index)
+    }
+
+    /// Index of an item served by this source
+    pub fn index_of(id: &str) -> Option<usize> {
+        id.strip_prefix("stress_")?.parse().ok()
+    }
+
+    // Small items sharing the bundled images, so memory is mostly the window
+    fn item(index: usize) -> VirtualFeedItem {
+        VirtualFeedItem::new_with_random_image(Self::id(index), format!("Stress item {}", index))
+    }
+
+    /// Up to `size` items starting at `start`
+    pub fn page_from(start: usize, size: usize) -> Vec<VirtualFeedItem> {
+        (start..(start + size).min(STRESS_ITEMS)).map(Self::item).collect()
+    }
+}
+
+impl FeedSource for StressSource {
+    fn load_older(&self, request: PageRequest) -> SourceFuture {
+        let end = request.cursor.as_deref().and_then(Self::index_of).unwrap_or(Self::START);
+        let start = end.saturating_sub(request.limit);
+        Box::pin(async move { Ok((start..end).map(Self::item).collect()) })
+    }
+
+    fn load_newer(&self, request: PageRequest) -> SourceFuture {
+        let start = match request.cursor.as_deref().and_then(Self::index_of) {
+            Some(index) => index + 1,
+            None => Self::START,
+        };
+        Box::pin(async move { Ok(Self::page_from(start, request.limit)) })
+    }
+}
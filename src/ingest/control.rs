@@ -0,0 +1,250 @@
+//! Control channel for scripts and the `dioxus-feed-ctl` companion.
+//!
+//! Desktop builds listen on a Unix socket in an owner-only directory of the
+//! data directory unless launched with `DIOXUS_FEED_CONTROL=0`. On Windows
+//! the channel is a named pipe, which other users of the machine could
+//! connect to, so it is only opened with `DIOXUS_FEED_CONTROL=1`. Each
+//! connection carries one request and its response as JSON lines (see
+//! [`dioxus_feed_control`]), the request at most [`MAX_REQUEST_BYTES`]
+//! long. Posts go straight to the inbox queue; refreshes and unread counts
+//! are answered by the feeds on the UI thread.
+
+use dioxus_feed_control::{control_path, ControlRequest, ControlResponse};
+#[cfg(unix)]
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use super::{enqueue, forward};
+
+/// Environment variable turning the control channel off when set to `0`,
+/// and on when set to `1` on Windows
+pub const CONTROL_ENV_VAR: &str = "DIOXUS_FEED_CONTROL";
+
+/// How long a request waits for the UI thread to answer
+const REPLY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long a Windows client may take to send its request or read the
+/// response, so a stalled one doesn't hold its task open
+#[cfg(windows)]
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Longest request line read, enough for a post with the largest image
+/// `dioxus-feed-ctl` inlines (2 MiB, base64-encoded)
+const MAX_REQUEST_BYTES: usize = 4 * 1024 * 1024;
+
+/// Start the control channel unless the environment turns it off, or on
+/// Windows, unless it turns it on
+pub fn start_from_env() -> Option<PathBuf> {
+    let setting = std::env::var(CONTROL_ENV_VAR).ok();
+    if setting.as_deref() == Some("0") {
+        return None;
+    }
+    // The pipe keeps the default security descriptor, which lets other users
+    // of the machine connect, so it is only opened when asked for
+    if cfg!(windows) && setting.as_deref() != Some("1") {
+        return None;
+    }
+    // Only desktop builds have sockets and pipes to listen on
+    if !cfg!(feature = "desktop") {
+        return None;
+    }
+    match start() {
+        Ok(path) => Some(path),
+        Err(e) => {
+            eprintln!("Failed to start the control channel: {}", e);
+            None
+        }
+    }
+}
+
+/// Answer one request line, as read up to [`MAX_REQUEST_BYTES`]
+fn respond(line: &str) -> ControlResponse {
+    if line.len() >= MAX_REQUEST_BYTES && !line.ends_with('\n') {
+        return ControlResponse::Error {
+            message: format!("Request larger than {} bytes", MAX_REQUEST_BYTES),
+        };
+    }
+    let request: ControlRequest = match serde_json::from_str(line.trim()) {
+        Ok(request) => request,
+        Err(e) => return ControlResponse::Error { message: format!("Invalid request: {}", e) },
+    };
+    match request {
+        ControlRequest::Post { item } => {
+            if item.text.trim().is_empty() {
+                return ControlResponse::Error { message: "The item has no text".to_string() };
+            }
+            enqueue(vec![item]);
+            ControlResponse::Ok { message: "Posted to the inbox".to_string() }
+        }
+        request => {
+            let (reply, answer) = mpsc::channel();
+            forward(request, reply);
+            answer.recv_timeout(REPLY_TIMEOUT).unwrap_or_else(|_| ControlResponse::Error {
+                message: "The app didn't answer in time".to_string(),
+            })
+        }
+    }
+}
+
+// Read a request line from `stream` and write the response line back
+#[cfg(unix)]
+fn serve_connection(stream: impl Read + Write) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.by_ref().take(MAX_REQUEST_BYTES as u64).read_line(&mut line)?;
+    let response = serde_json::to_string(&respond(&line)).map_err(std::io::Error::other)?;
+    let mut stream = reader.into_inner();
+    writeln!(stream, "{}", response)?;
+    stream.flush()
+}
+
+/// Listen on the socket in the data directory, replacing a stale one left
+/// by an earlier run
+#[cfg(unix)]
+pub fn start() -> std::io::Result<PathBuf> {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+    use std::os::unix::net::{UnixListener, UnixStream};
+
+    let path = control_path();
+    if UnixStream::connect(&path).is_ok() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::AddrInUse,
+            format!("another instance is listening on {}", path.display()),
+        ));
+    }
+    // The socket is created with the process umask, so it is bound in a
+    // directory only the owner can enter; other users can't reach it in the
+    // moment before its own permissions are narrowed
+    if let Some(parent) = path.parent() {
+        std::fs::DirBuilder::new().recursive(true).mode(0o700).create(parent)?;
+        std::fs::set_permissions(parent, std::fs::Permissions::from_mode(0o700))?;
+    }
+    let _ = std::fs::remove_file(&path);
+    let listener = UnixListener::bind(&path)?;
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))?;
+
+    std::thread::Builder::new()
+        .name("control".to_string())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                let result = stream.and_then(|stream| {
+                    stream.set_read_timeout(Some(REPLY_TIMEOUT))?;
+                    serve_connection(stream)
+                });
+                if let Err(e) = result {
+                    eprintln!("Control request failed: {}", e);
+                }
+            }
+        })?;
+    Ok(path)
+}
+
+// Read a request line from the pipe and write the response line back,
+// within [`CONNECTION_TIMEOUT`] for each
+#[cfg(windows)]
+async fn serve_pipe(pipe: tokio::net::windows::named_pipe::NamedPipeServer) -> std::io::Result<()> {
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+    use tokio::time::timeout;
+
+    let timed_out = |_| std::io::Error::new(std::io::ErrorKind::TimedOut, "the client took too long");
+    let mut reader = BufReader::new(pipe);
+    let mut line = String::new();
+    timeout(CONNECTION_TIMEOUT, (&mut reader).take(MAX_REQUEST_BYTES as u64).read_line(&mut line))
+        .await
+        .map_err(timed_out)??;
+    // Waiting on the UI thread blocks a blocking thread, not the pipe's runtime
+    let response = tokio::task::spawn_blocking(move || respond(&line))
+        .await
+        .map_err(std::io::Error::other)?;
+    let response = serde_json::to_string(&response).map_err(std::io::Error::other)?;
+    let mut pipe = reader.into_inner();
+    timeout(CONNECTION_TIMEOUT, pipe.write_all(format!("{}\n", response).as_bytes()))
+        .await
+        .map_err(timed_out)?
+}
+
+/// Listen on the named pipe, serving each client on a task of its own
+#[cfg(windows)]
+pub fn start() -> std::io::Result<PathBuf> {
+    use tokio::net::windows::named_pipe::ServerOptions;
+
+    let path = control_path();
+    let pipe_path = path.clone();
+    // The pipe is created on the listener thread's runtime, which reports
+    // back whether it could claim the name
+    let (started, result) = mpsc::channel();
+    std::thread::Builder::new()
+        .name("control".to_string())
+        .spawn(move || {
+            let runtime = match tokio::runtime::Builder::new_current_thread().enable_all().build() {
+                Ok(runtime) => runtime,
+                Err(e) => {
+                    let _ = started.send(Err(e));
+                    return;
+                }
+            };
+            runtime.block_on(async move {
+                let mut server = match ServerOptions::new().first_pipe_instance(true).create(&pipe_path) {
+                    Ok(server) => {
+                        let _ = started.send(Ok(()));
+                        server
+                    }
+                    Err(e) => {
+                        let _ = started.send(Err(e));
+                        return;
+                    }
+                };
+                loop {
+                    if let Err(e) = server.connect().await {
+                        eprintln!("Control connection failed: {}", e);
+                        continue;
+                    }
+                    let connected = server;
+                    server = match ServerOptions::new().create(&pipe_path) {
+                        Ok(server) => server,
+                        Err(e) => {
+                            eprintln!("Failed to reopen the control pipe: {}", e);
+                            return;
+                        }
+                    };
+
+                    tokio::spawn(async move {
+                        if let Err(e) = serve_pipe(connected).await {
+                            eprintln!("Control request failed: {}", e);
+                        }
+                    });
+                }
+            });
+        })?;
+
+    result
+        .recv()
+        .map_err(|_| std::io::Error::other("the control thread stopped"))??;
+    Ok(path)
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn start() -> std::io::Result<PathBuf> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "no sockets or pipes on this platform",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_cut_at_the_limit_are_refused() {
+        let line = "a".repeat(MAX_REQUEST_BYTES);
+        assert!(matches!(respond(&line), ControlResponse::Error { message } if message.starts_with("Request larger")));
+    }
+
+    #[test]
+    fn invalid_requests_are_answered_with_an_error() {
+        assert!(matches!(respond("not json\n"), ControlResponse::Error { message } if message.starts_with("Invalid request")));
+    }
+}
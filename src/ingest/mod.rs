@@ -0,0 +1,227 @@
+//! Items pushed into the app by local integrations.
+//!
+//! Integrations run off the UI thread and hand [`PushedItem`]s to [`enqueue`];
+//! [`use_inbox_pump`] moves them into the [`Inbox`] on the UI thread, where
+//! they are annotated by the item processors, show in the Inbox feed and are
+//! checked against alert rules. Control requests that need the feeds
+//! (refresh, unread counts) are passed to the handler registered with
+//! [`use_control_handler`] and answered from there.
+
+pub mod control;
+pub mod webhook;
+
+use dioxus::prelude::*;
+use dioxus_feed_control::{ControlRequest, ControlResponse};
+pub use dioxus_feed_control::PushedItem;
+use dioxus_feed_core::VirtualFeedItem;
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::assets::{BundledImages, MediaSrc};
+use crate::clock::use_clock;
+use crate::persistence::alerts::Alerts;
+use crate::processors::Processors;
+use crate::startup::StartupTrace;
+use crate::store::FeedItems;
+use webhook::WebhookEndpoint;
+
+/// How often queued items and control requests are picked up
+const PUMP_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Most items kept in the inbox; older ones are dropped
+const MAX_INBOX_ITEMS: usize = 1000;
+
+/// Longest text accepted for a pushed item
+pub const MAX_TEXT_CHARS: usize = 4000;
+
+impl PushedItem {
+    fn into_feed_item(self, id: String) -> VirtualFeedItem {
+        let text: String = self.text.chars().take(MAX_TEXT_CHARS).collect();
+        let mut item = VirtualFeedItem::new_with_random_image(id, text);
+        if let Some(author) = self.author.filter(|author| !author.trim().is_empty()) {
+            item = item.with_author(author);
+        }
+        if let Some(link) = self.link.filter(|link| link.starts_with("http://") || link.starts_with("https://")) {
+            item = item.with_link(link);
+        }
+        // The bundled image stays as the fallback of one that fails
+        let image = self.image.and_then(|image| {
+            if image.starts_with("data:image/") {
+                Some(image)
+            } else {
+                MediaSrc::remote(&image).ok().map(|src| src.to_string())
+            }
+        });
+        if let Some(image) = image {
+            let bundled = std::mem::replace(&mut item.image_url, image);
+            item.image_fallbacks.insert(0, bundled);
+        }
+        item
+    }
+}
+
+// Items handed over by integrations, waiting for the UI thread
+static QUEUE: Mutex<Vec<PushedItem>> = Mutex::new(Vec::new());
+
+/// Queue items for the inbox; callable from any thread
+pub fn enqueue(items: Vec<PushedItem>) {
+    match QUEUE.lock() {
+        Ok(mut queue) => queue.extend(items),
+        Err(e) => eprintln!("Failed to queue pushed items: {}", e),
+    }
+}
+
+fn take_queued() -> Vec<PushedItem> {
+    QUEUE.lock().map(|mut queue| std::mem::take(&mut *queue)).unwrap_or_default()
+}
+
+// A control request waiting for the UI thread, with where to send its answer
+struct PendingRequest {
+    request: ControlRequest,
+    reply: Sender<ControlResponse>,
+}
+
+static REQUESTS: Mutex<Vec<PendingRequest>> = Mutex::new(Vec::new());
+
+/// Queue a request for the handler on the UI thread; the answer arrives on
+/// `reply`. Callable from any thread.
+pub fn forward(request: ControlRequest, reply: Sender<ControlResponse>) {
+    match REQUESTS.lock() {
+        Ok(mut requests) => requests.push(PendingRequest { request, reply }),
+        Err(e) => eprintln!("Failed to queue control request: {}", e),
+    }
+}
+
+/// Answer forwarded control requests with `handler` on the UI thread. Only
+/// one component should register a handler.
+pub fn use_control_handler(handler: impl FnMut(ControlRequest) -> ControlResponse + 'static) {
+    let clock = use_clock();
+    let mut handler = use_hook(|| CopyValue::new(handler));
+    use_future(move || {
+        let clock = clock.clone();
+        async move {
+            loop {
+                clock.sleep(PUMP_INTERVAL).await;
+                let pending = REQUESTS.lock().map(|mut requests| std::mem::take(&mut *requests)).unwrap_or_default();
+                for PendingRequest { request, reply } in pending {
+                    // The client may have given up waiting
+                    let response = (handler.write())(request);
+                    let _ = reply.send(response);
+                }
+            }
+        }
+    });
+}
+
+/// Items pushed this session, oldest first.
+///
+/// Provided once at the app root with `use_context_provider(Inbox::new)`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Inbox {
+    items: Signal<FeedItems>,
+    next_id: CopyValue<u64>,
+}
+
+impl Inbox {
+    pub fn new() -> Self {
+        Self {
+            items: Signal::new(FeedItems::new()),
+            next_id: CopyValue::new(0),
+        }
+    }
+
+    pub fn items(&self) -> Signal<FeedItems> {
+        self.items
+    }
+
+    // Append pushed items, annotated by `processors`, returning them as feed items
+    fn push(&mut self, pushed: Vec<PushedItem>, now_ms: i64, processors: Option<Processors>) -> Vec<VirtualFeedItem> {
+        let mut items: Vec<VirtualFeedItem> = pushed
+            .into_iter()
+            .map(|pushed| {
+                let id = format!("push_{}_{}", *self.next_id.peek(), now_ms);
+                *self.next_id.write() += 1;
+                pushed.into_feed_item(id).with_published_at(now_ms)
+            })
+            .collect();
+        if let Some(mut processors) = processors {
+            processors.annotate(items.iter_mut());
+        }
+
+        let mut inbox = self.items.write();
+        inbox.extend(items.iter().cloned());
+        let excess = inbox.len().saturating_sub(MAX_INBOX_ITEMS);
+        if excess > 0 {
+            *inbox = inbox.split_off(excess);
+        }
+        items
+    }
+}
+
+impl Default for Inbox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Integrations running this session
+#[derive(Clone, PartialEq, Default)]
+pub struct Integrations {
+    pub webhook: Option<WebhookEndpoint>,
+    /// Socket or pipe the control channel listens on
+    pub control: Option<PathBuf>,
+}
+
+impl Integrations {
+    /// Whether any integration can push items
+    pub fn any(&self) -> bool {
+        self.webhook.is_some() || self.control.is_some()
+    }
+}
+
+/// Start the integrations enabled for this launch and move what they push
+/// into the inbox. Call once at the app root, below the `Inbox`, `Alerts`
+/// and `Processors` providers.
+pub fn use_inbox_pump() -> Integrations {
+    let mut inbox = use_context::<Inbox>();
+    let alerts = try_use_context::<Alerts>();
+    let processors = try_use_context::<Processors>();
+    let startup = try_use_context::<StartupTrace>();
+    let clock = use_clock();
+    let integrations = use_hook(|| Integrations {
+        webhook: webhook::start_from_env(),
+        control: control::start_from_env(),
+    });
+    // The endpoint file holds the session's token, which dies with the app
+    let webhook_started = integrations.webhook.is_some();
+    use_drop(move || {
+        if webhook_started {
+            webhook::stop();
+        }
+    });
+
+    use_future(move || {
+        let clock = clock.clone();
+        async move {
+            // Pushed items queue up until the first frame is on screen
+            if let Some(startup) = startup {
+                startup.after_first_paint(&clock).await;
+            }
+            loop {
+                clock.sleep(PUMP_INTERVAL).await;
+                let pushed = take_queued();
+                if pushed.is_empty() {
+                    continue;
+                }
+                let items = inbox.push(pushed, clock.utc_now().timestamp_millis(), processors);
+                if let Some(mut alerts) = alerts {
+                    alerts.check(&items);
+                }
+            }
+        }
+    });
+
+    integrations
+}
@@ -0,0 +1,457 @@
+//! Opt-in localhost HTTP listener for pushing items into the inbox.
+//!
+//! Launching with `DIOXUS_FEED_WEBHOOK=1` binds `127.0.0.1` on a random port
+//! and generates a token for the session. Scripts find both in
+//! `webhook-endpoint.txt` in the data directory (the URL on the first line,
+//! the token on the second) and send
+//!
+//! ```text
+//! POST /items
+//! Authorization: Bearer <token>
+//! Content-Type: application/json
+//!
+//! {"text": "Build finished", "author": "ci", "link": "https://…"}
+//! ```
+//!
+//! with one item or an array of them. The endpoint file is readable by its
+//! owner only and removed when the app quits. Each connection is served on
+//! its own thread, a few at a time, and has a few seconds to send a request
+//! of bounded size.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::{enqueue, PushedItem};
+use crate::auth::oauth::random_token;
+use crate::persistence::data_dir;
+
+/// Environment variable enabling the listener when set to `1`
+pub const WEBHOOK_ENV_VAR: &str = "DIOXUS_FEED_WEBHOOK";
+
+/// File in the data directory scripts read the endpoint from
+const ENDPOINT_FILE: &str = "webhook-endpoint.txt";
+
+/// Largest request body accepted
+const MAX_BODY_BYTES: usize = 64 * 1024;
+
+/// Most items accepted in one request
+const MAX_ITEMS_PER_REQUEST: usize = 100;
+
+/// How long a client may take to send its whole request
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Longest request or header line accepted
+const MAX_LINE_BYTES: usize = 8 * 1024;
+
+/// Most headers accepted in one request
+const MAX_HEADERS: usize = 64;
+
+/// Largest size of the request line and headers together
+const MAX_HEADER_BYTES: usize = 32 * 1024;
+
+/// Most connections served at once; more are closed unanswered
+const MAX_CONNECTIONS: usize = 8;
+
+/// Where the listener accepts items, and the token it requires
+#[derive(Clone, PartialEq, Debug)]
+pub struct WebhookEndpoint {
+    pub port: u16,
+    pub token: String,
+}
+
+impl WebhookEndpoint {
+    pub fn url(&self) -> String {
+        format!("http://127.0.0.1:{}/items", self.port)
+    }
+}
+
+/// Start the listener if the environment asks for it
+pub fn start_from_env() -> Option<WebhookEndpoint> {
+    if std::env::var(WEBHOOK_ENV_VAR).ok()? != "1" {
+        return None;
+    }
+    // Browsers can't listen on sockets
+    if cfg!(target_arch = "wasm32") {
+        eprintln!("{} is not supported in web builds", WEBHOOK_ENV_VAR);
+        return None;
+    }
+    match start() {
+        Ok(endpoint) => Some(endpoint),
+        Err(e) => {
+            eprintln!("Failed to start the webhook listener: {}", e);
+            None
+        }
+    }
+}
+
+/// Bind a random localhost port and serve it on a background thread
+pub fn start() -> std::io::Result<WebhookEndpoint> {
+    let listener = TcpListener::bind(("127.0.0.1", 0))?;
+    let endpoint = WebhookEndpoint {
+        port: listener.local_addr()?.port(),
+        token: random_token(24),
+    };
+    let contents = format!("{}\n{}\n", endpoint.url(), endpoint.token);
+    if let Err(e) = write_endpoint_file(contents.as_bytes()) {
+        eprintln!("Failed to write {}: {}", ENDPOINT_FILE, e);
+    }
+
+    let token: Arc<str> = endpoint.token.clone().into();
+    let open = Arc::new(AtomicUsize::new(0));
+    std::thread::Builder::new()
+        .name("webhook".to_string())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                let stream = match stream {
+                    Ok(stream) => stream,
+                    Err(e) => {
+                        eprintln!("Webhook connection failed: {}", e);
+                        continue;
+                    }
+                };
+                // Dropping the stream closes connections over the limit
+                if open.fetch_add(1, Ordering::AcqRel) >= MAX_CONNECTIONS {
+                    open.fetch_sub(1, Ordering::AcqRel);
+                    continue;
+                }
+                let (token, counter) = (token.clone(), open.clone());
+                let spawned = std::thread::Builder::new()
+                    .name("webhook-request".to_string())
+                    .spawn(move || {
+                        if let Err(e) = handle(stream, &token) {
+                            eprintln!("Webhook request failed: {}", e);
+                        }
+                        counter.fetch_sub(1, Ordering::AcqRel);
+                    });
+                if let Err(e) = spawned {
+                    eprintln!("Webhook connection failed: {}", e);
+                    open.fetch_sub(1, Ordering::AcqRel);
+                }
+            }
+        })?;
+    Ok(endpoint)
+}
+
+/// Remove the endpoint file, so the token doesn't outlive the session
+pub fn stop() {
+    let path = endpoint_path();
+    if let Err(e) = std::fs::remove_file(&path) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            eprintln!("Failed to remove {}: {}", path.display(), e);
+        }
+    }
+}
+
+fn endpoint_path() -> PathBuf {
+    data_dir().join(ENDPOINT_FILE)
+}
+
+// Create the endpoint file readable and writable by the owner only, since
+// anyone who can read the token can post to the inbox
+fn write_endpoint_file(contents: &[u8]) -> std::io::Result<()> {
+    let path = endpoint_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    // A file left by a run that didn't quit cleanly keeps its old permissions
+    // if opened as it is, so it is replaced
+    match std::fs::remove_file(&path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e),
+        _ => {}
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&path)?;
+    file.write_all(contents)?;
+    file.flush()
+}
+
+// Reads from a connection that fail once its deadline has passed, however
+// slowly the client trickles bytes in
+struct DeadlineReader {
+    stream: TcpStream,
+    deadline: Instant,
+}
+
+impl Read for DeadlineReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(std::io::Error::new(std::io::ErrorKind::TimedOut, "request took too long"));
+        }
+        self.stream.set_read_timeout(Some(remaining))?;
+        self.stream.read(buf)
+    }
+}
+
+// Read one request, queue its items and answer it
+fn handle(stream: TcpStream, token: &str) -> std::io::Result<()> {
+    let mut reader = BufReader::new(DeadlineReader {
+        stream: stream.try_clone()?,
+        deadline: Instant::now() + READ_TIMEOUT,
+    });
+    let (status, message) = match read_request(&mut reader, token) {
+        Ok(items) => {
+            let count = items.len();
+            enqueue(items);
+            ("202 Accepted", format!("Queued {} items", count))
+        }
+        Err(rejection) => (rejection.status(), rejection.to_string()),
+    };
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        message.len(),
+        message
+    )?;
+    stream.flush()
+}
+
+/// Why a request was refused
+#[derive(Debug, Clone, PartialEq)]
+enum Rejection {
+    Malformed(String),
+    NotFound,
+    Unauthorized,
+    HeadersTooLarge,
+    TooLarge,
+    InvalidItems(String),
+}
+
+impl Rejection {
+    fn status(&self) -> &'static str {
+        match self {
+            Rejection::Malformed(_) | Rejection::InvalidItems(_) => "400 Bad Request",
+            Rejection::NotFound => "404 Not Found",
+            Rejection::Unauthorized => "401 Unauthorized",
+            Rejection::HeadersTooLarge => "431 Request Header Fields Too Large",
+            Rejection::TooLarge => "413 Payload Too Large",
+        }
+    }
+}
+
+impl std::fmt::Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::Malformed(msg) => write!(f, "Malformed request: {}", msg),
+            Rejection::NotFound => write!(f, "Only POST /items is served"),
+            Rejection::Unauthorized => write!(f, "Missing or wrong bearer token"),
+            Rejection::HeadersTooLarge => write!(
+                f,
+                "More than {} headers, a line over {} bytes or headers over {} bytes",
+                MAX_HEADERS, MAX_LINE_BYTES, MAX_HEADER_BYTES
+            ),
+            Rejection::TooLarge => write!(f, "Body larger than {} bytes", MAX_BODY_BYTES),
+            Rejection::InvalidItems(msg) => write!(f, "Invalid items: {}", msg),
+        }
+    }
+}
+
+// Read a line of the request line and headers, charging it to what is left
+// of the header budget
+fn read_header_line(reader: &mut impl BufRead, line: &mut String, budget: &mut usize) -> Result<(), Rejection> {
+    line.clear();
+    let limit = MAX_LINE_BYTES.min(*budget);
+    if limit == 0 {
+        return Err(Rejection::HeadersTooLarge);
+    }
+    let read = reader
+        .by_ref()
+        .take(limit as u64)
+        .read_line(line)
+        .map_err(|e| Rejection::Malformed(e.to_string()))?;
+    if read == 0 {
+        return Err(Rejection::Malformed("connection closed before the headers ended".to_string()));
+    }
+    // A line filling the limit without ending was cut short
+    if read == limit && !line.ends_with('\n') {
+        return Err(Rejection::HeadersTooLarge);
+    }
+    *budget -= read;
+    Ok(())
+}
+
+// Parse the request and its items
+fn read_request(reader: &mut impl BufRead, token: &str) -> Result<Vec<PushedItem>, Rejection> {
+    let mut budget = MAX_HEADER_BYTES;
+    let mut line = String::new();
+    read_header_line(reader, &mut line, &mut budget)?;
+    let mut parts = line.split_whitespace();
+    let (method, path) = (parts.next().map(str::to_string), parts.next().map(str::to_string));
+
+    let mut content_length = 0;
+    let mut authorized = false;
+    let mut headers = 0;
+    loop {
+        read_header_line(reader, &mut line, &mut budget)?;
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        headers += 1;
+        if headers > MAX_HEADERS {
+            return Err(Rejection::HeadersTooLarge);
+        }
+        let Some((name, value)) = header.split_once(':') else {
+            return Err(Rejection::Malformed(format!("bad header {}", header)));
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = value.parse().map_err(|_| Rejection::Malformed("bad Content-Length".to_string()))?;
+        } else if name.eq_ignore_ascii_case("authorization") {
+            authorized = value
+                .strip_prefix("Bearer ")
+                .is_some_and(|sent| constant_time_eq(sent.trim().as_bytes(), token.as_bytes()));
+        }
+    }
+
+    if method.as_deref() != Some("POST") || path.as_deref() != Some("/items") {
+        return Err(Rejection::NotFound);
+    }
+    if !authorized {
+        return Err(Rejection::Unauthorized);
+    }
+    if content_length > MAX_BODY_BYTES {
+        return Err(Rejection::TooLarge);
+    }
+    let mut body = vec![0; content_length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|e| Rejection::Malformed(e.to_string()))?;
+
+    parse_items(&body)
+}
+
+// One item or an array of them
+fn parse_items(body: &[u8]) -> Result<Vec<PushedItem>, Rejection> {
+    let value: serde_json::Value = serde_json::from_slice(body).map_err(|e| Rejection::InvalidItems(e.to_string()))?;
+    let items: Vec<PushedItem> = match value {
+        serde_json::Value::Array(_) => serde_json::from_value(value),
+        _ => serde_json::from_value(value).map(|item| vec![item]),
+    }
+    .map_err(|e| Rejection::InvalidItems(e.to_string()))?;
+
+    if items.len() > MAX_ITEMS_PER_REQUEST {
+        return Err(Rejection::InvalidItems(format!("more than {} items", MAX_ITEMS_PER_REQUEST)));
+    }
+    if items.iter().any(|item| item.text.trim().is_empty()) {
+        return Err(Rejection::InvalidItems("an item has no text".to_string()));
+    }
+    Ok(items)
+}
+
+// Compare without stopping at the first difference, so response times don't
+// reveal how much of a guessed token was right
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    const TOKEN: &str = "secret";
+
+    fn request(headers: &str, body: &str) -> Cursor<Vec<u8>> {
+        Cursor::new(
+            format!(
+                "POST /items HTTP/1.1\r\n{}Content-Length: {}\r\n\r\n{}",
+                headers,
+                body.len(),
+                body
+            )
+            .into_bytes(),
+        )
+    }
+
+    #[test]
+    fn parses_one_item_or_an_array() {
+        let items = parse_items(br#"{"text": "Build finished", "author": "ci"}"#).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].text, "Build finished");
+        assert_eq!(items[0].author.as_deref(), Some("ci"));
+
+        let items = parse_items(br#"[{"text": "one"}, {"text": "two", "link": "https://example.com"}]"#).unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1].link.as_deref(), Some("https://example.com"));
+    }
+
+    #[test]
+    fn rejects_invalid_items() {
+        assert!(matches!(parse_items(b"not json"), Err(Rejection::InvalidItems(_))));
+        assert!(matches!(parse_items(br#"{"author": "ci"}"#), Err(Rejection::InvalidItems(_))));
+        assert!(matches!(parse_items(br#"{"text": "   "}"#), Err(Rejection::InvalidItems(_))));
+
+        let too_many = format!("[{}]", vec![r#"{"text": "x"}"#; MAX_ITEMS_PER_REQUEST + 1].join(","));
+        assert!(matches!(parse_items(too_many.as_bytes()), Err(Rejection::InvalidItems(_))));
+    }
+
+    #[test]
+    fn reads_an_authorized_request() {
+        let mut reader = request("Authorization: Bearer secret\r\n", r#"{"text": "hi"}"#);
+        let items = read_request(&mut reader, TOKEN).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].text, "hi");
+    }
+
+    #[test]
+    fn rejects_wrong_tokens_and_paths() {
+        let mut reader = request("Authorization: Bearer guess\r\n", r#"{"text": "hi"}"#);
+        assert_eq!(read_request(&mut reader, TOKEN), Err(Rejection::Unauthorized));
+
+        let mut reader = request("", r#"{"text": "hi"}"#);
+        assert_eq!(read_request(&mut reader, TOKEN), Err(Rejection::Unauthorized));
+
+        let mut reader = Cursor::new(b"GET /items HTTP/1.1\r\n\r\n".to_vec());
+        assert_eq!(read_request(&mut reader, TOKEN), Err(Rejection::NotFound));
+    }
+
+    #[test]
+    fn rejects_oversized_headers_and_bodies() {
+        let long_line = format!("X-Padding: {}\r\n", "a".repeat(MAX_LINE_BYTES));
+        let mut reader = request(&long_line, "{}");
+        assert_eq!(read_request(&mut reader, TOKEN), Err(Rejection::HeadersTooLarge));
+
+        let many = "X-Header: 1\r\n".repeat(MAX_HEADERS + 1);
+        let mut reader = request(&many, "{}");
+        assert_eq!(read_request(&mut reader, TOKEN), Err(Rejection::HeadersTooLarge));
+
+        let line = format!("X-Padding: {}\r\n", "a".repeat(MAX_LINE_BYTES / 2));
+        let mut reader = request(&line.repeat(MAX_HEADER_BYTES / line.len() + 1), "{}");
+        assert_eq!(read_request(&mut reader, TOKEN), Err(Rejection::HeadersTooLarge));
+
+        let mut reader = Cursor::new(
+            format!(
+                "POST /items HTTP/1.1\r\nAuthorization: Bearer secret\r\nContent-Length: {}\r\n\r\n",
+                MAX_BODY_BYTES + 1
+            )
+            .into_bytes(),
+        );
+        assert_eq!(read_request(&mut reader, TOKEN), Err(Rejection::TooLarge));
+    }
+
+    #[test]
+    fn rejects_requests_cut_short() {
+        let mut reader = Cursor::new(b"POST /items HTTP/1.1\r\nAuthorization: Bearer secret\r\n".to_vec());
+        assert!(matches!(read_request(&mut reader, TOKEN), Err(Rejection::Malformed(_))));
+    }
+
+    #[test]
+    fn compares_tokens_exactly() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secret2"));
+    }
+}
@@ -0,0 +1,388 @@
+//! Link policy for links inside item and article content.
+//!
+//! Every click on such a link is intercepted and classified here instead of
+//! being left to the webview: http(s) links open in the system browser,
+//! `myapp://` links are routed inside the app, links to anchors of the page
+//! being read scroll it, and everything else (`file://`, `javascript:`,
+//! unknown schemes) is blocked. External links are cleaned of trackers
+//! first.
+
+use dioxus::prelude::*;
+use regex::Regex;
+use std::sync::OnceLock;
+
+use crate::error::{report, FeedError};
+use crate::persistence::privacy::PrivacySettings;
+use crate::urlcleaner::{clean, clean_url, resolve_redirects};
+
+/// Scheme of links into the app itself
+pub const APP_SCHEME: &str = "myapp";
+
+/// Place in the app a `myapp://` link points to
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppRoute {
+    /// `myapp://feed/<key>`: a feed tab, by the key of its store
+    Feed(String),
+    /// `myapp://item/<key>/<id>`: an item within a feed
+    Item { feed: String, id: String },
+}
+
+impl AppRoute {
+    fn parse(url: &url::Url) -> Option<Self> {
+        let segments: Vec<String> = url
+            .path_segments()?
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| urlencoding::decode(segment).map(|decoded| decoded.into_owned()))
+            .collect::<Result<_, _>>()
+            .ok()?;
+        match (url.host_str()?, segments.as_slice()) {
+            ("feed", [feed]) => Some(AppRoute::Feed(feed.clone())),
+            ("item", [feed, id]) => Some(AppRoute::Item {
+                feed: feed.clone(),
+                id: id.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Why a link was not followed
+#[derive(Debug, Clone, PartialEq)]
+pub enum LinkBlocked {
+    LocalFile,
+    Script(String),
+    UnsupportedScheme(String),
+    UnknownRoute(String),
+    Invalid(String),
+}
+
+impl std::fmt::Display for LinkBlocked {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LinkBlocked::LocalFile => write!(f, "Links to local files are not opened"),
+            LinkBlocked::Script(scheme) => write!(f, "Scriptable {}: links are not opened", scheme),
+            LinkBlocked::UnsupportedScheme(scheme) => write!(f, "Unsupported link scheme: {}", scheme),
+            LinkBlocked::UnknownRoute(url) => write!(f, "No app page for {}", url),
+            LinkBlocked::Invalid(msg) => write!(f, "Invalid link: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for LinkBlocked {}
+
+/// What clicking a link does
+#[derive(Debug, Clone, PartialEq)]
+pub enum LinkAction {
+    /// Open in the system browser
+    External(String),
+    /// Navigate within the app
+    Route(AppRoute),
+    /// Scroll the page being read to this anchor
+    Anchor(String),
+    Blocked(LinkBlocked),
+}
+
+/// Decide what clicking `href` does. Relative links resolve against `base`,
+/// the page the link appears in; without one only absolute links and bare
+/// `#anchor`s are followed.
+pub fn classify(href: &str, base: Option<&url::Url>) -> LinkAction {
+    let href = href.trim();
+    if let Some(anchor) = href.strip_prefix('#') {
+        return LinkAction::Anchor(anchor.to_string());
+    }
+
+    let parsed = match base {
+        Some(base) => base.join(href),
+        None => url::Url::parse(href),
+    };
+    let url = match parsed {
+        Ok(url) => url,
+        Err(e) => return LinkAction::Blocked(LinkBlocked::Invalid(e.to_string())),
+    };
+
+    // Links back into the page being read, with or without its address
+    if let (Some(base), Some(fragment)) = (base, url.fragment().filter(|fragment| !fragment.is_empty())) {
+        if url.as_str().split('#').next() == base.as_str().split('#').next() {
+            return LinkAction::Anchor(fragment.to_string());
+        }
+    }
+
+    match url.scheme() {
+        "http" | "https" => LinkAction::External(clean(&url).to_string()),
+        APP_SCHEME => match AppRoute::parse(&url) {
+            Some(route) => LinkAction::Route(route),
+            None => LinkAction::Blocked(LinkBlocked::UnknownRoute(url.to_string())),
+        },
+        "file" => LinkAction::Blocked(LinkBlocked::LocalFile),
+        scheme @ ("javascript" | "vbscript" | "data") => LinkAction::Blocked(LinkBlocked::Script(scheme.to_string())),
+        scheme => LinkAction::Blocked(LinkBlocked::UnsupportedScheme(scheme.to_string())),
+    }
+}
+
+/// `text` split into runs, each with the URL it links to when it is one.
+///
+/// Finds http(s) and `myapp://` URLs in plain text such as item content;
+/// trailing punctuation is left out of the link, and links are shown and
+/// followed cleaned of trackers.
+pub fn link_segments(text: &str) -> Vec<(String, Option<String>)> {
+    static URL: OnceLock<Regex> = OnceLock::new();
+    let pattern = URL.get_or_init(|| {
+        Regex::new(&format!(r#"(?i)\b(?:https?|{})://[^\s<>"]+"#, APP_SCHEME)).expect("static regex should be valid")
+    });
+
+    let mut segments = Vec::new();
+    let mut position = 0;
+    for found in pattern.find_iter(text) {
+        let url = found.as_str().trim_end_matches(['.', ',', ';', ':', '!', '?', ')', ']', '}', '\'']);
+        if found.start() > position {
+            segments.push((text[position..found.start()].to_string(), None));
+        }
+        let cleaned = clean_url(url);
+        segments.push((cleaned.clone(), Some(cleaned)));
+        position = found.start() + url.len();
+    }
+    if position < text.len() || segments.is_empty() {
+        segments.push((text[position..].to_string(), None));
+    }
+    segments
+}
+
+/// Requests to navigate within the app, from `myapp://` links.
+///
+/// Provided by the tab bar, which owns the tabs and the list controller that
+/// a route resolves to, and takes each request as it arrives.
+#[derive(Clone, Copy, PartialEq)]
+pub struct LinkRouter {
+    pending: Signal<Option<AppRoute>>,
+}
+
+impl LinkRouter {
+    pub fn new() -> Self {
+        Self {
+            pending: Signal::new(None),
+        }
+    }
+
+    pub fn navigate(&mut self, route: AppRoute) {
+        self.pending.set(Some(route));
+    }
+
+    /// Take the latest navigation request, subscribing to new ones
+    pub fn take(&mut self) -> Option<AppRoute> {
+        let route = self.pending.read().clone();
+        if route.is_some() {
+            self.pending.set(None);
+        }
+        route
+    }
+}
+
+impl Default for LinkRouter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Open `url` in the system browser
+#[cfg(feature = "desktop")]
+pub fn open_external(url: &str) {
+    if let Err(e) = webbrowser::open(url) {
+        report(FeedError::OpenLink { url: url.to_string(), message: e.to_string() });
+    }
+}
+
+/// Open `url` in a new browser tab
+#[cfg(not(feature = "desktop"))]
+pub fn open_external(url: &str) {
+    let eval = document::eval(
+        r#"
+        const url = await dioxus.recv();
+        window.open(url, "_blank", "noopener,noreferrer");
+        "#,
+    );
+    if let Err(e) = eval.send(url.to_string()) {
+        report(FeedError::OpenLink { url: url.to_string(), message: e.to_string() });
+    }
+}
+
+/// Handle for following content links through the policy
+#[derive(Clone, Copy, PartialEq)]
+pub struct LinkPolicy {
+    router: Option<LinkRouter>,
+    privacy: Option<PrivacySettings>,
+}
+
+impl LinkPolicy {
+    /// Follow a clicked link. In-page anchors are returned for the caller to
+    /// scroll to, since only it knows the page; blocked links are reported.
+    pub fn follow(&self, href: &str, base: Option<&url::Url>) -> Option<String> {
+        match classify(href, base) {
+            LinkAction::External(url) if self.privacy.is_some_and(|privacy| privacy.resolve_redirects()) => {
+                spawn(async move {
+                    match resolve_redirects(&url).await {
+                        Ok(resolved) => open_external(&resolved),
+                        Err(e) => {
+                            eprintln!("Couldn't resolve redirects of {}: {}", url, e);
+                            open_external(&url);
+                        }
+                    }
+                });
+            }
+            LinkAction::External(url) => open_external(&url),
+            LinkAction::Route(route) => match self.router {
+                Some(mut router) => router.navigate(route),
+                None => eprintln!("Blocked link {}: no router to follow it", href),
+            },
+            LinkAction::Anchor(anchor) => return Some(anchor),
+            LinkAction::Blocked(reason) => report(FeedError::LinkBlocked { href: href.to_string(), source: reason }),
+        }
+        None
+    }
+}
+
+/// The link policy, routing `myapp://` links through the app's [`LinkRouter`]
+pub fn use_link_policy() -> LinkPolicy {
+    LinkPolicy {
+        router: try_use_context::<LinkRouter>(),
+        privacy: try_use_context::<PrivacySettings>(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::in_dom;
+
+    fn page() -> url::Url {
+        url::Url::parse("https://example.com/posts/1").unwrap()
+    }
+
+    fn blocked(href: &str) -> LinkBlocked {
+        match classify(href, Some(&page())) {
+            LinkAction::Blocked(reason) => reason,
+            action => panic!("{} wasn't blocked: {:?}", href, action),
+        }
+    }
+
+    #[test]
+    fn local_and_scriptable_links_are_blocked() {
+        assert_eq!(blocked("file:///etc/passwd"), LinkBlocked::LocalFile);
+        assert_eq!(blocked("javascript:alert(1)"), LinkBlocked::Script("javascript".to_string()));
+        assert_eq!(blocked("vbscript:msgbox(1)"), LinkBlocked::Script("vbscript".to_string()));
+        assert_eq!(blocked("data:text/html,<script>alert(1)</script>"), LinkBlocked::Script("data".to_string()));
+    }
+
+    #[test]
+    fn unknown_schemes_are_blocked() {
+        assert_eq!(blocked("ftp://example.com/file"), LinkBlocked::UnsupportedScheme("ftp".to_string()));
+        assert_eq!(blocked("mailto:someone@example.com"), LinkBlocked::UnsupportedScheme("mailto".to_string()));
+    }
+
+    #[test]
+    fn schemes_are_matched_whatever_their_case_or_padding() {
+        assert_eq!(blocked("JavaScript:alert(1)"), LinkBlocked::Script("javascript".to_string()));
+        assert_eq!(blocked("  javascript:alert(1)\n"), LinkBlocked::Script("javascript".to_string()));
+        assert_eq!(blocked("java\tscript:alert(1)"), LinkBlocked::Script("javascript".to_string()));
+        assert_eq!(blocked("FILE:///etc/passwd"), LinkBlocked::LocalFile);
+        assert_eq!(
+            classify(" HTTPS://Example.com/a ", None),
+            LinkAction::External("https://example.com/a".to_string())
+        );
+    }
+
+    #[test]
+    fn app_links_are_routed() {
+        assert_eq!(
+            classify("myapp://feed/home", None),
+            LinkAction::Route(AppRoute::Feed("home".to_string()))
+        );
+        assert_eq!(
+            classify("MYAPP://item/folder%3ANews/abc", None),
+            LinkAction::Route(AppRoute::Item { feed: "folder:News".to_string(), id: "abc".to_string() })
+        );
+        assert_eq!(blocked("myapp://settings"), LinkBlocked::UnknownRoute("myapp://settings".to_string()));
+        assert!(matches!(blocked("myapp://item/home"), LinkBlocked::UnknownRoute(_)));
+    }
+
+    #[test]
+    fn relative_links_resolve_against_the_page() {
+        let base = page();
+        assert_eq!(
+            classify("../about", Some(&base)),
+            LinkAction::External("https://example.com/about".to_string())
+        );
+        assert_eq!(
+            classify("/feed.xml", Some(&base)),
+            LinkAction::External("https://example.com/feed.xml".to_string())
+        );
+        assert!(matches!(classify("about", None), LinkAction::Blocked(LinkBlocked::Invalid(_))));
+    }
+
+    #[test]
+    fn anchors_into_the_page_scroll_it() {
+        let base = page();
+        assert_eq!(classify("#notes", None), LinkAction::Anchor("notes".to_string()));
+        assert_eq!(classify("#notes", Some(&base)), LinkAction::Anchor("notes".to_string()));
+        assert_eq!(
+            classify("https://example.com/posts/1#notes", Some(&base)),
+            LinkAction::Anchor("notes".to_string())
+        );
+        assert_eq!(
+            classify("https://example.com/posts/2#notes", Some(&base)),
+            LinkAction::External("https://example.com/posts/2#notes".to_string())
+        );
+    }
+
+    #[test]
+    fn urls_in_text_leave_out_trailing_punctuation() {
+        assert_eq!(
+            link_segments("See https://example.com/a."),
+            vec![
+                ("See ".to_string(), None),
+                ("https://example.com/a".to_string(), Some("https://example.com/a".to_string())),
+                (".".to_string(), None),
+            ]
+        );
+        assert_eq!(
+            link_segments("(myapp://feed/home), then"),
+            vec![
+                ("(".to_string(), None),
+                ("myapp://feed/home".to_string(), Some("myapp://feed/home".to_string())),
+                ("), then".to_string(), None),
+            ]
+        );
+        assert_eq!(
+            link_segments("https://example.com/a?utm_source=feed!")[0].1.as_deref(),
+            Some("https://example.com/a")
+        );
+        for punctuation in [",", ";", ":", "!", "?", "]", "}", "'"] {
+            let text = format!("https://example.com/a{}", punctuation);
+            let segments = link_segments(&text);
+            assert_eq!(segments[0].1.as_deref(), Some("https://example.com/a"), "{}", text);
+            assert_eq!(segments[1].0, punctuation);
+        }
+    }
+
+    #[test]
+    fn text_without_urls_is_one_segment() {
+        assert_eq!(link_segments("no links here"), vec![("no links here".to_string(), None)]);
+        assert_eq!(link_segments(""), vec![(String::new(), None)]);
+    }
+
+    #[test]
+    fn following_routes_anchors_and_blocks() {
+        in_dom(|| {
+            let mut router = LinkRouter::new();
+            let policy = LinkPolicy { router: Some(router), privacy: None };
+            let base = page();
+
+            assert_eq!(policy.follow("#notes", Some(&base)), Some("notes".to_string()));
+            assert_eq!(policy.follow("myapp://feed/home", None), None);
+            assert_eq!(router.take(), Some(AppRoute::Feed("home".to_string())));
+
+            assert_eq!(policy.follow("javascript:alert(1)", Some(&base)), None);
+            assert_eq!(policy.follow("file:///etc/passwd", Some(&base)), None);
+            assert_eq!(router.take(), None);
+        });
+    }
+}
@@ -1,64 +1,73 @@
+// The minimal preset shows the list without the feed app around it, and
+// uses only part of the list's API
+#![cfg_attr(not(feature = "full"), allow(dead_code))]
+
+#[cfg(feature = "full")]
+mod app;
+#[cfg(feature = "full")]
+mod assets;
+#[cfg(feature = "full")]
+mod auth;
+#[cfg(feature = "full")]
+mod captions;
+mod clock;
+#[cfg(feature = "full")]
+mod commands;
 mod components;
+#[cfg(feature = "full")]
+mod e2e;
+#[cfg(feature = "full")]
+mod error;
+#[cfg(feature = "full")]
+mod gallery;
+#[cfg(feature = "full")]
+mod ingest;
+#[cfg(feature = "full")]
+mod links;
+#[cfg(not(feature = "full"))]
+mod minimal;
+#[cfg(feature = "full")]
+mod notify;
+#[cfg(feature = "full")]
+mod persistence;
+#[cfg(feature = "full")]
+mod processors;
+#[cfg(feature = "desktop")]
 mod protocol;
+#[cfg(feature = "full")]
+mod reader;
+#[cfg(feature = "full")]
+mod remote;
+#[cfg(feature = "full")]
+mod shortcuts;
+#[cfg(feature = "full")]
+mod sources;
+#[cfg(feature = "full")]
+mod startup;
+#[cfg(feature = "full")]
+mod store;
+#[cfg(feature = "full")]
+mod summarizer;
+#[cfg(feature = "full")]
+mod urlcleaner;
+#[cfg(feature = "full")]
+mod work;
 
 use dioxus::prelude::*;
-use components::virtual_list::VirtualList;
-use protocol::myprotocol::register_myprotocol_handler;
 
 const MAIN_CSS: Asset = asset!("/assets/main.css");
 
 fn main() {
-    dioxus::LaunchBuilder::desktop()
-        .launch(App);
-}
+    #[cfg(feature = "full")]
+    startup::mark_launch();
 
-#[component]
-fn App() -> Element {
-    register_myprotocol_handler(vec!["assets".to_string()]); 
+    #[cfg(feature = "desktop")]
+    dioxus::LaunchBuilder::desktop()
+        .launch(app::App);
 
-    rsx! {
-        document::Link { rel: "stylesheet", href: MAIN_CSS }
-        
-        div {
-            style: "
-            	font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif;
-                box-sizing: border-box;
-                margin: 0;
-                padding: 20px;
-                background: #f8fafc;
-                min-height: 100vh;
-                display: flex;
-                justify-content: center;
-                align-items: center;
-            ",
+    #[cfg(all(feature = "full", not(feature = "desktop")))]
+    dioxus::launch(app::App);
 
-            div {
-                style: "
-                    max-width: 800px;
-                    width: 100%;
-                    background: white;
-                    border-radius: 8px;
-                    border: 1px solid #e2e8f0;
-                    overflow: hidden;
-                    padding: 20px;
-                    box-sizing: border-box;
-                ",
-                
-                header {
-                    class: "feed-header",
-                    h1 {
-                        class: "feed-title",
-                        "Feed"
-                    }
-                    p {
-                        class: "feed-desc",
-                        "Latest updates"
-                    }
-                }
-                
-                VirtualList {}
-            }
-        }
-    }
+    #[cfg(not(feature = "full"))]
+    dioxus::launch(minimal::MinimalApp);
 }
-
@@ -0,0 +1,43 @@
+//! App of the minimal preset: the virtual rows core over generated text
+//! items, without sources, reader mode, persistence or images.
+
+use dioxus::prelude::*;
+use dioxus_feed_core::VirtualFeedItem;
+
+use crate::components::scroll_fallback::ScrollDiagnostics;
+use crate::components::snackbar::{Snackbar, SnackbarHost};
+use crate::components::virtual_rows::{Row, VirtualRows};
+use crate::MAIN_CSS;
+
+const DEMO_ITEMS: usize = 1000;
+
+// Generated item `index`, of a length varying with the index
+fn demo_item(index: usize) -> VirtualFeedItem {
+    let words = 4 + (index * 7) % 40;
+    let content = (0..words)
+        .map(|word| if word == 0 { format!("Item {}:", index) } else { "lorem".to_string() })
+        .collect::<Vec<_>>()
+        .join(" ");
+    VirtualFeedItem::new(format!("item-{}", index), content, "")
+}
+
+#[component]
+pub fn MinimalApp() -> Element {
+    use_context_provider(Snackbar::new);
+    use_context_provider(ScrollDiagnostics::new);
+    let items = use_signal(|| (0..DEMO_ITEMS).map(demo_item).collect::<im::Vector<_>>());
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: MAIN_CSS }
+        VirtualRows {
+            items,
+            render_row: move |Row { item, .. }: Row<VirtualFeedItem>| rsx! {
+                div {
+                    style: "padding: 8px 12px; border-bottom: 1px solid #e2e8f0; font-size: 14px; line-height: 1.5; color: #0f172a;",
+                    "{item.content}"
+                }
+            },
+        }
+        SnackbarHost {}
+    }
+}
@@ -0,0 +1,35 @@
+//! System notifications: native on desktop, the Notification API elsewhere.
+
+/// Show a system notification. Failures are logged, never surfaced.
+#[cfg(feature = "desktop")]
+pub fn notify(title: &str, body: &str) {
+    if let Err(e) = notify_rust::Notification::new()
+        .appname("Dioxus Feed")
+        .summary(title)
+        .body(body)
+        .show()
+    {
+        eprintln!("Failed to show notification: {}", e);
+    }
+}
+
+/// Show a system notification. Failures are logged, never surfaced.
+#[cfg(not(feature = "desktop"))]
+pub fn notify(title: &str, body: &str) {
+    use dioxus::prelude::*;
+
+    // Asks for permission the first time; denied permission shows nothing
+    let eval = document::eval(
+        r#"
+        const [title, body] = await dioxus.recv();
+        if (!("Notification" in window)) return;
+        const permission = Notification.permission === "default"
+            ? await Notification.requestPermission()
+            : Notification.permission;
+        if (permission === "granted") new Notification(title, { body });
+        "#,
+    );
+    if let Err(e) = eval.send((title.to_string(), body.to_string())) {
+        eprintln!("Failed to show notification: {}", e);
+    }
+}
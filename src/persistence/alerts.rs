@@ -0,0 +1,231 @@
+use dioxus::prelude::*;
+use dioxus_feed_core::VirtualFeedItem;
+use serde::{Deserialize, Serialize};
+
+use super::{load_json, save_json};
+use crate::error::{report, FeedError};
+use crate::notify::notify;
+use crate::store::FeedItems;
+
+/// File name of the persisted alert rules document
+const ALERT_RULES_FILE: &str = "alert_rules.json";
+
+/// File name of the persisted alert matches document
+const ALERTS_FILE: &str = "alerts.json";
+
+/// Most matches kept in the Alerts feed; older ones are dropped
+const MAX_ALERTS: usize = 500;
+
+/// What an alert rule's pattern is matched against, and how
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum AlertRuleKind {
+    /// Case-insensitive word or phrase in the item text
+    Keyword,
+    /// Regular expression over the item text
+    Regex,
+    /// Case-insensitive match of the item's author
+    Author,
+}
+
+impl AlertRuleKind {
+    pub const ALL: [AlertRuleKind; 3] = [AlertRuleKind::Keyword, AlertRuleKind::Regex, AlertRuleKind::Author];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AlertRuleKind::Keyword => "Keyword",
+            AlertRuleKind::Regex => "Regex",
+            AlertRuleKind::Author => "Author",
+        }
+    }
+}
+
+/// Custom error type for alert rules
+#[derive(Debug, Clone, PartialEq)]
+pub enum AlertRuleError {
+    EmptyPattern,
+    InvalidRegex(String),
+}
+
+impl std::fmt::Display for AlertRuleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AlertRuleError::EmptyPattern => write!(f, "Pattern is empty"),
+            AlertRuleError::InvalidRegex(msg) => write!(f, "Invalid regex: {}", msg),
+        }
+    }
+}
+
+/// A user-defined rule raising an alert for matching incoming items
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct AlertRule {
+    pub id: u64,
+    pub kind: AlertRuleKind,
+    pub pattern: String,
+    pub enabled: bool,
+}
+
+impl AlertRule {
+    /// Whether `item` matches the rule. Disabled rules match nothing.
+    pub fn matches(&self, item: &VirtualFeedItem) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        match self.kind {
+            AlertRuleKind::Keyword => item.content.to_lowercase().contains(&self.pattern.to_lowercase()),
+            AlertRuleKind::Regex => regex::Regex::new(&self.pattern).is_ok_and(|regex| regex.is_match(&item.content)),
+            AlertRuleKind::Author => item
+                .author
+                .as_deref()
+                .is_some_and(|author| author.to_lowercase().contains(&self.pattern.to_lowercase())),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        format!("{}: {}", self.kind.label(), self.pattern)
+    }
+}
+
+/// An item that matched a rule, with the time it was matched
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct AlertMatch {
+    pub item: VirtualFeedItem,
+    pub rule_id: u64,
+    pub matched_at: i64,
+}
+
+/// Alert rules and the items they matched, newest first, both persisted on
+/// every change.
+///
+/// Stores check the items arriving through polls and refreshes; each new
+/// match raises a notification and is added to the Alerts feed. Provided once
+/// at the app root with `use_context_provider(Alerts::load)`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Alerts {
+    rules: Signal<Vec<AlertRule>>,
+    matches: Signal<Vec<AlertMatch>>,
+    items: Signal<FeedItems>,
+}
+
+impl Alerts {
+    /// Load rules and matches from the persistence layer
+    pub fn load() -> Self {
+        let rules: Vec<AlertRule> = load_json(ALERT_RULES_FILE)
+            .unwrap_or_else(|e| {
+                report(FeedError::Load { what: "alert rules", source: e });
+                None
+            })
+            .unwrap_or_default();
+        let matches: Vec<AlertMatch> = load_json(ALERTS_FILE)
+            .unwrap_or_else(|e| {
+                report(FeedError::Load { what: "alerts", source: e });
+                None
+            })
+            .unwrap_or_default();
+        let items = matches.iter().map(|entry| entry.item.clone()).collect();
+
+        Self {
+            rules: Signal::new(rules),
+            matches: Signal::new(matches),
+            items: Signal::new(items),
+        }
+    }
+
+    pub fn rules(&self) -> Vec<AlertRule> {
+        self.rules.read().clone()
+    }
+
+    /// Matched items in display order, for rendering through the virtual list
+    pub fn items(&self) -> Signal<FeedItems> {
+        self.items
+    }
+
+    /// Add an enabled rule, checking that its pattern is usable
+    pub fn add_rule(&mut self, kind: AlertRuleKind, pattern: &str) -> Result<(), AlertRuleError> {
+        let pattern = pattern.trim();
+        if pattern.is_empty() {
+            return Err(AlertRuleError::EmptyPattern);
+        }
+        if kind == AlertRuleKind::Regex {
+            regex::Regex::new(pattern).map_err(|e| AlertRuleError::InvalidRegex(e.to_string()))?;
+        }
+
+        let id = self.rules.read().iter().map(|rule| rule.id + 1).max().unwrap_or(0);
+        self.rules.write().push(AlertRule {
+            id,
+            kind,
+            pattern: pattern.to_string(),
+            enabled: true,
+        });
+        self.save_rules();
+        Ok(())
+    }
+
+    pub fn remove_rule(&mut self, id: u64) {
+        self.rules.write().retain(|rule| rule.id != id);
+        self.save_rules();
+    }
+
+    pub fn set_enabled(&mut self, id: u64, enabled: bool) {
+        if let Some(rule) = self.rules.write().iter_mut().find(|rule| rule.id == id) {
+            rule.enabled = enabled;
+        }
+        self.save_rules();
+    }
+
+    /// Check incoming items against the rules, notifying about and recording
+    /// each item matching one for the first time
+    pub fn check(&mut self, incoming: &[VirtualFeedItem]) {
+        let rules = self.rules.peek().clone();
+        if rules.iter().all(|rule| !rule.enabled) {
+            return;
+        }
+
+        let new_matches: Vec<AlertMatch> = {
+            let matches = self.matches.peek();
+            incoming
+                .iter()
+                .filter(|item| !matches.iter().any(|entry| entry.item.id == item.id))
+                .filter_map(|item| {
+                    let rule = rules.iter().find(|rule| rule.matches(item))?;
+                    notify(&format!("Alert: {}", rule.describe()), &item.content);
+                    Some(AlertMatch {
+                        item: item.clone(),
+                        rule_id: rule.id,
+                        matched_at: chrono::Utc::now().timestamp_millis(),
+                    })
+                })
+                .collect()
+        };
+        if new_matches.is_empty() {
+            return;
+        }
+
+        {
+            let mut matches = self.matches.write();
+            for entry in new_matches {
+                matches.insert(0, entry);
+            }
+            matches.truncate(MAX_ALERTS);
+        }
+        let items = self.matches.peek().iter().map(|entry| entry.item.clone()).collect();
+        self.items.set(items);
+        if let Err(e) = save_json(ALERTS_FILE, &*self.matches.peek()) {
+            report(FeedError::Save { what: "alerts", source: e });
+        }
+    }
+
+    /// Remove every recorded match
+    pub fn clear(&mut self) {
+        self.matches.write().clear();
+        self.items.write().clear();
+        if let Err(e) = save_json(ALERTS_FILE, &*self.matches.peek()) {
+            report(FeedError::Save { what: "alerts", source: e });
+        }
+    }
+
+    fn save_rules(&self) {
+        if let Err(e) = save_json(ALERT_RULES_FILE, &*self.rules.peek()) {
+            report(FeedError::Save { what: "alert rules", source: e });
+        }
+    }
+}
@@ -0,0 +1,249 @@
+use dioxus::prelude::*;
+use dioxus_feed_core::VirtualFeedItem;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use super::{escape_html, load_json, save_json, write_file, PersistenceError, EXPORTS_DIR};
+use crate::error::{report, FeedError};
+use crate::store::FeedItems;
+use crate::urlcleaner::clean_url;
+
+/// File name of the persisted bookmarks document
+const BOOKMARKS_FILE: &str = "bookmarks.json";
+
+/// A saved item with the time it was saved
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SavedItem {
+    pub item: VirtualFeedItem,
+    pub saved_at: i64,
+}
+
+/// Export formats supported by the saved items view
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ExportFormat {
+    Json,
+    Html,
+}
+
+/// Bookmarked items, newest first, persisted on every change.
+///
+/// Provided once at the app root with `use_context_provider(Bookmarks::load)`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Bookmarks {
+    saved: Signal<Vec<SavedItem>>,
+    items: Signal<FeedItems>,
+    // Entries deleted from the curated list this session, so undoing a
+    // delete restores when the item was saved
+    deleted: Signal<Vec<SavedItem>>,
+}
+
+impl Bookmarks {
+    /// Load bookmarks from the persistence layer
+    pub fn load() -> Self {
+        let saved: Vec<SavedItem> = load_json(BOOKMARKS_FILE)
+            .unwrap_or_else(|e| {
+                report(FeedError::Load { what: "bookmarks", source: e });
+                None
+            })
+            .unwrap_or_default();
+        let items = saved.iter().map(|entry| entry.item.clone()).collect();
+
+        Self {
+            saved: Signal::new(saved),
+            items: Signal::new(items),
+            deleted: Signal::new(Vec::new()),
+        }
+    }
+
+    /// Saved items in display order, for rendering through the virtual list
+    pub fn items(&self) -> Signal<FeedItems> {
+        self.items
+    }
+
+    pub fn is_saved(&self, id: &str) -> bool {
+        self.saved.read().iter().any(|entry| entry.item.id == id)
+    }
+
+    pub fn save(&mut self, item: VirtualFeedItem) {
+        if self.is_saved(&item.id) {
+            return;
+        }
+
+        self.saved.write().insert(0, SavedItem {
+            item,
+            saved_at: chrono::Utc::now().timestamp_millis(),
+        });
+        self.commit();
+    }
+
+    pub fn unsave(&mut self, id: &str) {
+        self.saved.write().retain(|entry| entry.item.id != id);
+        self.commit();
+    }
+
+    pub fn toggle(&mut self, item: VirtualFeedItem) {
+        if self.is_saved(&item.id) {
+            self.unsave(&item.id);
+        } else {
+            self.save(item);
+        }
+    }
+
+    /// Move the saved item with `id` to `index` in display order
+    pub fn reorder(&mut self, id: &str, index: usize) {
+        {
+            let mut saved = self.saved.write();
+            let Some(from) = saved.iter().position(|entry| entry.item.id == id) else {
+                return;
+            };
+            let entry = saved.remove(from);
+            let index = index.min(saved.len());
+            saved.insert(index, entry);
+        }
+        self.commit();
+    }
+
+    /// Delete the saved item with `id` from the curated list; unlike
+    /// [`Bookmarks::unsave`], the entry is kept for [`Bookmarks::restore`]
+    pub fn delete(&mut self, id: &str) {
+        let entry = {
+            let mut saved = self.saved.write();
+            let Some(index) = saved.iter().position(|entry| entry.item.id == id) else {
+                return;
+            };
+            saved.remove(index)
+        };
+        self.deleted.write().push(entry);
+        self.commit();
+    }
+
+    /// Put a deleted item back at `index` in display order, with the time it
+    /// was first saved
+    pub fn restore(&mut self, index: usize, item: VirtualFeedItem) {
+        if self.is_saved(&item.id) {
+            return;
+        }
+
+        let saved_at = {
+            let mut deleted = self.deleted.write();
+            match deleted.iter().rposition(|entry| entry.item.id == item.id) {
+                Some(position) => deleted.remove(position).saved_at,
+                None => chrono::Utc::now().timestamp_millis(),
+            }
+        };
+        {
+            let mut saved = self.saved.write();
+            let index = index.min(saved.len());
+            saved.insert(index, SavedItem { item, saved_at });
+        }
+        self.commit();
+    }
+
+    /// Write the saved items to an export file, returning its path
+    pub fn export(&self, format: ExportFormat) -> Result<PathBuf, PersistenceError> {
+        let saved = self.saved.read();
+        let timestamp = chrono::Utc::now().format("%Y%m%d-%H%M%S");
+
+        let (extension, contents) = match format {
+            ExportFormat::Json => (
+                "json",
+                serde_json::to_string_pretty(&*saved)
+                    .map_err(|e| PersistenceError::Serialization(e.to_string()))?,
+            ),
+            ExportFormat::Html => ("html", render_html_export(&saved)),
+        };
+
+        let name = format!("{}/bookmarks-{}.{}", EXPORTS_DIR, timestamp, extension);
+        write_file(&name, contents.as_bytes())
+    }
+
+    // Sync the rendered items and persist the change
+    fn commit(&mut self) {
+        let items = self.saved.read().iter().map(|entry| entry.item.clone()).collect();
+        self.items.set(items);
+
+        if let Err(e) = save_json(BOOKMARKS_FILE, &*self.saved.read()) {
+            report(FeedError::Save { what: "bookmarks", source: e });
+        }
+    }
+}
+
+// Render saved items as a standalone HTML document
+fn render_html_export(saved: &[SavedItem]) -> String {
+    let mut html = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Saved items</title>\n</head>\n<body>\n<h1>Saved items</h1>\n<ul>\n",
+    );
+
+    for entry in saved {
+        let saved_at = chrono::DateTime::from_timestamp_millis(entry.saved_at)
+            .map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+
+        html.push_str("<li>\n");
+        html.push_str(&format!("<p>{}</p>\n", escape_html(&entry.item.content)));
+        if let Some(link) = &entry.item.link {
+            let link = escape_html(&clean_url(link));
+            html.push_str(&format!("<p><a href=\"{}\">{}</a></p>\n", link, link));
+        }
+        html.push_str(&format!("<small>Saved {}</small>\n", saved_at));
+        html.push_str("</li>\n");
+    }
+
+    html.push_str("</ul>\n</body>\n</html>\n");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::edits::EditAction;
+    use crate::store::{in_dom, FeedStore};
+
+    fn item(id: &str) -> VirtualFeedItem {
+        VirtualFeedItem::new(id.to_string(), format!("Item {}", id), "")
+    }
+
+    fn persisted() -> Vec<SavedItem> {
+        load_json(BOOKMARKS_FILE).unwrap().unwrap_or_default()
+    }
+
+    fn persisted_ids() -> Vec<String> {
+        persisted().into_iter().map(|entry| entry.item.id).collect()
+    }
+
+    fn shown_ids(items: Signal<FeedItems>) -> Vec<String> {
+        items.peek().iter().map(|item| item.id.clone()).collect()
+    }
+
+    #[test]
+    fn curated_moves_and_deletes_are_persisted() {
+        in_dom(|| {
+            let mut bookmarks = Bookmarks::load();
+            for id in ["c", "b", "a"] {
+                bookmarks.save(item(id));
+            }
+            let saved_at = bookmarks.saved.peek()[1].saved_at;
+            let mut store = FeedStore::from_items(bookmarks.items()).with_bookmarks(bookmarks);
+
+            store.edit("c", EditAction::MoveUp);
+            store.edit("b", EditAction::Delete);
+            assert_eq!(persisted_ids(), ["a", "c"]);
+            assert!(!bookmarks.is_saved("b"));
+
+            // Saving rebuilds the items from the saved ones, which kept the edits
+            bookmarks.save(item("d"));
+            assert_eq!(persisted_ids(), ["d", "a", "c"]);
+            assert_eq!(shown_ids(bookmarks.items()), ["d", "a", "c"]);
+
+            assert!(store.undo());
+            assert!(bookmarks.is_saved("b"));
+            assert_eq!(persisted_ids(), ["d", "a", "b", "c"]);
+            let restored = persisted().into_iter().find(|entry| entry.item.id == "b").unwrap();
+            assert_eq!(restored.saved_at, saved_at);
+
+            assert!(store.undo());
+            assert_eq!(persisted_ids(), ["d", "a", "c", "b"]);
+            assert_eq!(shown_ids(Bookmarks::load().items()), ["d", "a", "c", "b"]);
+        });
+    }
+}
@@ -0,0 +1,79 @@
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use super::{load_json, save_json};
+use crate::error::{report, FeedError};
+
+/// File name of the persisted caption cache
+const CAPTIONS_FILE: &str = "captions.json";
+
+/// Most captions kept; the oldest are dropped first
+const MAX_CAPTIONS: usize = 2000;
+
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+struct CaptionsDocument {
+    /// Generated alt text by media hash
+    captions: HashMap<String, String>,
+    /// Media hashes, oldest first
+    order: VecDeque<String>,
+}
+
+/// Generated alt text cached by media hash, persisted on every addition.
+///
+/// Provided once at the app root with `use_context_provider(CaptionCache::load)`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct CaptionCache {
+    document: Signal<CaptionsDocument>,
+    // Hashes captioned or being captioned this session, so an image whose
+    // captioning failed isn't retried on every render
+    requested: CopyValue<HashSet<String>>,
+}
+
+impl CaptionCache {
+    /// Load cached captions from the persistence layer
+    pub fn load() -> Self {
+        let document: CaptionsDocument = load_json(CAPTIONS_FILE)
+            .unwrap_or_else(|e| {
+                report(FeedError::Load { what: "captions", source: e });
+                None
+            })
+            .unwrap_or_default();
+
+        Self {
+            document: Signal::new(document),
+            requested: CopyValue::new(HashSet::new()),
+        }
+    }
+
+    /// Caption of the image with media hash `hash`
+    pub fn get(&self, hash: &str) -> Option<String> {
+        self.document.read().captions.get(hash).cloned()
+    }
+
+    /// Claim captioning the image with `hash`, `false` if it was already
+    /// claimed this session
+    pub fn begin(&mut self, hash: &str) -> bool {
+        self.requested.write().insert(hash.to_string())
+    }
+
+    pub fn insert(&mut self, hash: &str, caption: String) {
+        let mut document = self.document.write();
+        if document.captions.insert(hash.to_string(), caption).is_none() {
+            document.order.push_back(hash.to_string());
+        }
+        while document.order.len() > MAX_CAPTIONS {
+            if let Some(oldest) = document.order.pop_front() {
+                document.captions.remove(&oldest);
+            }
+        }
+        drop(document);
+        self.save();
+    }
+
+    fn save(&self) {
+        if let Err(e) = save_json(CAPTIONS_FILE, &*self.document.peek()) {
+            report(FeedError::Save { what: "captions", source: e });
+        }
+    }
+}
@@ -0,0 +1,265 @@
+//! Optional encryption at rest for persisted documents.
+//!
+//! With encryption on, JSON documents are sealed with ChaCha20-Poly1305
+//! under a key kept in the OS keychain (Keychain, Credential Manager or the
+//! Secret Service), so cached feed content and credentials aren't stored in
+//! plaintext in the user profile. Encrypted files start with a marker, so
+//! plaintext documents from before encryption was turned on still load and
+//! are sealed on their next save. Exports are never encrypted, and neither
+//! are the privacy settings, which hold the setting itself.
+//!
+//! Encryption fails closed: while it is on, saving fails if the key can't be
+//! had rather than writing plaintext, and a document that couldn't be
+//! decrypted isn't written over for the rest of the session.
+//!
+//! Sealing needs the `encryption` feature; builds without it keep writing
+//! plaintext and report encrypted documents as unreadable.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use super::privacy::PRIVACY_FILE;
+use super::{data_dir, PersistenceError};
+
+/// Marker at the start of every encrypted document
+const MAGIC: &[u8] = b"DFENC1\n";
+
+/// Whether new documents are written encrypted
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Documents always written in plaintext: the privacy settings say whether
+/// the others are encrypted, so they must load without the key
+const PLAINTEXT_DOCUMENTS: &[&str] = &[PRIVACY_FILE];
+
+/// Names of the documents that failed to decrypt this session. Defaults
+/// stand in for them until the app restarts, which mustn't be saved over
+/// what is on disk.
+static UNREADABLE: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Whether this build can encrypt documents
+pub fn is_supported() -> bool {
+    cfg!(feature = "encryption")
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Turn encryption of newly written documents on or off. Turning it on
+/// creates the key in the keychain if there isn't one yet.
+pub fn set_enabled(enabled: bool) -> Result<(), PersistenceError> {
+    if enabled {
+        if !is_supported() {
+            return Err(PersistenceError::Encryption("This build has no encryption support".to_string()));
+        }
+        cipher::key(true)?;
+    }
+    ENABLED.store(enabled, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Apply the setting saved in the privacy settings at startup. Encryption is
+/// on even if the key can't be had, so saves fail instead of writing
+/// plaintext; the error says why.
+pub fn restore(enabled: bool) -> Result<(), PersistenceError> {
+    ENABLED.store(enabled, Ordering::Relaxed);
+    if !enabled {
+        return Ok(());
+    }
+    if !is_supported() {
+        return Err(PersistenceError::Encryption("This build has no encryption support".to_string()));
+    }
+    cipher::key(true).map(|_| ())
+}
+
+/// Whether `contents` is an encrypted document
+pub fn is_encrypted(contents: &[u8]) -> bool {
+    contents.starts_with(MAGIC)
+}
+
+// Whether the document `name` is written encrypted while encryption is
+// `enabled`
+fn seals(name: &str, enabled: bool) -> bool {
+    enabled && !PLAINTEXT_DOCUMENTS.contains(&name)
+}
+
+/// Contents to write for the document `name`: encrypted when encryption is
+/// on. Fails for documents that couldn't be decrypted this session.
+pub(super) fn seal(name: &str, contents: &[u8]) -> Result<Vec<u8>, PersistenceError> {
+    let unreadable = UNREADABLE.lock().expect("unreadable documents lock poisoned");
+    if unreadable.iter().any(|unreadable| unreadable == name) {
+        return Err(PersistenceError::Encryption(format!(
+            "{} couldn't be decrypted, so it isn't overwritten",
+            name
+        )));
+    }
+    drop(unreadable);
+
+    if !seals(name, is_enabled()) {
+        return Ok(contents.to_vec());
+    }
+    let mut sealed = MAGIC.to_vec();
+    sealed.extend(cipher::encrypt(contents)?);
+    Ok(sealed)
+}
+
+/// Plaintext of the document `name` read from disk, decrypting it if it is
+/// sealed whether or not encryption is currently on
+pub(super) fn open(name: &str, contents: Vec<u8>) -> Result<Vec<u8>, PersistenceError> {
+    let result = match contents.strip_prefix(MAGIC) {
+        Some(sealed) => cipher::decrypt(sealed),
+        None => return Ok(contents),
+    };
+    if result.is_err() {
+        let mut unreadable = UNREADABLE.lock().expect("unreadable documents lock poisoned");
+        if !unreadable.iter().any(|unreadable| unreadable == name) {
+            unreadable.push(name.to_string());
+        }
+    }
+    result
+}
+
+/// Rewrite every document in the data directory under the current setting,
+/// so turning encryption on or off applies to existing data at once.
+/// Returns how many documents were rewritten.
+pub fn rewrite_all() -> Result<usize, PersistenceError> {
+    let entries = match std::fs::read_dir(data_dir()) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(PersistenceError::Io(e.to_string())),
+    };
+    let mut rewritten = 0;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().is_none_or(|extension| extension != "json") {
+            continue;
+        }
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let contents = std::fs::read(&path).map_err(|e| PersistenceError::Io(e.to_string()))?;
+        if is_encrypted(&contents) == seals(name, is_enabled()) {
+            continue;
+        }
+        let plaintext = open(name, contents)?;
+        super::write_file(name, &seal(name, &plaintext)?)?;
+        rewritten += 1;
+    }
+    Ok(rewritten)
+}
+
+#[cfg(feature = "encryption")]
+mod cipher {
+    use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+    use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+    use std::sync::Mutex;
+
+    use super::PersistenceError;
+
+    /// Keychain entry holding the key
+    const KEYRING_SERVICE: &str = "dioxus-feed";
+    const KEYRING_USER: &str = "persistence-key";
+
+    /// Length of the nonce stored before each ciphertext
+    const NONCE_LEN: usize = 12;
+
+    /// Key read from the keychain, kept for the rest of the session
+    static KEY: Mutex<Option<Key>> = Mutex::new(None);
+
+    fn keychain_error(e: keyring::Error) -> PersistenceError {
+        PersistenceError::Encryption(format!("Keychain: {}", e))
+    }
+
+    /// The key from the keychain, generated and stored first if `create` is
+    /// set and there is none
+    pub(super) fn key(create: bool) -> Result<Key, PersistenceError> {
+        let mut cached = KEY.lock().expect("encryption key lock poisoned");
+        if let Some(key) = *cached {
+            return Ok(key);
+        }
+        let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER).map_err(keychain_error)?;
+        let key = match entry.get_secret() {
+            Ok(secret) if secret.len() == 32 => *Key::from_slice(&secret),
+            Ok(_) => return Err(PersistenceError::Encryption("Keychain holds a malformed key".to_string())),
+            Err(keyring::Error::NoEntry) if create => {
+                let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+                entry.set_secret(&key).map_err(keychain_error)?;
+                key
+            }
+            Err(e) => return Err(keychain_error(e)),
+        };
+        *cached = Some(key);
+        Ok(key)
+    }
+
+    pub(super) fn encrypt(plaintext: &[u8]) -> Result<Vec<u8>, PersistenceError> {
+        let cipher = ChaCha20Poly1305::new(&key(true)?);
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, plaintext)
+            .map_err(|_| PersistenceError::Encryption("Encryption failed".to_string()))?;
+        let mut sealed = nonce.to_vec();
+        sealed.extend(ciphertext);
+        Ok(sealed)
+    }
+
+    pub(super) fn decrypt(sealed: &[u8]) -> Result<Vec<u8>, PersistenceError> {
+        if sealed.len() < NONCE_LEN {
+            return Err(PersistenceError::Encryption("Encrypted document is truncated".to_string()));
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+        let cipher = ChaCha20Poly1305::new(&key(false)?);
+        cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| PersistenceError::Encryption("Document can't be decrypted with the keychain key".to_string()))
+    }
+}
+
+#[cfg(not(feature = "encryption"))]
+mod cipher {
+    use super::PersistenceError;
+
+    fn unsupported() -> PersistenceError {
+        PersistenceError::Encryption("This build has no encryption support".to_string())
+    }
+
+    pub(super) fn key(_create: bool) -> Result<(), PersistenceError> {
+        Err(unsupported())
+    }
+
+    pub(super) fn encrypt(_plaintext: &[u8]) -> Result<Vec<u8>, PersistenceError> {
+        Err(unsupported())
+    }
+
+    pub(super) fn decrypt(_sealed: &[u8]) -> Result<Vec<u8>, PersistenceError> {
+        Err(unsupported())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::{load_json, save_json, write_file};
+
+    #[test]
+    fn privacy_settings_are_never_sealed() {
+        assert!(!seals(PRIVACY_FILE, true));
+        assert!(seals("bookmarks.json", true));
+        assert!(!seals("bookmarks.json", false));
+    }
+
+    #[test]
+    fn documents_that_fail_to_decrypt_are_not_overwritten() {
+        const NAME: &str = "undecryptable.json";
+        let mut contents = MAGIC.to_vec();
+        contents.extend(b"not a sealed document");
+        let path = write_file(NAME, &contents).unwrap();
+
+        assert!(load_json::<Vec<String>>(NAME).is_err());
+        assert!(matches!(
+            save_json(NAME, &Vec::<String>::new()),
+            Err(PersistenceError::Encryption(_))
+        ));
+        assert_eq!(std::fs::read(path).unwrap(), contents);
+    }
+}
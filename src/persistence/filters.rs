@@ -0,0 +1,280 @@
+use dioxus::prelude::*;
+use dioxus_feed_core::{ContentFlag, VirtualFeedItem};
+use serde::{Deserialize, Serialize};
+
+use super::{load_json, save_json};
+use crate::error::{report, FeedError};
+
+/// File name of the persisted content filter profiles document
+const FILTERS_FILE: &str = "filters.json";
+
+/// Name of the profile created on first launch
+const DEFAULT_PROFILE: &str = "Default";
+
+/// How much flagged content a profile lets through
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub enum FilterLevel {
+    /// Flagged items are shown, blurred or not as content gating says
+    #[default]
+    Off,
+    /// Items flagged as spam are left out
+    Standard,
+    /// Every flagged item is left out
+    Strict,
+}
+
+impl FilterLevel {
+    pub const ALL: [FilterLevel; 3] = [FilterLevel::Off, FilterLevel::Standard, FilterLevel::Strict];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FilterLevel::Off => "Off",
+            FilterLevel::Standard => "Standard",
+            FilterLevel::Strict => "Strict",
+        }
+    }
+
+    /// Whether items carrying `flag` are left out at this level
+    pub fn removes(&self, flag: ContentFlag) -> bool {
+        match self {
+            FilterLevel::Off => false,
+            FilterLevel::Standard => flag == ContentFlag::Spam,
+            FilterLevel::Strict => true,
+        }
+    }
+}
+
+/// Custom error type for filter profiles
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterError {
+    EmptyName,
+    DuplicateName(String),
+}
+
+impl std::fmt::Display for FilterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FilterError::EmptyName => write!(f, "Profile name is empty"),
+            FilterError::DuplicateName(name) => write!(f, "A profile named {} already exists", name),
+        }
+    }
+}
+
+/// A named set of filters, e.g. one per person using the app
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct FilterProfile {
+    pub name: String,
+    #[serde(default)]
+    pub level: FilterLevel,
+    /// Case-insensitive words or phrases hiding items that mention them
+    #[serde(default)]
+    pub muted_keywords: Vec<String>,
+    /// Sources items may come from, any source while empty
+    #[serde(default)]
+    pub allowed_sources: Vec<String>,
+}
+
+impl FilterProfile {
+    fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            level: FilterLevel::Off,
+            muted_keywords: Vec::new(),
+            allowed_sources: Vec::new(),
+        }
+    }
+
+    /// Whether the profile filters anything at all
+    pub fn is_open(&self) -> bool {
+        self.level == FilterLevel::Off && self.muted_keywords.is_empty() && self.allowed_sources.is_empty()
+    }
+
+    /// Whether `item`, shown in the feed named `feed`, gets through. Items
+    /// that don't name their sources count as coming from the feed itself.
+    pub fn allows(&self, item: &VirtualFeedItem, feed: &str) -> bool {
+        if item.content_flags().any(|flag| self.level.removes(flag)) {
+            return false;
+        }
+
+        let content = item.content.to_lowercase();
+        let author = item.author.as_deref().map(str::to_lowercase).unwrap_or_default();
+        let muted = self.muted_keywords.iter().any(|keyword| {
+            let keyword = keyword.to_lowercase();
+            content.contains(&keyword) || author.contains(&keyword)
+        });
+        if muted {
+            return false;
+        }
+
+        if self.allowed_sources.is_empty() {
+            return true;
+        }
+        let is_allowed = |source: &str| self.allowed_sources.iter().any(|allowed| allowed.eq_ignore_ascii_case(source));
+        if item.sources.is_empty() {
+            is_allowed(feed)
+        } else {
+            item.sources.iter().any(|source| is_allowed(source))
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+struct FiltersDocument {
+    profiles: Vec<FilterProfile>,
+    /// Name of the profile in effect
+    active: String,
+}
+
+impl Default for FiltersDocument {
+    fn default() -> Self {
+        Self {
+            profiles: vec![FilterProfile::new(DEFAULT_PROFILE)],
+            active: DEFAULT_PROFILE.to_string(),
+        }
+    }
+}
+
+/// Content filter profiles and which one is in effect, persisted on every
+/// change.
+///
+/// Stores built `with_filters` leave out the items the active profile
+/// doesn't allow from [`FeedStore::ordered_items`](crate::store::FeedStore::ordered_items),
+/// so switching profiles re-filters every open feed. Provided once at the
+/// app root with `use_context_provider(ContentFilters::load)`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct ContentFilters {
+    document: Signal<FiltersDocument>,
+}
+
+impl ContentFilters {
+    /// Load filter profiles from the persistence layer
+    pub fn load() -> Self {
+        let mut document: FiltersDocument = load_json(FILTERS_FILE)
+            .unwrap_or_else(|e| {
+                report(FeedError::Load { what: "content filters", source: e });
+                None
+            })
+            .unwrap_or_default();
+        if document.profiles.is_empty() {
+            document = FiltersDocument::default();
+        }
+
+        Self {
+            document: Signal::new(document),
+        }
+    }
+
+    pub fn profiles(&self) -> Vec<FilterProfile> {
+        self.document.read().profiles.clone()
+    }
+
+    /// The profile in effect, the first one if the active name is unknown
+    pub fn active(&self) -> FilterProfile {
+        self.document.read().active_profile().clone()
+    }
+
+    pub fn set_active(&mut self, name: &str) {
+        let document = self.document.peek();
+        if document.active == name || !document.profiles.iter().any(|profile| profile.name == name) {
+            return;
+        }
+        drop(document);
+        self.document.write().active = name.to_string();
+        self.save();
+    }
+
+    /// Add an open profile named `name` and switch to it
+    pub fn add_profile(&mut self, name: &str) -> Result<(), FilterError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(FilterError::EmptyName);
+        }
+        if self.document.peek().profiles.iter().any(|profile| profile.name == name) {
+            return Err(FilterError::DuplicateName(name.to_string()));
+        }
+        let mut document = self.document.write();
+        document.profiles.push(FilterProfile::new(name));
+        document.active = name.to_string();
+        drop(document);
+        self.save();
+        Ok(())
+    }
+
+    /// Remove the profile named `name`; the last profile is kept
+    pub fn remove_profile(&mut self, name: &str) {
+        let document = self.document.peek();
+        if document.profiles.len() <= 1 || !document.profiles.iter().any(|profile| profile.name == name) {
+            return;
+        }
+        drop(document);
+        let mut document = self.document.write();
+        document.profiles.retain(|profile| profile.name != name);
+        if document.active == name {
+            document.active = document.profiles[0].name.clone();
+        }
+        drop(document);
+        self.save();
+    }
+
+    pub fn set_level(&mut self, level: FilterLevel) {
+        self.update_active(|profile| profile.level = level);
+    }
+
+    /// Replace the active profile's muted keywords
+    pub fn set_muted_keywords(&mut self, keywords: Vec<String>) {
+        let keywords = clean_list(keywords);
+        self.update_active(|profile| profile.muted_keywords = keywords);
+    }
+
+    /// Replace the active profile's allowed sources; none allows every source
+    pub fn set_allowed_sources(&mut self, sources: Vec<String>) {
+        let sources = clean_list(sources);
+        self.update_active(|profile| profile.allowed_sources = sources);
+    }
+
+    // Change the active profile, saving only if it changed
+    fn update_active(&mut self, update: impl FnOnce(&mut FilterProfile)) {
+        let mut profile = self.document.peek().active_profile().clone();
+        update(&mut profile);
+        if *self.document.peek().active_profile() == profile {
+            return;
+        }
+        let mut document = self.document.write();
+        let active = document.active.clone();
+        if let Some(existing) = document.profiles.iter_mut().find(|existing| existing.name == active) {
+            *existing = profile;
+        } else {
+            document.profiles[0] = profile;
+        }
+        drop(document);
+        self.save();
+    }
+
+    fn save(&self) {
+        if let Err(e) = save_json(FILTERS_FILE, &*self.document.peek()) {
+            report(FeedError::Save { what: "content filters", source: e });
+        }
+    }
+}
+
+impl FiltersDocument {
+    // Profile named by `active`, falling back to the first one
+    fn active_profile(&self) -> &FilterProfile {
+        self.profiles
+            .iter()
+            .find(|profile| profile.name == self.active)
+            .unwrap_or(&self.profiles[0])
+    }
+}
+
+// Trimmed, non-empty entries without duplicates
+fn clean_list(entries: Vec<String>) -> Vec<String> {
+    let mut cleaned: Vec<String> = Vec::new();
+    for entry in entries {
+        let entry = entry.trim();
+        if !entry.is_empty() && !cleaned.iter().any(|known| known.eq_ignore_ascii_case(entry)) {
+            cleaned.push(entry.to_string());
+        }
+    }
+    cleaned
+}
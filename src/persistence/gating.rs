@@ -0,0 +1,111 @@
+use dioxus::prelude::*;
+use dioxus_feed_core::VirtualFeedItem;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+use super::{load_json, save_json};
+use crate::error::{report, FeedError};
+
+/// File name of the persisted content gating document
+const GATING_FILE: &str = "gating.json";
+
+/// Most revealed items remembered; the oldest are gated again first
+const MAX_REVEALED: usize = 1000;
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+struct GatingDocument {
+    /// Whether flagged items are blurred until revealed
+    blur_flagged: bool,
+    /// Flagged items the user chose to show, oldest first
+    #[serde(default)]
+    revealed: VecDeque<String>,
+}
+
+impl Default for GatingDocument {
+    fn default() -> Self {
+        Self {
+            blur_flagged: true,
+            revealed: VecDeque::new(),
+        }
+    }
+}
+
+/// Whether items flagged sensitive or spam are blurred, and which ones were
+/// revealed anyway. Persisted on every change.
+///
+/// Provided once at the app root with `use_context_provider(ContentGating::load)`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct ContentGating {
+    document: Signal<GatingDocument>,
+}
+
+impl ContentGating {
+    /// Load gating settings from the persistence layer
+    pub fn load() -> Self {
+        let document: GatingDocument = load_json(GATING_FILE)
+            .unwrap_or_else(|e| {
+                report(FeedError::Load { what: "content gating", source: e });
+                None
+            })
+            .unwrap_or_default();
+
+        Self {
+            document: Signal::new(document),
+        }
+    }
+
+    pub fn blur_flagged(&self) -> bool {
+        self.document.read().blur_flagged
+    }
+
+    /// Whether `item` is shown blurred, with its media held back
+    pub fn is_gated(&self, item: &VirtualFeedItem) -> bool {
+        if item.content_flags().next().is_none() {
+            return false;
+        }
+        let document = self.document.read();
+        document.blur_flagged && !document.revealed.contains(&item.id)
+    }
+
+    /// Whether the user revealed the item with `id`
+    pub fn is_revealed(&self, id: &str) -> bool {
+        self.document.read().revealed.iter().any(|revealed| revealed == id)
+    }
+
+    pub fn set_blur_flagged(&mut self, blur_flagged: bool) {
+        if self.document.peek().blur_flagged == blur_flagged {
+            return;
+        }
+        self.document.write().blur_flagged = blur_flagged;
+        self.save();
+    }
+
+    /// Show the item with `id` despite its flags
+    pub fn reveal(&mut self, id: &str) {
+        if self.document.peek().revealed.iter().any(|revealed| revealed == id) {
+            return;
+        }
+        let mut document = self.document.write();
+        document.revealed.push_back(id.to_string());
+        while document.revealed.len() > MAX_REVEALED {
+            document.revealed.pop_front();
+        }
+        drop(document);
+        self.save();
+    }
+
+    /// Blur a revealed item again
+    pub fn conceal(&mut self, id: &str) {
+        if !self.document.peek().revealed.iter().any(|revealed| revealed == id) {
+            return;
+        }
+        self.document.write().revealed.retain(|revealed| revealed != id);
+        self.save();
+    }
+
+    fn save(&self) {
+        if let Err(e) = save_json(GATING_FILE, &*self.document.peek()) {
+            report(FeedError::Save { what: "content gating", source: e });
+        }
+    }
+}
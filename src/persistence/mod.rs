@@ -0,0 +1,130 @@
+pub mod alerts;
+pub mod bookmarks;
+pub mod captions;
+pub mod encryption;
+pub mod filters;
+pub mod gating;
+pub mod pane_layouts;
+pub mod polling;
+pub mod privacy;
+pub mod reading_width;
+pub mod read_state;
+pub mod row_heights;
+pub mod scroll_positions;
+pub mod search_index;
+pub mod seen_stories;
+pub mod session_export;
+pub mod setup;
+pub mod shortcuts;
+pub mod subscriptions;
+pub mod summarizer;
+pub mod theme;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::PathBuf;
+
+use crate::work::Cancelled;
+
+/// Directory name used under the platform data directory
+const APP_DATA_DIR: &str = "dioxus-feed";
+
+/// Directory (inside the data directory) that exports are written to
+pub(crate) const EXPORTS_DIR: &str = "exports";
+
+/// Custom error type for persistence
+#[derive(Debug, Clone, PartialEq)]
+pub enum PersistenceError {
+    Io(String),
+    Serialization(String),
+    Encryption(String),
+}
+
+impl std::fmt::Display for PersistenceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PersistenceError::Io(msg) => write!(f, "IO error: {}", msg),
+            PersistenceError::Serialization(msg) => write!(f, "Serialization error: {}", msg),
+            PersistenceError::Encryption(msg) => write!(f, "Encryption error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for PersistenceError {}
+
+impl From<Cancelled> for PersistenceError {
+    fn from(error: Cancelled) -> Self {
+        PersistenceError::Io(error.to_string())
+    }
+}
+
+/// Root directory for persisted app data.
+///
+/// Falls back to the working directory when the platform has no data directory.
+#[cfg(not(test))]
+pub fn data_dir() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| std::env::current_dir().unwrap_or_default())
+        .join(APP_DATA_DIR)
+}
+
+/// Tests persist under the temporary directory, each in its own directory;
+/// libtest names the thread running a test after it
+#[cfg(test)]
+pub fn data_dir() -> PathBuf {
+    let thread = std::thread::current();
+    let test = thread.name().unwrap_or("main").replace("::", "-");
+    std::env::temp_dir()
+        .join(format!("{}-tests-{}", APP_DATA_DIR, std::process::id()))
+        .join(test)
+}
+
+/// Load a JSON document from the data directory, `None` if it doesn't exist
+/// yet. Encrypted documents are decrypted.
+pub fn load_json<T: DeserializeOwned>(name: &str) -> Result<Option<T>, PersistenceError> {
+    let path = data_dir().join(name);
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let contents = std::fs::read(&path)
+        .map_err(|e| PersistenceError::Io(e.to_string()))?;
+    let contents = encryption::open(name, contents)?;
+    serde_json::from_slice(&contents)
+        .map(Some)
+        .map_err(|e| PersistenceError::Serialization(e.to_string()))
+}
+
+/// Save a JSON document to the data directory, encrypted when encryption at
+/// rest is on
+pub fn save_json<T: Serialize>(name: &str, value: &T) -> Result<(), PersistenceError> {
+    let contents = serde_json::to_string_pretty(value)
+        .map_err(|e| PersistenceError::Serialization(e.to_string()))?;
+    write_file(name, &encryption::seal(name, contents.as_bytes())?).map(|_| ())
+}
+
+/// Write raw bytes to the data directory, returning the full path
+pub fn write_file(name: &str, contents: &[u8]) -> Result<PathBuf, PersistenceError> {
+    let path = data_dir().join(name);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| PersistenceError::Io(e.to_string()))?;
+    }
+
+    // Write to a temporary file first so a crash never leaves a truncated document
+    let temp_path = path.with_extension("tmp");
+    std::fs::write(&temp_path, contents)
+        .map_err(|e| PersistenceError::Io(e.to_string()))?;
+    std::fs::rename(&temp_path, &path)
+        .map_err(|e| PersistenceError::Io(e.to_string()))?;
+
+    Ok(path)
+}
+
+/// Escape text for inclusion in exported HTML
+pub(crate) fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
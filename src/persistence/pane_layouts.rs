@@ -0,0 +1,64 @@
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::{load_json, save_json};
+use crate::error::{report, FeedError};
+
+/// File name of the persisted pane layouts document
+const PANE_LAYOUTS_FILE: &str = "pane_layouts.json";
+
+/// Size of a split pane's first pane, and whether it is collapsed
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub struct PaneLayout {
+    /// Width or height of the first pane in pixels
+    pub size: f64,
+    #[serde(default)]
+    pub collapsed: bool,
+}
+
+/// Layout of resizable panes, keyed by pane (e.g. `"split_view"`), persisted
+/// whenever a resize or collapse ends.
+///
+/// Provided once at the app root with `use_context_provider(PaneLayouts::load)`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct PaneLayouts {
+    layouts: Signal<HashMap<String, PaneLayout>>,
+}
+
+impl PaneLayouts {
+    /// Load pane layouts from the persistence layer
+    pub fn load() -> Self {
+        let layouts = load_json(PANE_LAYOUTS_FILE)
+            .unwrap_or_else(|e| {
+                report(FeedError::Load { what: "pane layouts", source: e });
+                None
+            })
+            .unwrap_or_default();
+
+        Self {
+            layouts: Signal::new(layouts),
+        }
+    }
+
+    /// Layout last saved for the pane with `key`
+    pub fn layout(&self, key: &str) -> Option<PaneLayout> {
+        self.layouts.peek().get(key).copied()
+    }
+
+    /// Record `layout` as the layout of the pane with `key`
+    pub fn remember(&mut self, key: &str, layout: PaneLayout) {
+        if self.layouts.peek().get(key) == Some(&layout) {
+            return;
+        }
+
+        self.layouts.write().insert(key.to_string(), layout);
+        self.save();
+    }
+
+    fn save(&self) {
+        if let Err(e) = save_json(PANE_LAYOUTS_FILE, &*self.layouts.peek()) {
+            report(FeedError::Save { what: "pane layouts", source: e });
+        }
+    }
+}
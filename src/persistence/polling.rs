@@ -0,0 +1,104 @@
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use super::{load_json, save_json};
+use crate::error::{report, FeedError};
+use crate::store::polling::PollBounds;
+
+/// File name of the persisted polling settings document
+const POLLING_FILE: &str = "polling.json";
+
+/// Default shortest time between polls of one feed
+const DEFAULT_MIN_SECS: u64 = 5;
+
+/// Default longest time between polls of one feed
+const DEFAULT_MAX_SECS: u64 = 15 * 60;
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+struct PollingDocument {
+    min_secs: u64,
+    max_secs: u64,
+}
+
+impl Default for PollingDocument {
+    fn default() -> Self {
+        Self {
+            min_secs: DEFAULT_MIN_SECS,
+            max_secs: DEFAULT_MAX_SECS,
+        }
+    }
+}
+
+/// Bounds of the adaptive poll interval, persisted on every change.
+///
+/// Provided once at the app root with `use_context_provider(PollingSettings::load)`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct PollingSettings {
+    document: Signal<PollingDocument>,
+}
+
+impl PollingSettings {
+    /// Load polling settings from the persistence layer
+    pub fn load() -> Self {
+        let document: PollingDocument = load_json(POLLING_FILE)
+            .unwrap_or_else(|e| {
+                report(FeedError::Load { what: "polling settings", source: e });
+                None
+            })
+            .unwrap_or_default();
+
+        Self {
+            document: Signal::new(document),
+        }
+    }
+
+    pub fn min_secs(&self) -> u64 {
+        self.document.read().min_secs
+    }
+
+    pub fn max_secs(&self) -> u64 {
+        self.document.read().max_secs
+    }
+
+    /// Current bounds, read without subscribing so poll loops pick up
+    /// changes at their next poll
+    pub fn bounds(&self) -> PollBounds {
+        let document = self.document.peek();
+        PollBounds::new(Duration::from_secs(document.min_secs), Duration::from_secs(document.max_secs))
+    }
+
+    /// Set the shortest interval, raising the longest to match if needed
+    pub fn set_min_secs(&mut self, min_secs: u64) {
+        let min_secs = min_secs.max(1);
+        if self.document.peek().min_secs == min_secs {
+            return;
+        }
+
+        let mut document = self.document.write();
+        document.min_secs = min_secs;
+        document.max_secs = document.max_secs.max(min_secs);
+        drop(document);
+        self.save();
+    }
+
+    /// Set the longest interval, lowering the shortest to match if needed
+    pub fn set_max_secs(&mut self, max_secs: u64) {
+        let max_secs = max_secs.max(1);
+        if self.document.peek().max_secs == max_secs {
+            return;
+        }
+
+        let mut document = self.document.write();
+        document.max_secs = max_secs;
+        document.min_secs = document.min_secs.min(max_secs);
+        drop(document);
+        self.save();
+    }
+
+    fn save(&self) {
+        if let Err(e) = save_json(POLLING_FILE, &*self.document.peek()) {
+            report(FeedError::Save { what: "polling settings", source: e });
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{encryption, load_json, save_json, PersistenceError};
+use crate::assets::remote_media_url;
+use crate::error::{report, FeedError};
+
+/// File name of the persisted privacy settings document, which is never
+/// encrypted
+pub(super) const PRIVACY_FILE: &str = "privacy.json";
+
+/// Whether remote content referenced by fetched pages is loaded, like the
+/// remote content setting of email clients
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum RemoteContent {
+    #[default]
+    Always,
+    /// Blocked until loading is allowed for the page being read
+    Ask,
+    Never,
+}
+
+impl RemoteContent {
+    pub const ALL: [RemoteContent; 3] = [RemoteContent::Always, RemoteContent::Ask, RemoteContent::Never];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RemoteContent::Always => "Always",
+            RemoteContent::Ask => "Ask",
+            RemoteContent::Never => "Never",
+        }
+    }
+}
+
+/// How a remote reference in fetched content is rendered
+#[derive(Clone, PartialEq, Debug)]
+pub enum RemoteLoad {
+    /// Load it from this URL, through the remote proxy on desktop
+    Load(String),
+    /// Show a placeholder instead
+    Blocked,
+}
+
+/// Rewrite a remote reference under `setting`; `allowed` is whether the user
+/// allowed remote content for the page it appears in
+pub fn rewrite_remote(url: &str, setting: RemoteContent, allowed: bool) -> RemoteLoad {
+    match setting {
+        RemoteContent::Always => RemoteLoad::Load(remote_media_url(url)),
+        RemoteContent::Ask if allowed => RemoteLoad::Load(remote_media_url(url)),
+        RemoteContent::Ask | RemoteContent::Never => RemoteLoad::Blocked,
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+struct PrivacyDocument {
+    remote_content: RemoteContent,
+    #[serde(default)]
+    resolve_redirects: bool,
+    #[serde(default)]
+    encrypt_at_rest: bool,
+}
+
+/// Privacy settings, persisted on every change.
+///
+/// Provided once at the app root with `use_context_provider(PrivacySettings::load)`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct PrivacySettings {
+    document: Signal<PrivacyDocument>,
+}
+
+impl PrivacySettings {
+    /// Load privacy settings from the persistence layer
+    pub fn load() -> Self {
+        let document: PrivacyDocument = load_json(PRIVACY_FILE)
+            .unwrap_or_else(|e| {
+                report(FeedError::Load { what: "privacy settings", source: e });
+                None
+            })
+            .unwrap_or_default();
+        if let Err(e) = encryption::restore(document.encrypt_at_rest) {
+            report(FeedError::Load { what: "encryption key", source: e });
+        }
+
+        Self {
+            document: Signal::new(document),
+        }
+    }
+
+    pub fn remote_content(&self) -> RemoteContent {
+        self.document.read().remote_content
+    }
+
+    pub fn set_remote_content(&mut self, remote_content: RemoteContent) {
+        if self.document.peek().remote_content == remote_content {
+            return;
+        }
+
+        self.document.write().remote_content = remote_content;
+        self.save();
+    }
+
+    /// Whether external links are resolved through their redirects before
+    /// opening, which requests each link from its server first
+    pub fn resolve_redirects(&self) -> bool {
+        self.document.read().resolve_redirects
+    }
+
+    pub fn set_resolve_redirects(&mut self, resolve_redirects: bool) {
+        if self.document.peek().resolve_redirects == resolve_redirects {
+            return;
+        }
+
+        self.document.write().resolve_redirects = resolve_redirects;
+        self.save();
+    }
+
+    /// Whether persisted documents are encrypted with a key from the keychain
+    pub fn encrypt_at_rest(&self) -> bool {
+        self.document.read().encrypt_at_rest
+    }
+
+    /// Turn encryption at rest on or off, rewriting the documents already
+    /// saved. Returns how many were rewritten.
+    pub fn set_encrypt_at_rest(&mut self, encrypt_at_rest: bool) -> Result<usize, PersistenceError> {
+        if self.document.peek().encrypt_at_rest == encrypt_at_rest {
+            return Ok(0);
+        }
+
+        encryption::set_enabled(encrypt_at_rest)?;
+        self.document.write().encrypt_at_rest = encrypt_at_rest;
+        self.save();
+        encryption::rewrite_all()
+    }
+
+    fn save(&self) {
+        if let Err(e) = save_json(PRIVACY_FILE, &*self.document.peek()) {
+            report(FeedError::Save { what: "privacy settings", source: e });
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use dioxus::prelude::*;
+use std::collections::HashMap;
+
+use super::{load_json, save_json};
+use crate::error::{report, FeedError};
+
+/// File name of the persisted read state document
+const READ_STATE_FILE: &str = "read_state.json";
+
+/// Newest item seen in each feed, keyed by feed id, persisted on every change.
+///
+/// The state loaded at launch is kept aside as the previous session's, so
+/// feeds can mark where the last visit ended while this session's reading
+/// moves the saved position on. Provided once at the app root with
+/// `use_context_provider(ReadState::load)`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct ReadState {
+    previous_session: Signal<HashMap<String, String>>,
+    newest_seen: Signal<HashMap<String, String>>,
+}
+
+impl ReadState {
+    /// Load read state from the persistence layer
+    pub fn load() -> Self {
+        let newest_seen: HashMap<String, String> = load_json(READ_STATE_FILE)
+            .unwrap_or_else(|e| {
+                report(FeedError::Load { what: "read state", source: e });
+                None
+            })
+            .unwrap_or_default();
+
+        Self {
+            previous_session: Signal::new(newest_seen.clone()),
+            newest_seen: Signal::new(newest_seen),
+        }
+    }
+
+    /// Id of the newest item seen in `feed_id` during the previous session
+    pub fn last_visit(&self, feed_id: &str) -> Option<String> {
+        self.previous_session.peek().get(feed_id).cloned()
+    }
+
+    /// Id of the newest item seen in `feed_id` so far, this session included
+    pub fn newest_seen(&self, feed_id: &str) -> Option<String> {
+        self.newest_seen.peek().get(feed_id).cloned()
+    }
+
+    /// Record `item_id` as the newest item seen in `feed_id`
+    pub fn mark_newest_seen(&mut self, feed_id: &str, item_id: &str) {
+        if self.newest_seen.peek().get(feed_id).is_some_and(|id| id == item_id) {
+            return;
+        }
+
+        self.newest_seen.write().insert(feed_id.to_string(), item_id.to_string());
+        if let Err(e) = save_json(READ_STATE_FILE, &*self.newest_seen.peek()) {
+            report(FeedError::Save { what: "read state", source: e });
+        }
+    }
+}
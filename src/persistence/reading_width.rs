@@ -0,0 +1,110 @@
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{load_json, save_json};
+use crate::error::{report, FeedError};
+
+/// File name of the persisted reading width document
+const READING_WIDTH_FILE: &str = "reading_width.json";
+
+/// Widest the feed is laid out, centered in the window
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadingWidth {
+    Narrow,
+    Medium,
+    Wide,
+    #[default]
+    Full,
+}
+
+impl ReadingWidth {
+    pub const ALL: [ReadingWidth; 4] = [
+        ReadingWidth::Narrow,
+        ReadingWidth::Medium,
+        ReadingWidth::Wide,
+        ReadingWidth::Full,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ReadingWidth::Narrow => "Narrow",
+            ReadingWidth::Medium => "Medium",
+            ReadingWidth::Wide => "Wide",
+            ReadingWidth::Full => "Full",
+        }
+    }
+
+    /// Largest width of the feed in pixels, `None` for the window's width
+    pub fn max_width(self) -> Option<f64> {
+        match self {
+            ReadingWidth::Narrow => Some(560.0),
+            ReadingWidth::Medium => Some(720.0),
+            ReadingWidth::Wide => Some(960.0),
+            ReadingWidth::Full => None,
+        }
+    }
+
+    /// The next wider width, wrapping around to the narrowest
+    pub fn next(self) -> ReadingWidth {
+        match self {
+            ReadingWidth::Narrow => ReadingWidth::Medium,
+            ReadingWidth::Medium => ReadingWidth::Wide,
+            ReadingWidth::Wide => ReadingWidth::Full,
+            ReadingWidth::Full => ReadingWidth::Narrow,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+struct ReadingWidthDocument {
+    reading_width: ReadingWidth,
+}
+
+/// Reading width picked by the user, persisted on every change.
+///
+/// Provided once at the app root with `use_context_provider(ReadingWidthSettings::load)`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct ReadingWidthSettings {
+    document: Signal<ReadingWidthDocument>,
+}
+
+impl ReadingWidthSettings {
+    /// Load the reading width from the persistence layer
+    pub fn load() -> Self {
+        let document: ReadingWidthDocument = load_json(READING_WIDTH_FILE)
+            .unwrap_or_else(|e| {
+                report(FeedError::Load { what: "reading width", source: e });
+                None
+            })
+            .unwrap_or_default();
+
+        Self {
+            document: Signal::new(document),
+        }
+    }
+
+    pub fn reading_width(&self) -> ReadingWidth {
+        self.document.read().reading_width
+    }
+
+    pub fn set_reading_width(&mut self, reading_width: ReadingWidth) {
+        if self.document.peek().reading_width == reading_width {
+            return;
+        }
+        self.document.write().reading_width = reading_width;
+        self.save();
+    }
+
+    /// Switch to the next wider width, or back to the narrowest
+    pub fn cycle(&mut self) {
+        let reading_width = self.document.peek().reading_width.next();
+        self.set_reading_width(reading_width);
+    }
+
+    fn save(&self) {
+        if let Err(e) = save_json(READING_WIDTH_FILE, &*self.document.peek()) {
+            report(FeedError::Save { what: "reading width", source: e });
+        }
+    }
+}
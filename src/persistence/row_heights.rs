@@ -0,0 +1,251 @@
+use dioxus::core::spawn_forever;
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+use super::{load_json, save_json};
+use crate::clock::sleep;
+use crate::error::{report, FeedError};
+use crate::work::try_run_blocking;
+
+/// File name of the persisted row height cache
+const ROW_HEIGHTS_FILE: &str = "row_heights.json";
+
+/// Most rows cached per feed; the oldest are dropped first
+const MAX_ROWS_PER_FEED: usize = 5000;
+
+/// Change in a feed's width below which its cached heights still hold
+const WIDTH_TOLERANCE: f64 = 1.0;
+
+/// How long after heights change the cache is saved, so the updates of a
+/// scroll session are written together
+const SAVE_DELAY: Duration = Duration::from_secs(5);
+
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+struct FeedHeights {
+    /// Row height in pixels by item id
+    heights: HashMap<String, f64>,
+    /// Item ids, oldest first
+    order: VecDeque<String>,
+    /// Width of the list the rows were laid out in
+    #[serde(default)]
+    width: Option<f64>,
+}
+
+impl FeedHeights {
+    // Heights among `heights` for rows with none cached yet
+    fn missing(&self, heights: impl IntoIterator<Item = (String, f64)>) -> Vec<(String, f64)> {
+        heights
+            .into_iter()
+            .filter(|(id, _)| !self.heights.contains_key(id))
+            .collect()
+    }
+
+    // Heights among `heights` that differ from the cached ones
+    fn changed(&self, heights: impl IntoIterator<Item = (String, f64)>) -> Vec<(String, f64)> {
+        heights
+            .into_iter()
+            .filter(|(id, height)| self.heights.get(id) != Some(height))
+            .collect()
+    }
+
+    // Whether rows laid out at `width` have the cached heights
+    fn holds_at(&self, width: f64) -> bool {
+        self.width.is_some_and(|cached| (cached - width).abs() < WIDTH_TOLERANCE)
+    }
+
+    // Record that the rows are laid out at `width`, dropping heights cached
+    // at another width. Returns whether there were any. Heights saved before
+    // widths were recorded are taken to be at this width.
+    fn lay_out_at(&mut self, width: f64) -> bool {
+        let stale = self.width.is_some() && !self.holds_at(width);
+        let dropped = stale && !self.heights.is_empty();
+        if stale {
+            self.heights.clear();
+            self.order.clear();
+        }
+        self.width = Some(width);
+        dropped
+    }
+
+    // Store `heights`, dropping the oldest rows past the limit
+    fn insert(&mut self, heights: Vec<(String, f64)>) {
+        for (id, height) in heights {
+            if self.heights.insert(id.clone(), height).is_none() {
+                self.order.push_back(id);
+            }
+        }
+        while self.order.len() > MAX_ROWS_PER_FEED {
+            if let Some(oldest) = self.order.pop_front() {
+                self.heights.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// Heights the rows of each feed were laid out at, keyed by feed id and
+/// item id, persisted a few seconds after rows are added or change.
+///
+/// Lists with a `feed_id` take row heights from here before estimating them,
+/// so a restored session lays rows out as they were and the saved scroll
+/// position lands on the same pixel offset without measuring anything.
+/// Provided once at the app root with `use_context_provider(RowHeightCache::load)`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct RowHeightCache {
+    feeds: Signal<HashMap<String, FeedHeights>>,
+    // Whether a save is scheduled, which covers changes made until it runs
+    save_pending: CopyValue<bool>,
+}
+
+impl RowHeightCache {
+    /// Load cached heights from the persistence layer
+    pub fn load() -> Self {
+        let feeds: HashMap<String, FeedHeights> = load_json(ROW_HEIGHTS_FILE)
+            .unwrap_or_else(|e| {
+                report(FeedError::Load { what: "row heights", source: e });
+                None
+            })
+            .unwrap_or_default();
+
+        Self {
+            feeds: Signal::new(feeds),
+            save_pending: CopyValue::new(false),
+        }
+    }
+
+    /// Cached height of the row of `item_id` in `feed_id`
+    pub fn height(&self, feed_id: &str, item_id: &str) -> Option<f64> {
+        self.feeds.read().get(feed_id)?.heights.get(item_id).copied()
+    }
+
+    /// Cache the heights of rows in `feed_id` that have none yet; cached
+    /// heights are kept
+    pub fn fill(&mut self, feed_id: &str, heights: impl IntoIterator<Item = (String, f64)>) {
+        let missing = match self.feeds.peek().get(feed_id) {
+            Some(feed) => feed.missing(heights),
+            None => heights.into_iter().collect(),
+        };
+        self.insert(feed_id, missing);
+    }
+
+    /// Cache the heights of rows in `feed_id`, replacing cached ones that
+    /// differ, e.g. rows measured after their content changed
+    pub fn update(&mut self, feed_id: &str, heights: impl IntoIterator<Item = (String, f64)>) {
+        let changed = match self.feeds.peek().get(feed_id) {
+            Some(feed) => feed.changed(heights),
+            None => heights.into_iter().collect(),
+        };
+        self.insert(feed_id, changed);
+    }
+
+    /// Record the width the rows of `feed_id` are laid out in now. Heights
+    /// cached at another width no longer hold and are dropped; returns
+    /// whether there were any. Caches saved before widths were recorded are
+    /// taken to be at this width.
+    pub fn measured_at(&mut self, feed_id: &str, width: f64) -> bool {
+        if self.feeds.peek().get(feed_id).is_some_and(|feed| feed.holds_at(width)) {
+            return false;
+        }
+        let dropped = self.feeds.write().entry(feed_id.to_string()).or_default().lay_out_at(width);
+        self.save_soon();
+        dropped
+    }
+
+    // Store `heights` for `feed_id`, dropping the oldest rows past the limit
+    fn insert(&mut self, feed_id: &str, heights: Vec<(String, f64)>) {
+        if heights.is_empty() {
+            return;
+        }
+        self.feeds.write().entry(feed_id.to_string()).or_default().insert(heights);
+        self.save_soon();
+    }
+
+    // Save the cache after SAVE_DELAY, unless a save is already waiting,
+    // serializing it on the blocking pool. The save runs at the app root, so
+    // it isn't lost with the list whose rows changed.
+    fn save_soon(&mut self) {
+        if *self.save_pending.peek() {
+            return;
+        }
+        self.save_pending.set(true);
+        let mut cache = *self;
+        spawn_forever(async move {
+            sleep(SAVE_DELAY).await;
+            cache.save_pending.set(false);
+            let feeds = cache.feeds.peek().clone();
+            if let Err(e) = try_run_blocking(move || save_json(ROW_HEIGHTS_FILE, &feeds)).await {
+                report(FeedError::Save { what: "row heights", source: e });
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn heights(rows: &[(&str, f64)]) -> Vec<(String, f64)> {
+        rows.iter().map(|(id, height)| (id.to_string(), *height)).collect()
+    }
+
+    #[test]
+    fn missing_skips_cached_rows() {
+        let mut feed = FeedHeights::default();
+        feed.insert(heights(&[("a", 100.0)]));
+        assert_eq!(feed.missing(heights(&[("a", 120.0), ("b", 80.0)])), heights(&[("b", 80.0)]));
+    }
+
+    #[test]
+    fn changed_keeps_new_and_different_rows() {
+        let mut feed = FeedHeights::default();
+        feed.insert(heights(&[("a", 100.0), ("b", 80.0)]));
+        assert_eq!(
+            feed.changed(heights(&[("a", 100.0), ("b", 90.0), ("c", 60.0)])),
+            heights(&[("b", 90.0), ("c", 60.0)])
+        );
+    }
+
+    #[test]
+    fn insert_replaces_heights_and_drops_the_oldest_rows() {
+        let mut feed = FeedHeights::default();
+        let rows: Vec<(String, f64)> = (0..MAX_ROWS_PER_FEED).map(|i| (i.to_string(), 100.0)).collect();
+        feed.insert(rows);
+        // Replacing a height doesn't make the row newer
+        feed.insert(heights(&[("0", 150.0)]));
+        assert_eq!(feed.heights.get("0"), Some(&150.0));
+        assert_eq!(feed.order.len(), MAX_ROWS_PER_FEED);
+
+        feed.insert(heights(&[("new", 50.0)]));
+        assert_eq!(feed.order.len(), MAX_ROWS_PER_FEED);
+        assert!(!feed.heights.contains_key("0"));
+        assert_eq!(feed.order.back().map(String::as_str), Some("new"));
+        assert_eq!(feed.heights.len(), MAX_ROWS_PER_FEED);
+    }
+
+    #[test]
+    fn heights_hold_within_the_width_tolerance() {
+        let mut feed = FeedHeights::default();
+        feed.insert(heights(&[("a", 100.0)]));
+        // Heights cached before widths were recorded are adopted
+        assert!(!feed.holds_at(600.0));
+        assert!(!feed.lay_out_at(600.0));
+        assert_eq!(feed.heights.len(), 1);
+
+        assert!(feed.holds_at(600.5));
+        assert!(!feed.holds_at(700.0));
+    }
+
+    #[test]
+    fn heights_at_another_width_are_dropped() {
+        let mut feed = FeedHeights::default();
+        feed.lay_out_at(600.0);
+        feed.insert(heights(&[("a", 100.0)]));
+
+        assert!(feed.lay_out_at(400.0));
+        assert!(feed.heights.is_empty() && feed.order.is_empty());
+        assert_eq!(feed.width, Some(400.0));
+        // Nothing left to drop
+        assert!(!feed.lay_out_at(800.0));
+    }
+}
@@ -0,0 +1,87 @@
+use dioxus::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use super::{load_json, save_json};
+use crate::components::list_controller::ScrollAnchor;
+use crate::error::{report, FeedError};
+
+/// File name of the persisted scroll positions document
+const SCROLL_POSITIONS_FILE: &str = "scroll_positions.json";
+
+/// A saved position; earlier versions saved only the top item's id
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SavedPosition {
+    Anchor(ScrollAnchor),
+    TopItem(String),
+}
+
+impl From<SavedPosition> for ScrollAnchor {
+    fn from(position: SavedPosition) -> Self {
+        match position {
+            SavedPosition::Anchor(anchor) => anchor,
+            SavedPosition::TopItem(item_id) => ScrollAnchor::item(item_id),
+        }
+    }
+}
+
+/// Position of lists whose store is rebuilt, keyed by list (e.g. by
+/// account), persisted on every change.
+///
+/// Stores of named feeds keep their scroll position for the session; lists
+/// that are reset when their source changes, like an account's timeline when
+/// switching accounts, come back to their anchor through this instead.
+/// Provided once at the app root with `use_context_provider(ScrollPositions::load)`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct ScrollPositions {
+    anchors: Signal<HashMap<String, ScrollAnchor>>,
+}
+
+impl ScrollPositions {
+    /// Load scroll positions from the persistence layer
+    pub fn load() -> Self {
+        let saved: HashMap<String, SavedPosition> = load_json(SCROLL_POSITIONS_FILE)
+            .unwrap_or_else(|e| {
+                report(FeedError::Load { what: "scroll positions", source: e });
+                None
+            })
+            .unwrap_or_default();
+        let anchors = saved
+            .into_iter()
+            .map(|(key, position)| (key, position.into()))
+            .collect();
+
+        Self {
+            anchors: Signal::new(anchors),
+        }
+    }
+
+    /// Position last saved for the list with `key`
+    pub fn anchor(&self, key: &str) -> Option<ScrollAnchor> {
+        self.anchors.peek().get(key).cloned()
+    }
+
+    /// Record `anchor` as the position of the list with `key`
+    pub fn remember(&mut self, key: &str, anchor: &ScrollAnchor) {
+        if self.anchors.peek().get(key) == Some(anchor) {
+            return;
+        }
+
+        self.anchors.write().insert(key.to_string(), anchor.clone());
+        self.save();
+    }
+
+    /// Drop the position of a list that is gone
+    pub fn forget(&mut self, key: &str) {
+        if self.anchors.write().remove(key).is_some() {
+            self.save();
+        }
+    }
+
+    fn save(&self) {
+        if let Err(e) = save_json(SCROLL_POSITIONS_FILE, &*self.anchors.peek()) {
+            report(FeedError::Save { what: "scroll positions", source: e });
+        }
+    }
+}
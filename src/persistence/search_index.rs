@@ -0,0 +1,448 @@
+use dioxus::core::spawn_forever;
+use dioxus::prelude::*;
+use dioxus_feed_core::VirtualFeedItem;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use super::{load_json, save_json};
+use crate::clock::sleep;
+use crate::error::{report, FeedError};
+use crate::work::{run_blocking, try_run_blocking};
+
+/// File name of the persisted search index document
+const SEARCH_INDEX_FILE: &str = "search_index.json";
+
+/// Most items kept in the index; past this the oldest are dropped
+const MAX_INDEXED_ITEMS: usize = 20_000;
+
+/// Items kept when the index outgrows [`MAX_INDEXED_ITEMS`], so it isn't
+/// rebuilt again on the next few loads
+const EVICT_TO_ITEMS: usize = MAX_INDEXED_ITEMS / 10 * 9;
+
+/// How long after items are indexed the document is saved, so a burst of
+/// loads writes it once
+const SAVE_DELAY: Duration = Duration::from_secs(2);
+
+/// Characters of context shown around the first match in a hit
+const SNIPPET_CHARS: usize = 120;
+
+/// Item text as indexed, with the feed it was loaded in
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct IndexedItem {
+    pub id: String,
+    /// Key of the feed tab the item belongs to
+    pub feed: String,
+    pub text: String,
+}
+
+/// A search result
+#[derive(Clone, PartialEq, Debug)]
+pub struct SearchHit {
+    pub id: String,
+    pub feed: String,
+    pub snippet: String,
+}
+
+// A document with its trigrams, computed on the blocking pool
+struct PreparedDoc {
+    doc: IndexedItem,
+    trigrams: HashSet<String>,
+}
+
+impl PreparedDoc {
+    fn new(doc: IndexedItem) -> Self {
+        let trigrams = trigrams(&doc.text.to_lowercase());
+        Self { doc, trigrams }
+    }
+}
+
+// Documents in the order they were indexed, with a trigram index over them
+#[derive(Default)]
+struct IndexState {
+    docs: Vec<IndexedItem>,
+    positions: HashMap<String, usize>,
+    trigrams: HashMap<String, Vec<usize>>,
+}
+
+impl IndexState {
+    fn build(docs: Vec<IndexedItem>) -> Self {
+        let mut state = Self::default();
+        for doc in docs {
+            state.insert(PreparedDoc::new(doc));
+        }
+        state
+    }
+
+    fn insert(&mut self, PreparedDoc { doc, trigrams }: PreparedDoc) {
+        let position = self.docs.len();
+        for trigram in trigrams {
+            self.trigrams.entry(trigram).or_default().push(position);
+        }
+        self.positions.insert(doc.id.clone(), position);
+        self.docs.push(doc);
+    }
+
+    // Documents to keep once there are more than MAX_INDEXED_ITEMS, the
+    // newest EVICT_TO_ITEMS
+    fn evicted(&self) -> Option<Vec<IndexedItem>> {
+        (self.docs.len() > MAX_INDEXED_ITEMS).then(|| self.docs[self.docs.len() - EVICT_TO_ITEMS..].to_vec())
+    }
+
+    // Positions of documents containing every trigram of `term`
+    fn candidates(&self, term: &str) -> Option<HashSet<usize>> {
+        let mut candidates: Option<HashSet<usize>> = None;
+        for trigram in trigrams(term) {
+            let postings: HashSet<usize> = self.trigrams.get(&trigram)?.iter().copied().collect();
+            candidates = Some(match candidates {
+                Some(current) => current.intersection(&postings).copied().collect(),
+                None => postings,
+            });
+        }
+        candidates
+    }
+
+    // Documents containing every word of `query`, best matches first
+    fn search(&self, query: &str, limit: usize) -> Vec<SearchHit> {
+        let query = query.to_lowercase();
+        let terms: Vec<&str> = query.split_whitespace().collect();
+        if terms.is_empty() {
+            return Vec::new();
+        }
+
+        // Terms too short for a trigram are only checked, not looked up
+        let mut candidates: Option<HashSet<usize>> = None;
+        for term in terms.iter().filter(|term| term.chars().count() >= 3) {
+            let Some(found) = self.candidates(term) else {
+                return Vec::new();
+            };
+            candidates = Some(match candidates {
+                Some(current) => current.intersection(&found).copied().collect(),
+                None => found,
+            });
+        }
+        let candidates: Vec<usize> = match candidates {
+            Some(candidates) => candidates.into_iter().collect(),
+            None => (0..self.docs.len()).collect(),
+        };
+
+        let mut scored: Vec<(usize, usize)> = candidates
+            .into_iter()
+            .filter_map(|position| {
+                let text = self.docs[position].text.to_lowercase();
+                let mut score = 0;
+                for term in &terms {
+                    let count = text.matches(term).count();
+                    if count == 0 {
+                        return None;
+                    }
+                    score += count;
+                }
+                Some((position, score))
+            })
+            .collect();
+        // More occurrences first, then the most recently indexed
+        scored.sort_by(|a, b| b.1.cmp(&a.1).then(b.0.cmp(&a.0)));
+
+        scored
+            .into_iter()
+            .take(limit)
+            .map(|(position, _)| {
+                let doc = &self.docs[position];
+                SearchHit {
+                    id: doc.id.clone(),
+                    feed: doc.feed.clone(),
+                    snippet: snippet(&doc.text, terms[0]),
+                }
+            })
+            .collect()
+    }
+}
+
+// Distinct character trigrams of lowercased text
+fn trigrams(text: &str) -> HashSet<String> {
+    let chars: Vec<char> = text.chars().collect();
+    chars.windows(3).map(|window| window.iter().collect()).collect()
+}
+
+// Text around the first occurrence of `term` in `text`
+fn snippet(text: &str, term: &str) -> String {
+    let lower = text.to_lowercase();
+    let start = lower
+        .find(term)
+        .map(|byte| char_index_before_lowering(text, byte))
+        .unwrap_or(0)
+        .saturating_sub(SNIPPET_CHARS / 4);
+    let snippet: String = text.chars().skip(start).take(SNIPPET_CHARS).collect();
+    let prefix = if start > 0 { "…" } else { "" };
+    let suffix = if text.chars().count() > start + SNIPPET_CHARS { "…" } else { "" };
+    format!("{}{}{}", prefix, snippet.trim(), suffix)
+}
+
+// Index of the char of `text` that byte `byte` of its lowercased form falls
+// in. Lowercasing can change how many chars and bytes a char takes (`İ` is
+// one char of two bytes but lowercases to two chars of three), so offsets in
+// the lowercased text don't carry over. `str::to_lowercase` only differs
+// from lowercasing char by char in which sigma it picks, and both are two
+// bytes long.
+fn char_index_before_lowering(text: &str, byte: usize) -> usize {
+    let mut lowered = 0;
+    text.chars()
+        .take_while(|c| {
+            let start = lowered;
+            lowered += c.to_lowercase().map(char::len_utf8).sum::<usize>();
+            start < byte
+        })
+        .count()
+}
+
+/// Full-text index over every item loaded into any feed, persisted so search
+/// covers items no longer in memory.
+///
+/// A trigram index narrows the candidates for each query term, which are then
+/// checked for the whole term. Stores add their items as they load them.
+/// Provided once at the app root with `use_context_provider(SearchIndex::load)`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct SearchIndex {
+    state: CopyValue<IndexState>,
+    /// Bumped whenever items are indexed, for searches to rerun
+    pub revision: Signal<u64>,
+    // Whether the index is being rebuilt on the blocking pool, which only one
+    // rebuild does at a time
+    rebuilding: CopyValue<bool>,
+    // Whether a save is scheduled, which covers items indexed until it runs
+    save_pending: CopyValue<bool>,
+}
+
+impl SearchIndex {
+    /// Load indexed items from the persistence layer. The index over them is
+    /// built on the blocking pool; until it is swapped in, searches only see
+    /// items indexed since launch.
+    pub fn load() -> Self {
+        let docs: Vec<IndexedItem> = load_json(SEARCH_INDEX_FILE)
+            .unwrap_or_else(|e| {
+                report(FeedError::Load { what: "search index", source: e });
+                None
+            })
+            .unwrap_or_default();
+
+        let mut index = Self {
+            state: CopyValue::new(IndexState::default()),
+            revision: Signal::new(0),
+            rebuilding: CopyValue::new(false),
+            save_pending: CopyValue::new(false),
+        };
+        if !docs.is_empty() {
+            index.rebuilding.set(true);
+            spawn(async move { index.rebuild(docs, 0).await });
+        }
+        index
+    }
+
+    pub fn indexed_count(&self) -> usize {
+        self.state.read().docs.len()
+    }
+
+    /// Index items loaded into the feed with key `feed`, skipping ones
+    /// already indexed. Their trigrams are computed on the blocking pool.
+    pub async fn add(&mut self, feed: String, items: Vec<VirtualFeedItem>) {
+        let docs: Vec<IndexedItem> = {
+            let state = self.state.peek();
+            items
+                .into_iter()
+                .filter(|item| !state.positions.contains_key(&item.id))
+                .map(|item| {
+                    let mut text = item.content;
+                    for extra in item.author.iter().chain(&item.sources) {
+                        text.push('\n');
+                        text.push_str(extra);
+                    }
+                    IndexedItem { id: item.id, feed: feed.clone(), text }
+                })
+                .collect()
+        };
+        if docs.is_empty() {
+            return;
+        }
+        let Ok(prepared) = run_blocking(move || docs.into_iter().map(PreparedDoc::new).collect::<Vec<_>>()).await else {
+            return;
+        };
+
+        let added = {
+            let mut state = self.state.write();
+            let before = state.docs.len();
+            // Another load may have indexed the same items meanwhile
+            for doc in prepared {
+                if !state.positions.contains_key(&doc.doc.id) {
+                    state.insert(doc);
+                }
+            }
+            state.docs.len() != before
+        };
+        if !added {
+            return;
+        }
+        self.revision += 1;
+
+        // Rebuilding is cheaper than shifting every posting list. The oldest
+        // tenth goes at once, so the rebuild doesn't repeat on every load.
+        if !*self.rebuilding.peek() {
+            let evicted = {
+                let state = self.state.peek();
+                state.evicted().map(|docs| (docs, state.docs.len()))
+            };
+            if let Some((docs, snapshot)) = evicted {
+                self.rebuilding.set(true);
+                self.rebuild(docs, snapshot).await;
+            }
+        }
+        self.save_soon();
+    }
+
+    // Build the index over `docs` on the blocking pool and swap it in,
+    // carrying over the documents indexed meanwhile, past the first
+    // `snapshot` of the current index
+    async fn rebuild(&mut self, docs: Vec<IndexedItem>, snapshot: usize) {
+        let Ok(rebuilt) = run_blocking(move || IndexState::build(docs)).await else {
+            self.rebuilding.set(false);
+            return;
+        };
+        {
+            let mut state = self.state.write();
+            let kept = snapshot.min(state.docs.len());
+            let newer = state.docs.split_off(kept);
+            *state = rebuilt;
+            for doc in newer {
+                if !state.positions.contains_key(&doc.id) {
+                    state.insert(PreparedDoc::new(doc));
+                }
+            }
+        }
+        self.rebuilding.set(false);
+        self.revision += 1;
+    }
+
+    // Save the indexed documents after SAVE_DELAY, unless a save is already
+    // waiting, serializing them on the blocking pool. The save runs at the
+    // app root, so it isn't lost with the feed that indexed the items.
+    fn save_soon(&mut self) {
+        if *self.save_pending.peek() {
+            return;
+        }
+        self.save_pending.set(true);
+        let mut index = *self;
+        spawn_forever(async move {
+            sleep(SAVE_DELAY).await;
+            // Saving mid-rebuild would drop the documents being rebuilt
+            while *index.rebuilding.peek() {
+                sleep(SAVE_DELAY).await;
+            }
+            index.save_pending.set(false);
+
+            let docs = index.state.peek().docs.clone();
+            if let Err(e) = try_run_blocking(move || save_json(SEARCH_INDEX_FILE, &docs)).await {
+                report(FeedError::Save { what: "search index", source: e });
+            }
+        });
+    }
+
+    /// Items containing every word of `query`, best matches first
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchHit> {
+        self.state.read().search(query, limit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(id: &str, text: &str) -> IndexedItem {
+        IndexedItem {
+            id: id.to_string(),
+            feed: "home".to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    fn ids(hits: &[SearchHit]) -> Vec<&str> {
+        hits.iter().map(|hit| hit.id.as_str()).collect()
+    }
+
+    #[test]
+    fn trigrams_are_distinct_char_windows() {
+        let expected: HashSet<String> = ["aba", "bab"].iter().map(|t| t.to_string()).collect();
+        assert_eq!(trigrams("ababa"), expected);
+        assert!(trigrams("ab").is_empty());
+        // Chars, not bytes
+        assert_eq!(trigrams("çaé"), ["çaé".to_string()].into_iter().collect());
+    }
+
+    #[test]
+    fn candidates_contain_every_trigram() {
+        let state = IndexState::build(vec![
+            doc("a", "rust is fast"),
+            doc("b", "trust the process"),
+            doc("c", "dust and rest"),
+        ]);
+        assert_eq!(state.candidates("rust"), Some([0, 1].into_iter().collect()));
+        assert_eq!(state.candidates("rest"), Some([2].into_iter().collect()));
+        // A trigram no document has rules every one out
+        assert_eq!(state.candidates("xyz"), None);
+    }
+
+    #[test]
+    fn search_ranks_by_occurrences_then_recency() {
+        let state = IndexState::build(vec![
+            doc("old", "feed reader"),
+            doc("many", "feed after feed after feed"),
+            doc("new", "another feed"),
+            doc("none", "nothing here"),
+        ]);
+        assert_eq!(ids(&state.search("feed", 10)), ["many", "new", "old"]);
+        assert_eq!(ids(&state.search("FEED", 1)), ["many"]);
+    }
+
+    #[test]
+    fn search_needs_every_term() {
+        let state = IndexState::build(vec![
+            doc("a", "rust feed reader"),
+            doc("b", "rust compiler"),
+            doc("c", "go feed"),
+        ]);
+        assert_eq!(ids(&state.search("rust feed", 10)), ["a"]);
+        // Terms too short for a trigram are still checked
+        assert_eq!(ids(&state.search("go", 10)), ["c"]);
+        assert!(state.search("   ", 10).is_empty());
+        assert!(state.search("missing", 10).is_empty());
+    }
+
+    #[test]
+    fn eviction_keeps_the_newest_ninety_percent() {
+        let mut state = IndexState::build((0..MAX_INDEXED_ITEMS).map(|i| doc(&i.to_string(), "x")).collect());
+        assert!(state.evicted().is_none());
+
+        state.insert(PreparedDoc::new(doc("last", "x")));
+        let kept = state.evicted().unwrap();
+        assert_eq!(kept.len(), EVICT_TO_ITEMS);
+        assert_eq!(kept.last().unwrap().id, "last");
+        assert_eq!(kept[0].id, (MAX_INDEXED_ITEMS + 1 - EVICT_TO_ITEMS).to_string());
+    }
+
+    #[test]
+    fn snippet_surrounds_the_match() {
+        let text = format!("{} needle {}", "a".repeat(100), "b".repeat(100));
+        let snippet = snippet(&text, "needle");
+        assert!(snippet.starts_with('…') && snippet.ends_with('…'));
+        assert!(snippet.contains("needle"));
+    }
+
+    #[test]
+    fn snippet_offsets_survive_lowercasing() {
+        // Each `İ` lowercases to two chars, which pushed the snippet past
+        // the match when offsets were taken from the lowercased text
+        let text = format!("{} needle", "İ".repeat(60));
+        let snippet = snippet(&text, "needle");
+        assert!(snippet.contains("needle"), "{}", snippet);
+        assert_eq!(char_index_before_lowering(&text, "i̇".len() * 60 + 1), 61);
+    }
+}
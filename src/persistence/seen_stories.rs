@@ -0,0 +1,47 @@
+use dioxus::prelude::*;
+use std::collections::HashSet;
+
+use super::{load_json, save_json};
+use crate::error::{report, FeedError};
+
+/// File name of the persisted seen stories document
+const SEEN_STORIES_FILE: &str = "seen_stories.json";
+
+/// Ids of the stories the user has opened, persisted on every change.
+///
+/// Provided once at the app root with `use_context_provider(SeenStories::load)`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct SeenStories {
+    seen: Signal<HashSet<String>>,
+}
+
+impl SeenStories {
+    /// Load seen stories from the persistence layer
+    pub fn load() -> Self {
+        let seen: HashSet<String> = load_json(SEEN_STORIES_FILE)
+            .unwrap_or_else(|e| {
+                report(FeedError::Load { what: "seen stories", source: e });
+                None
+            })
+            .unwrap_or_default();
+
+        Self {
+            seen: Signal::new(seen),
+        }
+    }
+
+    pub fn is_seen(&self, id: &str) -> bool {
+        self.seen.read().contains(id)
+    }
+
+    pub fn mark_seen(&mut self, id: &str) {
+        if self.is_seen(id) {
+            return;
+        }
+
+        self.seen.write().insert(id.to_string());
+        if let Err(e) = save_json(SEEN_STORIES_FILE, &*self.seen.read()) {
+            report(FeedError::Save { what: "seen stories", source: e });
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use dioxus_feed_core::VirtualFeedItem;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use super::{escape_html, write_file, PersistenceError, EXPORTS_DIR};
+use crate::urlcleaner::clean_url;
+
+/// Render loaded feed items as a standalone HTML document for archiving a
+/// reading session. `images` maps image URLs to data URLs; items whose image
+/// has no entry are exported without it.
+pub fn render_session_html(
+    title: &str,
+    items: &[VirtualFeedItem],
+    images: &HashMap<String, String>,
+) -> String {
+    let exported_at = chrono::Utc::now().format("%Y-%m-%d %H:%M");
+    let title = escape_html(title);
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>\n\
+         body {{ font-family: sans-serif; max-width: 720px; margin: 24px auto; color: #0f172a; }}\n\
+         article {{ display: flex; gap: 16px; padding: 16px 0; border-bottom: 1px solid #e2e8f0; break-inside: avoid; }}\n\
+         img {{ width: 120px; height: 120px; object-fit: cover; border-radius: 6px; flex-shrink: 0; }}\n\
+         </style>\n</head>\n<body>\n<h1>{title}</h1>\n<p><small>Exported {exported_at}, {count} items</small></p>\n",
+        count = items.len(),
+    );
+
+    for item in items {
+        html.push_str("<article>\n");
+        let image = item.image_candidates().find_map(|url| images.get(url));
+        if let Some(data_url) = image {
+            html.push_str(&format!("<img src=\"{}\" alt=\"\">\n", escape_html(data_url)));
+        }
+        html.push_str("<div>\n");
+        html.push_str(&format!("<p>{}</p>\n", escape_html(&item.content)));
+        if let Some(link) = &item.link {
+            let link = escape_html(&clean_url(link));
+            html.push_str(&format!("<p><a href=\"{}\">{}</a></p>\n", link, link));
+        }
+        html.push_str("</div>\n</article>\n");
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Write a rendered session document to the exports directory, returning its path
+pub fn save_session_html(html: &str) -> Result<PathBuf, PersistenceError> {
+    let timestamp = chrono::Utc::now().format("%Y%m%d-%H%M%S");
+    let name = format!("{}/session-{}.html", EXPORTS_DIR, timestamp);
+    write_file(&name, html.as_bytes())
+}
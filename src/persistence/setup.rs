@@ -0,0 +1,109 @@
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{load_json, save_json};
+use crate::error::{report, FeedError};
+
+/// File name of the persisted first-run configuration
+const SETUP_FILE: &str = "setup.json";
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+struct SetupDocument {
+    /// Whether the first-run flow was finished
+    completed: bool,
+    /// Whether the Home and Media tabs, filled with generated items, are shown
+    demo_feeds: bool,
+    /// Directories the user let the asset protocol serve images from
+    #[serde(default)]
+    media_directories: Vec<String>,
+}
+
+impl Default for SetupDocument {
+    fn default() -> Self {
+        Self {
+            completed: false,
+            demo_feeds: true,
+            media_directories: Vec::new(),
+        }
+    }
+}
+
+/// Configuration chosen in the first-run flow, persisted on every change.
+///
+/// Provided once at the app root with `use_context_provider(SetupConfig::load)`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct SetupConfig {
+    document: Signal<SetupDocument>,
+}
+
+impl SetupConfig {
+    /// Load the configuration from the persistence layer
+    pub fn load() -> Self {
+        let document: SetupDocument = load_json(SETUP_FILE)
+            .unwrap_or_else(|e| {
+                report(FeedError::Load { what: "setup", source: e });
+                None
+            })
+            .unwrap_or_default();
+
+        Self {
+            document: Signal::new(document),
+        }
+    }
+
+    pub fn is_completed(&self) -> bool {
+        self.document.read().completed
+    }
+
+    pub fn demo_feeds(&self) -> bool {
+        self.document.read().demo_feeds
+    }
+
+    /// Directories the asset protocol may serve images from, besides the
+    /// bundled assets
+    pub fn media_directories(&self) -> Vec<String> {
+        self.document.read().media_directories.clone()
+    }
+
+    pub fn set_demo_feeds(&mut self, demo_feeds: bool) {
+        if self.document.peek().demo_feeds == demo_feeds {
+            return;
+        }
+        self.document.write().demo_feeds = demo_feeds;
+        self.save();
+    }
+
+    /// Remember `directory` as allowed, returning whether it is new
+    pub fn add_media_directory(&mut self, directory: &str) -> bool {
+        if self.document.peek().media_directories.iter().any(|known| known == directory) {
+            return false;
+        }
+        self.document.write().media_directories.push(directory.to_string());
+        self.save();
+        true
+    }
+
+    pub fn remove_media_directory(&mut self, directory: &str) {
+        if !self.document.peek().media_directories.iter().any(|known| known == directory) {
+            return;
+        }
+        self.document.write().media_directories.retain(|known| known != directory);
+        self.save();
+    }
+
+    /// Mark the first-run flow as done, so later launches go straight to
+    /// the feeds
+    pub fn complete(&mut self) {
+        if self.document.peek().completed {
+            return;
+        }
+        self.document.write().completed = true;
+        self.save();
+    }
+
+    fn save(&self) {
+        if let Err(e) = save_json(SETUP_FILE, &*self.document.peek()) {
+            report(FeedError::Save { what: "setup", source: e });
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::{load_json, save_json};
+use crate::error::{report, FeedError};
+use crate::shortcuts::{ShortcutAction, RESERVED_KEYS};
+
+/// File name of the persisted shortcut bindings document
+const SHORTCUTS_FILE: &str = "shortcuts.json";
+
+/// Custom error type for shortcut bindings
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShortcutError {
+    /// The key is handled by the feed list itself
+    Reserved(String),
+    /// The key is already bound to another action
+    Conflict(String, ShortcutAction),
+}
+
+impl std::fmt::Display for ShortcutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShortcutError::Reserved(key) => write!(f, "{} is reserved", key),
+            ShortcutError::Conflict(key, action) => write!(f, "{} is already bound to {}", key, action.label()),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+struct ShortcutsDocument {
+    /// Keys the user bound, by action; others use their default key
+    bindings: HashMap<ShortcutAction, String>,
+}
+
+/// Key bound to each shortcut action, persisted on every change.
+///
+/// Provided once at the app root with `use_context_provider(ShortcutMap::load)`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct ShortcutMap {
+    document: Signal<ShortcutsDocument>,
+}
+
+impl ShortcutMap {
+    /// Load shortcut bindings from the persistence layer
+    pub fn load() -> Self {
+        let document: ShortcutsDocument = load_json(SHORTCUTS_FILE)
+            .unwrap_or_else(|e| {
+                report(FeedError::Load { what: "shortcuts", source: e });
+                None
+            })
+            .unwrap_or_default();
+
+        Self {
+            document: Signal::new(document),
+        }
+    }
+
+    /// Key bound to `action`
+    pub fn key(&self, action: ShortcutAction) -> String {
+        self.document
+            .read()
+            .bindings
+            .get(&action)
+            .cloned()
+            .unwrap_or_else(|| action.default_key().to_string())
+    }
+
+    /// Action bound to `key`, the first one if several are
+    pub fn action_for(&self, key: &str) -> Option<ShortcutAction> {
+        ShortcutAction::ALL.into_iter().find(|action| self.peek_key(*action) == key)
+    }
+
+    /// Actions sharing a key with an earlier one, e.g. after a default
+    /// changed to a key the user had bound elsewhere
+    pub fn conflicts(&self) -> Vec<ShortcutAction> {
+        let mut seen: Vec<String> = Vec::new();
+        let mut conflicts = Vec::new();
+        for action in ShortcutAction::ALL {
+            let key = self.key(action);
+            if seen.contains(&key) {
+                conflicts.push(action);
+            } else {
+                seen.push(key);
+            }
+        }
+        conflicts
+    }
+
+    /// Bind `action` to `key`, refusing reserved keys and keys bound to
+    /// another action
+    pub fn set_key(&mut self, action: ShortcutAction, key: &str) -> Result<(), ShortcutError> {
+        if RESERVED_KEYS.contains(&key) {
+            return Err(ShortcutError::Reserved(key.to_string()));
+        }
+        let taken_by = ShortcutAction::ALL
+            .into_iter()
+            .find(|other| *other != action && self.peek_key(*other) == key);
+        if let Some(other) = taken_by {
+            return Err(ShortcutError::Conflict(key.to_string(), other));
+        }
+        if self.peek_key(action) == key {
+            return Ok(());
+        }
+
+        let mut document = self.document.write();
+        if key == action.default_key() {
+            document.bindings.remove(&action);
+        } else {
+            document.bindings.insert(action, key.to_string());
+        }
+        drop(document);
+        self.save();
+        Ok(())
+    }
+
+    /// Go back to the default keys
+    pub fn reset(&mut self) {
+        if self.document.peek().bindings.is_empty() {
+            return;
+        }
+        self.document.write().bindings.clear();
+        self.save();
+    }
+
+    // Key bound to `action`, without subscribing to changes
+    fn peek_key(&self, action: ShortcutAction) -> String {
+        self.document
+            .peek()
+            .bindings
+            .get(&action)
+            .cloned()
+            .unwrap_or_else(|| action.default_key().to_string())
+    }
+
+    fn save(&self) {
+        if let Err(e) = save_json(SHORTCUTS_FILE, &*self.document.peek()) {
+            report(FeedError::Save { what: "shortcuts", source: e });
+        }
+    }
+}
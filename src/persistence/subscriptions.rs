@@ -0,0 +1,97 @@
+use dioxus::prelude::*;
+
+use super::{load_json, save_json};
+use crate::error::{report, FeedError};
+use crate::sources::opml::{parse_opml, OpmlError, OpmlFeed, OpmlFolder, DEFAULT_FOLDER};
+use crate::work::run_blocking;
+
+/// File name of the persisted subscriptions document
+const SUBSCRIPTIONS_FILE: &str = "subscriptions.json";
+
+/// RSS subscriptions grouped into folders, persisted on every change.
+///
+/// Each folder is shown as its own feed tab. Provided once at the app root
+/// with `use_context_provider(Subscriptions::load)`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Subscriptions {
+    folders: Signal<Vec<OpmlFolder>>,
+}
+
+impl Subscriptions {
+    /// Load subscriptions from the persistence layer
+    pub fn load() -> Self {
+        let folders: Vec<OpmlFolder> = load_json(SUBSCRIPTIONS_FILE)
+            .unwrap_or_else(|e| {
+                report(FeedError::Load { what: "subscriptions", source: e });
+                None
+            })
+            .unwrap_or_default();
+
+        Self {
+            folders: Signal::new(folders),
+        }
+    }
+
+    pub fn folders(&self) -> Vec<OpmlFolder> {
+        self.folders.read().clone()
+    }
+
+    pub fn folder(&self, title: &str) -> Option<OpmlFolder> {
+        self.folders.read().iter().find(|folder| folder.title == title).cloned()
+    }
+
+    /// Add the feeds listed in an OPML document, returning how many were new.
+    ///
+    /// Folders with the same title as an existing one are merged into it and
+    /// feeds already subscribed to are skipped. The document is parsed on the
+    /// blocking pool.
+    pub async fn import_opml(&mut self, xml: String) -> Result<usize, OpmlError> {
+        let imported = run_blocking(move || parse_opml(&xml)).await.map_err(|_| OpmlError::Cancelled)??;
+        Ok(self.merge(imported))
+    }
+
+    /// Subscribe to a single feed, in the default folder. Returns whether it
+    /// is new.
+    pub fn subscribe(&mut self, title: &str, xml_url: &str) -> bool {
+        let feed = OpmlFeed {
+            title: title.to_string(),
+            xml_url: xml_url.to_string(),
+            html_url: None,
+        };
+        let folder = OpmlFolder { title: DEFAULT_FOLDER.to_string(), feeds: vec![feed] };
+        self.merge(vec![folder]) > 0
+    }
+
+    // Merge `imported` into the folders and save them, returning how many
+    // feeds were new
+    fn merge(&mut self, imported: Vec<OpmlFolder>) -> usize {
+        let mut added = 0;
+        {
+            let mut folders = self.folders.write();
+            for folder in imported {
+                let index = match folders.iter().position(|existing| existing.title == folder.title) {
+                    Some(index) => index,
+                    None => {
+                        folders.push(OpmlFolder { title: folder.title.clone(), feeds: Vec::new() });
+                        folders.len() - 1
+                    }
+                };
+                for feed in folder.feeds {
+                    let subscribed = folders.iter().any(|existing| {
+                        existing.feeds.iter().any(|known| known.xml_url == feed.xml_url)
+                    });
+                    if !subscribed {
+                        folders[index].feeds.push(feed);
+                        added += 1;
+                    }
+                }
+            }
+            folders.retain(|folder| !folder.feeds.is_empty());
+        }
+
+        if let Err(e) = save_json(SUBSCRIPTIONS_FILE, &*self.folders.peek()) {
+            report(FeedError::Save { what: "subscriptions", source: e });
+        }
+        added
+    }
+}
@@ -0,0 +1,130 @@
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{load_json, save_json};
+use crate::captions::http::HttpCaptioner;
+use crate::captions::CaptionerHandle;
+use crate::error::{report, FeedError};
+use crate::summarizer::http::HttpSummarizer;
+use crate::summarizer::SummarizerHandle;
+
+/// File name of the persisted summarizer settings document
+const SUMMARIZER_FILE: &str = "summarizer.json";
+
+/// Model asked for when none is set
+const DEFAULT_MODEL: &str = "llama3.2";
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+struct SummarizerDocument {
+    /// Chat completions URL, empty while summaries are off
+    endpoint: String,
+    model: String,
+    /// Vision model writing alt text at the same endpoint, empty while
+    /// captions are off
+    #[serde(default)]
+    caption_model: String,
+}
+
+impl Default for SummarizerDocument {
+    fn default() -> Self {
+        Self {
+            endpoint: String::new(),
+            model: DEFAULT_MODEL.to_string(),
+            caption_model: String::new(),
+        }
+    }
+}
+
+/// Where summaries and image captions are generated, persisted on every
+/// change.
+///
+/// Provided once at the app root with `use_context_provider(SummarizerSettings::load)`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct SummarizerSettings {
+    document: Signal<SummarizerDocument>,
+}
+
+impl SummarizerSettings {
+    /// Load summarizer settings from the persistence layer
+    pub fn load() -> Self {
+        let document: SummarizerDocument = load_json(SUMMARIZER_FILE)
+            .unwrap_or_else(|e| {
+                report(FeedError::Load { what: "summarizer settings", source: e });
+                None
+            })
+            .unwrap_or_default();
+
+        Self {
+            document: Signal::new(document),
+        }
+    }
+
+    pub fn endpoint(&self) -> String {
+        self.document.read().endpoint.clone()
+    }
+
+    pub fn model(&self) -> String {
+        self.document.read().model.clone()
+    }
+
+    pub fn caption_model(&self) -> String {
+        self.document.read().caption_model.clone()
+    }
+
+    /// Summarizer for the current settings, a no-op one without an endpoint
+    pub fn summarizer(&self) -> SummarizerHandle {
+        let document = self.document.read();
+        if document.endpoint.is_empty() {
+            return SummarizerHandle::default();
+        }
+        SummarizerHandle::new(HttpSummarizer::new(document.endpoint.clone(), document.model.clone()))
+    }
+
+    /// Captioner for the current settings, a no-op one without an endpoint
+    /// or caption model
+    pub fn captioner(&self) -> CaptionerHandle {
+        let document = self.document.read();
+        if document.endpoint.is_empty() || document.caption_model.is_empty() {
+            return CaptionerHandle::default();
+        }
+        CaptionerHandle::new(HttpCaptioner::new(document.endpoint.clone(), document.caption_model.clone()))
+    }
+
+    /// Set the endpoint; an empty one turns summaries and captions off
+    pub fn set_endpoint(&mut self, endpoint: &str) {
+        let endpoint = endpoint.trim();
+        if self.document.peek().endpoint == endpoint {
+            return;
+        }
+        self.document.write().endpoint = endpoint.to_string();
+        self.save();
+    }
+
+    pub fn set_model(&mut self, model: &str) {
+        let model = match model.trim() {
+            "" => DEFAULT_MODEL,
+            model => model,
+        };
+        if self.document.peek().model == model {
+            return;
+        }
+        self.document.write().model = model.to_string();
+        self.save();
+    }
+
+    /// Set the caption model; an empty one turns captions off
+    pub fn set_caption_model(&mut self, model: &str) {
+        let model = model.trim();
+        if self.document.peek().caption_model == model {
+            return;
+        }
+        self.document.write().caption_model = model.to_string();
+        self.save();
+    }
+
+    fn save(&self) {
+        if let Err(e) = save_json(SUMMARIZER_FILE, &*self.document.peek()) {
+            report(FeedError::Save { what: "summarizer settings", source: e });
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{load_json, save_json};
+use crate::error::{report, FeedError};
+
+/// File name of the persisted theme document
+const THEME_FILE: &str = "theme.json";
+
+/// Color scheme of the app
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Theme {
+    #[default]
+    Light,
+    Dark,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 2] = [Theme::Light, Theme::Dark];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Theme::Light => "Light",
+            Theme::Dark => "Dark",
+        }
+    }
+
+    /// The other theme
+    pub fn toggled(self) -> Theme {
+        match self {
+            Theme::Light => Theme::Dark,
+            Theme::Dark => Theme::Light,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+struct ThemeDocument {
+    theme: Theme,
+}
+
+/// Theme picked by the user, persisted on every change.
+///
+/// Provided once at the app root with `use_context_provider(ThemeSettings::load)`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct ThemeSettings {
+    document: Signal<ThemeDocument>,
+}
+
+impl ThemeSettings {
+    /// Load the theme from the persistence layer
+    pub fn load() -> Self {
+        let document: ThemeDocument = load_json(THEME_FILE)
+            .unwrap_or_else(|e| {
+                report(FeedError::Load { what: "theme", source: e });
+                None
+            })
+            .unwrap_or_default();
+
+        Self {
+            document: Signal::new(document),
+        }
+    }
+
+    pub fn theme(&self) -> Theme {
+        self.document.read().theme
+    }
+
+    pub fn set_theme(&mut self, theme: Theme) {
+        if self.document.peek().theme == theme {
+            return;
+        }
+        self.document.write().theme = theme;
+        self.save();
+    }
+
+    /// Switch between light and dark
+    pub fn toggle(&mut self) {
+        let theme = self.document.peek().theme.toggled();
+        self.set_theme(theme);
+    }
+
+    fn save(&self) {
+        if let Err(e) = save_json(THEME_FILE, &*self.document.peek()) {
+            report(FeedError::Save { what: "theme", source: e });
+        }
+    }
+}
@@ -0,0 +1,162 @@
+//! Item processors annotating items as they arrive.
+//!
+//! Stores built `with_processors` run every loaded page through the
+//! [`Processors`] before inserting it, and the inbox does the same for pushed
+//! items. Processors only see the item and answer with [`Annotation`]s, which
+//! rows show as labels; they can't change or drop the item. An annotation can
+//! carry a [`ContentFlag`](dioxus_feed_core::ContentFlag), which gates the item behind a reveal.
+//!
+//! Third-party processors are WASI modules dropped into the `plugins`
+//! directory under the data directory and loaded at launch, see [`wasm`].
+
+pub mod wasm;
+
+use dioxus::prelude::*;
+use dioxus_feed_core::{Annotation, VirtualFeedItem};
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use crate::persistence::data_dir;
+
+/// Directory under the data directory plugins are loaded from
+pub const PLUGINS_DIR: &str = "plugins";
+
+/// Most annotations one processor may add to an item
+const MAX_ANNOTATIONS: usize = 8;
+
+/// Longest annotation label accepted
+const MAX_LABEL_CHARS: usize = 40;
+
+/// Custom error type for item processors
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProcessorError {
+    /// The processor couldn't be loaded
+    Load(String),
+    /// Processing an item failed or returned something unusable
+    Run(String),
+}
+
+impl std::fmt::Display for ProcessorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProcessorError::Load(msg) => write!(f, "Load error: {}", msg),
+            ProcessorError::Run(msg) => write!(f, "Processing error: {}", msg),
+        }
+    }
+}
+
+/// Something that reads an item and annotates it
+pub trait ItemProcessor {
+    /// Name shown in settings and on the annotations it adds
+    fn name(&self) -> &str;
+
+    /// Annotations for `item`, empty when it has nothing to say
+    fn process(&self, item: &VirtualFeedItem) -> Result<Vec<Annotation>, ProcessorError>;
+}
+
+/// A plugin that failed to load or to process an item
+#[derive(Debug, Clone, PartialEq)]
+pub struct PluginFailure {
+    pub plugin: String,
+    pub error: ProcessorError,
+}
+
+/// Processors loaded at launch and the latest failure of each plugin.
+///
+/// Provided once at the app root with `use_context_provider(Processors::load)`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Processors {
+    processors: Signal<Vec<Rc<dyn ItemProcessor>>>,
+    failures: Signal<Vec<PluginFailure>>,
+}
+
+impl Processors {
+    /// Load every `.wasm` plugin in the plugins directory, in name order
+    pub fn load() -> Self {
+        let mut processors: Vec<Rc<dyn ItemProcessor>> = Vec::new();
+        let mut failures = Vec::new();
+        for path in plugin_paths() {
+            let plugin = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+            match wasm::load(&path) {
+                Ok(processor) => processors.push(processor),
+                Err(error) => {
+                    eprintln!("Failed to load plugin {}: {}", plugin, error);
+                    failures.push(PluginFailure { plugin, error });
+                }
+            }
+        }
+
+        Self {
+            processors: Signal::new(processors),
+            failures: Signal::new(failures),
+        }
+    }
+
+    /// Names of the loaded processors
+    pub fn names(&self) -> Vec<String> {
+        self.processors.read().iter().map(|processor| processor.name().to_string()).collect()
+    }
+
+    pub fn failures(&self) -> Vec<PluginFailure> {
+        self.failures.read().clone()
+    }
+
+    /// Replace the annotations of `items` with what the processors say about
+    /// them now. A processor failing on an item leaves it unannotated by that
+    /// processor.
+    pub fn annotate<'a>(&mut self, items: impl IntoIterator<Item = &'a mut VirtualFeedItem>) {
+        let processors = self.processors.peek().clone();
+        if processors.is_empty() {
+            return;
+        }
+        for item in items {
+            item.annotations.clear();
+            for processor in &processors {
+                match checked_annotations(processor.as_ref(), item) {
+                    Ok(annotations) => item.annotations.extend(annotations),
+                    Err(error) => self.record_failure(processor.name(), error),
+                }
+            }
+        }
+    }
+
+    // Keep only the latest failure of each plugin
+    fn record_failure(&mut self, plugin: &str, error: ProcessorError) {
+        eprintln!("Plugin {} failed: {}", plugin, error);
+        let mut failures = self.failures.write();
+        failures.retain(|failure| failure.plugin != plugin);
+        failures.push(PluginFailure { plugin: plugin.to_string(), error });
+    }
+}
+
+// Run `processor` over `item`, holding its answer to the host's limits
+fn checked_annotations(processor: &dyn ItemProcessor, item: &VirtualFeedItem) -> Result<Vec<Annotation>, ProcessorError> {
+    let annotations = processor.process(item)?;
+    if annotations.len() > MAX_ANNOTATIONS {
+        return Err(ProcessorError::Run(format!("more than {} annotations for one item", MAX_ANNOTATIONS)));
+    }
+    Ok(annotations
+        .into_iter()
+        .filter(|annotation| !annotation.label.trim().is_empty())
+        .map(|annotation| Annotation {
+            label: annotation.label.trim().chars().take(MAX_LABEL_CHARS).collect(),
+            detail: annotation.detail,
+            processor: processor.name().to_string(),
+            flag: annotation.flag,
+        })
+        .collect())
+}
+
+// Plugin files in the plugins directory, sorted so they run in a stable order
+fn plugin_paths() -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(data_dir().join(PLUGINS_DIR)) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "wasm"))
+        .collect();
+    paths.sort();
+    paths
+}
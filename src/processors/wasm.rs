@@ -0,0 +1,179 @@
+//! Third-party processors compiled to WebAssembly (WASI preview 1).
+//!
+//! Each item is processed in a fresh instance with no preopened
+//! directories, environment or network, a memory cap and a fuel budget, so
+//! a plugin can only read the item it's given and answer with annotations.
+//! Modules export:
+//!
+//! ```text
+//! memory                          linear memory
+//! alloc(len: i32) -> i32          space for the input, written by the host
+//! process(ptr: i32, len: i32) -> i64
+//! ```
+//!
+//! The input is the item as JSON (`{"id", "text", "author", "link",
+//! "sources"}`) and `process` returns `ptr << 32 | len` of a JSON array of
+//! annotations (`[{"label": "…", "detail": "…", "flag": "spam"}]`, with
+//! `detail` and `flag` optional) in its memory. A `flag` of `"sensitive"` or
+//! `"spam"` blurs the item until the reader reveals it. Whatever the plugin
+//! writes to stderr is passed through for debugging.
+//!
+//! Running plugins needs the `plugins` feature; builds without it report
+//! every plugin as unsupported.
+
+use std::path::Path;
+use std::rc::Rc;
+
+use super::{ItemProcessor, ProcessorError};
+
+/// Whether this build can run plugins
+pub fn is_supported() -> bool {
+    cfg!(feature = "plugins")
+}
+
+/// Compile the module at `path` and check it exports the processor ABI
+pub fn load(path: &Path) -> Result<Rc<dyn ItemProcessor>, ProcessorError> {
+    runtime::load(path)
+}
+
+#[cfg(feature = "plugins")]
+mod runtime {
+    use serde::Serialize;
+    use std::path::Path;
+    use std::rc::Rc;
+    use std::sync::OnceLock;
+    use wasmtime::{Config, Engine, InstancePre, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+    use wasmtime_wasi::preview1::{self, WasiP1Ctx};
+    use wasmtime_wasi::WasiCtxBuilder;
+
+    use super::super::{ItemProcessor, ProcessorError};
+    use dioxus_feed_core::{Annotation, VirtualFeedItem};
+
+    /// Largest linear memory a plugin may grow to
+    const MAX_MEMORY_BYTES: usize = 16 * 1024 * 1024;
+
+    /// Fuel each item may burn, roughly one unit per instruction
+    const FUEL_PER_ITEM: u64 = 20_000_000;
+
+    /// Largest annotation document read back from a plugin
+    const MAX_OUTPUT_BYTES: usize = 16 * 1024;
+
+    /// The part of an item plugins get to read
+    #[derive(Serialize)]
+    struct PluginItem<'a> {
+        id: &'a str,
+        text: &'a str,
+        author: Option<&'a str>,
+        link: Option<&'a str>,
+        sources: &'a [String],
+    }
+
+    impl<'a> From<&'a VirtualFeedItem> for PluginItem<'a> {
+        fn from(item: &'a VirtualFeedItem) -> Self {
+            Self {
+                id: &item.id,
+                text: &item.content,
+                author: item.author.as_deref(),
+                link: item.link.as_deref(),
+                sources: &item.sources,
+            }
+        }
+    }
+
+    /// Per-instance state: the sandboxed WASI context and memory limits
+    struct PluginState {
+        wasi: WasiP1Ctx,
+        limits: StoreLimits,
+    }
+
+    /// Engine shared by every plugin, configured for fuel metering
+    fn engine() -> Result<&'static Engine, ProcessorError> {
+        static ENGINE: OnceLock<Result<Engine, String>> = OnceLock::new();
+        ENGINE
+            .get_or_init(|| {
+                let mut config = Config::new();
+                config.consume_fuel(true);
+                Engine::new(&config).map_err(|e| e.to_string())
+            })
+            .as_ref()
+            .map_err(|e| ProcessorError::Load(e.clone()))
+    }
+
+    struct WasmProcessor {
+        name: String,
+        pre: InstancePre<PluginState>,
+    }
+
+    pub(super) fn load(path: &Path) -> Result<Rc<dyn ItemProcessor>, ProcessorError> {
+        let engine = engine()?;
+        let module = Module::from_file(engine, path).map_err(|e| ProcessorError::Load(e.to_string()))?;
+        for export in ["memory", "alloc", "process"] {
+            if module.get_export(export).is_none() {
+                return Err(ProcessorError::Load(format!("the module doesn't export `{}`", export)));
+            }
+        }
+
+        let mut linker = Linker::new(engine);
+        preview1::add_to_linker_sync(&mut linker, |state: &mut PluginState| &mut state.wasi)
+            .map_err(|e| ProcessorError::Load(e.to_string()))?;
+        let pre = linker
+            .instantiate_pre(&module)
+            .map_err(|e| ProcessorError::Load(e.to_string()))?;
+
+        let name = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        Ok(Rc::new(WasmProcessor { name, pre }))
+    }
+
+    impl ItemProcessor for WasmProcessor {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn process(&self, item: &VirtualFeedItem) -> Result<Vec<Annotation>, ProcessorError> {
+            let run = |e: wasmtime::Error| ProcessorError::Run(e.to_string());
+            let input = serde_json::to_vec(&PluginItem::from(item)).map_err(|e| ProcessorError::Run(e.to_string()))?;
+
+            let state = PluginState {
+                wasi: WasiCtxBuilder::new().inherit_stderr().build_p1(),
+                limits: StoreLimitsBuilder::new().memory_size(MAX_MEMORY_BYTES).instances(1).build(),
+            };
+            let mut store = Store::new(self.pre.module().engine(), state);
+            store.limiter(|state| &mut state.limits);
+            store.set_fuel(FUEL_PER_ITEM).map_err(run)?;
+
+            let instance = self.pre.instantiate(&mut store).map_err(run)?;
+            let memory = instance
+                .get_memory(&mut store, "memory")
+                .ok_or_else(|| ProcessorError::Run("`memory` isn't a memory".to_string()))?;
+            let alloc = instance.get_typed_func::<i32, i32>(&mut store, "alloc").map_err(run)?;
+            let process = instance.get_typed_func::<(i32, i32), i64>(&mut store, "process").map_err(run)?;
+
+            let len = i32::try_from(input.len()).map_err(|_| ProcessorError::Run("item too large".to_string()))?;
+            let ptr = alloc.call(&mut store, len).map_err(run)?;
+            memory.write(&mut store, ptr as u32 as usize, &input).map_err(|e| ProcessorError::Run(e.to_string()))?;
+
+            let packed = process.call(&mut store, (ptr, len)).map_err(run)? as u64;
+            let (out_ptr, out_len) = ((packed >> 32) as usize, (packed & 0xffff_ffff) as usize);
+            if out_len > MAX_OUTPUT_BYTES {
+                return Err(ProcessorError::Run(format!("answer larger than {} bytes", MAX_OUTPUT_BYTES)));
+            }
+            let output = memory
+                .data(&store)
+                .get(out_ptr..out_ptr + out_len)
+                .ok_or_else(|| ProcessorError::Run("answer outside the plugin's memory".to_string()))?;
+            serde_json::from_slice(output).map_err(|e| ProcessorError::Run(format!("invalid annotations: {}", e)))
+        }
+    }
+}
+
+#[cfg(not(feature = "plugins"))]
+mod runtime {
+    use std::path::Path;
+    use std::rc::Rc;
+
+    use super::super::{ItemProcessor, ProcessorError};
+
+    pub(super) fn load(_path: &Path) -> Result<Rc<dyn ItemProcessor>, ProcessorError> {
+        Err(ProcessorError::Load("This build has no plugin support".to_string()))
+    }
+}
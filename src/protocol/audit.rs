@@ -0,0 +1,176 @@
+//! Opt-in audit log of `myprotocol` requests.
+//!
+//! Every request is recorded with how it was resolved: the file or URL it
+//! was allowed to reach and the access rule that allowed it, or why it was
+//! rejected. Entries are JSON lines in a size-rotated file, so packaged apps
+//! can show that filesystem access stays within the configured directories.
+
+use serde::Serialize;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use super::myprotocol::{AccessRule, Grant, ProtocolError};
+use crate::persistence::data_dir;
+
+/// Environment variable enabling the audit log: `1` writes to the data
+/// directory, any other value is taken as the log file's path
+pub const AUDIT_ENV_VAR: &str = "DIOXUS_FEED_AUDIT_LOG";
+
+/// Name of the log file in the data directory
+const AUDIT_FILE_NAME: &str = "protocol-audit.log";
+
+/// Size at which the log is rotated
+const DEFAULT_MAX_BYTES: u64 = 1024 * 1024;
+
+/// Rotated files kept besides the current one (`.1` is the newest)
+const DEFAULT_MAX_FILES: usize = 3;
+
+/// One audited request, written as a JSON line
+#[derive(Debug, Serialize)]
+struct AuditEntry<'a> {
+    at: String,
+    request: &'a str,
+    outcome: &'static str,
+    /// File path or remote URL the request reached
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resolved: Option<String>,
+    /// Access rule that allowed a file or metadata request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+}
+
+struct AuditFile {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+    file: Option<File>,
+}
+
+impl AuditFile {
+    fn write_line(&mut self, line: &str) -> std::io::Result<()> {
+        let size = std::fs::metadata(&self.path).map(|meta| meta.len()).unwrap_or(0);
+        if size > 0 && size + line.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        if self.file.is_none() {
+            if let Some(parent) = self.path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            self.file = Some(OpenOptions::new().create(true).append(true).open(&self.path)?);
+        }
+        let file = self.file.as_mut().expect("audit file was just opened");
+        file.write_all(line.as_bytes())?;
+        file.write_all(b"\n")
+    }
+
+    // Shift `log.N` to `log.N+1`, dropping the oldest, and start a new file
+    fn rotate(&mut self) -> std::io::Result<()> {
+        self.file = None;
+        if self.max_files == 0 {
+            return std::fs::remove_file(&self.path);
+        }
+        let _ = std::fs::remove_file(rotated_path(&self.path, self.max_files));
+        for index in (1..self.max_files).rev() {
+            let from = rotated_path(&self.path, index);
+            if from.exists() {
+                std::fs::rename(&from, rotated_path(&self.path, index + 1))?;
+            }
+        }
+        std::fs::rename(&self.path, rotated_path(&self.path, 1))
+    }
+}
+
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+/// Rotating audit log shared by the protocol handler's requests
+#[derive(Clone)]
+pub struct AuditLog {
+    file: Arc<Mutex<AuditFile>>,
+}
+
+impl AuditLog {
+    /// Log to `path`, rotating at 1 MiB and keeping three old files
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            file: Arc::new(Mutex::new(AuditFile {
+                path: path.into(),
+                max_bytes: DEFAULT_MAX_BYTES,
+                max_files: DEFAULT_MAX_FILES,
+                file: None,
+            })),
+        }
+    }
+
+    /// Rotate once the file would grow past `max_bytes`
+    pub fn max_bytes(self, max_bytes: u64) -> Self {
+        self.file.lock().expect("audit log lock poisoned").max_bytes = max_bytes;
+        self
+    }
+
+    /// Keep this many rotated files besides the current one
+    pub fn max_files(self, max_files: usize) -> Self {
+        self.file.lock().expect("audit log lock poisoned").max_files = max_files;
+        self
+    }
+
+    /// The audit log requested through [`AUDIT_ENV_VAR`], if any
+    pub fn from_env() -> Option<Self> {
+        match std::env::var(AUDIT_ENV_VAR).ok()?.as_str() {
+            "" | "0" => None,
+            "1" => Some(Self::new(data_dir().join(AUDIT_FILE_NAME))),
+            path => Some(Self::new(path)),
+        }
+    }
+
+    /// Record how the request for `request` was resolved. Failures to write
+    /// are logged and never affect the response.
+    pub fn record(&self, request: &str, resolution: Result<&Grant, &ProtocolError>) {
+        let (resolved, rule) = match resolution {
+            Ok(Grant::File { path, rule }) | Ok(Grant::Meta { path, rule }) => {
+                (Some(path.display().to_string()), Some(rule_name(rule)))
+            }
+            Ok(Grant::Remote { url }) => (Some(url.clone()), None),
+            Err(_) => (None, None),
+        };
+        let entry = AuditEntry {
+            at: chrono::Utc::now().to_rfc3339(),
+            request,
+            outcome: match resolution {
+                Ok(Grant::File { .. }) => "file",
+                Ok(Grant::Meta { .. }) => "meta",
+                Ok(Grant::Remote { .. }) => "remote",
+                Err(_) => "rejected",
+            },
+            resolved,
+            rule,
+            reason: resolution.err().map(|e| e.to_string()),
+        };
+
+        let line = match serde_json::to_string(&entry) {
+            Ok(line) => line,
+            Err(e) => {
+                eprintln!("Failed to serialize audit entry: {}", e);
+                return;
+            }
+        };
+        let mut file = self.file.lock().expect("audit log lock poisoned");
+        if let Err(e) = file.write_line(&line) {
+            eprintln!("Failed to write audit log {}: {}", file.path.display(), e);
+        }
+    }
+}
+
+fn rule_name(rule: &AccessRule) -> String {
+    match rule {
+        AccessRule::AllowAll => format!("allow-all ({})", super::myprotocol::ALLOW_ALL_FILESYSTEM),
+        AccessRule::Directory(dir) => format!("directory {}", dir),
+    }
+}
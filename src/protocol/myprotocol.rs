@@ -2,10 +2,25 @@ use dioxus::desktop::wry::http::Response;
 use dioxus::desktop::use_asset_handler;
 use tokio::io::AsyncReadExt;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use super::audit::AuditLog;
+use crate::assets::FileMeta;
+use crate::error::report;
+use crate::remote::{get_public, read_body, RemoteError};
 
 // Constants
 const MYPROTOCOL_PREFIX: &str = "/myprotocol/";
 
+/// Path segment routing requests to the remote image proxy
+const REMOTE_PROXY_SEGMENT: &str = "remote/";
+
+/// Path segment routing requests to the file metadata endpoint
+pub const META_SEGMENT: &str = "__meta/";
+
+/// Largest remote image proxied, in bytes
+const MAX_REMOTE_IMAGE_BYTES: usize = 32 * 1024 * 1024;
+
 /// Special symbol to allow filesystem-wide access
 pub const ALLOW_ALL_FILESYSTEM: &str = "*";
 
@@ -25,13 +40,18 @@ const SUPPORTED_IMAGE_EXTENSIONS: &[&str] = &[
 ];
 
 /// Custom error type for protocol handling
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ProtocolError {
     PathNotAllowed(String),
     UnsupportedExtension(String),
     FileNotFound(String),
     InvalidPath(String),
     IoError(String),
+    RemoteFetch(String),
+    /// The remote server reported the image as missing
+    RemoteNotFound(String),
+    /// The remote host resolves to this machine or the local network
+    RemoteNotAllowed(String),
 }
 
 impl std::fmt::Display for ProtocolError {
@@ -42,42 +62,181 @@ impl std::fmt::Display for ProtocolError {
             ProtocolError::FileNotFound(path) => write!(f, "File not found: {}", path),
             ProtocolError::InvalidPath(path) => write!(f, "Invalid path: {}", path),
             ProtocolError::IoError(msg) => write!(f, "IO error: {}", msg),
+            ProtocolError::RemoteFetch(msg) => write!(f, "Remote fetch failed: {}", msg),
+            ProtocolError::RemoteNotFound(url) => write!(f, "Remote image not found: {}", url),
+            ProtocolError::RemoteNotAllowed(reason) => write!(f, "Remote host not allowed: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for ProtocolError {}
+
+impl From<RemoteError> for ProtocolError {
+    fn from(error: RemoteError) -> Self {
+        match error {
+            RemoteError::InvalidUrl(url) => ProtocolError::InvalidPath(url),
+            RemoteError::NotAllowed(reason) => ProtocolError::RemoteNotAllowed(reason),
+            RemoteError::Fetch(_) | RemoteError::TooLarge(_) => ProtocolError::RemoteFetch(error.to_string()),
+        }
+    }
+}
+
+/// Reasons a directory can't be granted to the protocol
+#[derive(Debug, Clone, PartialEq)]
+pub enum GrantError {
+    /// The path doesn't exist or isn't a directory
+    NotADirectory(String),
+    /// The directory holds the whole filesystem or the user's home, which
+    /// would expose far more than images
+    TooBroad(String),
+}
+
+impl std::fmt::Display for GrantError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GrantError::NotADirectory(path) => write!(f, "Not a directory: {}", path),
+            GrantError::TooBroad(path) => write!(f, "{} is too broad, choose a folder inside it", path),
+        }
+    }
+}
+
+/// Access rule that let a file request through
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccessRule {
+    /// Filesystem-wide access was configured with [`ALLOW_ALL_FILESYSTEM`]
+    AllowAll,
+    /// The file is inside this allowed directory, as configured
+    Directory(String),
+}
+
+/// What a request was allowed to reach
+#[derive(Debug, Clone, PartialEq)]
+pub enum Grant {
+    File { path: PathBuf, rule: AccessRule },
+    Meta { path: PathBuf, rule: AccessRule },
+    Remote { url: String },
+}
+
+/// Directories the protocol may serve files from, shared with the handler
+/// so access granted while the app runs applies to the next request.
+#[derive(Clone, Default)]
+pub struct ProtocolPolicy {
+    directories: Arc<RwLock<Vec<String>>>,
+}
+
+impl PartialEq for ProtocolPolicy {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.directories, &other.directories)
+    }
+}
+
+impl ProtocolPolicy {
+    /// Policy allowing `directories`; use `vec!["*".to_string()]` to allow
+    /// access to the entire filesystem
+    pub fn new(directories: Vec<String>) -> Self {
+        Self {
+            directories: Arc::new(RwLock::new(directories)),
         }
     }
+
+    /// Allow files inside `directory` from now on, returning whether it
+    /// wasn't allowed yet
+    pub fn allow(&self, directory: &str) -> bool {
+        let mut directories = self.directories.write().unwrap_or_else(|e| e.into_inner());
+        if directories.iter().any(|allowed| allowed == directory) {
+            return false;
+        }
+        directories.push(directory.to_string());
+        true
+    }
+
+    /// Stop serving files inside `directory`
+    pub fn revoke(&self, directory: &str) {
+        self.directories
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|allowed| allowed != directory);
+    }
+
+    /// Allowed directories, in the order they were allowed
+    pub fn directories(&self) -> Vec<String> {
+        self.directories.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+/// Check that `path` can be granted to the protocol, returning it
+/// canonicalized. Filesystem roots and the home directory itself are refused.
+pub fn grantable_directory(path: &Path) -> Result<PathBuf, GrantError> {
+    let canonical = path
+        .canonicalize()
+        .ok()
+        .filter(|canonical| canonical.is_dir())
+        .ok_or_else(|| GrantError::NotADirectory(path.display().to_string()))?;
+    let is_home = dirs::home_dir()
+        .and_then(|home| home.canonicalize().ok())
+        .is_some_and(|home| home == canonical);
+    if canonical.parent().is_none() || is_home {
+        return Err(GrantError::TooBroad(canonical.display().to_string()));
+    }
+    Ok(canonical)
 }
 
 /// Register the custom asset handler for the "myprotocol" scheme.
 /// 
 /// # Parameters
-/// * `allowed_directories` - Vector of directory paths that are allowed for file access.
-///                          Use `vec!["*".to_string()]` to allow access to entire filesystem 
+/// * `policy` - Directories that are allowed for file access, which can be
+///              extended while the app runs
+/// * `audit` - Log recording every request and how it was resolved, if enabled
 /// ```
-pub fn register_myprotocol_handler(allowed_directories: Vec<String>) {
+pub fn register_myprotocol_handler(policy: ProtocolPolicy, audit: Option<AuditLog>) {
     use_asset_handler("myprotocol", move |request, responder| {
-        let allowed_dirs = allowed_directories.clone();
+        let allowed_dirs = policy.directories();
+        let audit = audit.clone();
         tokio::spawn(async move {
-            match handle_protocol_request(request.uri().path(), &allowed_dirs).await {
-                Ok(response) => responder.respond(response),
+            let path = request.uri().path();
+            let result = handle_protocol_request(path, &allowed_dirs).await;
+            if let Some(audit) = &audit {
+                audit.record(path, result.as_ref().map(|(_, grant)| grant));
+            }
+            match result {
+                Ok((response, _)) => responder.respond(response),
                 Err(e) => {
-                    eprintln!("Protocol error: {}", e);
-                    let error_response = create_error_response(&e);
-                    responder.respond(error_response);
+                    responder.respond(create_error_response(&e));
+                    report(e.into());
                 }
             }
         });
     });
 }
 
-/// Handle the protocol request and return appropriate response
-async fn handle_protocol_request(path: &str, allowed_directories: &[String]) -> Result<Response<Vec<u8>>, ProtocolError> {
+/// Handle the protocol request and return appropriate response, with what
+/// the request was allowed to reach
+async fn handle_protocol_request(
+    path: &str,
+    allowed_directories: &[String],
+) -> Result<(Response<Vec<u8>>, Grant), ProtocolError> {
     // URL decode the path to handle %20 (spaces) and other encoded characters
     let decoded_path = urlencoding::decode(path)
         .map_err(|_| ProtocolError::InvalidPath(path.to_string()))?;
     
     let file_path_str = extract_file_path(&decoded_path)?;    
-    let validated_path = validate_file_path(&file_path_str, allowed_directories)?;
     
-    load_file_response(&validated_path).await
+    if let Some(remote_url) = file_path_str.strip_prefix(REMOTE_PROXY_SEGMENT) {
+        let response = load_remote_response(remote_url).await?;
+        return Ok((response, Grant::Remote { url: remote_url.to_string() }));
+    }
+    
+    // Metadata is subject to the same access rules as the file itself
+    if let Some(meta_path) = file_path_str.strip_prefix(META_SEGMENT) {
+        let (path, rule) = validate_file_path(meta_path, allowed_directories)?;
+        let response = load_meta_response(&path).await?;
+        return Ok((response, Grant::Meta { path, rule }));
+    }
+    
+    let (path, rule) = validate_file_path(&file_path_str, allowed_directories)?;
+    
+    let response = load_file_response(&path).await?;
+    Ok((response, Grant::File { path, rule }))
 }
 
 /// Extract the actual file path from the protocol-prefixed path
@@ -90,8 +249,9 @@ fn extract_file_path(decoded_path: &str) -> Result<String, ProtocolError> {
     }
 }
 
-/// Validate file path against allowed directories and supported extensions
-fn validate_file_path(file_path: &str, allowed_directories: &[String]) -> Result<PathBuf, ProtocolError> {
+/// Validate file path against allowed directories and supported extensions,
+/// returning the path with the rule that allowed it
+fn validate_file_path(file_path: &str, allowed_directories: &[String]) -> Result<(PathBuf, AccessRule), ProtocolError> {
     let path = Path::new(file_path);
     
     // Check file extension
@@ -99,7 +259,7 @@ fn validate_file_path(file_path: &str, allowed_directories: &[String]) -> Result
     
     // Check if filesystem-wide access is allowed
     if allowed_directories.len() == 1 && allowed_directories[0] == ALLOW_ALL_FILESYSTEM {
-        return Ok(path.to_path_buf());
+        return Ok((path.to_path_buf(), AccessRule::AllowAll));
     }
     
     // Validate against allowed directories
@@ -121,7 +281,7 @@ fn validate_file_extension(path: &Path) -> Result<(), ProtocolError> {
 }
 
 /// Validate that the path is within allowed directories
-fn validate_directory_access(path: &Path, allowed_directories: &[String]) -> Result<PathBuf, ProtocolError> {
+fn validate_directory_access(path: &Path, allowed_directories: &[String]) -> Result<(PathBuf, AccessRule), ProtocolError> {
     // Convert to absolute path if possible
     let abs_path = if path.is_absolute() {
         path.to_path_buf()
@@ -147,7 +307,7 @@ fn validate_directory_access(path: &Path, allowed_directories: &[String]) -> Res
         
         if let Ok(canonical_allowed) = allowed_path.canonicalize() {
             if canonical_path.starts_with(&canonical_allowed) {
-                return Ok(canonical_path);
+                return Ok((canonical_path, AccessRule::Directory(allowed_dir.clone())));
             }
         }
     }
@@ -173,6 +333,78 @@ async fn load_file_response(file_path: &Path) -> Result<Response<Vec<u8>>, Proto
     Ok(response)
 }
 
+/// Read file metadata and create a JSON response
+async fn load_meta_response(file_path: &Path) -> Result<Response<Vec<u8>>, ProtocolError> {
+    let metadata = tokio::fs::metadata(file_path).await
+        .map_err(|_| ProtocolError::FileNotFound(file_path.display().to_string()))?;
+    
+    // Only the image header is read to find the dimensions
+    let header_path = file_path.to_path_buf();
+    let dimensions = tokio::task::spawn_blocking(move || imagesize::size(header_path).ok())
+        .await
+        .map_err(|e| ProtocolError::IoError(e.to_string()))?;
+    
+    let meta = FileMeta {
+        size: metadata.len(),
+        mime: mime_guess::from_path(file_path).first_or_octet_stream().to_string(),
+        width: dimensions.as_ref().map(|size| size.width as u32),
+        height: dimensions.as_ref().map(|size| size.height as u32),
+    };
+    let body = serde_json::to_vec(&meta)
+        .map_err(|e| ProtocolError::IoError(e.to_string()))?;
+    
+    Response::builder()
+        .header("Content-Type", "application/json")
+        .body(body)
+        .map_err(|e| ProtocolError::IoError(e.to_string()))
+}
+
+/// Build a protocol URL that proxies a remote image through `myprotocol`
+pub fn remote_proxy_url(url: &str) -> String {
+    format!("myprotocol/{}{}", REMOTE_PROXY_SEGMENT, urlencoding::encode(url))
+}
+
+/// The remote URL a [`remote_proxy_url`] proxies, `None` for other URLs
+pub fn remote_proxy_target(url: &str) -> Option<String> {
+    let encoded = url.strip_prefix("myprotocol/")?.strip_prefix(REMOTE_PROXY_SEGMENT)?;
+    urlencoding::decode(encoded).ok().map(|url| url.into_owned())
+}
+
+/// Fetch a remote image and create HTTP response.
+///
+/// Pages can put any URL in an image, so it is fetched only from a public
+/// host, as [`crate::remote`] describes.
+async fn load_remote_response(url: &str) -> Result<Response<Vec<u8>>, ProtocolError> {
+    let (target, response) = get_public(url).await?;
+    let url = target.as_str();
+    
+    // Missing images are reported as such so clients don't retry them
+    if matches!(response.status().as_u16(), 404 | 410) {
+        return Err(ProtocolError::RemoteNotFound(url.to_string()));
+    }
+    if !response.status().is_success() {
+        return Err(ProtocolError::RemoteFetch(format!("{} returned {}", url, response.status())));
+    }
+    
+    // Only images may be proxied
+    let mime = response.headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string())
+        .unwrap_or_else(|| mime_guess::from_path(url).first_or_octet_stream().to_string());
+    
+    if !mime.starts_with("image/") {
+        return Err(ProtocolError::UnsupportedExtension(mime));
+    }
+    
+    let bytes = read_body(response, MAX_REMOTE_IMAGE_BYTES).await?;
+    
+    Response::builder()
+        .header("Content-Type", mime)
+        .body(bytes)
+        .map_err(|e| ProtocolError::IoError(e.to_string()))
+}
+
 /// Create appropriate error response based on error type
 fn create_error_response(error: &ProtocolError) -> Response<Vec<u8>> {
     let (status, message) = match error {
@@ -181,6 +413,9 @@ fn create_error_response(error: &ProtocolError) -> Response<Vec<u8>> {
         ProtocolError::UnsupportedExtension(_) => (415, "Unsupported media type"),
         ProtocolError::InvalidPath(_) => (400, "Bad request"),
         ProtocolError::IoError(_) => (500, "Internal server error"),
+        ProtocolError::RemoteFetch(_) => (502, "Bad gateway"),
+        ProtocolError::RemoteNotFound(_) => (404, "Remote image not found"),
+        ProtocolError::RemoteNotAllowed(_) => (403, "Remote host not allowed"),
     };
     
     Response::builder()
@@ -193,3 +428,15 @@ fn create_error_response(error: &ProtocolError) -> Response<Vec<u8>> {
                 .unwrap()
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remote_proxy_urls_round_trip() {
+        let url = "https://example.com/a b.png?size=large&x=1";
+        assert_eq!(remote_proxy_target(&remote_proxy_url(url)).as_deref(), Some(url));
+        assert_eq!(remote_proxy_target("myprotocol/assets/a.png"), None);
+    }
+}
@@ -0,0 +1,199 @@
+pub mod readability;
+
+use dioxus::prelude::*;
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
+
+use readability::{extract_article, Article};
+use crate::remote::{get_public, read_body, RemoteError};
+use crate::work::{try_run_blocking, Cancelled};
+
+/// Most articles kept in the cache; the least recently read are dropped
+const MAX_CACHED_ARTICLES: usize = 50;
+
+/// Largest page read for an article, in bytes
+const MAX_ARTICLE_BYTES: usize = 5 * 1024 * 1024;
+
+/// Custom error type for reader mode
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReaderError {
+    InvalidUrl(String),
+    Network(String),
+    HttpStatus(u16),
+    NotHtml(String),
+    NoReadableContent,
+    /// The host resolves to this machine or the local network
+    NotAllowed(String),
+    /// The page is larger than [`MAX_ARTICLE_BYTES`]
+    TooLarge,
+    /// Extraction stopped before it finished
+    Cancelled,
+}
+
+impl std::fmt::Display for ReaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReaderError::InvalidUrl(url) => write!(f, "Invalid article URL: {}", url),
+            ReaderError::Network(msg) => write!(f, "Network error: {}", msg),
+            ReaderError::HttpStatus(status) => write!(f, "Server responded with status {}", status),
+            ReaderError::NotHtml(mime) => write!(f, "Not an HTML page: {}", mime),
+            ReaderError::NoReadableContent => write!(f, "No readable content found"),
+            ReaderError::NotAllowed(reason) => write!(f, "Article host not allowed: {}", reason),
+            ReaderError::TooLarge => write!(f, "Page is larger than {} MiB", MAX_ARTICLE_BYTES / (1024 * 1024)),
+            ReaderError::Cancelled => write!(f, "Article extraction was cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for ReaderError {}
+
+impl From<Cancelled> for ReaderError {
+    fn from(_: Cancelled) -> Self {
+        ReaderError::Cancelled
+    }
+}
+
+impl From<RemoteError> for ReaderError {
+    fn from(error: RemoteError) -> Self {
+        match error {
+            RemoteError::InvalidUrl(url) => ReaderError::InvalidUrl(url),
+            RemoteError::Fetch(msg) => ReaderError::Network(msg),
+            RemoteError::NotAllowed(reason) => ReaderError::NotAllowed(reason),
+            RemoteError::TooLarge(_) => ReaderError::TooLarge,
+        }
+    }
+}
+
+/// Fetch a page and extract its readable content.
+///
+/// Item links can point anywhere, so the page is only fetched from a public
+/// host and read up to [`MAX_ARTICLE_BYTES`] (see [`crate::remote`]).
+/// Article images keep their absolute remote URLs; the reader view rewrites
+/// them when rendering, according to the remote content privacy setting.
+pub async fn fetch_article(url: &str) -> Result<Article, ReaderError> {
+    let (_, response) = get_public(url).await?;
+
+    if !response.status().is_success() {
+        return Err(ReaderError::HttpStatus(response.status().as_u16()));
+    }
+
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    if !content_type.is_empty() && !content_type.contains("html") {
+        return Err(ReaderError::NotHtml(content_type));
+    }
+
+    let html = String::from_utf8_lossy(&read_body(response, MAX_ARTICLE_BYTES).await?).into_owned();
+
+    let url = url.to_string();
+    try_run_blocking(move || extract_article(&html, &url, str::to_string)).await
+}
+
+// Articles by URL, with the URLs in order of use, least recent first
+#[derive(Default)]
+struct CachedArticles {
+    articles: HashMap<String, Article>,
+    order: VecDeque<String>,
+}
+
+impl CachedArticles {
+    fn get(&mut self, url: &str) -> Option<Article> {
+        let article = self.articles.get(url)?.clone();
+        self.touch(url);
+        Some(article)
+    }
+
+    fn insert(&mut self, url: String, article: Article) {
+        if self.articles.insert(url.clone(), article).is_some() {
+            self.touch(&url);
+        } else {
+            self.order.push_back(url);
+        }
+        while self.order.len() > MAX_CACHED_ARTICLES {
+            if let Some(oldest) = self.order.pop_front() {
+                self.articles.remove(&oldest);
+            }
+        }
+    }
+
+    // Mark `url` as the most recently used
+    fn touch(&mut self, url: &str) {
+        if let Some(position) = self.order.iter().position(|cached| cached == url) {
+            if let Some(url) = self.order.remove(position) {
+                self.order.push_back(url);
+            }
+        }
+    }
+}
+
+/// In-memory cache of extracted articles keyed by URL, keeping the
+/// [`MAX_CACHED_ARTICLES`] most recently read
+#[derive(Clone, Default)]
+pub struct ArticleCache(Rc<RefCell<CachedArticles>>);
+
+impl ArticleCache {
+    pub fn get(&self, url: &str) -> Option<Article> {
+        self.0.borrow_mut().get(url)
+    }
+
+    /// Return the cached article, fetching and caching it on a miss
+    pub async fn get_or_fetch(&self, url: String) -> Result<Article, ReaderError> {
+        if let Some(article) = self.get(&url) {
+            return Ok(article);
+        }
+
+        let article = fetch_article(&url).await?;
+        self.0.borrow_mut().insert(url, article.clone());
+        Ok(article)
+    }
+}
+
+/// Access the app-wide article cache
+pub fn use_article_cache() -> ArticleCache {
+    use_root_context(ArticleCache::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn article(url: &str) -> Article {
+        Article {
+            url: url.to_string(),
+            title: url.to_string(),
+            blocks: Vec::new(),
+            anchors: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn cache_drops_the_least_recently_read() {
+        let mut cache = CachedArticles::default();
+        for i in 0..MAX_CACHED_ARTICLES {
+            cache.insert(i.to_string(), article(&i.to_string()));
+        }
+        // Reading the oldest makes it the newest
+        assert!(cache.get("0").is_some());
+
+        cache.insert("new".to_string(), article("new"));
+        assert_eq!(cache.articles.len(), MAX_CACHED_ARTICLES);
+        assert!(cache.get("0").is_some());
+        assert!(cache.get("1").is_none());
+        assert!(cache.get("new").is_some());
+    }
+
+    #[test]
+    fn replacing_an_article_keeps_one_entry() {
+        let mut cache = CachedArticles::default();
+        cache.insert("a".to_string(), article("a"));
+        cache.insert("b".to_string(), article("b"));
+        cache.insert("a".to_string(), article("a"));
+        assert_eq!(cache.order, ["b", "a"]);
+        assert_eq!(cache.articles.len(), 2);
+    }
+}
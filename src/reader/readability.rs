@@ -0,0 +1,343 @@
+use scraper::{ElementRef, Html, Node, Selector};
+use std::collections::HashMap;
+
+use super::ReaderError;
+use crate::links::{classify, LinkAction};
+
+/// Minimum paragraph length (characters) counted towards a container score
+const MIN_PARAGRAPH_LENGTH: usize = 25;
+
+/// Elements whose content is never part of the readable article
+const UNLIKELY_CONTAINERS: &[&str] = &["nav", "header", "footer", "aside", "form", "script", "style", "noscript"];
+
+/// Readable content extracted from an article page
+#[derive(Clone, PartialEq, Debug)]
+pub struct Article {
+    pub url: String,
+    pub title: String,
+    pub blocks: Vec<ArticleBlock>,
+    /// In-page anchors (element ids and `a[name]`s) and the index of the block
+    /// each one lands on, for links between parts of the article such as
+    /// footnotes
+    pub anchors: HashMap<String, usize>,
+}
+
+/// A single block of readable content
+#[derive(Clone, PartialEq, Debug)]
+pub enum ArticleBlock {
+    Heading(String),
+    Paragraph(String),
+    /// Paragraph containing links
+    LinkedParagraph(Vec<TextRun>),
+    Quote(String),
+    Code(String),
+    Image { src: String, alt: String },
+}
+
+/// A run of paragraph text, a link when `href` is set.
+///
+/// Links to anchors within the article are kept as `#anchor`, others are
+/// resolved against the page URL; clicks go through the link policy.
+#[derive(Clone, PartialEq, Debug)]
+pub struct TextRun {
+    pub text: String,
+    pub href: Option<String>,
+}
+
+/// Extract the readable content of an HTML page.
+///
+/// Paragraphs are scored readability-style: each paragraph adds to its parent
+/// (and half to its grandparent) based on its length and comma count, and the
+/// highest-scoring container is taken as the article body. Image sources are
+/// resolved against `page_url` and passed through `resolve_image`.
+pub fn extract_article(
+    html: &str,
+    page_url: &str,
+    resolve_image: impl Fn(&str) -> String,
+) -> Result<Article, ReaderError> {
+    let document = Html::parse_document(html);
+    let base_url = url::Url::parse(page_url)
+        .map_err(|e| ReaderError::InvalidUrl(e.to_string()))?;
+
+    let title = extract_title(&document).unwrap_or_else(|| page_url.to_string());
+    let container = find_best_container(&document).ok_or(ReaderError::NoReadableContent)?;
+
+    let mut blocks = Vec::new();
+    let mut anchors = HashMap::new();
+    collect_blocks(container, &base_url, &resolve_image, &mut blocks, &mut anchors);
+
+    if blocks.is_empty() {
+        return Err(ReaderError::NoReadableContent);
+    }
+
+    // Anchors after the last block land on it; links to anchors outside the
+    // extracted content stay plain text
+    for index in anchors.values_mut() {
+        *index = (*index).min(blocks.len() - 1);
+    }
+    for block in blocks.iter_mut() {
+        if let ArticleBlock::LinkedParagraph(runs) = block {
+            for run in runs.iter_mut() {
+                let anchor = run.href.as_deref().and_then(|href| href.strip_prefix('#'));
+                if anchor.is_some_and(|anchor| !anchors.contains_key(anchor)) {
+                    run.href = None;
+                }
+            }
+        }
+    }
+
+    Ok(Article {
+        url: page_url.to_string(),
+        title,
+        blocks,
+        anchors,
+    })
+}
+
+/// Prefer the Open Graph title, then `<title>`, then the first `<h1>`
+fn extract_title(document: &Html) -> Option<String> {
+    let og_title = selector("meta[property='og:title']");
+    if let Some(content) = document.select(&og_title).next().and_then(|el| el.attr("content")) {
+        let content = normalize_whitespace(content);
+        if !content.is_empty() {
+            return Some(content);
+        }
+    }
+
+    ["title", "h1"].iter().find_map(|name| {
+        document
+            .select(&selector(name))
+            .next()
+            .map(|el| normalize_whitespace(&el.text().collect::<String>()))
+            .filter(|text| !text.is_empty())
+    })
+}
+
+/// Score paragraph containers and return the highest-scoring one
+fn find_best_container(document: &Html) -> Option<ElementRef<'_>> {
+    let mut scores = HashMap::<_, f64>::new();
+
+    for paragraph in document.select(&selector("p")) {
+        if is_unlikely(paragraph) {
+            continue;
+        }
+
+        let text = normalize_whitespace(&paragraph.text().collect::<String>());
+        if text.len() < MIN_PARAGRAPH_LENGTH {
+            continue;
+        }
+
+        let score = 1.0 + text.matches(',').count() as f64 + (text.len() / 100).min(3) as f64;
+
+        if let Some(parent) = paragraph.parent() {
+            *scores.entry(parent.id()).or_default() += score;
+            if let Some(grandparent) = parent.parent() {
+                *scores.entry(grandparent.id()).or_default() += score / 2.0;
+            }
+        }
+    }
+
+    scores
+        .into_iter()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .and_then(|(id, _)| document.tree.get(id))
+        .and_then(ElementRef::wrap)
+}
+
+/// Whether an element sits inside page chrome rather than content
+fn is_unlikely(element: ElementRef<'_>) -> bool {
+    element
+        .ancestors()
+        .filter_map(ElementRef::wrap)
+        .any(|ancestor| UNLIKELY_CONTAINERS.contains(&ancestor.value().name()))
+}
+
+/// Walk the container in document order, emitting readable blocks and
+/// recording where each anchor lands
+fn collect_blocks(
+    element: ElementRef<'_>,
+    base_url: &url::Url,
+    resolve_image: &impl Fn(&str) -> String,
+    blocks: &mut Vec<ArticleBlock>,
+    anchors: &mut HashMap<String, usize>,
+) {
+    for child in element.child_elements() {
+        let name = child.value().name();
+        if UNLIKELY_CONTAINERS.contains(&name) {
+            continue;
+        }
+
+        // Anchors land on the next block emitted; leaf blocks take the anchors
+        // of everything inside them, containers record their own and recurse
+        let is_leaf = matches!(name, "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "p" | "blockquote" | "pre");
+        record_anchors(child, is_leaf, blocks.len(), anchors);
+
+        let text = || normalize_whitespace(&child.text().collect::<String>());
+
+        match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => push_text(blocks, ArticleBlock::Heading(text())),
+            "p" => {
+                let runs = text_runs(child, base_url);
+                if runs.iter().any(|run| run.href.is_some()) {
+                    blocks.push(ArticleBlock::LinkedParagraph(runs));
+                } else {
+                    push_text(blocks, ArticleBlock::Paragraph(text()));
+                }
+                // Paragraphs may still wrap inline images
+                collect_images(child, base_url, resolve_image, blocks);
+            }
+            "blockquote" => push_text(blocks, ArticleBlock::Quote(text())),
+            "pre" => {
+                let code = child.text().collect::<String>();
+                if !code.trim().is_empty() {
+                    blocks.push(ArticleBlock::Code(code));
+                }
+            }
+            "img" => push_image(child, base_url, resolve_image, blocks),
+            _ => collect_blocks(child, base_url, resolve_image, blocks, anchors),
+        }
+    }
+}
+
+/// Map the anchors an element defines to `index`, keeping the first block
+/// an anchor was seen at
+fn record_anchors(element: ElementRef<'_>, deep: bool, index: usize, anchors: &mut HashMap<String, usize>) {
+    let mut record = |element: ElementRef<'_>| {
+        let name = (element.value().name() == "a").then(|| element.attr("name")).flatten();
+        for anchor in [element.attr("id"), name].into_iter().flatten() {
+            if !anchor.is_empty() {
+                anchors.entry(anchor.to_string()).or_insert(index);
+            }
+        }
+    };
+    if deep {
+        element.descendants().filter_map(ElementRef::wrap).for_each(record);
+    } else {
+        record(element);
+    }
+}
+
+/// The text of a paragraph split into runs at links.
+///
+/// Whitespace is collapsed across the whole paragraph, so runs join back into
+/// the same text `normalize_whitespace` would give.
+fn text_runs(paragraph: ElementRef<'_>, base_url: &url::Url) -> Vec<TextRun> {
+    fn walk(element: ElementRef<'_>, base_url: &url::Url, runs: &mut Vec<TextRun>) {
+        for node in element.children() {
+            match node.value() {
+                Node::Text(text) => push_run(runs, text, None),
+                Node::Element(_) => {
+                    let Some(child) = ElementRef::wrap(node) else {
+                        continue;
+                    };
+                    let href = (child.value().name() == "a")
+                        .then(|| child.attr("href"))
+                        .flatten()
+                        .map(|href| resolve_href(href, base_url));
+                    match href {
+                        Some(href) => push_run(runs, &child.text().collect::<String>(), Some(href)),
+                        None => walk(child, base_url, runs),
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut runs = Vec::new();
+    walk(paragraph, base_url, &mut runs);
+    if let Some(first) = runs.first_mut() {
+        first.text = first.text.trim_start().to_string();
+    }
+    if let Some(last) = runs.last_mut() {
+        last.text = last.text.trim_end().to_string();
+    }
+    runs.retain(|run| !run.text.is_empty());
+    runs
+}
+
+// Append text to the runs, merging it into the last run when both are plain
+// and collapsing whitespace across the boundary
+fn push_run(runs: &mut Vec<TextRun>, text: &str, href: Option<String>) {
+    let mut collapsed = String::with_capacity(text.len());
+    let mut after_space = runs.last().is_none_or(|run| run.text.ends_with(' '));
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !after_space {
+                collapsed.push(' ');
+            }
+            after_space = true;
+        } else {
+            collapsed.push(c);
+            after_space = false;
+        }
+    }
+    match runs.last_mut() {
+        Some(last) if href.is_none() && last.href.is_none() => last.text.push_str(&collapsed),
+        _ => runs.push(TextRun { text: collapsed, href }),
+    }
+}
+
+/// A link's target as `#anchor` when it points into this same page, else
+/// resolved against the page URL
+fn resolve_href(href: &str, base_url: &url::Url) -> String {
+    match classify(href, Some(base_url)) {
+        LinkAction::Anchor(anchor) => format!("#{}", anchor),
+        _ => base_url.join(href.trim()).map(String::from).unwrap_or_else(|_| href.to_string()),
+    }
+}
+
+fn push_text(blocks: &mut Vec<ArticleBlock>, block: ArticleBlock) {
+    let is_empty = match &block {
+        ArticleBlock::Heading(text) | ArticleBlock::Paragraph(text) | ArticleBlock::Quote(text) => text.is_empty(),
+        ArticleBlock::LinkedParagraph(runs) => runs.is_empty(),
+        _ => false,
+    };
+    if !is_empty {
+        blocks.push(block);
+    }
+}
+
+/// Emit images nested anywhere inside an element
+fn collect_images(
+    element: ElementRef<'_>,
+    base_url: &url::Url,
+    resolve_image: &impl Fn(&str) -> String,
+    blocks: &mut Vec<ArticleBlock>,
+) {
+    for image in element.select(&selector("img")) {
+        push_image(image, base_url, resolve_image, blocks);
+    }
+}
+
+fn push_image(
+    image: ElementRef<'_>,
+    base_url: &url::Url,
+    resolve_image: &impl Fn(&str) -> String,
+    blocks: &mut Vec<ArticleBlock>,
+) {
+    let Some(src) = image.attr("src").or_else(|| image.attr("data-src")) else {
+        return;
+    };
+
+    // Skip inline data and anything that doesn't resolve to http(s)
+    let Ok(absolute) = base_url.join(src) else {
+        return;
+    };
+    if absolute.scheme() != "http" && absolute.scheme() != "https" {
+        return;
+    }
+
+    blocks.push(ArticleBlock::Image {
+        src: resolve_image(absolute.as_str()),
+        alt: image.attr("alt").unwrap_or_default().to_string(),
+    });
+}
+
+fn normalize_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn selector(source: &str) -> Selector {
+    Selector::parse(source).expect("static selector should be valid")
+}
@@ -0,0 +1,295 @@
+//! Fetching URLs taken from feed content, on hosts of the public internet.
+//!
+//! Items and pages can name any URL, so the proxied images and reader-mode
+//! articles fetched on their behalf must not reach this machine or the local
+//! network. A host resolving to an internal address is refused, the request
+//! goes to the addresses that were checked rather than resolving the host
+//! again, and redirects are followed here, each one checked the same way.
+//! Bodies are read up to a limit.
+
+use std::net::IpAddr;
+#[cfg(not(target_arch = "wasm32"))]
+use std::net::{SocketAddr, ToSocketAddrs};
+
+/// Most redirects followed for a fetch, each checked like the first URL
+const MAX_REDIRECTS: usize = 5;
+
+/// Why a public URL couldn't be fetched
+#[derive(Debug, Clone, PartialEq)]
+pub enum RemoteError {
+    /// Not an http(s) URL with a host
+    InvalidUrl(String),
+    Fetch(String),
+    /// The host resolves to this machine or the local network
+    NotAllowed(String),
+    /// The body is longer than the limit given, in bytes
+    TooLarge(usize),
+}
+
+impl std::fmt::Display for RemoteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RemoteError::InvalidUrl(url) => write!(f, "Invalid URL: {}", url),
+            RemoteError::Fetch(msg) => write!(f, "{}", msg),
+            RemoteError::NotAllowed(reason) => write!(f, "Host not allowed: {}", reason),
+            RemoteError::TooLarge(max) => write!(f, "Response is larger than {} bytes", max),
+        }
+    }
+}
+
+impl std::error::Error for RemoteError {}
+
+/// Parse a URL that may be fetched: http(s) with a host
+fn parse_public_url(url: &str) -> Result<url::Url, RemoteError> {
+    url::Url::parse(url)
+        .ok()
+        .filter(|parsed| matches!(parsed.scheme(), "http" | "https") && parsed.host().is_some())
+        .ok_or_else(|| RemoteError::InvalidUrl(url.to_string()))
+}
+
+/// GET `url` from a public host, following up to [`MAX_REDIRECTS`]
+/// redirects. Returns the URL the response came from with the response,
+/// whatever its status.
+pub async fn get_public(url: &str) -> Result<(url::Url, reqwest::Response), RemoteError> {
+    let mut target = parse_public_url(url)?;
+    let mut redirects = 0;
+    loop {
+        let client = public_client(&target).await?;
+        let response = client
+            .get(target.clone())
+            .send()
+            .await
+            .map_err(|e| RemoteError::Fetch(e.to_string()))?;
+        if !response.status().is_redirection() {
+            return Ok((target, response));
+        }
+        let location = response
+            .headers()
+            .get(reqwest::header::LOCATION)
+            .and_then(|value| value.to_str().ok())
+            .ok_or_else(|| RemoteError::Fetch(format!("{} redirected nowhere", target)))?;
+        if redirects == MAX_REDIRECTS {
+            return Err(RemoteError::Fetch(format!("{} redirected too many times", url)));
+        }
+        redirects += 1;
+        let next = target
+            .join(location)
+            .map_err(|_| RemoteError::InvalidUrl(location.to_string()))?;
+        target = parse_public_url(next.as_str())?;
+    }
+}
+
+/// Read the body of `response`, failing once it goes over `max_bytes`
+/// rather than reading the rest
+pub async fn read_body(mut response: reqwest::Response, max_bytes: usize) -> Result<Vec<u8>, RemoteError> {
+    if response.content_length().is_some_and(|length| length > max_bytes as u64) {
+        return Err(RemoteError::TooLarge(max_bytes));
+    }
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(|e| RemoteError::Fetch(e.to_string()))? {
+        append_capped(&mut body, &chunk, max_bytes)?;
+    }
+    Ok(body)
+}
+
+// Append `chunk` to `body` unless that takes it over `max_bytes`
+fn append_capped(body: &mut Vec<u8>, chunk: &[u8], max_bytes: usize) -> Result<(), RemoteError> {
+    if body.len() + chunk.len() > max_bytes {
+        return Err(RemoteError::TooLarge(max_bytes));
+    }
+    body.extend_from_slice(chunk);
+    Ok(())
+}
+
+/// Client fetching `url` from the addresses its host resolves to, once none
+/// of them is internal. Redirects are left to the caller.
+#[cfg(not(target_arch = "wasm32"))]
+async fn public_client(url: &url::Url) -> Result<reqwest::Client, RemoteError> {
+    let port = url.port_or_known_default().unwrap_or(443);
+    let builder = reqwest::Client::builder().redirect(reqwest::redirect::Policy::none());
+    let (builder, addresses) = match url.host() {
+        Some(url::Host::Ipv4(ip)) => (builder, vec![SocketAddr::new(ip.into(), port)]),
+        Some(url::Host::Ipv6(ip)) => (builder, vec![SocketAddr::new(ip.into(), port)]),
+        Some(url::Host::Domain(domain)) => {
+            let host = domain.to_string();
+            let addresses = tokio::task::spawn_blocking(move || {
+                (host.as_str(), port).to_socket_addrs().map(|addresses| addresses.collect::<Vec<_>>())
+            })
+            .await
+            .map_err(|e| RemoteError::Fetch(e.to_string()))?
+            .map_err(|e| RemoteError::Fetch(format!("{}: {}", domain, e)))?;
+            (builder.resolve_to_addrs(domain, &addresses), addresses)
+        }
+        None => return Err(RemoteError::InvalidUrl(url.to_string())),
+    };
+    if addresses.is_empty() {
+        return Err(RemoteError::Fetch(format!("{} has no addresses", url)));
+    }
+    if let Some(address) = addresses.iter().find(|address| is_internal(address.ip())) {
+        return Err(RemoteError::NotAllowed(format!(
+            "{} resolves to {}",
+            url.host_str().unwrap_or_default(),
+            address.ip()
+        )));
+    }
+    builder.build().map_err(|e| RemoteError::Fetch(e.to_string()))
+}
+
+/// The browser resolves hosts itself, so only address literals are checked
+#[cfg(target_arch = "wasm32")]
+async fn public_client(url: &url::Url) -> Result<reqwest::Client, RemoteError> {
+    let ip = match url.host() {
+        Some(url::Host::Ipv4(ip)) => Some(IpAddr::V4(ip)),
+        Some(url::Host::Ipv6(ip)) => Some(IpAddr::V6(ip)),
+        Some(url::Host::Domain(_)) => None,
+        None => return Err(RemoteError::InvalidUrl(url.to_string())),
+    };
+    if let Some(ip) = ip.filter(|ip| is_internal(*ip)) {
+        return Err(RemoteError::NotAllowed(ip.to_string()));
+    }
+    Ok(reqwest::Client::new())
+}
+
+/// Whether `ip` belongs to this machine or a local network rather than the
+/// public internet: loopback, private, link-local, carrier-grade NAT,
+/// multicast, unspecified and broadcast addresses, and the IPv6 prefixes
+/// that embed an IPv4 address (IPv4-mapped, NAT64 and 6to4)
+fn is_internal(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [first, second, ..] = ip.octets();
+            ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                || first == 0
+                // 100.64.0.0/10, shared address space
+                || (first == 100 && (second & 0xc0) == 64)
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(mapped) => is_internal(IpAddr::V4(mapped)),
+            None => {
+                let segments = ip.segments();
+                let first = segments[0];
+                ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_multicast()
+                    // fc00::/7, unique local
+                    || (first & 0xfe00) == 0xfc00
+                    // fe80::/10, link-local
+                    || (first & 0xffc0) == 0xfe80
+                    // fec0::/10, deprecated site-local
+                    || (first & 0xffc0) == 0xfec0
+                    // 64:ff9b::/96, NAT64 to any IPv4 address
+                    || segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0]
+                    // 2002::/16, 6to4 to any IPv4 address
+                    || first == 0x2002
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(address: &str) -> IpAddr {
+        address.parse().unwrap()
+    }
+
+    #[test]
+    fn internal_addresses_are_refused() {
+        for address in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.10",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "255.255.255.255",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "::ffff:192.168.0.1",
+        ] {
+            assert!(is_internal(ip(address)), "{} should be internal", address);
+        }
+    }
+
+    #[test]
+    fn multicast_addresses_are_refused() {
+        for address in ["224.0.0.1", "239.255.255.250", "ff02::1", "ff05::1:3"] {
+            assert!(is_internal(ip(address)), "{} should be internal", address);
+        }
+    }
+
+    #[test]
+    fn site_local_addresses_are_refused() {
+        for address in ["fec0::1", "feff:ffff::1"] {
+            assert!(is_internal(ip(address)), "{} should be internal", address);
+        }
+    }
+
+    #[test]
+    fn nat64_addresses_are_refused() {
+        for address in ["64:ff9b::7f00:1", "64:ff9b::808:808"] {
+            assert!(is_internal(ip(address)), "{} should be internal", address);
+        }
+    }
+
+    #[test]
+    fn six_to_four_addresses_are_refused() {
+        for address in ["2002:7f00:1::1", "2002:c0a8:1::1"] {
+            assert!(is_internal(ip(address)), "{} should be internal", address);
+        }
+    }
+
+    #[test]
+    fn public_addresses_are_allowed() {
+        for address in [
+            "93.184.216.34",
+            "1.1.1.1",
+            "100.128.0.1",
+            "172.32.0.1",
+            "223.255.255.255",
+            "2606:4700::1111",
+            "::ffff:8.8.8.8",
+            "64:ff9c::1",
+            "2003::1",
+        ] {
+            assert!(!is_internal(ip(address)), "{} should be public", address);
+        }
+    }
+
+    #[test]
+    fn public_urls_need_http_and_a_host() {
+        assert!(parse_public_url("https://example.com/image.png").is_ok());
+        assert!(parse_public_url("http://[2606:4700::1111]/image.png").is_ok());
+        assert!(parse_public_url("file:///etc/passwd").is_err());
+        assert!(parse_public_url("ftp://example.com/image.png").is_err());
+        assert!(parse_public_url("not a url").is_err());
+    }
+
+    #[test]
+    fn internal_hosts_are_refused_before_fetching() {
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        for url in ["http://127.0.0.1:8080/image.png", "http://[::1]/image.png", "http://localhost/image.png"] {
+            let result = runtime.block_on(get_public(url));
+            assert!(matches!(result, Err(RemoteError::NotAllowed(_))), "{} wasn't refused", url);
+        }
+    }
+
+    #[test]
+    fn bodies_stop_at_the_limit() {
+        let mut body = Vec::new();
+        assert_eq!(append_capped(&mut body, b"12345", 8), Ok(()));
+        assert_eq!(append_capped(&mut body, b"678", 8), Ok(()));
+        assert_eq!(append_capped(&mut body, b"9", 8), Err(RemoteError::TooLarge(8)));
+        assert_eq!(body, b"12345678");
+    }
+}
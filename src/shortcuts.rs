@@ -0,0 +1,133 @@
+//! Keyboard shortcuts for the whole app.
+//!
+//! Keys pressed anywhere outside text fields are reported by the webview and
+//! looked up in the [`ShortcutMap`], which holds the user's bindings. The
+//! component owning the feeds carries out the matching [`ShortcutAction`].
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::persistence::shortcuts::ShortcutMap;
+
+/// Keys the feed list handles itself, which can't be bound
+pub const RESERVED_KEYS: &[&str] = &["Ctrl+f", "Ctrl+z", "Ctrl+y", "Meta+f", "Meta+z", "Meta+y", "Escape", "Tab", "Enter"];
+
+// Reports every key pressed outside text fields, named like `key_name`
+const KEY_SCRIPT: &str = r#"
+    document.addEventListener('keydown', (event) => {
+        const target = event.target;
+        if (target && (target.isContentEditable || ['INPUT', 'TEXTAREA', 'SELECT'].includes(target.tagName))) {
+            return;
+        }
+        if (['Shift', 'Control', 'Alt', 'Meta'].includes(event.key)) {
+            return;
+        }
+        const parts = [];
+        if (event.ctrlKey) parts.push('Ctrl');
+        if (event.altKey) parts.push('Alt');
+        if (event.metaKey) parts.push('Meta');
+        parts.push(event.key);
+        dioxus.send(parts.join('+'));
+    });
+    await new Promise(() => {});
+"#;
+
+/// Something a shortcut does
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShortcutAction {
+    NextItem,
+    PreviousItem,
+    OpenItem,
+    Refresh,
+    Search,
+    Help,
+    CommandPalette,
+}
+
+impl ShortcutAction {
+    pub const ALL: [ShortcutAction; 7] = [
+        ShortcutAction::NextItem,
+        ShortcutAction::PreviousItem,
+        ShortcutAction::OpenItem,
+        ShortcutAction::Refresh,
+        ShortcutAction::Search,
+        ShortcutAction::Help,
+        ShortcutAction::CommandPalette,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ShortcutAction::NextItem => "Next item",
+            ShortcutAction::PreviousItem => "Previous item",
+            ShortcutAction::OpenItem => "Open item in reader mode",
+            ShortcutAction::Refresh => "Refresh feed",
+            ShortcutAction::Search => "Search",
+            ShortcutAction::Help => "Show shortcuts",
+            ShortcutAction::CommandPalette => "Command palette",
+        }
+    }
+
+    /// Key bound until the user picks another
+    pub fn default_key(&self) -> &'static str {
+        match self {
+            ShortcutAction::NextItem => "j",
+            ShortcutAction::PreviousItem => "k",
+            ShortcutAction::OpenItem => "o",
+            ShortcutAction::Refresh => "r",
+            ShortcutAction::Search => "/",
+            ShortcutAction::Help => "?",
+            ShortcutAction::CommandPalette => "Ctrl+k",
+        }
+    }
+}
+
+/// Name of a key press as shortcuts are bound to it, e.g. `j`, `?` or
+/// `Ctrl+k`; `None` for a modifier pressed on its own. Shift isn't named,
+/// since it already changes the key (`J`, `?`).
+pub fn key_name(key: &Key, modifiers: Modifiers) -> Option<String> {
+    let name = match key {
+        Key::Character(character) => character.clone(),
+        Key::Shift | Key::Control | Key::Alt | Key::Meta => return None,
+        named => named.to_string(),
+    };
+    let mut parts = Vec::new();
+    if modifiers.ctrl() {
+        parts.push("Ctrl");
+    }
+    if modifiers.alt() {
+        parts.push("Alt");
+    }
+    if modifiers.meta() {
+        parts.push("Meta");
+    }
+    parts.push(&name);
+    Some(parts.join("+"))
+}
+
+/// Carry out shortcuts pressed anywhere in the app with `handler`. Bindings
+/// come from the [`ShortcutMap`] in context, or the defaults without one.
+/// Only one component should register a handler.
+pub fn use_shortcuts(handler: impl FnMut(ShortcutAction) + 'static) {
+    let shortcuts = try_use_context::<ShortcutMap>();
+    let mut handler = use_hook(|| CopyValue::new(handler));
+    use_future(move || async move {
+        let mut eval = document::eval(KEY_SCRIPT);
+        loop {
+            let key = match eval.recv::<String>().await {
+                Ok(key) => key,
+                Err(e) => {
+                    eprintln!("Stopped listening for shortcuts: {}", e);
+                    return;
+                }
+            };
+            let action = match shortcuts {
+                Some(shortcuts) => shortcuts.action_for(&key),
+                None => ShortcutAction::ALL.into_iter().find(|action| action.default_key() == key),
+            };
+            if let Some(action) = action {
+                (handler.write())(action);
+            }
+        }
+    });
+}
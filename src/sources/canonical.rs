@@ -0,0 +1,177 @@
+//! Canonical identities for items that reach the app through more than one
+//! source, and the strategy for merging their copies.
+//!
+//! Two copies of an item share a canonical id when their links (or, without
+//! a link, their ids) normalize to the same URL. Merging is order-independent
+//! apart from which copy's text is kept, so copies can arrive in any order.
+//! Items from different sources whose text is nearly the same are collapsed
+//! too, but kept whole under the first one as "also shared" copies.
+
+use dioxus_feed_core::{SharedCopy, VirtualFeedItem};
+use super::simhash::{fingerprint, is_near_duplicate};
+use crate::urlcleaner::{is_tracking_param, unwrap_redirect};
+
+/// Normalize an http(s) URL so links to the same page compare equal.
+///
+/// Links through known redirectors are unwrapped, the scheme becomes https,
+/// `www.` and the fragment are dropped, tracking parameters are removed, the
+/// rest are sorted and a trailing slash is trimmed. Returns `None` for
+/// anything that isn't an http(s) URL.
+pub fn canonical_url(url: &str) -> Option<String> {
+    let mut parsed = url::Url::parse(url.trim()).ok()?;
+    while let Some(target) = unwrap_redirect(&parsed) {
+        parsed = target;
+    }
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return None;
+    }
+    let host = parsed.host_str()?.trim_start_matches("www.").to_string();
+
+    let mut query: Vec<(String, String)> = parsed
+        .query_pairs()
+        .filter(|(key, _)| !is_tracking_param(key))
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    query.sort();
+
+    let mut canonical = format!("https://{}", host);
+    if let Some(port) = parsed.port() {
+        canonical.push_str(&format!(":{}", port));
+    }
+    canonical.push_str(parsed.path().trim_end_matches('/'));
+    if !query.is_empty() {
+        let query = url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(query)
+            .finish();
+        canonical.push('?');
+        canonical.push_str(&query);
+    }
+    Some(canonical)
+}
+
+/// Canonical id of an item: its normalized link, else its normalized id when
+/// the id is a URL (as RSS guids often are), else the id unchanged
+pub fn canonical_id(item: &VirtualFeedItem) -> String {
+    item.link
+        .as_deref()
+        .and_then(canonical_url)
+        .or_else(|| canonical_url(&item.id))
+        .unwrap_or_else(|| item.id.clone())
+}
+
+/// Give `item` its canonical id, recording `source` as where it came from
+pub fn canonicalize(mut item: VirtualFeedItem, source: &str) -> VirtualFeedItem {
+    item.id = canonical_id(&item);
+    if !item.sources.iter().any(|known| known == source) {
+        item.sources.push(source.to_string());
+    }
+    item
+}
+
+/// Merge another copy of the same item into `item`.
+///
+/// The first copy's text and image are kept; anything it lacks is filled in
+/// from `other`, other's images become fallbacks and the sources are united.
+pub fn merge_item(item: &mut VirtualFeedItem, other: VirtualFeedItem) {
+    // Taken before the fields below are moved out of `other`
+    let candidates: Vec<String> = other.image_candidates().map(str::to_string).collect();
+    if item.content.is_empty() {
+        item.content = other.content;
+    }
+    if item.link.is_none() {
+        item.link = other.link;
+    }
+    if item.author.is_none() {
+        item.author = other.author;
+    }
+    if item.published_at.is_none() {
+        item.published_at = other.published_at;
+    }
+    // The larger count wins, so merging the same copy again changes nothing
+    item.engagement = item.engagement.max(other.engagement);
+    if item.image_size().is_none() && other.image_url == item.image_url {
+        item.image_width = other.image_width;
+        item.image_height = other.image_height;
+    }
+    if item.image_aspect_ratio.is_none() && other.image_url == item.image_url {
+        item.image_aspect_ratio = other.image_aspect_ratio;
+    }
+    if item.image_alt.is_none() && other.image_url == item.image_url {
+        item.image_alt = other.image_alt;
+    }
+
+    for candidate in candidates {
+        if !item.image_candidates().any(|known| known == candidate) {
+            item.image_fallbacks.push(candidate);
+        }
+    }
+    for source in other.sources {
+        if !item.sources.contains(&source) {
+            item.sources.push(source);
+        }
+    }
+    // A flag on any copy applies to all of them
+    for flag in other.flags {
+        if !item.flags.contains(&flag) {
+            item.flags.push(flag);
+        }
+    }
+    for copy in other.also_shared {
+        if !item.also_shared.iter().any(|known| known.id == copy.id) {
+            item.also_shared.push(copy);
+        }
+    }
+}
+
+/// Collapse `other`, a near-duplicate from another source, into `item`
+pub fn fold_near_duplicate(item: &mut VirtualFeedItem, other: VirtualFeedItem) {
+    let copies = std::iter::once(SharedCopy {
+        id: other.id,
+        content: other.content,
+        link: other.link,
+        sources: other.sources,
+    })
+    .chain(other.also_shared);
+    for copy in copies {
+        if copy.id != item.id && !item.also_shared.iter().any(|known| known.id == copy.id) {
+            item.also_shared.push(copy);
+        }
+    }
+}
+
+/// Index of the item in `items` that `candidate` is a near-duplicate of.
+///
+/// Only items from sources the candidate didn't come from qualify, so a feed
+/// repeating itself isn't collapsed; items without sources never match.
+pub fn find_near_duplicate<'a>(
+    items: impl IntoIterator<Item = &'a VirtualFeedItem>,
+    candidate: &VirtualFeedItem,
+) -> Option<usize> {
+    if candidate.sources.is_empty() {
+        return None;
+    }
+    let target = fingerprint(&candidate.content)?;
+    items.into_iter().position(|item| {
+        !item.sources.is_empty()
+            && item.sources.iter().all(|source| !candidate.sources.contains(source))
+            && fingerprint(&item.content).is_some_and(|hash| is_near_duplicate(hash, target))
+    })
+}
+
+/// Collapse copies and near-duplicates of the same item in `items`, keeping
+/// the position of the first one. Items must already have canonical ids.
+pub fn merge_duplicates(items: Vec<VirtualFeedItem>) -> Vec<VirtualFeedItem> {
+    let mut merged: Vec<VirtualFeedItem> = Vec::with_capacity(items.len());
+    let mut positions = std::collections::HashMap::new();
+    for item in items {
+        if let Some(&index) = positions.get(&item.id) {
+            merge_item(&mut merged[index], item);
+        } else if let Some(index) = find_near_duplicate(&merged, &item) {
+            fold_near_duplicate(&mut merged[index], item);
+        } else {
+            positions.insert(item.id.clone(), merged.len());
+            merged.push(item);
+        }
+    }
+    merged
+}
@@ -0,0 +1,149 @@
+use dioxus_feed_core::VirtualFeedItem;
+use std::time::Duration;
+
+use super::{FeedSource, PageRequest, SourceFuture};
+use crate::assets::BundledImages;
+use crate::clock::ClockHandle;
+
+// Demo source configuration
+const OLDER_LOAD_DELAY_MS: u64 = 800; // Simulated latency loading older items
+const NEWER_LOAD_DELAY_MS: u64 = 500; // Simulated latency loading newer items
+const REFRESH_DELAY_MS: u64 = 600; // Simulated latency fetching the newest page
+const REFRESH_PAGE_SIZE: usize = 3; // Items in the newest page
+const POLLING_INTERVAL_MS: u64 = 5000; // 5 seconds for new items
+const MAX_DEMO_ENGAGEMENT: u32 = 500; // Upper bound of generated engagement counts
+
+/// Synthetic source generating placeholder items
+#[derive(Clone)]
+pub struct DemoSource {
+    /// Only generate items with photo media
+    pub photos_only: bool,
+    // Drives the simulated latency and the timestamps in generated ids
+    clock: ClockHandle,
+}
+
+impl DemoSource {
+    pub fn new() -> Self {
+        Self {
+            photos_only: false,
+            clock: ClockHandle::default(),
+        }
+    }
+
+    pub fn photos_only() -> Self {
+        Self {
+            photos_only: true,
+            clock: ClockHandle::default(),
+        }
+    }
+
+    pub fn with_clock(mut self, clock: ClockHandle) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Items shown before the first load
+    pub fn initial_items(&self) -> Vec<VirtualFeedItem> {
+        if self.photos_only {
+            return vec![
+                self.item("photo_initial_1".to_string(), "Photos from across your feed".to_string()),
+                self.item("photo_initial_2".to_string(), "Only items with photo media appear here".to_string()),
+            ];
+        }
+
+        vec![
+            self.item("initial_1".to_string(), "Welcome to the feed! This is item 1".to_string()),
+            self.item("initial_2".to_string(), "Here's another item in your feed".to_string()),
+            self.item("initial_3".to_string(), "Scroll up or down to load more content".to_string()),
+            self.item("initial_4".to_string(), "Images load asynchronously via custom protocol".to_string())
+                .with_link("https://dioxuslabs.com/blog/release-060/"),
+            self.item("initial_5".to_string(), "Infinite scrolling in both directions".to_string()),
+        ]
+    }
+
+    fn item(&self, id: String, content: String) -> VirtualFeedItem {
+        // Made-up engagement, stable per id, so the Top ordering has
+        // something to rank by. Items are left undated and so are aged by
+        // their position.
+        let engagement = id.bytes().fold(0u32, |hash, byte| hash.wrapping_mul(31).wrapping_add(byte as u32)) % MAX_DEMO_ENGAGEMENT;
+        let item = if self.photos_only {
+            VirtualFeedItem::new_with_random_photo(id, content)
+        } else {
+            VirtualFeedItem::new_with_random_image(id, content)
+        };
+        item.with_engagement(engagement)
+    }
+}
+
+impl Default for DemoSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FeedSource for DemoSource {
+    fn load_older(&self, request: PageRequest) -> SourceFuture {
+        let source = self.clone();
+        Box::pin(async move {
+            // Simulate loading delay
+            source.clock.sleep(Duration::from_millis(OLDER_LOAD_DELAY_MS)).await;
+
+            let items = (1..=request.limit)
+                .map(|i| {
+                    let item_id = format!("older_{}_{}", request.loaded + i, source.clock.utc_now().timestamp_millis());
+                    let content = format!("Older content item {} - loaded from top", request.loaded + i);
+                    source.item(item_id, content)
+                })
+                .collect();
+            Ok(items)
+        })
+    }
+
+    fn load_newer(&self, request: PageRequest) -> SourceFuture {
+        let source = self.clone();
+        Box::pin(async move {
+            // Simulate loading delay
+            source.clock.sleep(Duration::from_millis(NEWER_LOAD_DELAY_MS)).await;
+
+            let items = (1..=request.limit)
+                .map(|i| {
+                    let item_id = format!("newer_{}_{}", request.loaded + i, source.clock.utc_now().timestamp_millis());
+                    let content = format!("Newer content item {} - loaded from bottom", request.loaded + i);
+                    source.item(item_id, content)
+                })
+                .collect();
+            Ok(items)
+        })
+    }
+
+    fn poll(&self, request: PageRequest) -> SourceFuture {
+        let source = self.clone();
+        Box::pin(async move {
+            let next_num = request.loaded + 1;
+            let item_id = format!("auto_{}_{}", next_num, source.clock.utc_now().timestamp_millis());
+            let content = format!("Auto-generated item {} - real-time update", next_num);
+            Ok(vec![source.item(item_id, content)])
+        })
+    }
+
+    fn refresh(&self, request: PageRequest) -> SourceFuture {
+        let source = self.clone();
+        Box::pin(async move {
+            // Simulate loading delay
+            source.clock.sleep(Duration::from_millis(REFRESH_DELAY_MS)).await;
+
+            let items = (1..=REFRESH_PAGE_SIZE.min(request.limit))
+                .map(|i| {
+                    let item_id = format!("fresh_{}_{}", request.loaded + i, source.clock.utc_now().timestamp_millis());
+                    let content = format!("Fresh item {} - pulled in by refresh", request.loaded + i);
+                    source.item(item_id, content)
+                })
+                .collect();
+            Ok(items)
+        })
+    }
+
+    fn poll_interval(&self) -> Option<Duration> {
+        Some(Duration::from_millis(POLLING_INTERVAL_MS))
+    }
+}
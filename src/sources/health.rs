@@ -0,0 +1,91 @@
+use chrono::{DateTime, Utc};
+use dioxus::prelude::*;
+use dioxus_feed_core::VirtualFeedItem;
+
+use super::SourceError;
+
+/// How long a source that didn't say when to retry is considered rate limited
+const DEFAULT_RATE_LIMIT_SECS: i64 = 60;
+
+/// Sync history of one source
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SourceStats {
+    pub name: String,
+    /// When the source last answered successfully
+    pub last_sync: Option<DateTime<Utc>>,
+    /// Error from the latest request, cleared by the next success
+    pub last_error: Option<String>,
+    pub items_fetched: usize,
+    /// Set while the source has asked not to be called again yet
+    pub rate_limited_until: Option<DateTime<Utc>>,
+}
+
+impl SourceStats {
+    /// Whether the source is still rate limited at `now`
+    pub fn is_rate_limited(&self, now: DateTime<Utc>) -> bool {
+        self.rate_limited_until.is_some_and(|until| until > now)
+    }
+}
+
+/// Registry of every attached source's sync history, written by the stores
+/// and aggregate sources as they sync.
+///
+/// Provided once at the app root with `use_context_provider(SourceHealth::new)`;
+/// entries appear the first time a source is synced.
+#[derive(Clone, Copy, PartialEq)]
+pub struct SourceHealth {
+    sources: Signal<Vec<SourceStats>>,
+}
+
+impl SourceHealth {
+    pub fn new() -> Self {
+        Self {
+            sources: Signal::new(Vec::new()),
+        }
+    }
+
+    /// Stats of every source synced so far, in the order they first synced
+    pub fn sources(&self) -> Vec<SourceStats> {
+        self.sources.read().clone()
+    }
+
+    /// Stats of the source called `name`, if it has synced
+    pub fn stats(&self, name: &str) -> Option<SourceStats> {
+        self.sources.peek().iter().find(|stats| stats.name == name).cloned()
+    }
+
+    /// Record the outcome of a request made to the source called `name`
+    pub fn record(&mut self, name: &str, result: &Result<Vec<VirtualFeedItem>, SourceError>, at: DateTime<Utc>) {
+        let mut sources = self.sources.write();
+        let index = match sources.iter().position(|stats| stats.name == name) {
+            Some(index) => index,
+            None => {
+                sources.push(SourceStats { name: name.to_string(), ..Default::default() });
+                sources.len() - 1
+            }
+        };
+        let stats = &mut sources[index];
+
+        match result {
+            Ok(items) => {
+                stats.last_sync = Some(at);
+                stats.last_error = None;
+                stats.items_fetched += items.len();
+                stats.rate_limited_until = None;
+            }
+            Err(e) => {
+                stats.last_error = Some(e.to_string());
+                if let SourceError::RateLimited(retry_after) = e {
+                    let wait = retry_after.and_then(|wait| chrono::Duration::from_std(wait).ok());
+                    stats.rate_limited_until = Some(at + wait.unwrap_or(chrono::Duration::seconds(DEFAULT_RATE_LIMIT_SECS)));
+                }
+            }
+        }
+    }
+}
+
+impl Default for SourceHealth {
+    fn default() -> Self {
+        Self::new()
+    }
+}
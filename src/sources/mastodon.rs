@@ -0,0 +1,200 @@
+use dioxus_feed_core::{ContentFlag, VirtualFeedItem};
+use serde::Deserialize;
+use std::time::Duration;
+
+use super::rss::{strip_markup, truncate};
+use super::{FeedSource, PageRequest, SourceError, SourceFuture};
+use crate::assets::{BundledImages, MediaSrc};
+use crate::auth::AuthManager;
+
+// Mastodon source configuration
+const POLLING_INTERVAL_SECS: u64 = 120;
+const MAX_CONTENT_CHARS: usize = 280; // Summary length shown in rows
+const MAX_PAGE_SIZE: usize = 40; // The API's upper limit
+
+#[derive(Deserialize)]
+struct Status {
+    id: String,
+    url: Option<String>,
+    content: String,
+    created_at: String,
+    account: StatusAccount,
+    #[serde(default)]
+    replies_count: u32,
+    #[serde(default)]
+    reblogs_count: u32,
+    #[serde(default)]
+    favourites_count: u32,
+    #[serde(default)]
+    media_attachments: Vec<Attachment>,
+    // Marked sensitive by its author
+    #[serde(default)]
+    sensitive: bool,
+    reblog: Option<Box<Status>>,
+}
+
+#[derive(Deserialize)]
+struct StatusAccount {
+    acct: String,
+    display_name: String,
+}
+
+#[derive(Deserialize)]
+struct Attachment {
+    #[serde(rename = "type")]
+    kind: String,
+    url: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    meta: Option<AttachmentMeta>,
+}
+
+// Size of the attachment as uploaded, when the server has processed it
+#[derive(Deserialize)]
+struct AttachmentMeta {
+    #[serde(default)]
+    original: Option<MediaSize>,
+}
+
+#[derive(Deserialize)]
+struct MediaSize {
+    #[serde(default)]
+    width: Option<u32>,
+    #[serde(default)]
+    height: Option<u32>,
+    #[serde(default)]
+    aspect: Option<f64>,
+}
+
+/// Source reading the home timeline of a connected Mastodon account.
+///
+/// Requests carry the account's access token from the [`AuthManager`], which
+/// refreshes it when needed. Item ids are `<server>/<status id>`, so cursors
+/// map back onto the API's `max_id`/`min_id`.
+#[derive(Clone)]
+pub struct MastodonSource {
+    auth: AuthManager,
+    account_key: String,
+    base_url: String,
+}
+
+impl MastodonSource {
+    pub fn new(auth: AuthManager, account_key: impl Into<String>, base_url: impl Into<String>) -> Self {
+        Self {
+            auth,
+            account_key: account_key.into(),
+            base_url: base_url.into(),
+        }
+    }
+
+    // Page of the home timeline; `cursor` is `max_id` for older pages,
+    // `min_id` for the page right after the newest item and `since_id` for
+    // the newest items after it
+    fn timeline(&self, request: PageRequest, cursor_param: &'static str) -> SourceFuture {
+        let source = self.clone();
+        Box::pin(async move {
+            let token = source
+                .auth
+                .access_token(&source.account_key)
+                .await
+                .map_err(|e| SourceError::Network(e.to_string()))?;
+            let mut url = url::Url::parse(&format!("{}/api/v1/timelines/home", source.base_url))
+                .map_err(|e| SourceError::InvalidResponse(e.to_string()))?;
+            url.query_pairs_mut()
+                .append_pair("limit", &request.limit.min(MAX_PAGE_SIZE).to_string());
+            if let Some(id) = request.cursor.as_deref().and_then(|cursor| cursor.rsplit('/').next()) {
+                url.query_pairs_mut().append_pair(cursor_param, id);
+            }
+
+            let response = reqwest::Client::new()
+                .get(url)
+                .bearer_auth(token)
+                .send()
+                .await
+                .map_err(|e| SourceError::Network(e.to_string()))?;
+            if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                return Err(SourceError::RateLimited(None));
+            }
+            if !response.status().is_success() {
+                return Err(SourceError::Network(format!("{} returned {}", source.base_url, response.status())));
+            }
+            let statuses: Vec<Status> = response
+                .json()
+                .await
+                .map_err(|e| SourceError::InvalidResponse(e.to_string()))?;
+
+            // The API lists newest first
+            let host = source.base_url.trim_start_matches("https://");
+            Ok(statuses.into_iter().rev().map(|status| status_item(host, status)).collect())
+        })
+    }
+}
+
+impl FeedSource for MastodonSource {
+    fn load_older(&self, request: PageRequest) -> SourceFuture {
+        self.timeline(request, "max_id")
+    }
+
+    fn load_newer(&self, request: PageRequest) -> SourceFuture {
+        self.timeline(request, "min_id")
+    }
+
+    // Polls only transfer statuses newer than the newest one shown
+    fn poll(&self, request: PageRequest) -> SourceFuture {
+        self.timeline(request, "since_id")
+    }
+
+    fn poll_interval(&self) -> Option<Duration> {
+        Some(Duration::from_secs(POLLING_INTERVAL_SECS))
+    }
+}
+
+// Build an item from a status, showing the boosted status for boosts
+fn status_item(host: &str, status: Status) -> VirtualFeedItem {
+    let id = format!("{}/{}", host, status.id);
+    let status = match status.reblog {
+        Some(boosted) => *boosted,
+        None => status,
+    };
+
+    let content = truncate(&strip_markup(&status.content), MAX_CONTENT_CHARS);
+    let engagement = status
+        .replies_count
+        .saturating_add(status.reblogs_count)
+        .saturating_add(status.favourites_count);
+    let mut item = VirtualFeedItem::new_with_random_image(id, content).with_engagement(engagement);
+    let author = match status.account.display_name.trim() {
+        "" => status.account.acct.clone(),
+        name => name.to_string(),
+    };
+    item = item.with_author(author);
+    if let Some(link) = status.url {
+        item = item.with_link(link);
+    }
+    if let Ok(date) = chrono::DateTime::parse_from_rfc3339(&status.created_at) {
+        item = item.with_published_at(date.timestamp_millis());
+    }
+    if status.sensitive {
+        item = item.with_flag(ContentFlag::Sensitive);
+    }
+    // Attached images show first, with the bundled image as a fallback
+    let attachment = status
+        .media_attachments
+        .into_iter()
+        .find(|attachment| attachment.kind == "image");
+    if let Some(attachment) = attachment {
+        if let Ok(image) = MediaSrc::remote(&attachment.url) {
+            let bundled = std::mem::replace(&mut item.image_url, image.to_string());
+            item.image_fallbacks.insert(0, bundled);
+            item.image_alt = attachment.description.filter(|alt| !alt.trim().is_empty());
+            // Declared up front so the row doesn't wait on the metadata endpoint
+            if let Some(size) = attachment.meta.and_then(|meta| meta.original) {
+                item.image_width = size.width;
+                item.image_height = size.height;
+                item.image_aspect_ratio = size.aspect;
+            }
+        }
+    }
+    item
+}
@@ -0,0 +1,96 @@
+pub mod canonical;
+pub mod demo;
+pub mod health;
+pub mod mastodon;
+pub mod opml;
+pub mod rss;
+pub mod simhash;
+pub mod stories;
+#[cfg(feature = "fullstack")]
+pub mod server;
+
+use dioxus_feed_core::VirtualFeedItem;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use crate::store::Eviction;
+use crate::work::Cancelled;
+
+/// Boxed future returned by feed sources
+pub type SourceFuture = Pin<Box<dyn Future<Output = Result<Vec<VirtualFeedItem>, SourceError>>>>;
+
+/// Custom error type for feed sources
+#[derive(Debug, Clone, PartialEq)]
+pub enum SourceError {
+    Network(String),
+    InvalidResponse(String),
+    /// The source asked to be called less often, optionally saying when to retry
+    RateLimited(Option<Duration>),
+}
+
+impl std::fmt::Display for SourceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SourceError::Network(msg) => write!(f, "Network error: {}", msg),
+            SourceError::InvalidResponse(msg) => write!(f, "Invalid response: {}", msg),
+            SourceError::RateLimited(Some(wait)) => write!(f, "Rate limited, retry in {}s", wait.as_secs()),
+            SourceError::RateLimited(None) => write!(f, "Rate limited"),
+        }
+    }
+}
+
+impl std::error::Error for SourceError {}
+
+impl From<Cancelled> for SourceError {
+    fn from(error: Cancelled) -> Self {
+        SourceError::InvalidResponse(format!("not parsed, {}", error))
+    }
+}
+
+/// Parameters for a page request
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageRequest {
+    /// Id of the edge item the page continues from, `None` for an empty feed
+    pub cursor: Option<String>,
+    /// Number of items currently held by the store
+    pub loaded: usize,
+    /// Maximum number of items to return
+    pub limit: usize,
+}
+
+/// Provider of feed items.
+///
+/// Pages are returned in display order (oldest first); `load_older` pages are
+/// prepended to the feed and `load_newer`/`poll` pages are appended.
+pub trait FeedSource {
+    /// Load items older than the first item in the feed
+    fn load_older(&self, request: PageRequest) -> SourceFuture;
+
+    /// Load items newer than the last item in the feed
+    fn load_newer(&self, request: PageRequest) -> SourceFuture;
+
+    /// Check for new items in the background. `request.cursor` is the newest
+    /// item the feed has; sources should only transfer what came after it
+    /// (`since_id`, conditional requests) and answer an empty page when
+    /// nothing did, which leaves the store untouched.
+    fn poll(&self, request: PageRequest) -> SourceFuture {
+        self.load_newer(request)
+    }
+
+    /// Fetch the newest page for a manual refresh. The page may overlap
+    /// items already in the feed; the store merges them into their rows.
+    fn refresh(&self, request: PageRequest) -> SourceFuture {
+        self.poll(request)
+    }
+
+    /// How often to poll for new items, `None` disables polling
+    fn poll_interval(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Called when the store evicted a range of items to stay within its
+    /// window or cap, e.g. to drop what the source cached for it. Scrolling
+    /// back loads the range again through `load_older` or `load_newer`.
+    fn evicted(&self, _eviction: &Eviction) {}
+}
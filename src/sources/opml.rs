@@ -0,0 +1,198 @@
+//! OPML subscription lists, as exported by most RSS readers.
+//!
+//! Nested outlines are folders; outlines with an `xmlUrl` are feeds. Feeds
+//! outside any folder are collected into [`DEFAULT_FOLDER`].
+
+use serde::{Deserialize, Serialize};
+
+/// Folder that feeds listed outside any folder are imported into
+pub const DEFAULT_FOLDER: &str = "Subscriptions";
+
+/// Custom error type for OPML parsing
+#[derive(Debug, Clone, PartialEq)]
+pub enum OpmlError {
+    InvalidXml(String),
+    /// The document has no `<body>` or isn't OPML
+    NotOpml,
+    NoFeeds,
+    /// The import stopped before the document was parsed
+    Cancelled,
+}
+
+impl std::fmt::Display for OpmlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OpmlError::InvalidXml(msg) => write!(f, "Invalid XML: {}", msg),
+            OpmlError::NotOpml => write!(f, "Not an OPML document"),
+            OpmlError::NoFeeds => write!(f, "No feeds found"),
+            OpmlError::Cancelled => write!(f, "Import cancelled"),
+        }
+    }
+}
+
+/// An RSS or Atom feed listed in an OPML document
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpmlFeed {
+    pub title: String,
+    pub xml_url: String,
+    pub html_url: Option<String>,
+}
+
+/// A folder of feeds, shown as one tab
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpmlFolder {
+    pub title: String,
+    pub feeds: Vec<OpmlFeed>,
+}
+
+/// Parse an OPML document into folders of feeds, in document order.
+///
+/// Folders nested inside folders are flattened into their top-level folder.
+pub fn parse_opml(xml: &str) -> Result<Vec<OpmlFolder>, OpmlError> {
+    let document = roxmltree::Document::parse(xml).map_err(|e| OpmlError::InvalidXml(e.to_string()))?;
+    let root = document.root_element();
+    if !root.has_tag_name("opml") {
+        return Err(OpmlError::NotOpml);
+    }
+    let body = root
+        .children()
+        .find(|node| node.has_tag_name("body"))
+        .ok_or(OpmlError::NotOpml)?;
+
+    let mut loose = Vec::new();
+    let mut folders = Vec::new();
+    for outline in body.children().filter(|node| node.has_tag_name("outline")) {
+        if let Some(feed) = outline_feed(&outline) {
+            loose.push(feed);
+            continue;
+        }
+
+        let mut feeds = Vec::new();
+        collect_feeds(&outline, &mut feeds);
+        if !feeds.is_empty() {
+            folders.push(OpmlFolder {
+                title: outline_title(&outline).unwrap_or_else(|| DEFAULT_FOLDER.to_string()),
+                feeds,
+            });
+        }
+    }
+
+    if !loose.is_empty() {
+        folders.insert(0, OpmlFolder {
+            title: DEFAULT_FOLDER.to_string(),
+            feeds: loose,
+        });
+    }
+    if folders.is_empty() {
+        return Err(OpmlError::NoFeeds);
+    }
+    Ok(folders)
+}
+
+// Feeds anywhere below a folder outline
+fn collect_feeds(folder: &roxmltree::Node, feeds: &mut Vec<OpmlFeed>) {
+    for outline in folder.children().filter(|node| node.has_tag_name("outline")) {
+        match outline_feed(&outline) {
+            Some(feed) => feeds.push(feed),
+            None => collect_feeds(&outline, feeds),
+        }
+    }
+}
+
+// The feed an outline describes, if it has a feed URL
+fn outline_feed(outline: &roxmltree::Node) -> Option<OpmlFeed> {
+    let xml_url = outline.attribute("xmlUrl")?.trim().to_string();
+    if xml_url.is_empty() {
+        return None;
+    }
+    Some(OpmlFeed {
+        title: outline_title(outline).unwrap_or_else(|| xml_url.clone()),
+        xml_url,
+        html_url: outline.attribute("htmlUrl").map(str::to_string),
+    })
+}
+
+// Outlines carry their name in `title`, `text` or both
+fn outline_title(outline: &roxmltree::Node) -> Option<String> {
+    outline
+        .attribute("title")
+        .or_else(|| outline.attribute("text"))
+        .map(str::trim)
+        .filter(|title| !title.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed(title: &str, xml_url: &str) -> OpmlFeed {
+        OpmlFeed {
+            title: title.to_string(),
+            xml_url: xml_url.to_string(),
+            html_url: None,
+        }
+    }
+
+    #[test]
+    fn parses_folders_and_loose_feeds() {
+        let folders = parse_opml(
+            r#"<?xml version="1.0"?>
+            <opml version="2.0">
+              <head><title>Export</title></head>
+              <body>
+                <outline text="Tech">
+                  <outline text="Rust Blog" xmlUrl="https://blog.rust-lang.org/feed.xml" htmlUrl="https://blog.rust-lang.org/"/>
+                  <outline title="Nested">
+                    <outline xmlUrl=" https://example.com/nested.xml "/>
+                  </outline>
+                </outline>
+                <outline text="Loose" xmlUrl="https://example.com/loose.xml"/>
+                <outline text="Empty folder"/>
+              </body>
+            </opml>"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            folders,
+            vec![
+                OpmlFolder {
+                    title: DEFAULT_FOLDER.to_string(),
+                    feeds: vec![feed("Loose", "https://example.com/loose.xml")],
+                },
+                OpmlFolder {
+                    title: "Tech".to_string(),
+                    feeds: vec![
+                        OpmlFeed {
+                            html_url: Some("https://blog.rust-lang.org/".to_string()),
+                            ..feed("Rust Blog", "https://blog.rust-lang.org/feed.xml")
+                        },
+                        // Untitled feeds are named by their URL
+                        feed("https://example.com/nested.xml", "https://example.com/nested.xml"),
+                    ],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn prefers_title_over_text() {
+        let folders = parse_opml(
+            r#"<opml><body><outline title="Title" text="Text" xmlUrl="https://example.com/a.xml"/></body></opml>"#,
+        )
+        .unwrap();
+        assert_eq!(folders[0].feeds[0].title, "Title");
+    }
+
+    #[test]
+    fn rejects_other_documents() {
+        assert!(matches!(parse_opml("<opml><body>"), Err(OpmlError::InvalidXml(_))));
+        assert_eq!(parse_opml("<rss><channel/></rss>"), Err(OpmlError::NotOpml));
+        assert_eq!(parse_opml("<opml><head/></opml>"), Err(OpmlError::NotOpml));
+        assert_eq!(
+            parse_opml(r#"<opml><body><outline text="Folder" xmlUrl=" "/></body></opml>"#),
+            Err(OpmlError::NoFeeds)
+        );
+    }
+}
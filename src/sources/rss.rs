@@ -0,0 +1,502 @@
+use dioxus_feed_core::{ContentFlag, VirtualFeedItem};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Duration;
+
+use super::canonical::{canonicalize, merge_duplicates};
+use super::health::SourceHealth;
+use super::{FeedSource, PageRequest, SourceError, SourceFuture};
+use crate::assets::{BundledImages, MediaSrc};
+use crate::clock::ClockHandle;
+use crate::remote::{read_body, RemoteError};
+use crate::work::try_run_blocking;
+
+// RSS source configuration
+const POLLING_INTERVAL_SECS: u64 = 300; // Feeds rarely change more often
+const MAX_CONTENT_CHARS: usize = 280; // Summary length shown in rows
+const MAX_FEED_BYTES: usize = 10 * 1024 * 1024; // Largest feed document read
+
+/// Source reading an RSS 2.0 or Atom feed.
+///
+/// Feeds have no paging, so every request fetches the whole document and
+/// returns the items after the cursor, or all of them when the cursor isn't
+/// in the feed. There is never anything older. Once the store has items,
+/// requests are conditional on the `ETag`/`Last-Modified` of the previous
+/// response, so an unchanged feed costs a 304 and returns an empty page.
+#[derive(Clone)]
+pub struct RssSource {
+    pub url: String,
+    validators: Rc<RefCell<CacheValidators>>,
+}
+
+impl RssSource {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            validators: Rc::new(RefCell::new(CacheValidators::default())),
+        }
+    }
+}
+
+impl FeedSource for RssSource {
+    fn load_older(&self, _request: PageRequest) -> SourceFuture {
+        Box::pin(async { Ok(Vec::new()) })
+    }
+
+    fn load_newer(&self, request: PageRequest) -> SourceFuture {
+        let url = self.url.clone();
+        let validators = self.validators.clone();
+        Box::pin(async move {
+            // An empty store needs the whole document whatever changed
+            let sent = if request.loaded > 0 {
+                validators.borrow().clone()
+            } else {
+                CacheValidators::default()
+            };
+            let items = match fetch_feed(&url, &sent).await? {
+                FeedResponse::Unchanged => return Ok(Vec::new()),
+                FeedResponse::Fetched { items, validators: received } => {
+                    *validators.borrow_mut() = received;
+                    items
+                }
+            };
+            // Only items after the cursor are newer
+            let start = request
+                .cursor
+                .and_then(|cursor| items.iter().position(|item| item.id == cursor))
+                .map_or(0, |index| index + 1);
+            Ok(items.into_iter().skip(start).collect())
+        })
+    }
+
+    fn poll_interval(&self) -> Option<Duration> {
+        Some(Duration::from_secs(POLLING_INTERVAL_SECS))
+    }
+}
+
+/// Validators of a feed response, sent back with the next request so an
+/// unchanged feed can answer 304 without a body
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct CacheValidators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+/// Outcome of a conditional feed request
+pub enum FeedResponse {
+    /// The feed hasn't changed since the validators were recorded
+    Unchanged,
+    /// Items oldest first, with the validators to send next time
+    Fetched {
+        items: Vec<VirtualFeedItem>,
+        validators: CacheValidators,
+    },
+}
+
+/// Fetch a feed unless it is unchanged since `validators` were recorded
+pub async fn fetch_feed(url: &str, validators: &CacheValidators) -> Result<FeedResponse, SourceError> {
+    let mut request = reqwest::Client::new().get(url);
+    if let Some(etag) = &validators.etag {
+        request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+    }
+    if let Some(last_modified) = &validators.last_modified {
+        request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|e| SourceError::Network(e.to_string()))?;
+    if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+        return Ok(FeedResponse::Unchanged);
+    }
+    if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse().ok())
+            .map(Duration::from_secs);
+        return Err(SourceError::RateLimited(retry_after));
+    }
+    if !response.status().is_success() {
+        return Err(SourceError::Network(format!("{} returned {}", url, response.status())));
+    }
+    let header = |name: reqwest::header::HeaderName| {
+        response.headers().get(name).and_then(|value| value.to_str().ok()).map(str::to_string)
+    };
+    let validators = CacheValidators {
+        etag: header(reqwest::header::ETAG),
+        last_modified: header(reqwest::header::LAST_MODIFIED),
+    };
+    let body = read_body(response, MAX_FEED_BYTES).await.map_err(|e| match e {
+        RemoteError::TooLarge(max) => {
+            SourceError::InvalidResponse(format!("{} is larger than {} MiB", url, max / (1024 * 1024)))
+        }
+        e => SourceError::Network(e.to_string()),
+    })?;
+    let xml = String::from_utf8_lossy(&body).into_owned();
+
+    Ok(FeedResponse::Fetched {
+        items: try_run_blocking(move || parse_feed(&xml)).await?,
+        validators,
+    })
+}
+
+/// Parse an RSS 2.0 or Atom document into items, oldest first
+pub fn parse_feed(xml: &str) -> Result<Vec<VirtualFeedItem>, SourceError> {
+    let document = roxmltree::Document::parse(xml).map_err(|e| SourceError::InvalidResponse(e.to_string()))?;
+
+    // RSS items live in <channel>, Atom entries directly in <feed>
+    let entries = document
+        .descendants()
+        .filter(|node| node.has_tag_name("item") || node.has_tag_name("entry"));
+
+    let mut items: Vec<VirtualFeedItem> = entries.filter_map(|entry| parse_entry(&entry)).collect();
+    // Feeds list their newest entry first
+    items.reverse();
+    Ok(items)
+}
+
+// Build an item from an RSS <item> or Atom <entry>
+fn parse_entry(entry: &roxmltree::Node) -> Option<VirtualFeedItem> {
+    let child_text = |name: &str| {
+        entry
+            .children()
+            .find(|node| node.tag_name().name() == name)
+            .and_then(|node| node.text())
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+    };
+
+    // Atom links are attributes, RSS links are text
+    let link = entry
+        .children()
+        .find(|node| node.has_tag_name("link"))
+        .and_then(|node| node.attribute("href").or_else(|| node.text()))
+        .map(str::trim)
+        .filter(|link| !link.is_empty())
+        .map(str::to_string);
+    let id = child_text("guid").or_else(|| child_text("id")).map(str::to_string).or_else(|| link.clone())?;
+
+    let title = child_text("title").unwrap_or_default();
+    let summary = child_text("description")
+        .or_else(|| child_text("summary"))
+        .map(strip_markup)
+        .unwrap_or_default();
+    let content = match (title.is_empty(), summary.is_empty()) {
+        (false, false) => format!("{} — {}", title, truncate(&summary, MAX_CONTENT_CHARS)),
+        (false, true) => title.to_string(),
+        _ => truncate(&summary, MAX_CONTENT_CHARS),
+    };
+
+    // RSS has <author> or <dc:creator> text, Atom an <author> with a <name>
+    let author = entry
+        .children()
+        .find(|node| matches!(node.tag_name().name(), "author" | "creator"))
+        .and_then(|node| {
+            node.children()
+                .find(|child| child.has_tag_name("name"))
+                .and_then(|name| name.text())
+                .or_else(|| node.text())
+        })
+        .map(str::trim)
+        .filter(|author| !author.is_empty())
+        .map(str::to_string);
+
+    // RSS dates are RFC 2822, Atom dates RFC 3339
+    let published_at = child_text("pubDate")
+        .and_then(|date| chrono::DateTime::parse_from_rfc2822(date).ok())
+        .or_else(|| {
+            child_text("published")
+                .or_else(|| child_text("updated"))
+                .and_then(|date| chrono::DateTime::parse_from_rfc3339(date).ok())
+        })
+        .map(|date| date.timestamp_millis());
+    // Comment counts from the slash namespace (RSS <comments> is a URL)
+    let engagement = child_text("comments").and_then(|count| count.parse().ok());
+
+    let mut item = VirtualFeedItem::new_with_random_image(id, content);
+    if let Some(link) = link {
+        item = item.with_link(link);
+    }
+    if let Some(published_at) = published_at {
+        item = item.with_published_at(published_at);
+    }
+    if let Some(engagement) = engagement {
+        item = item.with_engagement(engagement);
+    }
+    if let Some(author) = author {
+        item = item.with_author(author);
+    }
+    // Enclosed images show first, with the bundled image as a fallback
+    if let Some((url, alt)) = entry_image(entry) {
+        if let Ok(image) = MediaSrc::remote(&url) {
+            let bundled = std::mem::replace(&mut item.image_url, image.to_string());
+            item.image_fallbacks.insert(0, bundled);
+            item.image_alt = alt;
+        }
+    }
+    if is_adult(entry) {
+        item = item.with_flag(ContentFlag::Sensitive);
+    }
+    Some(item)
+}
+
+// Whether a Media RSS <rating>, on the entry or its media, marks it adult
+fn is_adult(entry: &roxmltree::Node) -> bool {
+    entry
+        .descendants()
+        .filter(|node| node.tag_name().name() == "rating")
+        .filter_map(|node| node.text())
+        .any(|rating| rating.trim().eq_ignore_ascii_case("adult"))
+}
+
+// Image attached through <enclosure> or Media RSS, with the Media RSS
+// description as its alt text
+fn entry_image(entry: &roxmltree::Node) -> Option<(String, Option<String>)> {
+    let node = entry
+        .children()
+        .filter(|node| matches!(node.tag_name().name(), "enclosure" | "content" | "thumbnail"))
+        .filter(|node| {
+            node.attribute("type").is_none_or(|mime| mime.starts_with("image/"))
+                || node.attribute("medium") == Some("image")
+        })
+        .find(|node| node.attribute("url").is_some())?;
+    let alt = node
+        .children()
+        .find(|child| child.tag_name().name() == "description")
+        .and_then(|child| child.text())
+        .map(strip_markup)
+        .filter(|alt| !alt.is_empty());
+    Some((node.attribute("url")?.to_string(), alt))
+}
+
+// Reduce HTML in descriptions to plain text
+pub(super) fn strip_markup(html: &str) -> String {
+    let fragment = scraper::Html::parse_fragment(html);
+    let text: Vec<&str> = fragment.root_element().text().collect();
+    text.join(" ").split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+pub(super) fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let truncated: String = text.chars().take(max_chars).collect();
+    format!("{}…", truncated.trim_end())
+}
+
+/// Source merging several feeds into one, as for an imported folder.
+///
+/// Items get canonical ids, so a story carried by several feeds becomes one
+/// item listing every feed in its `sources`. Feeds are fetched whole, so the
+/// source remembers what it has handed out and only returns items that are
+/// new or have gained a source; the store merges the latter into their rows.
+/// Feeds unchanged since the last fetch answer 304 and contribute nothing.
+#[derive(Clone)]
+pub struct AggregateSource {
+    name: String,
+    // Sources with the name shown on their items
+    sources: Vec<(String, Rc<dyn FeedSource>)>,
+    // Sources each delivered canonical id was seen from
+    delivered: Rc<RefCell<HashMap<String, Vec<String>>>>,
+    health: Option<(SourceHealth, ClockHandle)>,
+}
+
+impl AggregateSource {
+    pub fn new(name: impl Into<String>, sources: Vec<(String, Rc<dyn FeedSource>)>) -> Self {
+        Self {
+            name: name.into(),
+            sources,
+            delivered: Rc::new(RefCell::new(HashMap::new())),
+            health: None,
+        }
+    }
+
+    /// Record each source's syncs in `health` as "name › source", timestamped
+    /// by `clock`
+    pub fn with_health(mut self, health: SourceHealth, clock: ClockHandle) -> Self {
+        self.health = Some((health, clock));
+        self
+    }
+
+    // Canonical items from every source, in source order with duplicates
+    // merged. A failing source doesn't hide the others unless all of them fail.
+    fn fetch_all(&self, request: PageRequest) -> SourceFuture {
+        let name = self.name.clone();
+        let sources = self.sources.clone();
+        let mut health = self.health.clone();
+        Box::pin(async move {
+            let mut items = Vec::new();
+            let mut last_error = None;
+            for (source_name, source) in &sources {
+                let request = PageRequest { cursor: None, ..request.clone() };
+                let result = source.load_newer(request).await;
+                if let Some((health, clock)) = &mut health {
+                    health.record(&format!("{} › {}", name, source_name), &result, clock.utc_now());
+                }
+                match result {
+                    Ok(page) => items.extend(page.into_iter().map(|item| canonicalize(item, source_name))),
+                    Err(e) => {
+                        eprintln!("Failed to load {}: {}", source_name, e);
+                        last_error = Some(e);
+                    }
+                }
+            }
+            match last_error {
+                Some(e) if items.is_empty() => Err(e),
+                _ => Ok(merge_duplicates(items)),
+            }
+        })
+    }
+}
+
+impl FeedSource for AggregateSource {
+    fn load_older(&self, _request: PageRequest) -> SourceFuture {
+        Box::pin(async { Ok(Vec::new()) })
+    }
+
+    fn load_newer(&self, request: PageRequest) -> SourceFuture {
+        // An empty store starts over, e.g. after it was reset
+        if request.loaded == 0 {
+            self.delivered.borrow_mut().clear();
+        }
+        let page = self.fetch_all(request);
+        let delivered = self.delivered.clone();
+        Box::pin(async move {
+            let items = page.await?;
+            let mut delivered = delivered.borrow_mut();
+            Ok(items
+                .into_iter()
+                .filter(|item| {
+                    let known = delivered.entry(item.id.clone()).or_default();
+                    let before = known.len();
+                    for source in &item.sources {
+                        if !known.contains(source) {
+                            known.push(source.clone());
+                        }
+                    }
+                    known.len() > before
+                })
+                .collect())
+        })
+    }
+
+    // The store merges items it already has from refreshed pages
+    fn refresh(&self, request: PageRequest) -> SourceFuture {
+        let page = self.fetch_all(request);
+        let delivered = self.delivered.clone();
+        Box::pin(async move {
+            let items = page.await?;
+            let mut delivered = delivered.borrow_mut();
+            for item in &items {
+                delivered.insert(item.id.clone(), item.sources.clone());
+            }
+            Ok(items)
+        })
+    }
+
+    fn poll_interval(&self) -> Option<Duration> {
+        Some(Duration::from_secs(POLLING_INTERVAL_SECS))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assets::media_origin;
+
+    #[test]
+    fn parses_rss_items_oldest_first() {
+        let items = parse_feed(
+            r#"<?xml version="1.0"?>
+            <rss version="2.0" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:media="http://search.yahoo.com/mrss/">
+              <channel>
+                <title>Example</title>
+                <item>
+                  <title>Second</title>
+                  <link>https://example.com/2</link>
+                  <guid>item-2</guid>
+                  <description><![CDATA[<p>Some <b>bold</b> text</p>]]></description>
+                  <dc:creator>Ada</dc:creator>
+                  <pubDate>Tue, 02 Jan 2024 10:00:00 +0000</pubDate>
+                  <media:content url="https://example.com/2.jpg" medium="image">
+                    <media:description>A photo</media:description>
+                  </media:content>
+                </item>
+                <item>
+                  <title>First</title>
+                  <link>https://example.com/1</link>
+                  <media:rating>adult</media:rating>
+                </item>
+              </channel>
+            </rss>"#,
+        )
+        .unwrap();
+
+        assert_eq!(items.len(), 2);
+        let (first, second) = (&items[0], &items[1]);
+        // Without a guid the link identifies the item
+        assert_eq!(first.id, "https://example.com/1");
+        assert_eq!(first.content, "First");
+        assert!(first.flags.contains(&ContentFlag::Sensitive));
+
+        assert_eq!(second.id, "item-2");
+        assert_eq!(second.content, "Second — Some bold text");
+        assert_eq!(second.link.as_deref(), Some("https://example.com/2"));
+        assert_eq!(second.author.as_deref(), Some("Ada"));
+        assert_eq!(second.published_at, Some(1_704_189_600_000));
+        assert_eq!(media_origin(&second.image_url).as_deref(), Some("https://example.com/2.jpg"));
+        assert_eq!(second.image_alt.as_deref(), Some("A photo"));
+        assert!(second.flags.is_empty());
+    }
+
+    #[test]
+    fn parses_atom_entries() {
+        let items = parse_feed(
+            r#"<feed xmlns="http://www.w3.org/2005/Atom">
+              <entry>
+                <id>urn:entry:1</id>
+                <title>Atom entry</title>
+                <link href="https://example.com/atom"/>
+                <author><name>Grace</name></author>
+                <updated>2024-01-02T10:00:00Z</updated>
+                <summary>Short summary</summary>
+              </entry>
+            </feed>"#,
+        )
+        .unwrap();
+
+        assert_eq!(items.len(), 1);
+        let item = &items[0];
+        assert_eq!(item.id, "urn:entry:1");
+        assert_eq!(item.content, "Atom entry — Short summary");
+        assert_eq!(item.link.as_deref(), Some("https://example.com/atom"));
+        assert_eq!(item.author.as_deref(), Some("Grace"));
+        assert_eq!(item.published_at, Some(1_704_189_600_000));
+    }
+
+    #[test]
+    fn skips_entries_without_an_id_or_link() {
+        let items = parse_feed("<rss><channel><item><title>Nowhere</title></item></channel></rss>").unwrap();
+        assert!(items.is_empty());
+        assert!(matches!(parse_feed("<rss><channel>"), Err(SourceError::InvalidResponse(_))));
+    }
+
+    #[test]
+    fn summaries_are_truncated() {
+        let long = "word ".repeat(100);
+        let xml = format!("<rss><channel><item><guid>1</guid><description>{}</description></item></channel></rss>", long);
+        let items = parse_feed(&xml).unwrap();
+        assert!(items[0].content.ends_with('…'));
+        assert!(items[0].content.chars().count() <= MAX_CONTENT_CHARS + 1);
+    }
+
+    #[test]
+    fn strips_markup_and_truncates() {
+        assert_eq!(strip_markup("<p>Hello <a href='#'>world</a></p>\n<p>again</p>"), "Hello world again");
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("abcdef ghi", 7), "abcdef…");
+    }
+}
@@ -0,0 +1,21 @@
+use dioxus::prelude::*;
+use dioxus_feed_core::VirtualFeedItem;
+
+use crate::components::feed_tabs::FEEDS;
+
+/// First page of the feed with id `feed_id`.
+///
+/// Used during server-side rendering; the result is serialized into the page
+/// and reused while the client hydrates, so the list paints with items
+/// instead of popping them in after launch. Feeds built from local state,
+/// such as saved items, have no server-rendered page.
+#[server]
+pub async fn initial_page(feed_id: String) -> Result<Vec<VirtualFeedItem>, ServerFnError> {
+    let items = FEEDS
+        .iter()
+        .find(|feed| feed.id == feed_id)
+        .and_then(|feed| feed.source())
+        .map(|source| source.initial_items())
+        .unwrap_or_default();
+    Ok(items)
+}
@@ -0,0 +1,54 @@
+//! Simhash fingerprints for spotting near-duplicate item text, such as the
+//! same announcement reposted with a different intro or link.
+
+/// Fingerprints differing in at most this many bits are near-duplicates
+pub const NEAR_DUPLICATE_DISTANCE: u32 = 3;
+
+/// Texts with fewer words are too short to compare reliably
+const MIN_WORDS: usize = 4;
+
+/// 64-bit simhash of the words in `text`, `None` when it is too short.
+///
+/// Words are lowercased and stripped of punctuation, and each word pair is
+/// a feature alongside the words themselves, so word order counts a little.
+pub fn fingerprint(text: &str) -> Option<u64> {
+    let words: Vec<String> = text
+        .split_whitespace()
+        .map(|word| word.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect::<String>())
+        .filter(|word| !word.is_empty())
+        .collect();
+    if words.len() < MIN_WORDS {
+        return None;
+    }
+
+    let mut weights = [0i32; 64];
+    let pairs = words.windows(2).map(|pair| format!("{} {}", pair[0], pair[1]));
+    for feature in words.iter().cloned().chain(pairs) {
+        let hash = fnv1a(&feature);
+        for (bit, weight) in weights.iter_mut().enumerate() {
+            if hash & (1 << bit) != 0 {
+                *weight += 1;
+            } else {
+                *weight -= 1;
+            }
+        }
+    }
+
+    Some(weights
+        .iter()
+        .enumerate()
+        .filter(|(_, weight)| **weight > 0)
+        .fold(0u64, |hash, (bit, _)| hash | (1 << bit)))
+}
+
+/// Whether two fingerprints belong to near-duplicate texts
+pub fn is_near_duplicate(a: u64, b: u64) -> bool {
+    (a ^ b).count_ones() <= NEAR_DUPLICATE_DISTANCE
+}
+
+// Stable 64-bit FNV-1a, so fingerprints don't change between runs
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
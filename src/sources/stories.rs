@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use super::SourceError;
+use crate::assets::MediaSrc;
+use crate::clock::ClockHandle;
+
+// Demo story configuration
+const STORY_LOAD_DELAY_MS: u64 = 300; // Simulated latency loading the rail
+const DEMO_STORY_COUNT: usize = 40;
+const DEMO_AUTHORS: &[&str] = &["Ada", "Grace", "Linus", "Margaret", "Dennis", "Barbara", "Ken", "Radia"];
+
+/// A story shown in the rail above the feed
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Story {
+    pub id: String,
+    pub author: String,
+    pub image_url: String,
+}
+
+/// Boxed future returned by story sources
+pub type StoryFuture = Pin<Box<dyn Future<Output = Result<Vec<Story>, SourceError>>>>;
+
+/// Provider of the stories in the rail, separate from the feed's items
+pub trait StorySource {
+    /// Current stories, in display order
+    fn load_stories(&self) -> StoryFuture;
+}
+
+/// Synthetic source cycling through the bundled images
+#[derive(Clone, Default)]
+pub struct DemoStories {
+    clock: ClockHandle,
+}
+
+impl DemoStories {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_clock(mut self, clock: ClockHandle) -> Self {
+        self.clock = clock;
+        self
+    }
+}
+
+impl StorySource for DemoStories {
+    fn load_stories(&self) -> StoryFuture {
+        let clock = self.clock.clone();
+        Box::pin(async move {
+            clock.sleep(Duration::from_millis(STORY_LOAD_DELAY_MS)).await;
+
+            (0..DEMO_STORY_COUNT)
+                .map(|i| {
+                    let image = format!("assets/images/sample{}.svg", i % 6 + 1);
+                    let image_url = MediaSrc::asset(&image)
+                        .map_err(|e| SourceError::InvalidResponse(e.to_string()))?
+                        .to_string();
+                    Ok(Story {
+                        id: format!("story_{}", i + 1),
+                        author: DEMO_AUTHORS[i % DEMO_AUTHORS.len()].to_string(),
+                        image_url,
+                    })
+                })
+                .collect()
+        })
+    }
+}
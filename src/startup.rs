@@ -0,0 +1,153 @@
+//! Cold-start tracing, and work held back until the first frame is painted.
+//!
+//! `main` records the launch time; the app root marks the phases of startup
+//! against it, and the first list to render an item closes the trace with a
+//! report of the time to first item, checked against [`STARTUP_BUDGET`].
+//! Work the first frame doesn't need (registering the asset protocol, image
+//! loads and metadata requests, polling and the inbox pump) waits for
+//! [`StartupTrace::painted`].
+
+use dioxus::prelude::*;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use crate::clock::{ClockHandle, Instant};
+
+/// Time from launch to the first item rendered that cold starts should stay under
+pub const STARTUP_BUDGET: Duration = Duration::from_millis(800);
+
+/// Environment variable printing the startup report even within budget
+pub const STARTUP_TRACE_ENV_VAR: &str = "DIOXUS_FEED_STARTUP_TRACE";
+
+/// How often deferred work checks whether the first frame was painted
+const PAINT_CHECK_INTERVAL: Duration = Duration::from_millis(16);
+
+// Resolves after the frame following the first render, i.e. once it was painted
+const FIRST_PAINT_SCRIPT: &str = r#"
+    await new Promise(resolve => requestAnimationFrame(() => requestAnimationFrame(resolve)));
+    return true;
+"#;
+
+static LAUNCHED_AT: OnceLock<Instant> = OnceLock::new();
+
+/// Record the launch time; call first thing in `main`
+pub fn mark_launch() {
+    LAUNCHED_AT.get_or_init(Instant::now);
+}
+
+// Time since launch, or since the first call when `mark_launch` wasn't called
+fn since_launch() -> Duration {
+    LAUNCHED_AT.get_or_init(Instant::now).elapsed()
+}
+
+/// Phases of startup with when they were reached, closed once the first item
+/// renders
+#[derive(Debug, Clone, PartialEq)]
+pub struct StartupReport {
+    pub phases: Vec<(&'static str, Duration)>,
+    pub first_item: Duration,
+}
+
+impl StartupReport {
+    pub fn within_budget(&self) -> bool {
+        self.first_item <= STARTUP_BUDGET
+    }
+}
+
+impl std::fmt::Display for StartupReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "first item rendered after {} ms (budget {} ms)",
+            self.first_item.as_millis(),
+            STARTUP_BUDGET.as_millis()
+        )?;
+        for (phase, at) in &self.phases {
+            write!(f, "\n  {:>6} ms  {}", at.as_millis(), phase)?;
+        }
+        Ok(())
+    }
+}
+
+/// Startup trace and first-paint gate of the app.
+///
+/// Provided once at the app root with `use_context_provider(StartupTrace::new)`;
+/// components work without it, as if the first frame was already painted.
+#[derive(Clone, Copy, PartialEq)]
+pub struct StartupTrace {
+    painted: Signal<bool>,
+    phases: CopyValue<Vec<(&'static str, Duration)>>,
+    report: CopyValue<Option<StartupReport>>,
+}
+
+impl StartupTrace {
+    pub fn new() -> Self {
+        Self {
+            painted: Signal::new(false),
+            phases: CopyValue::new(vec![("launch", Duration::ZERO)]),
+            report: CopyValue::new(None),
+        }
+    }
+
+    /// Record that startup reached `phase`; ignored once the trace is closed
+    pub fn mark(&mut self, phase: &'static str) {
+        if self.report.peek().is_none() {
+            self.phases.write().push((phase, since_launch()));
+        }
+    }
+
+    /// Whether the first frame was painted; reactive, so deferred work can
+    /// start when it flips
+    pub fn painted(&self) -> bool {
+        (self.painted)()
+    }
+
+    /// Wait until the first frame was painted
+    pub async fn after_first_paint(&self, clock: &ClockHandle) {
+        while !*self.painted.peek() {
+            clock.sleep(PAINT_CHECK_INTERVAL).await;
+        }
+    }
+
+    /// Close the trace when the first item renders, printing the report if
+    /// it went over budget or tracing was asked for
+    pub fn first_item_rendered(&mut self) {
+        if self.report.peek().is_some() {
+            return;
+        }
+        let report = StartupReport {
+            phases: self.phases.peek().clone(),
+            first_item: since_launch(),
+        };
+        if !report.within_budget() || std::env::var(STARTUP_TRACE_ENV_VAR).is_ok_and(|value| value == "1") {
+            eprintln!("Startup: {}", report);
+        }
+        self.report.set(Some(report));
+    }
+
+    /// The closed trace, once an item rendered
+    pub fn report(&self) -> Option<StartupReport> {
+        self.report.peek().clone()
+    }
+}
+
+impl Default for StartupTrace {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Flip [`StartupTrace::painted`] once the first frame is on screen. Call
+/// once at the app root, below the provider.
+pub fn use_first_paint() {
+    let trace = use_context::<StartupTrace>();
+    use_future(move || async move {
+        let mut trace = trace;
+        // Without a webview to ask, deferred work starts right away
+        if let Err(e) = document::eval(FIRST_PAINT_SCRIPT).join::<bool>().await {
+            eprintln!("Failed to wait for first paint: {}", e);
+        }
+        trace.mark("first paint");
+        trace.painted.set(true);
+    });
+}
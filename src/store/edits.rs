@@ -0,0 +1,271 @@
+use dioxus_feed_core::VirtualFeedItem;
+
+/// Number of edits kept for undo
+pub const MAX_EDIT_HISTORY: usize = 100;
+
+/// Edit requested from a row of a curated list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditAction {
+    MoveUp,
+    MoveDown,
+    Hide,
+    TogglePin,
+    Delete,
+}
+
+/// A reversible change to a store's items.
+///
+/// Every command carries what is needed to build its inverse, so undoing is
+/// applying the inverse and redoing is applying the command again.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EditCommand {
+    /// Move the item with `id` from index `from` to index `to`
+    Move { id: String, from: usize, to: usize },
+    Hide { id: String },
+    Unhide { id: String },
+    Pin { id: String },
+    Unpin { id: String },
+    /// Remove `item`, which is at `index`
+    Delete { index: usize, item: VirtualFeedItem },
+    /// Put `item` back at `index`
+    Insert { index: usize, item: VirtualFeedItem },
+}
+
+impl EditCommand {
+    /// The command undoing this one
+    pub fn inverse(&self) -> EditCommand {
+        match self {
+            EditCommand::Move { id, from, to } => EditCommand::Move {
+                id: id.clone(),
+                from: *to,
+                to: *from,
+            },
+            EditCommand::Hide { id } => EditCommand::Unhide { id: id.clone() },
+            EditCommand::Unhide { id } => EditCommand::Hide { id: id.clone() },
+            EditCommand::Pin { id } => EditCommand::Unpin { id: id.clone() },
+            EditCommand::Unpin { id } => EditCommand::Pin { id: id.clone() },
+            EditCommand::Delete { index, item } => EditCommand::Insert { index: *index, item: item.clone() },
+            EditCommand::Insert { index, item } => EditCommand::Delete { index: *index, item: item.clone() },
+        }
+    }
+}
+
+/// Undo and redo stacks of applied edits, most recent last
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct EditHistory {
+    undo: Vec<EditCommand>,
+    redo: Vec<EditCommand>,
+}
+
+impl EditHistory {
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Remember a newly applied edit; it replaces anything left to redo
+    pub fn push(&mut self, command: EditCommand) {
+        self.redo.clear();
+        if self.undo.len() == MAX_EDIT_HISTORY {
+            self.undo.remove(0);
+        }
+        self.undo.push(command);
+    }
+
+    /// Take the last edit to undo, moving it to the redo stack
+    pub fn undo(&mut self) -> Option<EditCommand> {
+        let command = self.undo.pop()?;
+        self.redo.push(command.clone());
+        Some(command)
+    }
+
+    /// Take the last undone edit to apply again
+    pub fn redo(&mut self) -> Option<EditCommand> {
+        let command = self.redo.pop()?;
+        self.undo.push(command.clone());
+        Some(command)
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::filters::ContentFilters;
+    use crate::store::ranking::FeedRanking;
+    use crate::store::{in_dom, FeedItems, FeedStore};
+    use dioxus::prelude::*;
+
+    fn item(id: &str) -> VirtualFeedItem {
+        VirtualFeedItem::new(id.to_string(), format!("Item {}", id), "")
+    }
+
+    fn hide(id: &str) -> EditCommand {
+        EditCommand::Hide { id: id.to_string() }
+    }
+
+    fn shown_ids(store: &FeedStore) -> Vec<String> {
+        store.ordered_items().iter().map(|item| item.id.clone()).collect()
+    }
+
+    #[test]
+    fn inverses_undo_each_other() {
+        let commands = [
+            EditCommand::Move { id: "a".to_string(), from: 0, to: 2 },
+            hide("a"),
+            EditCommand::Pin { id: "a".to_string() },
+            EditCommand::Delete { index: 1, item: item("a") },
+        ];
+        for command in commands {
+            assert_ne!(command.inverse(), command);
+            assert_eq!(command.inverse().inverse(), command);
+        }
+    }
+
+    #[test]
+    fn undo_then_redo_gives_back_the_same_edit() {
+        let mut history = EditHistory::default();
+        history.push(hide("a"));
+        history.push(hide("b"));
+
+        assert_eq!(history.undo(), Some(hide("b")));
+        assert!(history.can_redo());
+        assert_eq!(history.redo(), Some(hide("b")));
+        assert!(!history.can_redo());
+
+        assert_eq!(history.undo(), Some(hide("b")));
+        assert_eq!(history.undo(), Some(hide("a")));
+        assert_eq!(history.undo(), None);
+        assert!(!history.can_undo());
+        assert_eq!(history.redo(), Some(hide("a")));
+        assert_eq!(history.redo(), Some(hide("b")));
+        assert_eq!(history.redo(), None);
+    }
+
+    #[test]
+    fn a_new_edit_clears_what_was_left_to_redo() {
+        let mut history = EditHistory::default();
+        history.push(hide("a"));
+        history.push(hide("b"));
+        history.undo();
+        history.undo();
+        assert!(history.can_redo());
+
+        history.push(hide("c"));
+        assert!(!history.can_redo());
+        assert_eq!(history.redo(), None);
+        assert_eq!(history.undo(), Some(hide("c")));
+        assert_eq!(history.undo(), None);
+    }
+
+    #[test]
+    fn history_keeps_the_latest_edits() {
+        let mut history = EditHistory::default();
+        for index in 0..=MAX_EDIT_HISTORY {
+            history.push(hide(&index.to_string()));
+        }
+        let mut undone = 0;
+        while let Some(command) = history.undo() {
+            undone += 1;
+            if !history.can_undo() {
+                assert_eq!(command, hide("1"));
+            }
+        }
+        assert_eq!(undone, MAX_EDIT_HISTORY);
+    }
+
+    #[test]
+    fn store_edits_round_trip_through_undo_and_redo() {
+        in_dom(|| {
+            let items = ["0", "1", "2", "3"].map(item).into_iter().collect();
+            let mut store = FeedStore::from_items(Signal::new(items));
+            let original = shown_ids(&store);
+
+            store.edit("2", EditAction::MoveUp);
+            store.edit("0", EditAction::Hide);
+            store.edit("3", EditAction::TogglePin);
+            store.edit("1", EditAction::Delete);
+            let edited = shown_ids(&store);
+            assert_eq!(edited, ["3", "2"]);
+
+            while store.undo() {}
+            assert_eq!(shown_ids(&store), original);
+            assert!(!store.is_pinned("3"));
+            assert!(!store.can_undo());
+
+            while store.redo() {}
+            assert_eq!(shown_ids(&store), edited);
+            assert!(store.is_pinned("3"));
+            assert!(!store.can_redo());
+        });
+    }
+
+    #[test]
+    fn moves_trade_places_with_the_item_shown_next_to_it() {
+        in_dom(|| {
+            let mut filters = ContentFilters::load();
+            filters.set_muted_keywords(vec!["muted".to_string()]);
+            let mut items: FeedItems = ["0", "1", "2", "3", "4"].map(item).into_iter().collect();
+            items[2].content = "Item 2, muted".to_string();
+            let mut store = FeedStore::from_items(Signal::new(items)).with_filters(filters, "Saved");
+            store.edit("1", EditAction::Hide);
+            assert_eq!(shown_ids(&store), ["0", "3", "4"]);
+
+            store.edit("3", EditAction::MoveUp);
+            assert_eq!(shown_ids(&store), ["3", "0", "4"]);
+            store.edit("0", EditAction::MoveDown);
+            assert_eq!(shown_ids(&store), ["3", "4", "0"]);
+
+            assert!(store.undo());
+            assert!(store.undo());
+            assert_eq!(shown_ids(&store), ["0", "3", "4"]);
+        });
+    }
+
+    #[test]
+    fn moves_that_would_show_no_change_are_not_made() {
+        in_dom(|| {
+            let items = ["0", "1", "2"].map(item).into_iter().collect();
+            let mut store = FeedStore::from_items(Signal::new(items));
+            store.edit("2", EditAction::TogglePin);
+            assert_eq!(shown_ids(&store), ["2", "0", "1"]);
+            let history = store.edits.peek().clone();
+
+            // Unpinned items stay below pinned ones
+            store.edit("0", EditAction::MoveUp);
+            store.edit("2", EditAction::MoveDown);
+            store.edit("1", EditAction::MoveDown);
+            assert_eq!(shown_ids(&store), ["2", "0", "1"]);
+            assert_eq!(*store.edits.peek(), history);
+
+            // The ranking orders the items
+            store.ranking.set(FeedRanking::Top);
+            store.edit("1", EditAction::MoveUp);
+            assert_eq!(*store.edits.peek(), history);
+        });
+    }
+
+    #[test]
+    fn store_edit_after_undo_clears_redo() {
+        in_dom(|| {
+            let items = ["0", "1", "2"].map(item).into_iter().collect();
+            let mut store = FeedStore::from_items(Signal::new(items));
+
+            store.edit("0", EditAction::Hide);
+            assert!(store.undo());
+            assert!(store.can_redo());
+
+            store.edit("1", EditAction::Hide);
+            assert!(!store.can_redo());
+            assert!(!store.redo());
+            assert_eq!(shown_ids(&store), ["0", "2"]);
+        });
+    }
+}
@@ -0,0 +1,88 @@
+use dioxus::prelude::*;
+use std::collections::HashMap;
+
+/// State of one item that changes while it is loaded
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ItemState {
+    /// Arrived through a poll or refresh within the highlight duration
+    pub fresh: bool,
+    pub pinned: bool,
+}
+
+/// One signal per item for its [`ItemState`].
+///
+/// Rows read their own item's signal, so changing one item re-renders only
+/// the row showing it instead of the list and every visible row. Signals are
+/// created on first use and owned by the root scope, since they are created
+/// while rendering lists that may unmount before the store does.
+#[derive(Clone, Copy, PartialEq)]
+pub struct ItemStates {
+    states: CopyValue<HashMap<String, Signal<ItemState>>>,
+}
+
+impl ItemStates {
+    pub fn new() -> Self {
+        Self {
+            states: CopyValue::new(HashMap::new()),
+        }
+    }
+
+    /// Signal holding the state of the item with `id`
+    pub fn get(&self, id: &str) -> Signal<ItemState> {
+        if let Some(signal) = self.states.peek().get(id) {
+            return *signal;
+        }
+        let signal = Signal::new_in_scope(ItemState::default(), ScopeId::ROOT);
+        let mut states = self.states;
+        states.write().insert(id.to_string(), signal);
+        signal
+    }
+
+    /// Current state of the item with `id`, without subscribing to it
+    pub fn peek(&self, id: &str) -> ItemState {
+        self.states
+            .peek()
+            .get(id)
+            .map(|signal| *signal.peek())
+            .unwrap_or_default()
+    }
+
+    /// Change the state of the item with `id`, writing its signal only if the
+    /// state actually changed
+    pub fn update(&self, id: &str, update: impl FnOnce(&mut ItemState)) {
+        let mut signal = self.get(id);
+        let mut state = *signal.peek();
+        update(&mut state);
+        if state != *signal.peek() {
+            signal.set(state);
+        }
+    }
+
+    /// Number of items with a signal
+    pub fn len(&self) -> usize {
+        self.states.peek().len()
+    }
+
+    /// Drop the signal of an item that left the feed
+    pub fn remove(&self, id: &str) {
+        let mut states = self.states;
+        let removed = states.write().remove(id);
+        if let Some(signal) = removed {
+            signal.manually_drop();
+        }
+    }
+
+    /// Drop every item's signal
+    pub fn clear(&self) {
+        let mut states = self.states;
+        for (_, signal) in states.write().drain() {
+            signal.manually_drop();
+        }
+    }
+}
+
+impl Default for ItemStates {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,255 @@
+use dioxus::prelude::*;
+use dioxus_feed_core::Dimensions;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::startup::StartupTrace;
+
+/// Automatic retries of a failed image before it is shown as failed
+pub const MAX_MEDIA_RETRIES: u32 = 3;
+
+/// Images fetched at once; further rows wait for a free slot
+pub const MAX_CONCURRENT_MEDIA_LOADS: usize = 4;
+
+/// Delay before the first retry, doubled for each further attempt
+const MEDIA_RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// Load state of a media resource
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum MediaStatus {
+    #[default]
+    Loading,
+    Loaded,
+    Failed,
+}
+
+/// Delay before retry number `attempt` (starting at 0)
+pub fn media_retry_delay(attempt: u32) -> Duration {
+    MEDIA_RETRY_BASE_DELAY * 2u32.saturating_pow(attempt)
+}
+
+/// Whether a failed load with response `status` may succeed if retried.
+/// Client errors such as a missing file or unsupported type are permanent;
+/// server errors and failed requests are worth another attempt.
+pub fn is_transient_failure(status: Option<u16>) -> bool {
+    match status {
+        Some(status) => !(400..500).contains(&status) || status == 408 || status == 429,
+        None => true,
+    }
+}
+
+/// In-memory cache of media load states and dimensions keyed by URL.
+///
+/// Rows are unmounted as they scroll out of the virtual list; consulting the
+/// cache on mount lets an already-loaded image render immediately instead of
+/// showing the loading placeholder again. The cache is not reactive, so
+/// recording a status never re-renders other rows.
+#[derive(Clone, Default)]
+pub struct MediaCache {
+    statuses: Rc<RefCell<HashMap<String, MediaStatus>>>,
+    dimensions: Rc<RefCell<HashMap<String, Dimensions>>>,
+}
+
+impl MediaCache {
+    /// Last known status of the media at `url`
+    pub fn status(&self, url: &str) -> MediaStatus {
+        self.statuses.borrow().get(url).copied().unwrap_or_default()
+    }
+
+    /// Record the outcome of loading the media at `url`
+    pub fn set_status(&self, url: &str, status: MediaStatus) {
+        self.statuses.borrow_mut().insert(url.to_string(), status);
+    }
+
+    /// Known dimensions of the image at `url`
+    pub fn dimensions(&self, url: &str) -> Option<Dimensions> {
+        self.dimensions.borrow().get(url).copied()
+    }
+
+    pub fn set_dimensions(&self, url: &str, dimensions: Dimensions) {
+        self.dimensions.borrow_mut().insert(url.to_string(), dimensions);
+    }
+}
+
+/// Access the app-wide media cache
+pub fn use_media_cache() -> MediaCache {
+    use_root_context(MediaCache::default)
+}
+
+// Ask the protocol's metadata endpoint for the size of a protocol image
+#[cfg(feature = "desktop")]
+async fn fetch_dimensions(url: &str) -> Option<Dimensions> {
+    let url = crate::assets::ProtocolUrl::parse(url)?;
+    let meta = crate::assets::fetch_meta(&url).await?;
+    meta.width.zip(meta.height)
+}
+
+// Other platforms have no metadata endpoint
+#[cfg(not(feature = "desktop"))]
+async fn fetch_dimensions(_url: &str) -> Option<Dimensions> {
+    None
+}
+
+/// Dimensions of the image at `url`, used to reserve its space before it
+/// loads.
+///
+/// `known` comes from the item model and wins when present. Otherwise the
+/// cache is consulted, then the metadata endpoint; fetched sizes are cached
+/// so rows remounted by the virtual list lay out correctly on first render.
+pub fn use_media_dimensions(url: String, known: Option<Dimensions>) -> Option<Dimensions> {
+    let cache = use_media_cache();
+    let cached = known.or_else(|| cache.dimensions(&url));
+    let painted = use_painted();
+
+    let fetched = use_resource(use_reactive!(|(url, cached, painted)| {
+        let cache = cache.clone();
+        async move {
+            // The protocol is registered after the first paint
+            if cached.is_some() || url.is_empty() || !painted {
+                return cached;
+            }
+            let dimensions = fetch_dimensions(&url).await?;
+            cache.set_dimensions(&url, dimensions);
+            Some(dimensions)
+        }
+    }));
+
+    cached.or_else(|| fetched.cloned().flatten())
+}
+
+// Where a row is in the loader's queue
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SlotState {
+    Idle,
+    Waiting,
+    Active,
+}
+
+struct Slot {
+    state: SlotState,
+    /// Rows away from the viewport, lower loads first
+    priority: u32,
+    granted: Signal<bool>,
+}
+
+#[derive(Default)]
+struct LoaderState {
+    next_id: u64,
+    slots: HashMap<u64, Slot>,
+}
+
+/// App-wide queue limiting how many images load at once.
+///
+/// Opening the app renders the visible rows plus the buffer; letting every
+/// row fetch immediately would have the buffered rows compete with the
+/// visible ones. Rows instead wait for one of [`MAX_CONCURRENT_MEDIA_LOADS`]
+/// slots, handed out nearest-to-viewport first.
+#[derive(Clone, Default)]
+pub struct MediaLoader(Rc<RefCell<LoaderState>>);
+
+impl MediaLoader {
+    fn register(&self, granted: Signal<bool>) -> u64 {
+        let mut state = self.0.borrow_mut();
+        let id = state.next_id;
+        state.next_id += 1;
+        state.slots.insert(id, Slot { state: SlotState::Idle, priority: 0, granted });
+        id
+    }
+
+    fn unregister(&self, id: u64) {
+        self.0.borrow_mut().slots.remove(&id);
+        self.pump();
+    }
+
+    fn set_priority(&self, id: u64, priority: u32) {
+        if let Some(slot) = self.0.borrow_mut().slots.get_mut(&id) {
+            slot.priority = priority;
+        }
+    }
+
+    fn request(&self, id: u64) {
+        if let Some(slot) = self.0.borrow_mut().slots.get_mut(&id) {
+            if slot.state == SlotState::Idle {
+                slot.state = SlotState::Waiting;
+            }
+        }
+        self.pump();
+    }
+
+    fn release(&self, id: u64) {
+        let released = self.0.borrow_mut().slots.get_mut(&id).and_then(|slot| {
+            let was_granted = slot.state == SlotState::Active;
+            slot.state = SlotState::Idle;
+            was_granted.then_some(slot.granted)
+        });
+        if let Some(mut granted) = released {
+            granted.set(false);
+        }
+        self.pump();
+    }
+
+    // Grant free slots to the waiting rows nearest the viewport
+    fn pump(&self) {
+        let mut granted = Vec::new();
+        {
+            let mut state = self.0.borrow_mut();
+            let mut active = state.slots.values().filter(|slot| slot.state == SlotState::Active).count();
+            while active < MAX_CONCURRENT_MEDIA_LOADS {
+                let next = state
+                    .slots
+                    .iter()
+                    .filter(|(_, slot)| slot.state == SlotState::Waiting)
+                    .min_by_key(|(id, slot)| (slot.priority, **id))
+                    .map(|(id, _)| *id);
+                let Some(slot) = next.and_then(|id| state.slots.get_mut(&id)) else {
+                    break;
+                };
+                slot.state = SlotState::Active;
+                granted.push(slot.granted);
+                active += 1;
+            }
+        }
+        // Signals are written after the borrow ends
+        for mut signal in granted {
+            signal.set(true);
+        }
+    }
+}
+
+// Whether the first frame was painted, or true without a startup trace
+fn use_painted() -> bool {
+    try_use_context::<StartupTrace>().map(|startup| startup.painted()).unwrap_or(true)
+}
+
+/// Access the app-wide media loader
+pub fn use_media_loader() -> MediaLoader {
+    use_root_context(MediaLoader::default)
+}
+
+/// Whether the calling row may start loading its image.
+///
+/// While `wanted` holds the row queues for a slot with `priority` (rows away
+/// from the viewport); the slot is given back once `wanted` turns false,
+/// typically when the image has loaded or failed, or when the row unmounts.
+pub fn use_media_slot(wanted: bool, priority: u32) -> bool {
+    // Images are non-critical for the first frame
+    let wanted = wanted && use_painted();
+    let loader = use_media_loader();
+    let granted = use_signal(|| false);
+    let id = use_hook(|| loader.register(granted));
+    loader.set_priority(id, priority);
+
+    let effect_loader = loader.clone();
+    use_effect(use_reactive!(|wanted| {
+        if wanted {
+            effect_loader.request(id);
+        } else {
+            effect_loader.release(id);
+        }
+    }));
+    use_drop(move || loader.unregister(id));
+
+    granted()
+}
@@ -0,0 +1,1538 @@
+pub mod edits;
+pub mod item_state;
+pub mod media;
+pub mod polling;
+pub mod progress;
+pub mod ranking;
+#[cfg(debug_assertions)]
+pub mod recorder;
+pub mod summaries;
+pub mod transaction;
+
+use dioxus::prelude::*;
+use dioxus_feed_core::VirtualFeedItem;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::clock::{ClockHandle, Instant};
+use crate::components::virtual_list::ITEM_HEIGHT;
+use crate::persistence::alerts::Alerts;
+use crate::persistence::bookmarks::Bookmarks;
+use crate::persistence::filters::ContentFilters;
+use crate::persistence::search_index::SearchIndex;
+use crate::processors::Processors;
+use crate::sources::health::SourceHealth;
+use crate::sources::{FeedSource, PageRequest, SourceError};
+use crate::summarizer::SummarizerHandle;
+use edits::{EditAction, EditCommand, EditHistory};
+use item_state::{ItemState, ItemStates};
+use ranking::{FeedRanking, RankingConfig};
+use summaries::{Summaries, Summary, SummaryStatus, DIGEST_ITEMS};
+use transaction::StoreTransaction;
+
+pub use dioxus_feed_core::Sequence;
+
+/// Number of items requested from the source per edge load
+pub const ITEMS_PER_LOAD: usize = 5;
+
+/// Most older items prepended at once; larger pages are staged and
+/// prepended this many at a time, see [`FeedStore::prepend_staged`]
+pub const PREPEND_CHUNK_SIZE: usize = 25;
+
+/// How long items inserted by a poll or refresh stay marked as fresh
+pub const FRESH_HIGHLIGHT_DURATION: Duration = Duration::from_millis(2000);
+
+/// Items of a feed, oldest first.
+///
+/// A persistent vector: clones share structure, and prepends, appends and
+/// removals in the middle are O(log n), so taking a copy to update, snapshot
+/// or render doesn't copy every item.
+pub type FeedItems = im::Vector<VirtualFeedItem>;
+
+/// Lifecycle of a feed, driving the loading, error and end-of-feed UI.
+///
+/// A single status replaces independent loading flags, so states such as
+/// "loading both edges while erroring" can't be represented.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FeedStatus {
+    /// Waiting for the first page of an empty feed
+    Initializing,
+    Idle,
+    LoadingTop,
+    LoadingBottom,
+    Refreshing,
+    Error(SourceError),
+    /// The source has no more items past the flagged edges
+    EndReached { top: bool, bottom: bool },
+}
+
+impl FeedStatus {
+    /// Resting status once a load has finished
+    fn settled(top: bool, bottom: bool) -> Self {
+        if top || bottom {
+            FeedStatus::EndReached { top, bottom }
+        } else {
+            FeedStatus::Idle
+        }
+    }
+
+    pub fn is_loading(&self) -> bool {
+        matches!(
+            self,
+            FeedStatus::Initializing | FeedStatus::LoadingTop | FeedStatus::LoadingBottom | FeedStatus::Refreshing
+        )
+    }
+
+    pub fn top_reached(&self) -> bool {
+        matches!(self, FeedStatus::EndReached { top: true, .. })
+    }
+
+    pub fn bottom_reached(&self) -> bool {
+        matches!(self, FeedStatus::EndReached { bottom: true, .. })
+    }
+
+    /// Whether older items may be requested in this status
+    pub fn can_load_top(&self) -> bool {
+        !self.is_loading() && !self.top_reached()
+    }
+
+    /// Whether newer items may be requested in this status
+    pub fn can_load_bottom(&self) -> bool {
+        !self.is_loading() && !self.bottom_reached()
+    }
+}
+
+/// Store mutation, recorded for time-travel debugging in debug builds
+#[derive(Debug, Clone, PartialEq)]
+pub enum StoreEvent {
+    Initialized { count: usize },
+    LoadedOlder { count: usize },
+    LoadedNewer { count: usize },
+    Polled { count: usize },
+    Refreshed { count: usize },
+    Evicted { count: usize },
+    Reset,
+    /// Items were reordered, hidden, pinned or deleted by hand
+    Edited,
+    /// The list moved its scroll position to keep items anchored
+    ScrollAnchor { scroll_top: f64 },
+}
+
+impl std::fmt::Display for StoreEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StoreEvent::Initialized { count } => write!(f, "Initialized with {} items", count),
+            StoreEvent::LoadedOlder { count } => write!(f, "Loaded {} older", count),
+            StoreEvent::LoadedNewer { count } => write!(f, "Loaded {} newer", count),
+            StoreEvent::Polled { count } => write!(f, "Polled {} new", count),
+            StoreEvent::Refreshed { count } => write!(f, "Refreshed {} new", count),
+            StoreEvent::Evicted { count } => write!(f, "Evicted {}", count),
+            StoreEvent::Reset => write!(f, "Reset"),
+            StoreEvent::Edited => write!(f, "Edited items"),
+            StoreEvent::ScrollAnchor { scroll_top } => write!(f, "Anchored scroll at {:.0}px", scroll_top),
+        }
+    }
+}
+
+/// What a store holds besides its items, for checking long sessions stay
+/// bounded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StoreFootprint {
+    pub items: usize,
+    /// Per-item state signals, created as rows render
+    pub item_states: usize,
+    /// Items still highlighted, each with an expiry pending
+    pub fresh: usize,
+    /// Older items waiting for prepends to resume
+    pub staged: usize,
+    /// Page requests in flight
+    pub in_flight: usize,
+}
+
+/// Feed edge a page request extends
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Edge {
+    Top,
+    Bottom,
+}
+
+/// Items evicted from one edge of a feed to keep it within its bounds.
+///
+/// Sources are told through [`FeedSource::evicted`], and lists with
+/// `max_items` call their `on_evict` handler, so callers know which range
+/// would have to be fetched again.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Eviction {
+    /// Edge the items left from; `Top` holds the oldest items
+    pub edge: Edge,
+    /// Sequences the items held
+    pub sequences: Range<Sequence>,
+    /// Ids of the evicted items, oldest first
+    pub ids: Vec<String>,
+}
+
+/// Identity of a page request: two requests with the same key would fetch
+/// the same page, so only one may be in flight at a time
+type RequestKey = (Edge, Option<String>);
+
+/// Marks a request as in flight until dropped, including when the awaiting
+/// task is cancelled
+struct InFlightGuard {
+    in_flight: CopyValue<HashSet<RequestKey>>,
+    key: RequestKey,
+    generation: CopyValue<u64>,
+    started_generation: u64,
+    status: Signal<FeedStatus>,
+    // Loading status set for this request and the status to restore if the
+    // task is cancelled before settling it
+    restore: Option<(FeedStatus, FeedStatus)>,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        // A reset already forgot this request, and a newer one may share its key
+        match self.generation.try_read() {
+            Ok(generation) if *generation == self.started_generation => {}
+            _ => return,
+        }
+
+        // The store may already be gone if its owner unmounted
+        if let Ok(mut in_flight) = self.in_flight.try_write() {
+            in_flight.remove(&self.key);
+        }
+
+        if let Some((loading, previous)) = self.restore.take() {
+            if let Ok(mut status) = self.status.try_write() {
+                if *status == loading {
+                    *status = previous;
+                }
+            }
+        }
+    }
+}
+
+/// Reactive state for a single feed: its items, status and the scroll
+/// position to restore when the feed is shown again.
+///
+/// Stores are `Copy` handles to signals owned by the scope that created them,
+/// so they outlive the `VirtualList` rendering them.
+#[derive(Clone, Copy, PartialEq)]
+pub struct FeedStore {
+    pub items: Signal<FeedItems>,
+    pub scroll_top: Signal<f64>,
+    /// Height of the viewport showing the feed, reported by the list
+    /// rendering it; 0 until a list has mounted
+    pub viewport_height: Signal<f64>,
+    /// Height of each row as laid out by the list rendering the feed
+    pub row_height: Signal<f64>,
+    pub status: Signal<FeedStatus>,
+    /// Order the items are shown in, see [`FeedStore::ordered_items`]
+    pub ranking: Signal<FeedRanking>,
+    pub ranking_config: Signal<RankingConfig>,
+    /// Items inserted by polls and refreshes, with the time they arrived
+    pub fresh_ids: Signal<HashMap<String, Instant>>,
+    // Items hidden and pinned by hand in a curated list, and the edits that
+    // can be undone
+    hidden_ids: Signal<HashSet<String>>,
+    pinned_ids: Signal<Vec<String>>,
+    edits: Signal<EditHistory>,
+    // Per-item fresh and pinned flags, each in its own signal for the row
+    item_states: ItemStates,
+    // Summaries generated on demand, kept while the store lives
+    summaries: Summaries,
+    // Sequence of the first item
+    first_seq: Signal<Sequence>,
+    // Sequence of the oldest item the source has, once it reported nothing older
+    beginning: Signal<Option<Sequence>>,
+    // Older items loaded while prepends were held, oldest first
+    staged_older: Signal<Vec<VirtualFeedItem>>,
+    prepends_held: Signal<bool>,
+    // Most items held, see `with_window`
+    window: Signal<Option<usize>>,
+    // Items requested per edge load
+    page_size: Signal<usize>,
+    source: Signal<Option<Rc<dyn FeedSource>>>,
+    clock: Signal<ClockHandle>,
+    // Registry syncs are reported to, with the name they are reported under
+    health: Signal<Option<(SourceHealth, String)>>,
+    // Rules incoming items are checked against
+    alerts: Signal<Option<Alerts>>,
+    // Saved items the store's items are the display order of; curated edits
+    // that move or remove items go through them to be persisted
+    bookmarks: Signal<Option<Bookmarks>>,
+    // Index loaded items are added to, with the feed key they are added under
+    search: Signal<Option<(SearchIndex, String)>>,
+    // Processors annotating loaded items before they are inserted
+    processors: Signal<Option<Processors>>,
+    // Filters deciding which items are shown, with the feed name items
+    // without sources are matched under
+    filters: Signal<Option<(ContentFilters, String)>>,
+    in_flight: CopyValue<HashSet<RequestKey>>,
+    // Bumped whenever the items are replaced wholesale, invalidating responses
+    // to requests made against the previous items
+    generation: CopyValue<u64>,
+    #[cfg(debug_assertions)]
+    recorder: recorder::Recorder,
+}
+
+impl FeedStore {
+    /// Create a store backed by a source
+    pub fn new(items: Vec<VirtualFeedItem>, source: impl FeedSource + 'static) -> Self {
+        Self::with_items(Signal::new(items.into()), Some(Rc::new(source)))
+    }
+
+    /// Create a store over existing items that never loads more
+    pub fn from_items(items: Signal<FeedItems>) -> Self {
+        Self::with_items(items, None)
+    }
+
+    fn with_items(items: Signal<FeedItems>, source: Option<Rc<dyn FeedSource>>) -> Self {
+        let status = match &source {
+            // Without a source there is nothing to load at either edge
+            None => FeedStatus::EndReached { top: true, bottom: true },
+            Some(_) if items.peek().is_empty() => FeedStatus::Initializing,
+            Some(_) => FeedStatus::Idle,
+        };
+
+        Self {
+            items,
+            scroll_top: Signal::new(0.0),
+            viewport_height: Signal::new(0.0),
+            row_height: Signal::new(ITEM_HEIGHT),
+            status: Signal::new(status),
+            ranking: Signal::new(FeedRanking::default()),
+            ranking_config: Signal::new(RankingConfig::default()),
+            fresh_ids: Signal::new(HashMap::new()),
+            hidden_ids: Signal::new(HashSet::new()),
+            pinned_ids: Signal::new(Vec::new()),
+            edits: Signal::new(EditHistory::default()),
+            item_states: ItemStates::new(),
+            summaries: Summaries::new(),
+            first_seq: Signal::new(0),
+            beginning: Signal::new(None),
+            staged_older: Signal::new(Vec::new()),
+            prepends_held: Signal::new(false),
+            window: Signal::new(None),
+            page_size: Signal::new(ITEMS_PER_LOAD),
+            source: Signal::new(source),
+            clock: Signal::new(ClockHandle::default()),
+            health: Signal::new(None),
+            alerts: Signal::new(None),
+            bookmarks: Signal::new(None),
+            search: Signal::new(None),
+            processors: Signal::new(None),
+            filters: Signal::new(None),
+            in_flight: CopyValue::new(HashSet::new()),
+            generation: CopyValue::new(0),
+            #[cfg(debug_assertions)]
+            recorder: recorder::Recorder::new(),
+        }
+    }
+
+    /// Use `clock` for highlight expiry and snapshot timestamps
+    pub fn with_clock(mut self, clock: ClockHandle) -> Self {
+        self.clock.set(clock);
+        self
+    }
+
+    /// Request pages of `size` items from the source instead of
+    /// [`ITEMS_PER_LOAD`]
+    pub fn with_page_size(mut self, size: usize) -> Self {
+        self.page_size.set(size.max(1));
+        self
+    }
+
+    /// Hold at most `window` items. Loading older items evicts the newest
+    /// past the window and loading newer ones the oldest, reopening that
+    /// edge, so memory stays constant however far the feed is scrolled and
+    /// evicted items are loaded again when scrolled back to. Polls and
+    /// refreshes don't evict.
+    pub fn with_window(mut self, window: usize) -> Self {
+        self.window.set(Some(window.max(1)));
+        self
+    }
+
+    /// Check items arriving through polls and refreshes against `alerts`
+    pub fn with_alerts(mut self, alerts: Alerts) -> Self {
+        self.alerts.set(Some(alerts));
+        self
+    }
+
+    /// Curate the saved items of `bookmarks`, which must be the items the
+    /// store was created over: moves and deletes are applied to them and
+    /// persisted
+    pub fn with_bookmarks(mut self, bookmarks: Bookmarks) -> Self {
+        self.bookmarks.set(Some(bookmarks));
+        self
+    }
+
+    /// Add every item loaded from the source to `index` under the feed key `feed`
+    pub fn with_search(mut self, index: SearchIndex, feed: impl Into<String>) -> Self {
+        self.search.set(Some((index, feed.into())));
+        let items: Vec<VirtualFeedItem> = self.items.peek().iter().cloned().collect();
+        self.index_items(&items);
+        self
+    }
+
+    /// Annotate the items and every page loaded from the source with `processors`
+    pub fn with_processors(mut self, mut processors: Processors) -> Self {
+        processors.annotate(self.items.write().iter_mut());
+        self.processors.set(Some(processors));
+        self
+    }
+
+    /// Leave the items the active profile of `filters` doesn't allow out of
+    /// the shown items and alerts; `name` is the feed items without sources
+    /// are taken to come from
+    pub fn with_filters(mut self, filters: ContentFilters, name: impl Into<String>) -> Self {
+        self.filters.set(Some((filters, name.into())));
+        self
+    }
+
+    /// Report every sync with the source to `health` under `name`
+    pub fn with_health(mut self, health: SourceHealth, name: impl Into<String>) -> Self {
+        self.health.set(Some((health, name.into())));
+        self
+    }
+
+    pub fn source(&self) -> Option<Rc<dyn FeedSource>> {
+        self.source.read().clone()
+    }
+
+    pub fn clock(&self) -> ClockHandle {
+        self.clock.peek().clone()
+    }
+
+    /// Load the first page of an initializing feed, returning how many items arrived
+    pub async fn initialize(mut self) -> usize {
+        let Some(source) = self.source() else {
+            return 0;
+        };
+        if *self.status.read() != FeedStatus::Initializing {
+            return 0;
+        }
+        let Some(_guard) = self.begin_request(Edge::Bottom, None, None) else {
+            return 0;
+        };
+
+        let generation = *self.generation.read();
+        let result = source.load_newer(self.page_request(None)).await;
+        self.report_health(&result);
+        if *self.generation.read() != generation {
+            return 0;
+        }
+        let result = self.annotate(result);
+
+        match result {
+            Ok(new_items) => {
+                let count = new_items.len();
+                self.index_items(&new_items);
+                self.items.write().append(new_items.into());
+                self.status.set(FeedStatus::settled(count == 0, count == 0));
+                self.record(StoreEvent::Initialized { count });
+                count
+            }
+            Err(e) => {
+                eprintln!("Failed to load feed: {}", e);
+                self.status.set(FeedStatus::Error(e));
+                0
+            }
+        }
+    }
+
+    /// Load older items from the source, returning how many were prepended
+    pub async fn load_older(mut self) -> usize {
+        let Some(source) = self.source() else {
+            return 0;
+        };
+        let previous = self.status.read().clone();
+        if !previous.can_load_top() || self.at_beginning() || !self.staged_older.peek().is_empty() {
+            return 0;
+        }
+
+        let cursor = self.items.read().front().map(|item| item.id.clone());
+        let restore = (FeedStatus::LoadingTop, previous.clone());
+        let Some(_guard) = self.begin_request(Edge::Top, cursor.clone(), Some(restore)) else {
+            return 0;
+        };
+
+        self.status.set(FeedStatus::LoadingTop);
+        let generation = *self.generation.read();
+        let result = source.load_older(self.page_request(cursor)).await;
+        self.report_health(&result);
+        if *self.generation.read() != generation {
+            return 0;
+        }
+        let result = self.annotate(result);
+
+        match result {
+            Ok(new_items) => {
+                let count = new_items.len();
+                // An empty page means there is nothing older
+                if count == 0 {
+                    self.beginning.set(Some(self.first_sequence()));
+                }
+                self.status.set(FeedStatus::settled(count == 0, previous.bottom_reached()));
+                self.index_items(&new_items);
+                if count > 0 && (*self.prepends_held.peek() || count > PREPEND_CHUNK_SIZE) {
+                    self.staged_older.set(new_items);
+                    return 0;
+                }
+                if count > 0 {
+                    self.transaction(|tx| tx.prepend(new_items));
+                }
+                self.record(StoreEvent::LoadedOlder { count });
+                self.trim_window(Edge::Top);
+                count
+            }
+            Err(e) => {
+                eprintln!("Failed to load older items: {}", e);
+                self.status.set(FeedStatus::Error(e));
+                0
+            }
+        }
+    }
+
+    /// Hold older items back instead of prepending them, e.g. while the list
+    /// is flung upward and rows shifting under the viewport would hitch.
+    /// Items loaded meanwhile are staged until [`FeedStore::prepend_staged`],
+    /// like pages of more than [`PREPEND_CHUNK_SIZE`] items always are, and
+    /// no further older items are loaded before that.
+    pub fn hold_prepends(&mut self, hold: bool) {
+        if *self.prepends_held.peek() != hold {
+            self.prepends_held.set(hold);
+        }
+    }
+
+    /// Number of older items waiting to be prepended
+    pub fn staged_count(&self) -> usize {
+        self.staged_older.read().len()
+    }
+
+    /// Prepend up to `limit` of the staged older items, the newest first so
+    /// history grows outward from the rows in view, returning how many were
+    /// prepended. Nothing is prepended while prepends are held.
+    pub fn prepend_staged(&mut self, limit: usize) -> usize {
+        if self.staged_older.peek().is_empty() || *self.prepends_held.peek() {
+            return 0;
+        }
+        let chunk = {
+            let mut staged = self.staged_older.write();
+            let split = staged.len().saturating_sub(limit);
+            staged.split_off(split)
+        };
+        let count = chunk.len();
+        self.transaction(|tx| tx.prepend(chunk));
+        self.record(StoreEvent::LoadedOlder { count });
+        self.trim_window(Edge::Top);
+        count
+    }
+
+    /// Load newer items from the source, returning how many were appended
+    pub async fn load_newer(mut self) -> usize {
+        let Some(source) = self.source() else {
+            return 0;
+        };
+        let previous = self.status.read().clone();
+        if !previous.can_load_bottom() {
+            return 0;
+        }
+
+        let cursor = self.items.read().back().map(|item| item.id.clone());
+        let restore = (FeedStatus::LoadingBottom, previous.clone());
+        let Some(_guard) = self.begin_request(Edge::Bottom, cursor.clone(), Some(restore)) else {
+            return 0;
+        };
+
+        self.status.set(FeedStatus::LoadingBottom);
+        let generation = *self.generation.read();
+        let result = source.load_newer(self.page_request(cursor)).await;
+        self.report_health(&result);
+        if *self.generation.read() != generation {
+            return 0;
+        }
+        let result = self.annotate(result);
+
+        match result {
+            Ok(page) => {
+                let new_items = self.merge_page(page);
+                let count = new_items.len();
+                if count > 0 {
+                    self.index_items(&new_items);
+                }
+                // An empty page means there is nothing newer
+                self.status.set(FeedStatus::settled(previous.top_reached(), count == 0));
+                self.record(StoreEvent::LoadedNewer { count });
+                self.trim_window(Edge::Bottom);
+                count
+            }
+            Err(e) => {
+                eprintln!("Failed to load newer items: {}", e);
+                self.status.set(FeedStatus::Error(e));
+                0
+            }
+        }
+    }
+
+    /// Poll the source for new items, returning how many were appended.
+    /// Appended items are marked fresh. An empty delta writes nothing, so
+    /// idle polls don't re-render the list.
+    pub async fn poll(mut self) -> usize {
+        let Some(source) = self.source() else {
+            return 0;
+        };
+        if self.status.read().is_loading() {
+            return 0;
+        }
+        // Back off while the source has asked not to be called
+        if self.is_rate_limited() {
+            return 0;
+        }
+        // Polling would overwrite the state being inspected
+        #[cfg(debug_assertions)]
+        if self.recorder.is_travelling() {
+            return 0;
+        }
+
+        // Polls extend the same edge as bottom loads, so they share its key
+        let cursor = self.items.read().back().map(|item| item.id.clone());
+        let Some(_guard) = self.begin_request(Edge::Bottom, cursor.clone(), None) else {
+            return 0;
+        };
+
+        let generation = *self.generation.read();
+        let result = source.poll(self.page_request(cursor)).await;
+        self.report_health(&result);
+        if *self.generation.read() != generation {
+            return 0;
+        }
+        let result = self.annotate(result);
+
+        match result {
+            Ok(page) if page.is_empty() => 0,
+            Ok(page) => {
+                let new_items = self.merge_page(page);
+                let count = new_items.len();
+                if count > 0 {
+                    self.mark_fresh(&new_items);
+                    self.check_alerts(&new_items);
+                    self.index_items(&new_items);
+
+                    // New arrivals mean the bottom edge is open again
+                    let status = self.status.read().clone();
+                    if status.bottom_reached() {
+                        self.status.set(FeedStatus::settled(status.top_reached(), false));
+                    }
+                    self.record(StoreEvent::Polled { count });
+                }
+                count
+            }
+            Err(e) => {
+                eprintln!("Failed to poll for new items: {}", e);
+                0
+            }
+        }
+    }
+
+    /// Fetch the newest page while the current items stay on screen
+    /// (stale-while-revalidate), returning how many new items were inserted.
+    ///
+    /// Items already in the feed are merged into their rows, and the rest are
+    /// inserted at the newest edge and marked fresh.
+    pub async fn refresh(mut self) -> usize {
+        let Some(source) = self.source() else {
+            return 0;
+        };
+        let previous = self.status.read().clone();
+        if previous.is_loading() {
+            return 0;
+        }
+
+        let cursor = self.items.read().back().map(|item| item.id.clone());
+        let restore = (FeedStatus::Refreshing, previous.clone());
+        let Some(_guard) = self.begin_request(Edge::Bottom, cursor.clone(), Some(restore)) else {
+            return 0;
+        };
+
+        self.status.set(FeedStatus::Refreshing);
+        let generation = *self.generation.read();
+        let result = source.refresh(self.page_request(cursor)).await;
+        self.report_health(&result);
+        if *self.generation.read() != generation {
+            return 0;
+        }
+        let result = self.annotate(result);
+
+        match result {
+            Ok(page) => {
+                let new_items = self.merge_page(page);
+
+                let count = new_items.len();
+                if count > 0 {
+                    self.mark_fresh(&new_items);
+                    self.check_alerts(&new_items);
+                    self.index_items(&new_items);
+                }
+
+                let status = match previous {
+                    FeedStatus::Error(_) => FeedStatus::Idle,
+                    FeedStatus::EndReached { top, .. } => FeedStatus::settled(top, false),
+                    other => other,
+                };
+                self.status.set(status);
+                self.record(StoreEvent::Refreshed { count });
+                count
+            }
+            Err(e) => {
+                eprintln!("Failed to refresh feed: {}", e);
+                self.status.set(FeedStatus::Error(e));
+                0
+            }
+        }
+    }
+
+    /// Items in the order the feed is shown: as loaded for `Latest`, by score
+    /// for `Top`, with pinned items first and hidden and filtered ones left
+    /// out. Loads and cursors always work on the loaded order.
+    pub fn ordered_items(&self) -> FeedItems {
+        let mut ranked = match *self.ranking.read() {
+            FeedRanking::Latest => self.items.read().clone(),
+            FeedRanking::Top => self.ranking_config.read().rank(&self.items.read()),
+        };
+        if let Some((filters, feed)) = &*self.filters.read() {
+            let profile = filters.active();
+            if !profile.is_open() {
+                ranked.retain(|item| profile.allows(item, feed));
+            }
+        }
+        let hidden = self.hidden_ids.read();
+        let pinned = self.pinned_ids.read();
+        if hidden.is_empty() && pinned.is_empty() {
+            return ranked;
+        }
+        let (mut shown, unpinned): (FeedItems, FeedItems) = ranked
+            .into_iter()
+            .filter(|item| !hidden.contains(&item.id))
+            .partition(|item| pinned.contains(&item.id));
+        shown.append(unpinned);
+        shown
+    }
+
+    /// Shown items by `author`, newest first. Items without a publication
+    /// time come last, in the order they are shown.
+    pub fn items_by(&self, author: &str) -> Vec<VirtualFeedItem> {
+        let mut items: Vec<VirtualFeedItem> = self
+            .ordered_items()
+            .into_iter()
+            .filter(|item| item.author.as_deref() == Some(author))
+            .collect();
+        items.sort_by_key(|item| std::cmp::Reverse(item.published_at));
+        items
+    }
+
+    pub fn is_pinned(&self, id: &str) -> bool {
+        self.pinned_ids.read().iter().any(|pinned| pinned == id)
+    }
+
+    /// Signal with the fresh and pinned state of the item with `id`. Rows
+    /// read it rather than the store, so changing one item's state only
+    /// re-renders its row.
+    pub fn item_state(&self, id: &str) -> Signal<ItemState> {
+        self.item_states.get(id)
+    }
+
+    /// Signal with the cached summary of the item with `id`, read by its row
+    pub fn summary(&self, id: &str) -> Signal<Summary> {
+        self.summaries.item(id)
+    }
+
+    /// Signal with the summary of the newest items taken together
+    pub fn digest(&self) -> Signal<Summary> {
+        self.summaries.digest()
+    }
+
+    /// Summarize the item with `id` unless its summary is cached or underway
+    pub async fn summarize_item(self, summarizer: SummarizerHandle, id: String) {
+        let content = self.items.peek().iter().find(|item| item.id == id).map(|item| item.content.clone());
+        if let Some(content) = content {
+            summaries::stream(summarizer, content, self.summaries.item(&id)).await;
+        }
+    }
+
+    /// Summarize the newest items together, replacing the previous digest
+    pub async fn summarize_digest(self, summarizer: SummarizerHandle) {
+        let mut digest = self.summaries.digest();
+        if digest.peek().status == SummaryStatus::Streaming {
+            return;
+        }
+        let text = self
+            .items
+            .peek()
+            .iter()
+            .rev()
+            .take(DIGEST_ITEMS)
+            .map(|item| match &item.author {
+                Some(author) => format!("- {}: {}", author, item.content),
+                None => format!("- {}", item.content),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        if text.is_empty() {
+            return;
+        }
+        digest.set(Summary::default());
+        summaries::stream(summarizer, text, digest).await;
+    }
+
+    /// Dismiss the digest
+    pub fn clear_digest(&self) {
+        let mut digest = self.summaries.digest();
+        digest.set(Summary::default());
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.edits.read().can_undo()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.edits.read().can_redo()
+    }
+
+    /// Apply a curation edit to the item with `id`, keeping it for undo.
+    /// Moves swap the item with its neighbour in the shown order, stepping
+    /// over hidden and filtered items.
+    pub fn edit(&mut self, id: &str, action: EditAction) {
+        let command = {
+            let items = self.items.peek();
+            let Some(index) = items.iter().position(|item| item.id == id) else {
+                return;
+            };
+            match action {
+                EditAction::MoveUp | EditAction::MoveDown => {
+                    let Some(to) = self.move_target(id, action) else {
+                        return;
+                    };
+                    EditCommand::Move { id: id.to_string(), from: index, to }
+                }
+                EditAction::Hide => EditCommand::Hide { id: id.to_string() },
+                EditAction::TogglePin if self.is_pinned(id) => EditCommand::Unpin { id: id.to_string() },
+                EditAction::TogglePin => EditCommand::Pin { id: id.to_string() },
+                EditAction::Delete => EditCommand::Delete { index, item: items[index].clone() },
+            }
+        };
+        self.apply_edit(&command);
+        self.edits.write().push(command);
+    }
+
+    // Loaded index the item with `id` moves to for it to trade places with
+    // the item shown before or after it. Pinned items only trade places with
+    // each other, as do the rest, and nothing moves while the ranking orders
+    // the items.
+    fn move_target(&self, id: &str, action: EditAction) -> Option<usize> {
+        if *self.ranking.peek() == FeedRanking::Top {
+            return None;
+        }
+        let shown = self.ordered_items();
+        let position = shown.iter().position(|item| item.id == id)?;
+        let neighbour = match action {
+            EditAction::MoveUp => shown.get(position.checked_sub(1)?)?,
+            _ => shown.get(position + 1)?,
+        };
+        if self.is_pinned(id) != self.is_pinned(&neighbour.id) {
+            return None;
+        }
+        self.items.peek().iter().position(|item| item.id == neighbour.id)
+    }
+
+    /// Revert the last edit, returning whether there was one
+    pub fn undo(&mut self) -> bool {
+        let Some(command) = self.edits.write().undo() else {
+            return false;
+        };
+        self.apply_edit(&command.inverse());
+        true
+    }
+
+    /// Apply the last undone edit again, returning whether there was one
+    pub fn redo(&mut self) -> bool {
+        let Some(command) = self.edits.write().redo() else {
+            return false;
+        };
+        self.apply_edit(&command);
+        true
+    }
+
+    // Carry out an edit. Items are found by id where they may have moved
+    // since the edit was made, e.g. by a load at the top.
+    fn apply_edit(&mut self, command: &EditCommand) {
+        if self.edit_bookmarks(command) {
+            self.record(StoreEvent::Edited);
+            return;
+        }
+        match command {
+            EditCommand::Move { id, to, .. } => {
+                let mut items = self.items.write();
+                if let Some(index) = items.iter().position(|item| &item.id == id) {
+                    let item = items.remove(index);
+                    let to = (*to).min(items.len());
+                    items.insert(to, item);
+                }
+            }
+            EditCommand::Hide { id } => {
+                self.hidden_ids.write().insert(id.clone());
+            }
+            EditCommand::Unhide { id } => {
+                self.hidden_ids.write().remove(id);
+            }
+            EditCommand::Pin { id } => {
+                self.pinned_ids.write().push(id.clone());
+                self.item_states.update(id, |state| state.pinned = true);
+            }
+            EditCommand::Unpin { id } => {
+                self.pinned_ids.write().retain(|pinned| pinned != id);
+                self.item_states.update(id, |state| state.pinned = false);
+            }
+            EditCommand::Delete { item, .. } => self.items.write().retain(|existing| existing.id != item.id),
+            EditCommand::Insert { index, item } => {
+                let mut items = self.items.write();
+                let index = (*index).min(items.len());
+                items.insert(index, item.clone());
+            }
+        }
+        self.record(StoreEvent::Edited);
+    }
+
+    // Move or remove saved items through the bookmarks, which persist the
+    // change and write the items back. Returns whether `command` was one.
+    fn edit_bookmarks(&self, command: &EditCommand) -> bool {
+        let Some(mut bookmarks) = *self.bookmarks.peek() else {
+            return false;
+        };
+        match command {
+            EditCommand::Move { id, to, .. } => bookmarks.reorder(id, *to),
+            EditCommand::Delete { item, .. } => bookmarks.delete(&item.id),
+            EditCommand::Insert { index, item } => bookmarks.restore(*index, item.clone()),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Apply several mutations (inserts, evictions, updates) as one write,
+    /// so the list renders and lays out once for the whole batch rather than
+    /// once per mutation. Nothing is written if the batch changed nothing.
+    ///
+    /// ```ignore
+    /// store.transaction(|tx| {
+    ///     tx.append(page);
+    ///     tx.evict_front(evicted);
+    ///     tx.update(&id, |item| item.engagement = Some(likes));
+    /// });
+    /// ```
+    ///
+    /// Transactions aren't recorded; call [`FeedStore::record`] after one
+    /// to snapshot it.
+    pub fn transaction<R>(&mut self, mutate: impl FnOnce(&mut StoreTransaction) -> R) -> R {
+        let mut tx = StoreTransaction::new(self.items.peek().clone(), *self.first_seq.peek());
+        let result = mutate(&mut tx);
+        if let Some((items, first_seq, removed)) = tx.finish() {
+            // Expiring the highlight of a removed item would recreate its state
+            let fresh_ids = self.fresh_ids.peek();
+            let fresh_removed = removed.iter().any(|id| fresh_ids.contains_key(id));
+            drop(fresh_ids);
+            if fresh_removed {
+                self.fresh_ids.write().retain(|id, _| !removed.contains(id));
+            }
+            for id in &removed {
+                self.item_states.remove(id);
+                self.summaries.remove(id);
+            }
+            self.items.set(items);
+            if first_seq != *self.first_seq.peek() {
+                self.first_seq.set(first_seq);
+            }
+        }
+        result
+    }
+
+    /// Sequence of the first item in the feed
+    pub fn first_sequence(&self) -> Sequence {
+        *self.first_seq.read()
+    }
+
+    /// Whether the oldest item the source has is loaded. Unlike the status's
+    /// top edge, this holds through errors and refreshes until that item is
+    /// evicted or the feed is reset.
+    pub fn at_beginning(&self) -> bool {
+        self.beginning.read().is_some_and(|beginning| self.first_sequence() <= beginning)
+    }
+
+    /// Whether older items may be requested now
+    pub fn can_load_top(&self) -> bool {
+        self.status.read().can_load_top() && !self.at_beginning() && self.staged_older.read().is_empty()
+    }
+
+    /// Sequence of the item currently at `index`
+    pub fn sequence_at(&self, index: usize) -> Sequence {
+        self.first_sequence() + index as Sequence
+    }
+
+    /// Current index of the item with sequence `seq`, if it is still loaded
+    pub fn index_of(&self, seq: Sequence) -> Option<usize> {
+        let index = usize::try_from(seq - self.first_sequence()).ok()?;
+        (index < self.items.read().len()).then_some(index)
+    }
+
+    /// Loaded indices spanning the items with `ids`, or an empty range if
+    /// none is loaded. Lists address rows in the shown order, which ranking,
+    /// filters, pins and hidden items make differ from the loaded one; this
+    /// maps them to the indices [`FeedStore::evict_outside`] keeps.
+    pub fn loaded_span<'a>(&self, ids: impl IntoIterator<Item = &'a str>) -> Range<usize> {
+        let ids: HashSet<&str> = ids.into_iter().collect();
+        let items = self.items.peek();
+        let mut found = items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| ids.contains(item.id.as_str()))
+            .map(|(index, _)| index);
+        let Some(first) = found.next() else {
+            return 0..0;
+        };
+        let last = found.last().unwrap_or(first);
+        first..last + 1
+    }
+
+    /// Whether an item arrived through a poll or refresh within the
+    /// highlight duration
+    pub fn is_fresh(&self, id: &str) -> bool {
+        let now = self.clock().now();
+        self.fresh_ids
+            .read()
+            .get(id)
+            .is_some_and(|inserted| now.duration_since(*inserted) < FRESH_HIGHLIGHT_DURATION)
+    }
+
+    /// Forget items whose highlight has ended
+    pub fn expire_fresh(&mut self) {
+        let now = self.clock().now();
+        let expired: Vec<String> = self
+            .fresh_ids
+            .peek()
+            .iter()
+            .filter(|(_, inserted)| now.duration_since(**inserted) >= FRESH_HIGHLIGHT_DURATION)
+            .map(|(id, _)| id.clone())
+            .collect();
+        if expired.is_empty() {
+            return;
+        }
+        {
+            let mut fresh_ids = self.fresh_ids.write();
+            for id in &expired {
+                fresh_ids.remove(id);
+            }
+        }
+        for id in &expired {
+            self.item_states.update(id, |state| state.fresh = false);
+        }
+    }
+
+    // Record newly inserted items as fresh
+    fn mark_fresh(&mut self, new_items: &[VirtualFeedItem]) {
+        self.expire_fresh();
+        let now = self.clock().now();
+        self.fresh_ids
+            .write()
+            .extend(new_items.iter().map(|item| (item.id.clone(), now)));
+        for item in new_items {
+            self.item_states.update(&item.id, |state| state.fresh = true);
+        }
+    }
+
+    /// Count what the store holds, without subscribing to it
+    pub fn footprint(&self) -> StoreFootprint {
+        StoreFootprint {
+            items: self.items.peek().len(),
+            item_states: self.item_states.len(),
+            fresh: self.fresh_ids.peek().len(),
+            staged: self.staged_older.peek().len(),
+            in_flight: self.in_flight.peek().len(),
+        }
+    }
+
+    /// Return an errored feed to idle so loads can be retried
+    pub fn clear_error(&mut self) {
+        if matches!(*self.status.peek(), FeedStatus::Error(_)) {
+            self.status.set(FeedStatus::Idle);
+        }
+    }
+
+    /// Replace all items, discarding the responses of requests still in flight
+    pub fn reset(&mut self, items: Vec<VirtualFeedItem>) {
+        *self.generation.write() += 1;
+        self.in_flight.write().clear();
+        self.items.set(items.into());
+        self.first_seq.set(0);
+        self.beginning.set(None);
+        self.staged_older.write().clear();
+        self.status.set(FeedStatus::Idle);
+        // Edits refer to items that are gone
+        self.hidden_ids.write().clear();
+        self.pinned_ids.write().clear();
+        self.edits.write().clear();
+        self.fresh_ids.write().clear();
+        self.item_states.clear();
+        self.record(StoreEvent::Reset);
+    }
+
+    /// Snapshot the store after a mutation. Only debug builds keep snapshots.
+    pub fn record(&mut self, event: StoreEvent) {
+        #[cfg(debug_assertions)]
+        {
+            let snapshot = recorder::Snapshot {
+                event,
+                recorded_at: self.clock().utc_now(),
+                items: self.items.peek().clone(),
+                first_seq: *self.first_seq.peek(),
+                status: self.status.peek().clone(),
+                scroll_top: *self.scroll_top.peek(),
+            };
+            self.recorder.record(snapshot);
+        }
+        #[cfg(not(debug_assertions))]
+        let _ = event;
+    }
+
+    #[cfg(debug_assertions)]
+    pub fn recorder(&self) -> recorder::Recorder {
+        self.recorder
+    }
+
+    /// Show the recorded snapshot at `index`, returning the scroll position
+    /// it was taken at. Requests still in flight are discarded.
+    #[cfg(debug_assertions)]
+    pub fn time_travel(&mut self, index: usize) -> Option<f64> {
+        let snapshot = self.recorder.select(index)?;
+        *self.generation.write() += 1;
+        self.in_flight.write().clear();
+        self.items.set(snapshot.items);
+        self.first_seq.set(snapshot.first_seq);
+        self.status.set(snapshot.status);
+        self.scroll_top.set(snapshot.scroll_top);
+        Some(snapshot.scroll_top)
+    }
+
+    // Register a request, or `None` if the same request is already in flight
+    // and this one should be coalesced into it. Loads that set a loading
+    // status pass it with the status to fall back to if they are cancelled.
+    fn begin_request(
+        &mut self,
+        edge: Edge,
+        cursor: Option<String>,
+        restore: Option<(FeedStatus, FeedStatus)>,
+    ) -> Option<InFlightGuard> {
+        let key = (edge, cursor);
+        if !self.in_flight.write().insert(key.clone()) {
+            return None;
+        }
+
+        Some(InFlightGuard {
+            in_flight: self.in_flight,
+            key,
+            generation: self.generation,
+            started_generation: *self.generation.read(),
+            status: self.status,
+            restore,
+        })
+    }
+
+    // Merge a page into the feed in one write: copies of items the feed
+    // already has are merged into their rows and the rest are appended.
+    // Returns the appended items.
+    fn merge_page(&mut self, page: Vec<VirtualFeedItem>) -> Vec<VirtualFeedItem> {
+        self.transaction(|tx| {
+            let new_items = tx.merge_known(page);
+            tx.append(new_items.clone());
+            new_items
+        })
+    }
+
+    // Run the processors over a loaded page
+    fn annotate(&self, result: Result<Vec<VirtualFeedItem>, SourceError>) -> Result<Vec<VirtualFeedItem>, SourceError> {
+        let Some(mut processors) = *self.processors.peek() else {
+            return result;
+        };
+        result.map(|mut page| {
+            processors.annotate(page.iter_mut());
+            page
+        })
+    }
+
+    // Add loaded items to the search index, in the background
+    fn index_items(&self, items: &[VirtualFeedItem]) {
+        if let Some((mut index, feed)) = self.search.peek().clone() {
+            let items = items.to_vec();
+            spawn(async move { index.add(feed, items).await });
+        }
+    }
+
+    // Raise alerts for incoming items matching the user's rules
+    fn check_alerts(&self, incoming: &[VirtualFeedItem]) {
+        let Some(mut alerts) = *self.alerts.peek() else {
+            return;
+        };
+        match &*self.filters.peek() {
+            Some((filters, feed)) => {
+                let profile = filters.active();
+                let allowed: Vec<VirtualFeedItem> =
+                    incoming.iter().filter(|item| profile.allows(item, feed)).cloned().collect();
+                alerts.check(&allowed);
+            }
+            None => alerts.check(incoming),
+        }
+    }
+
+    // Whether the health registry has the source marked as rate limited
+    fn is_rate_limited(&self) -> bool {
+        let Some((health, name)) = self.health.peek().clone() else {
+            return false;
+        };
+        health.stats(&name).is_some_and(|stats| stats.is_rate_limited(self.clock().utc_now()))
+    }
+
+    // Record the outcome of a source request in the health registry
+    fn report_health(&self, result: &Result<Vec<VirtualFeedItem>, SourceError>) {
+        if let Some((mut health, name)) = self.health.peek().clone() {
+            health.record(&name, result, self.clock().utc_now());
+        }
+    }
+
+    fn page_request(&self, cursor: Option<String>) -> PageRequest {
+        PageRequest {
+            cursor,
+            loaded: self.items.read().len(),
+            limit: *self.page_size.peek(),
+        }
+    }
+
+    // Evict the items past the window from the edge opposite the one
+    // `loaded` extended
+    fn trim_window(&mut self, loaded: Edge) {
+        let Some(window) = *self.window.peek() else {
+            return;
+        };
+        let excess = self.items.peek().len().saturating_sub(window);
+        let edge = match loaded {
+            Edge::Top => Edge::Bottom,
+            Edge::Bottom => Edge::Top,
+        };
+        self.evict(edge, excess);
+    }
+
+    /// Evict the items past `max_items`, leaving the items at the loaded
+    /// indices in `keep` (the rows in and around the viewport, mapped with
+    /// [`FeedStore::loaded_span`]) loaded. The edge with
+    /// more items outside `keep` goes first. An edge with a load in flight,
+    /// or older items staged, is left alone so its page can't land past a
+    /// gap; the next call catches up.
+    pub fn evict_outside(&mut self, max_items: usize, keep: Range<usize>) -> Vec<Eviction> {
+        let len = self.items.peek().len();
+        let mut excess = len.saturating_sub(max_items);
+        if excess == 0 {
+            return Vec::new();
+        }
+        let above = keep.start.min(len);
+        let below = len.saturating_sub(keep.end);
+        let mut edges = [(Edge::Top, above), (Edge::Bottom, below)];
+        if below > above {
+            edges.reverse();
+        }
+
+        let mut evictions = Vec::new();
+        for (edge, outside) in edges {
+            if excess == 0 || self.edge_busy(edge) {
+                continue;
+            }
+            if let Some(eviction) = self.evict(edge, excess.min(outside)) {
+                excess -= eviction.ids.len();
+                evictions.push(eviction);
+            }
+        }
+        evictions
+    }
+
+    // Whether items are about to be inserted at `edge`
+    fn edge_busy(&self, edge: Edge) -> bool {
+        self.in_flight.peek().iter().any(|(key_edge, _)| *key_edge == edge)
+            || (edge == Edge::Top && !self.staged_older.peek().is_empty())
+    }
+
+    // Evict `count` items from `edge`, open that edge for loads again and
+    // tell the source
+    fn evict(&mut self, edge: Edge, count: usize) -> Option<Eviction> {
+        let (start, ids) = {
+            let items = self.items.peek();
+            let count = count.min(items.len());
+            if count == 0 {
+                return None;
+            }
+            let start = match edge {
+                Edge::Top => 0,
+                Edge::Bottom => items.len() - count,
+            };
+            let ids: Vec<String> = items.iter().skip(start).take(count).map(|item| item.id.clone()).collect();
+            (start, ids)
+        };
+        let first = *self.first_seq.peek() + start as Sequence;
+        let count = self.transaction(|tx| match edge {
+            Edge::Top => tx.evict_front(ids.len()),
+            Edge::Bottom => tx.evict_back(ids.len()),
+        });
+
+        let status = self.status.peek().clone();
+        if !status.is_loading() && !matches!(status, FeedStatus::Error(_)) {
+            self.status.set(match edge {
+                Edge::Top => FeedStatus::settled(false, status.bottom_reached()),
+                Edge::Bottom => FeedStatus::settled(status.top_reached(), false),
+            });
+        }
+        self.record(StoreEvent::Evicted { count });
+
+        let eviction = Eviction {
+            edge,
+            sequences: first..first + count as Sequence,
+            ids,
+        };
+        if let Some(source) = self.source() {
+            source.evicted(&eviction);
+        }
+        Some(eviction)
+    }
+}
+
+/// Run `test` in the root scope of a VirtualDom, which owns the signals of
+/// the stores it creates
+#[cfg(test)]
+pub(crate) fn in_dom(test: impl FnOnce()) {
+    let dom = VirtualDom::new(|| rsx! {});
+    dom.in_scope(ScopeId::ROOT, test);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::SourceFuture;
+    use std::cell::RefCell;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::{Context, Poll, Waker};
+
+    fn items(count: usize) -> Vec<VirtualFeedItem> {
+        (0..count)
+            .map(|i| VirtualFeedItem::new(i.to_string(), format!("Item {}", i), ""))
+            .collect()
+    }
+
+    fn loaded_ids(store: &FeedStore) -> Vec<String> {
+        store.items.peek().iter().map(|item| item.id.clone()).collect()
+    }
+
+    // Source whose pages arrive when the test releases them
+    #[derive(Clone, Default)]
+    struct HeldSource {
+        page: Rc<RefCell<Option<Vec<VirtualFeedItem>>>>,
+    }
+
+    impl HeldSource {
+        fn release(&self, page: Vec<VirtualFeedItem>) {
+            *self.page.borrow_mut() = Some(page);
+        }
+
+        fn next_page(&self) -> SourceFuture {
+            let page = self.page.clone();
+            Box::pin(std::future::poll_fn(move |_| match page.borrow_mut().take() {
+                Some(page) => Poll::Ready(Ok(page)),
+                None => Poll::Pending,
+            }))
+        }
+    }
+
+    impl FeedSource for HeldSource {
+        fn load_older(&self, _request: PageRequest) -> SourceFuture {
+            self.next_page()
+        }
+
+        fn load_newer(&self, _request: PageRequest) -> SourceFuture {
+            self.next_page()
+        }
+    }
+
+    // Poll `future` once in the root scope of `dom`, which owns the signals
+    // it works on
+    fn poll_in<F: Future>(dom: &VirtualDom, future: Pin<&mut F>) -> Poll<F::Output> {
+        dom.in_scope(ScopeId::ROOT, || future.poll(&mut Context::from_waker(Waker::noop())))
+    }
+
+    fn cursor(id: &str) -> Option<String> {
+        Some(id.to_string())
+    }
+
+    #[test]
+    fn a_request_already_in_flight_is_coalesced() {
+        in_dom(|| {
+            let mut store = FeedStore::new(items(3), HeldSource::default());
+            let _guard = store.begin_request(Edge::Top, cursor("0"), None).unwrap();
+            assert!(store.begin_request(Edge::Top, cursor("0"), None).is_none());
+            // Other pages are other requests
+            assert!(store.begin_request(Edge::Top, cursor("1"), None).is_some());
+            assert!(store.begin_request(Edge::Bottom, cursor("0"), None).is_some());
+        });
+    }
+
+    #[test]
+    fn dropping_the_guard_releases_the_request() {
+        in_dom(|| {
+            let mut store = FeedStore::new(items(3), HeldSource::default());
+            let guard = store.begin_request(Edge::Top, cursor("0"), Some((FeedStatus::LoadingTop, FeedStatus::Idle)));
+            store.status.set(FeedStatus::LoadingTop);
+            assert!(store.edge_busy(Edge::Top));
+
+            drop(guard);
+            assert!(!store.edge_busy(Edge::Top));
+            assert_eq!(*store.status.peek(), FeedStatus::Idle);
+            assert!(store.begin_request(Edge::Top, cursor("0"), None).is_some());
+        });
+    }
+
+    #[test]
+    fn a_guard_from_before_a_reset_leaves_newer_requests_alone() {
+        in_dom(|| {
+            let mut store = FeedStore::new(items(3), HeldSource::default());
+            let loading = || Some((FeedStatus::LoadingTop, FeedStatus::Idle));
+            let stale = store.begin_request(Edge::Top, cursor("0"), loading());
+            store.reset(items(3));
+            let current = store.begin_request(Edge::Top, cursor("0"), loading());
+            assert!(current.is_some());
+            store.status.set(FeedStatus::LoadingTop);
+
+            drop(stale);
+            assert!(store.begin_request(Edge::Top, cursor("0"), None).is_none());
+            assert_eq!(*store.status.peek(), FeedStatus::LoadingTop);
+
+            drop(current);
+            assert!(!store.edge_busy(Edge::Top));
+            assert_eq!(*store.status.peek(), FeedStatus::Idle);
+        });
+    }
+
+    #[test]
+    fn a_refresh_merges_the_page_it_waited_for() {
+        let dom = VirtualDom::new(|| rsx! {});
+        let source = HeldSource::default();
+        let store = dom.in_scope(ScopeId::ROOT, || FeedStore::new(items(3), source.clone()));
+
+        let mut refresh = Box::pin(store.refresh());
+        assert!(poll_in(&dom, refresh.as_mut()).is_pending());
+        source.release(vec![VirtualFeedItem::new("new".to_string(), "New item".to_string(), "")]);
+        assert_eq!(poll_in(&dom, refresh.as_mut()), Poll::Ready(1));
+        dom.in_scope(ScopeId::ROOT, || {
+            assert_eq!(loaded_ids(&store), ["0", "1", "2", "new"]);
+            assert!(store.is_fresh("new"));
+        });
+    }
+
+    #[test]
+    fn a_reset_discards_the_response_of_an_older_refresh() {
+        let dom = VirtualDom::new(|| rsx! {});
+        let source = HeldSource::default();
+        let mut store = dom.in_scope(ScopeId::ROOT, || FeedStore::new(items(3), source.clone()));
+
+        let mut refresh = Box::pin(store.refresh());
+        assert!(poll_in(&dom, refresh.as_mut()).is_pending());
+        dom.in_scope(ScopeId::ROOT, || {
+            assert_eq!(*store.status.peek(), FeedStatus::Refreshing);
+            store.reset(items(2));
+        });
+
+        source.release(vec![VirtualFeedItem::new("stale".to_string(), "Stale item".to_string(), "")]);
+        assert_eq!(poll_in(&dom, refresh.as_mut()), Poll::Ready(0));
+        dom.in_scope(ScopeId::ROOT, || {
+            assert_eq!(loaded_ids(&store), ["0", "1"]);
+            assert_eq!(*store.status.peek(), FeedStatus::Idle);
+        });
+    }
+
+    #[test]
+    fn cancelling_a_refresh_restores_the_status() {
+        let dom = VirtualDom::new(|| rsx! {});
+        let store = dom.in_scope(ScopeId::ROOT, || FeedStore::new(items(3), HeldSource::default()));
+
+        let mut refresh = Box::pin(store.refresh());
+        assert!(poll_in(&dom, refresh.as_mut()).is_pending());
+        dom.in_scope(ScopeId::ROOT, || {
+            assert!(store.edge_busy(Edge::Bottom));
+            drop(refresh);
+            assert!(!store.edge_busy(Edge::Bottom));
+            assert_eq!(*store.status.peek(), FeedStatus::Idle);
+        });
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn fresh_highlights_expire_on_the_clock() {
+        let dom = VirtualDom::new(|| rsx! {});
+        let mut store = dom.in_scope(ScopeId::ROOT, || {
+            let mut store = FeedStore::from_items(Signal::new(items(3).into()));
+            store.mark_fresh(&items(1));
+            store
+        });
+
+        tokio::time::advance(FRESH_HIGHLIGHT_DURATION - Duration::from_millis(1)).await;
+        dom.in_scope(ScopeId::ROOT, || {
+            store.expire_fresh();
+            assert!(store.is_fresh("0"));
+            assert!(!store.is_fresh("1"));
+        });
+
+        tokio::time::advance(Duration::from_millis(1)).await;
+        dom.in_scope(ScopeId::ROOT, || {
+            assert!(!store.is_fresh("0"));
+            store.expire_fresh();
+            assert!(store.fresh_ids.peek().is_empty());
+        });
+    }
+
+    #[test]
+    fn loaded_span_maps_shown_rows_to_loaded_indices() {
+        in_dom(|| {
+            let mut store = FeedStore::from_items(Signal::new(items(6).into()));
+            store.edit("4", EditAction::TogglePin);
+            // Shown: 4, 0, 1, 2, 3, 5
+            let shown = store.ordered_items();
+            assert_eq!(store.loaded_span(shown.iter().take(1).map(|item| item.id.as_str())), 4..5);
+            assert_eq!(store.loaded_span(shown.iter().skip(1).take(2).map(|item| item.id.as_str())), 0..2);
+            assert_eq!(store.loaded_span(["missing"]), 0..0);
+        });
+    }
+
+    #[test]
+    fn eviction_keeps_the_rows_in_view_when_hidden_items_come_first() {
+        in_dom(|| {
+            let mut store = FeedStore::from_items(Signal::new(items(10).into()));
+            for id in ["0", "1", "2", "3", "4"] {
+                store.edit(id, EditAction::Hide);
+            }
+            // The first two rows shown hold the items loaded at 5 and 6
+            let shown = store.ordered_items();
+            let keep = store.loaded_span(shown.iter().take(2).map(|item| item.id.as_str()));
+            assert_eq!(keep, 5..7);
+
+            let evictions = store.evict_outside(5, keep);
+            let evicted: Vec<&str> = evictions
+                .iter()
+                .flat_map(|eviction| eviction.ids.iter().map(String::as_str))
+                .collect();
+            assert_eq!(evicted, ["0", "1", "2", "3", "4"]);
+            assert_eq!(loaded_ids(&store), ["5", "6", "7", "8", "9"]);
+        });
+    }
+}
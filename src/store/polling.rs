@@ -0,0 +1,105 @@
+use std::time::Duration;
+
+/// Factor the interval shrinks by after a poll that brought in a full page
+const SPEED_UP: f64 = 0.5;
+
+/// Factor the interval grows by after a poll that found nothing
+const SLOW_DOWN: f64 = 1.5;
+
+/// Shortest and longest time between polls, whatever the feed's activity
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PollBounds {
+    pub min: Duration,
+    pub max: Duration,
+}
+
+impl PollBounds {
+    /// Bounds with `min` no greater than `max`
+    pub fn new(min: Duration, max: Duration) -> Self {
+        Self { min: min.min(max), max }
+    }
+
+    fn clamp(&self, interval: Duration) -> Duration {
+        interval.clamp(self.min, self.max)
+    }
+}
+
+/// Poll interval following the feed's rate of change.
+///
+/// Starts at the source's own interval; each poll moves it towards the
+/// minimum by how full its page was, or towards the maximum when it found
+/// nothing, so busy feeds are checked often and idle ones rarely.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdaptiveInterval {
+    current: Duration,
+}
+
+impl AdaptiveInterval {
+    pub fn new(initial: Duration) -> Self {
+        Self { current: initial }
+    }
+
+    /// Interval before the next poll
+    pub fn current(&self, bounds: PollBounds) -> Duration {
+        bounds.clamp(self.current)
+    }
+
+    /// Adjust after a poll that brought in `new_items` of at most `page_size`
+    pub fn record(&mut self, new_items: usize, page_size: usize, bounds: PollBounds) {
+        let factor = if new_items == 0 {
+            SLOW_DOWN
+        } else {
+            // A full page means items were probably missed, so speed up most
+            let fullness = (new_items as f64 / page_size.max(1) as f64).min(1.0);
+            1.0 - (1.0 - SPEED_UP) * fullness
+        };
+        self.current = bounds.clamp(self.current.mul_f64(factor));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::ClockHandle;
+    use tokio::time::{advance, timeout};
+
+    fn secs(secs: u64) -> Duration {
+        Duration::from_secs(secs)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn polls_wait_out_the_interval_on_the_clock() {
+        let clock = ClockHandle::default();
+        let bounds = PollBounds::new(secs(10), secs(60));
+        let mut interval = AdaptiveInterval::new(secs(20));
+        let start = clock.now();
+
+        // A poll that found nothing waits longer
+        interval.record(0, 5, bounds);
+        clock.sleep(interval.current(bounds)).await;
+        assert_eq!(clock.now() - start, secs(30));
+
+        // A full page halves the wait
+        interval.record(5, 5, bounds);
+        let mut next = clock.sleep(interval.current(bounds));
+        advance(secs(14)).await;
+        assert!(timeout(Duration::ZERO, &mut next).await.is_err(), "polled before the interval passed");
+        advance(secs(1)).await;
+        next.await;
+        assert_eq!(clock.now() - start, secs(45));
+    }
+
+    #[test]
+    fn the_interval_stays_within_its_bounds() {
+        let bounds = PollBounds::new(secs(10), secs(60));
+        let mut interval = AdaptiveInterval::new(secs(20));
+        for _ in 0..10 {
+            interval.record(0, 5, bounds);
+        }
+        assert_eq!(interval.current(bounds), secs(60));
+        for _ in 0..10 {
+            interval.record(5, 5, bounds);
+        }
+        assert_eq!(interval.current(bounds), secs(10));
+    }
+}
@@ -0,0 +1,45 @@
+use dioxus::prelude::*;
+
+use super::FeedStore;
+
+/// How far through a feed's loaded items the reader has scrolled
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FeedProgress {
+    /// Items whose top has scrolled into view
+    pub items_read: usize,
+    pub total_items: usize,
+    /// Whether the feed has no newer items to load, so the total is final
+    pub complete: bool,
+}
+
+impl FeedProgress {
+    /// Share of the items read, from 0 to 1
+    pub fn fraction(&self) -> f64 {
+        if self.total_items == 0 {
+            return 0.0;
+        }
+        self.items_read as f64 / self.total_items as f64
+    }
+
+    /// Items below the viewport; an estimate unless the feed is complete
+    pub fn remaining(&self) -> usize {
+        self.total_items - self.items_read
+    }
+}
+
+/// Reading progress through the feed in `store`, updated as it scrolls.
+///
+/// Rows are counted at the store's row height, so the count is approximate
+/// when the list interleaves other content.
+pub fn use_feed_progress(store: FeedStore) -> Memo<FeedProgress> {
+    use_memo(move || {
+        let total_items = store.items.read().len();
+        let viewport_bottom = (store.scroll_top)() + (store.viewport_height)();
+        let items_read = ((viewport_bottom / (store.row_height)()).ceil() as usize).min(total_items);
+        FeedProgress {
+            items_read,
+            total_items,
+            complete: store.status.read().bottom_reached(),
+        }
+    })
+}
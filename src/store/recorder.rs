@@ -0,0 +1,85 @@
+use dioxus::prelude::*;
+use std::collections::VecDeque;
+
+use super::{FeedItems, FeedStatus, Sequence, StoreEvent};
+
+/// Number of snapshots kept before the oldest are dropped
+pub const RECORDER_CAPACITY: usize = 100;
+
+/// Store state captured after a mutation
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub event: StoreEvent,
+    pub recorded_at: chrono::DateTime<chrono::Utc>,
+    pub items: FeedItems,
+    pub first_seq: Sequence,
+    pub status: FeedStatus,
+    pub scroll_top: f64,
+}
+
+/// Ring buffer of store snapshots for stepping through a feed's history.
+///
+/// Only compiled into debug builds. While a past snapshot is selected the
+/// store shows that state; recording a new mutation discards the snapshots
+/// after it, like an undo history.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Recorder {
+    snapshots: Signal<VecDeque<Snapshot>>,
+    // Selected snapshot while time-travelling, `None` when following live state
+    cursor: Signal<Option<usize>>,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Self {
+            snapshots: Signal::new(VecDeque::with_capacity(RECORDER_CAPACITY)),
+            cursor: Signal::new(None),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.read().len()
+    }
+
+    /// Index of the snapshot currently shown
+    pub fn position(&self) -> Option<usize> {
+        let len = self.len();
+        self.cursor.read().or(len.checked_sub(1))
+    }
+
+    pub fn is_travelling(&self) -> bool {
+        self.cursor.read().is_some()
+    }
+
+    pub fn snapshot(&self, index: usize) -> Option<Snapshot> {
+        self.snapshots.read().get(index).cloned()
+    }
+
+    pub(super) fn record(&mut self, snapshot: Snapshot) {
+        let cursor = *self.cursor.peek();
+        let mut snapshots = self.snapshots.write();
+        if let Some(cursor) = cursor {
+            snapshots.truncate(cursor + 1);
+            self.cursor.set(None);
+        }
+        if snapshots.len() == RECORDER_CAPACITY {
+            snapshots.pop_front();
+        }
+        snapshots.push_back(snapshot);
+    }
+
+    // Select the snapshot at `index`, returning it for the store to apply
+    pub(super) fn select(&mut self, index: usize) -> Option<Snapshot> {
+        let snapshot = self.snapshot(index)?;
+        // Selecting the newest snapshot returns to following live state
+        let cursor = (index + 1 < self.len()).then_some(index);
+        self.cursor.set(cursor);
+        Some(snapshot)
+    }
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,104 @@
+use dioxus::prelude::*;
+use std::collections::HashMap;
+
+use crate::summarizer::SummarizerHandle;
+
+/// Newest items summarized together in a digest
+pub const DIGEST_ITEMS: usize = 20;
+
+/// Progress of one summary
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum SummaryStatus {
+    /// Not requested yet
+    #[default]
+    Idle,
+    /// Pieces are still arriving
+    Streaming,
+    Done,
+    Failed(String),
+}
+
+/// A summary as far as it has been generated
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Summary {
+    pub text: String,
+    pub status: SummaryStatus,
+}
+
+impl Summary {
+    /// Whether the summary is being generated or already complete, so
+    /// requesting it again would only repeat work
+    pub fn is_settled(&self) -> bool {
+        matches!(self.status, SummaryStatus::Streaming | SummaryStatus::Done)
+    }
+}
+
+/// Cached summaries of a store's items and of its digest, one signal each.
+///
+/// Rows read their own item's signal, so a streaming summary only
+/// re-renders the row it belongs to. Like [`super::item_state::ItemStates`],
+/// signals are created on first use and owned by the root scope.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Summaries {
+    items: CopyValue<HashMap<String, Signal<Summary>>>,
+    /// Summary of the newest items taken together
+    digest: Signal<Summary>,
+}
+
+impl Summaries {
+    pub fn new() -> Self {
+        Self {
+            items: CopyValue::new(HashMap::new()),
+            digest: Signal::new(Summary::default()),
+        }
+    }
+
+    /// Signal holding the summary of the item with `id`
+    pub fn item(&self, id: &str) -> Signal<Summary> {
+        if let Some(signal) = self.items.peek().get(id) {
+            return *signal;
+        }
+        let signal = Signal::new_in_scope(Summary::default(), ScopeId::ROOT);
+        let mut items = self.items;
+        items.write().insert(id.to_string(), signal);
+        signal
+    }
+
+    pub fn digest(&self) -> Signal<Summary> {
+        self.digest
+    }
+
+    /// Drop the summary of an item that left the feed
+    pub fn remove(&self, id: &str) {
+        let mut items = self.items;
+        let removed = items.write().remove(id);
+        if let Some(signal) = removed {
+            signal.manually_drop();
+        }
+    }
+}
+
+impl Default for Summaries {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Stream a summary of `text` into `summary`, unless one is already cached
+/// or underway
+pub(super) async fn stream(summarizer: SummarizerHandle, text: String, mut summary: Signal<Summary>) {
+    if summary.peek().is_settled() {
+        return;
+    }
+    summary.set(Summary { text: String::new(), status: SummaryStatus::Streaming });
+    let result = summarizer
+        .summarize(text, Box::new(move |chunk| summary.write().text.push_str(chunk)))
+        .await;
+    summary.write().status = match result {
+        Ok(()) => SummaryStatus::Done,
+        Err(e) => {
+            eprintln!("Failed to summarize: {}", e);
+            SummaryStatus::Failed(e.to_string())
+        }
+    };
+}
@@ -0,0 +1,271 @@
+use dioxus_feed_core::VirtualFeedItem;
+use std::collections::HashMap;
+
+use crate::sources::canonical::{find_near_duplicate, fold_near_duplicate, merge_item};
+
+use super::{FeedItems, Sequence};
+
+/// Batch of mutations applied to a store's items, built up by
+/// [`FeedStore::transaction`](super::FeedStore::transaction).
+///
+/// Mutations work on a working copy; the store writes it back once when the
+/// transaction ends, so the list re-renders and recomputes its layout once
+/// however many inserts, evictions and updates it contains.
+pub struct StoreTransaction {
+    items: FeedItems,
+    first_seq: Sequence,
+    mutations: usize,
+    // Ids of the items evicted or removed
+    removed: Vec<String>,
+}
+
+impl StoreTransaction {
+    pub(super) fn new(items: FeedItems, first_seq: Sequence) -> Self {
+        Self {
+            items,
+            first_seq,
+            mutations: 0,
+            removed: Vec::new(),
+        }
+    }
+
+    // Items and first sequence to write back with the ids of the items that
+    // left, if anything changed
+    pub(super) fn finish(self) -> Option<(FeedItems, Sequence, Vec<String>)> {
+        (self.mutations > 0).then_some((self.items, self.first_seq, self.removed))
+    }
+
+    /// Items as they stand in the transaction
+    pub fn items(&self) -> &FeedItems {
+        &self.items
+    }
+
+    pub fn first_sequence(&self) -> Sequence {
+        self.first_seq
+    }
+
+    /// Number of mutations made so far
+    pub fn mutations(&self) -> usize {
+        self.mutations
+    }
+
+    /// Insert older items before the first, keeping existing sequences
+    pub fn prepend(&mut self, items: Vec<VirtualFeedItem>) {
+        if items.is_empty() {
+            return;
+        }
+        self.first_seq -= items.len() as Sequence;
+        let mut items = FeedItems::from(items);
+        items.append(std::mem::take(&mut self.items));
+        self.items = items;
+        self.mutations += 1;
+    }
+
+    /// Insert newer items after the last
+    pub fn append(&mut self, items: Vec<VirtualFeedItem>) {
+        if items.is_empty() {
+            return;
+        }
+        self.items.append(items.into());
+        self.mutations += 1;
+    }
+
+    /// Drop up to `count` of the oldest items, returning how many were dropped.
+    /// The remaining items keep their sequences.
+    pub fn evict_front(&mut self, count: usize) -> usize {
+        let count = count.min(self.items.len());
+        if count > 0 {
+            let kept = self.items.split_off(count);
+            self.removed.extend(self.items.iter().map(|item| item.id.clone()));
+            self.items = kept;
+            self.first_seq += count as Sequence;
+            self.mutations += 1;
+        }
+        count
+    }
+
+    /// Drop up to `count` of the newest items, returning how many were dropped
+    pub fn evict_back(&mut self, count: usize) -> usize {
+        let count = count.min(self.items.len());
+        if count > 0 {
+            let evicted = self.items.split_off(self.items.len() - count);
+            self.removed.extend(evicted.into_iter().map(|item| item.id));
+            self.mutations += 1;
+        }
+        count
+    }
+
+    /// Change the item with `id` in place, returning whether it was found
+    pub fn update(&mut self, id: &str, update: impl FnOnce(&mut VirtualFeedItem)) -> bool {
+        let Some(item) = self.items.iter_mut().find(|item| item.id == id) else {
+            return false;
+        };
+        let before = item.clone();
+        update(item);
+        if *item != before {
+            self.mutations += 1;
+        }
+        true
+    }
+
+    /// Remove the item with `id`; the items after it move up a sequence
+    pub fn remove(&mut self, id: &str) -> Option<VirtualFeedItem> {
+        let index = self.items.iter().position(|item| item.id == id)?;
+        self.mutations += 1;
+        let item = self.items.remove(index);
+        self.removed.push(item.id.clone());
+        Some(item)
+    }
+
+    // Merge copies of items the feed already has into their rows (e.g. the
+    // same story from another source) and fold near-duplicates from other
+    // sources under them, returning the items new to the feed
+    pub(super) fn merge_known(&mut self, page: Vec<VirtualFeedItem>) -> Vec<VirtualFeedItem> {
+        let mut new_items = Vec::new();
+        // Merging changes items in place, so their positions hold throughout
+        let positions: HashMap<String, usize> = self
+            .items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| (item.id.clone(), index))
+            .collect();
+        for item in page {
+            let (index, near) = match positions.get(&item.id) {
+                Some(&index) => (index, false),
+                None => match find_near_duplicate(&self.items, &item) {
+                    Some(index) => (index, true),
+                    None => {
+                        new_items.push(item);
+                        continue;
+                    }
+                },
+            };
+            let before = self.items[index].clone();
+            if near {
+                fold_near_duplicate(&mut self.items[index], item);
+            } else {
+                merge_item(&mut self.items[index], item);
+            }
+            if self.items[index] != before {
+                self.mutations += 1;
+            }
+        }
+        new_items
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::{in_dom, FeedStore};
+    use dioxus::prelude::*;
+
+    fn items(ids: &[&str]) -> Vec<VirtualFeedItem> {
+        ids.iter()
+            .map(|id| VirtualFeedItem::new(id.to_string(), format!("Item {}", id), ""))
+            .collect()
+    }
+
+    fn ids(items: &FeedItems) -> Vec<String> {
+        items.iter().map(|item| item.id.clone()).collect()
+    }
+
+    #[test]
+    fn mutations_keep_sequences_and_removed_ids() {
+        let mut tx = StoreTransaction::new(items(&["1", "2", "3"]).into(), 10);
+        tx.prepend(items(&["0"]));
+        tx.append(items(&["4"]));
+        assert_eq!(tx.evict_front(2), 2);
+        assert_eq!(tx.evict_back(1), 1);
+        assert_eq!(tx.remove("3").map(|item| item.id), Some("3".to_string()));
+        assert!(tx.update("2", |item| item.content = "Edited".to_string()));
+        assert_eq!(tx.mutations(), 6);
+
+        let (items, first_seq, removed) = tx.finish().unwrap();
+        assert_eq!(ids(&items), ["2"]);
+        assert_eq!(items[0].content, "Edited");
+        assert_eq!(first_seq, 11);
+        assert_eq!(removed, ["0", "1", "4", "3"]);
+    }
+
+    #[test]
+    fn nothing_is_written_back_when_nothing_changed() {
+        let mut tx = StoreTransaction::new(items(&["0", "1"]).into(), 0);
+        tx.prepend(Vec::new());
+        tx.append(Vec::new());
+        assert_eq!(tx.evict_front(0), 0);
+        assert!(tx.update("0", |_| {}));
+        assert!(!tx.update("missing", |item| item.content.clear()));
+        assert!(tx.remove("missing").is_none());
+        assert_eq!(tx.mutations(), 0);
+        assert!(tx.finish().is_none());
+    }
+
+    #[test]
+    fn a_batch_notifies_the_items_subscribers_once() {
+        in_dom(|| {
+            let mut store = FeedStore::from_items(Signal::new(items(&["0", "1", "2"]).into()));
+            let (subscriber, mut notified) = ReactiveContext::new();
+            subscriber.run_in(|| {
+                store.items.read();
+            });
+
+            let evicted = store.transaction(|tx| {
+                tx.append(items(&["3", "4"]));
+                tx.update("2", |item| item.content = "Edited".to_string());
+                tx.evict_front(2)
+            });
+            assert_eq!(evicted, 2);
+            assert_eq!(ids(&store.items.peek()), ["2", "3", "4"]);
+            assert_eq!(store.first_sequence(), 2);
+
+            let mut notifications = 0;
+            while notified.try_recv().is_ok() {
+                notifications += 1;
+            }
+            assert_eq!(notifications, 1);
+        });
+    }
+
+    #[test]
+    fn a_batch_that_changes_nothing_notifies_no_one() {
+        in_dom(|| {
+            let mut store = FeedStore::from_items(Signal::new(items(&["0", "1"]).into()));
+            let (subscriber, mut notified) = ReactiveContext::new();
+            subscriber.run_in(|| {
+                store.items.read();
+            });
+
+            store.transaction(|tx| {
+                tx.update("0", |_| {});
+                tx.evict_back(0);
+            });
+            assert!(notified.try_recv().is_err());
+        });
+    }
+
+    #[test]
+    fn an_aborted_batch_leaves_the_items_unchanged() {
+        in_dom(|| {
+            let mut store = FeedStore::from_items(Signal::new(items(&["0", "1", "2"]).into()));
+
+            let aborted = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                store.transaction(|tx| {
+                    tx.append(items(&["3"]));
+                    tx.evict_front(2);
+                    panic!("mutation failed partway");
+                })
+            }));
+            assert!(aborted.is_err());
+            assert_eq!(ids(&store.items.peek()), ["0", "1", "2"]);
+            assert_eq!(store.first_sequence(), 0);
+
+            // A transaction dropped before it is finished writes nothing back
+            let mut tx = StoreTransaction::new(store.items.peek().clone(), 0);
+            tx.remove("1");
+            tx.append(items(&["3"]));
+            drop(tx);
+            assert_eq!(ids(&store.items.peek()), ["0", "1", "2"]);
+        });
+    }
+}
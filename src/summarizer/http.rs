@@ -0,0 +1,139 @@
+//! Reference summarizer backed by an OpenAI-compatible chat completions
+//! endpoint (OpenAI, Ollama, llama.cpp's server, …), streamed over
+//! server-sent events.
+
+use serde::Deserialize;
+use serde_json::json;
+
+use super::{Summarizer, SummaryError, SummaryFuture};
+
+/// Environment variable holding the API key sent as a bearer token, if any
+pub const API_KEY_ENV_VAR: &str = "DIOXUS_FEED_SUMMARIZER_KEY";
+
+/// Instructions sent ahead of the text
+const SYSTEM_PROMPT: &str = "Summarize the following feed content in at most three short sentences. \
+Answer with the summary only, in the language of the content.";
+
+/// Summarizer posting to `endpoint` with `model`
+pub struct HttpSummarizer {
+    endpoint: String,
+    model: String,
+    api_key: Option<String>,
+}
+
+impl HttpSummarizer {
+    /// Summarizer for `endpoint`, authenticated with the key from the
+    /// environment when one is set
+    pub fn new(endpoint: impl Into<String>, model: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            model: model.into(),
+            api_key: std::env::var(API_KEY_ENV_VAR).ok().filter(|key| !key.is_empty()),
+        }
+    }
+}
+
+impl Summarizer for HttpSummarizer {
+    fn summarize(&self, text: String, mut on_chunk: Box<dyn FnMut(&str)>) -> SummaryFuture {
+        let endpoint = self.endpoint.clone();
+        let body = json!({
+            "model": self.model,
+            "stream": true,
+            "messages": [
+                { "role": "system", "content": SYSTEM_PROMPT },
+                { "role": "user", "content": text },
+            ],
+        });
+        let api_key = self.api_key.clone();
+
+        Box::pin(async move {
+            let mut request = reqwest::Client::new().post(&endpoint).json(&body);
+            if let Some(api_key) = api_key {
+                request = request.bearer_auth(api_key);
+            }
+            let response = request
+                .send()
+                .await
+                .map_err(|e| SummaryError::Network(e.to_string()))?;
+            if !response.status().is_success() {
+                return Err(SummaryError::InvalidResponse(format!("HTTP {}", response.status())));
+            }
+
+            // Events arrive as `data: …` lines, possibly split across chunks
+            let mut response = Some(response);
+            let mut pending = Vec::new();
+            while let Some(chunk) = next_chunk(&mut response)
+                .await
+                .map_err(|e| SummaryError::Network(e.to_string()))?
+            {
+                pending.extend(chunk);
+                while let Some(end) = pending.iter().position(|&byte| byte == b'\n') {
+                    let line: Vec<u8> = pending.drain(..=end).collect();
+                    match parse_event(String::from_utf8_lossy(&line).trim())? {
+                        Event::Delta(text) => on_chunk(&text),
+                        Event::Done => return Ok(()),
+                        Event::Other => {}
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+// One server-sent event of a streamed completion
+enum Event {
+    Delta(String),
+    Done,
+    Other,
+}
+
+#[derive(Deserialize)]
+struct StreamChunk {
+    choices: Vec<StreamChoice>,
+}
+
+#[derive(Deserialize)]
+struct StreamChoice {
+    delta: StreamDelta,
+}
+
+#[derive(Deserialize)]
+struct StreamDelta {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+fn parse_event(line: &str) -> Result<Event, SummaryError> {
+    let Some(data) = line.strip_prefix("data:").map(str::trim) else {
+        return Ok(Event::Other);
+    };
+    if data == "[DONE]" {
+        return Ok(Event::Done);
+    }
+    let chunk: StreamChunk = serde_json::from_str(data).map_err(|e| SummaryError::InvalidResponse(e.to_string()))?;
+    let text: String = chunk
+        .choices
+        .into_iter()
+        .filter_map(|choice| choice.delta.content)
+        .collect();
+    Ok(if text.is_empty() { Event::Other } else { Event::Delta(text) })
+}
+
+// Next piece of the response body, `None` once it has all been read
+#[cfg(not(target_arch = "wasm32"))]
+async fn next_chunk(response: &mut Option<reqwest::Response>) -> reqwest::Result<Option<Vec<u8>>> {
+    match response {
+        Some(response) => Ok(response.chunk().await?.map(|chunk| chunk.to_vec())),
+        None => Ok(None),
+    }
+}
+
+// Browsers hand the body over in one piece
+#[cfg(target_arch = "wasm32")]
+async fn next_chunk(response: &mut Option<reqwest::Response>) -> reqwest::Result<Option<Vec<u8>>> {
+    match response.take() {
+        Some(response) => Ok(Some(response.bytes().await?.to_vec())),
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,96 @@
+//! On-demand summaries of long items and of whole feeds.
+//!
+//! A [`Summarizer`] turns text into a summary delivered in pieces as it is
+//! generated, so rows can show it progressively. Stores cache the results
+//! (see [`crate::store::summaries`]). [`NoopSummarizer`] is used while
+//! summaries are off; [`http::HttpSummarizer`] talks to an
+//! OpenAI-compatible chat completions endpoint.
+
+pub mod http;
+
+use dioxus_feed_core::VirtualFeedItem;
+use std::future::Future;
+use std::ops::Deref;
+use std::pin::Pin;
+use std::rc::Rc;
+
+
+/// Boxed future returned by summarizers, resolving once the summary is complete
+pub type SummaryFuture = Pin<Box<dyn Future<Output = Result<(), SummaryError>>>>;
+
+/// Shortest item content offered a summary
+pub const MIN_SUMMARY_CHARS: usize = 280;
+
+/// Custom error type for summarizers
+#[derive(Debug, Clone, PartialEq)]
+pub enum SummaryError {
+    /// No summarizer is configured
+    Unavailable,
+    Network(String),
+    InvalidResponse(String),
+}
+
+impl std::fmt::Display for SummaryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SummaryError::Unavailable => write!(f, "Summaries are turned off"),
+            SummaryError::Network(msg) => write!(f, "Network error: {}", msg),
+            SummaryError::InvalidResponse(msg) => write!(f, "Invalid response: {}", msg),
+        }
+    }
+}
+
+/// Something that summarizes text
+pub trait Summarizer {
+    /// Summarize `text`, passing each piece of the summary to `on_chunk` as
+    /// it is generated
+    fn summarize(&self, text: String, on_chunk: Box<dyn FnMut(&str)>) -> SummaryFuture;
+
+    /// Whether summaries can be requested at all; the UI hides its
+    /// "Summarize" actions otherwise
+    fn is_available(&self) -> bool {
+        true
+    }
+}
+
+/// Summarizer used while summaries are off
+pub struct NoopSummarizer;
+
+impl Summarizer for NoopSummarizer {
+    fn summarize(&self, _text: String, _on_chunk: Box<dyn FnMut(&str)>) -> SummaryFuture {
+        Box::pin(async { Err(SummaryError::Unavailable) })
+    }
+
+    fn is_available(&self) -> bool {
+        false
+    }
+}
+
+/// Shared handle to a summarizer
+#[derive(Clone)]
+pub struct SummarizerHandle(Rc<dyn Summarizer>);
+
+impl SummarizerHandle {
+    pub fn new(summarizer: impl Summarizer + 'static) -> Self {
+        Self(Rc::new(summarizer))
+    }
+}
+
+impl Default for SummarizerHandle {
+    fn default() -> Self {
+        Self::new(NoopSummarizer)
+    }
+}
+
+impl Deref for SummarizerHandle {
+    type Target = dyn Summarizer;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+/// Whether `item` is long enough to be worth summarizing
+pub fn is_long(item: &VirtualFeedItem) -> bool {
+    item.content.chars().count() >= MIN_SUMMARY_CHARS
+}
@@ -0,0 +1,250 @@
+//! Cleaning of URLs the app shows, opens, shares or compares.
+//!
+//! Tracking parameters (`utm_*` and known click identifiers) are stripped
+//! and links wrapped by known redirectors (search results, social sites'
+//! outbound link checkers) are unwrapped to their target. Redirects served
+//! by the target itself can optionally be resolved over the network.
+
+/// Query parameters that only track where a click came from
+const TRACKING_PARAMS: &[&str] = &[
+    "fbclid", "gclid", "dclid", "gbraid", "wbraid", "msclkid", "yclid", "twclid", "mc_cid", "mc_eid", "ref_src",
+    "ref_url", "igshid", "_hsenc", "_hsmi", "mkt_tok", "oly_anon_id", "oly_enc_id", "vero_id",
+];
+
+/// Prefixes of parameter families used only for tracking
+const TRACKING_PREFIXES: &[&str] = &["utm_", "pk_", "mtm_"];
+
+/// A redirecting wrapper around outbound links
+struct Redirector {
+    host: &'static str,
+    /// Path of the redirecting page, `None` when every path on the host
+    /// redirects
+    path: Option<&'static str>,
+    /// Query parameter holding the target, empty when the whole query is
+    /// the target
+    param: &'static str,
+}
+
+/// Known redirectors. Only their redirecting pages are unwrapped, so e.g. a
+/// Google search for a URL stays a search.
+const REDIRECTORS: &[Redirector] = &[
+    Redirector { host: "www.google.com", path: Some("/url"), param: "q" },
+    Redirector { host: "google.com", path: Some("/url"), param: "q" },
+    Redirector { host: "l.facebook.com", path: Some("/l.php"), param: "u" },
+    Redirector { host: "lm.facebook.com", path: Some("/l.php"), param: "u" },
+    Redirector { host: "l.instagram.com", path: Some("/"), param: "u" },
+    Redirector { host: "out.reddit.com", path: None, param: "url" },
+    Redirector { host: "www.youtube.com", path: Some("/redirect"), param: "q" },
+    Redirector { host: "href.li", path: Some("/"), param: "" },
+];
+
+/// Upper bound on redirects followed by [`resolve_redirects`]
+#[cfg(not(target_arch = "wasm32"))]
+const MAX_REDIRECTS: usize = 10;
+
+/// How long [`resolve_redirects`] waits for the whole chain before the link
+/// is opened as it is
+#[cfg(not(target_arch = "wasm32"))]
+const RESOLVE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// Whether a query parameter only tracks where a click came from
+pub fn is_tracking_param(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    TRACKING_PARAMS.contains(&key.as_str()) || TRACKING_PREFIXES.iter().any(|prefix| key.starts_with(prefix))
+}
+
+/// Target of a link wrapped by a known redirector, `None` for other links
+pub fn unwrap_redirect(url: &url::Url) -> Option<url::Url> {
+    let host = url.host_str()?;
+    let redirector = REDIRECTORS.iter().find(|redirector| {
+        redirector.host == host && redirector.path.is_none_or(|path| path == url.path())
+    })?;
+    let target = if redirector.param.is_empty() {
+        // href.li puts the target after the `?` as is
+        url.query()?.to_string()
+    } else {
+        url.query_pairs().find(|(key, _)| key == redirector.param)?.1.into_owned()
+    };
+    url::Url::parse(&target)
+        .ok()
+        .filter(|target| matches!(target.scheme(), "http" | "https"))
+}
+
+/// Strip tracking parameters from `url` and unwrap known redirectors.
+///
+/// Other parameters are kept as they were written, byte for byte and in
+/// order, and so is the fragment. Anything that isn't an http(s) URL is
+/// returned unchanged.
+pub fn clean(url: &url::Url) -> url::Url {
+    let mut url = url.clone();
+    // Redirectors can wrap each other; each unwrap strictly shortens the URL
+    while let Some(target) = unwrap_redirect(&url) {
+        url = target;
+    }
+    if !matches!(url.scheme(), "http" | "https") || url.query().is_none() {
+        return url;
+    }
+
+    // Re-encoding the decoded pairs would change how the rest are escaped
+    // (`%20` to `+`, `~` to `%7E`, …), which some servers treat differently
+    let query = url.query().unwrap_or_default();
+    let kept: Vec<&str> = query.split('&').filter(|pair| !is_tracking_pair(pair)).collect();
+    if kept.len() == query.split('&').count() {
+        return url;
+    }
+    if kept.iter().all(|pair| pair.is_empty()) {
+        url.set_query(None);
+    } else {
+        url.set_query(Some(&kept.join("&")));
+    }
+    url
+}
+
+// Whether a raw `key=value` pair of a query is a tracking parameter, going by
+// its decoded key
+fn is_tracking_pair(pair: &str) -> bool {
+    url::form_urlencoded::parse(pair.as_bytes())
+        .next()
+        .is_some_and(|(key, _)| is_tracking_param(&key))
+}
+
+/// [`clean`] for a URL as text, returning it trimmed but otherwise unchanged
+/// when it doesn't parse
+pub fn clean_url(url: &str) -> String {
+    match url::Url::parse(url.trim()) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => clean(&parsed).to_string(),
+        _ => url.trim().to_string(),
+    }
+}
+
+/// Follow the redirects `url` answers with and return the cleaned final URL.
+///
+/// Costs a request to the link's server (and each one it redirects to), so
+/// it only runs when the user opted in. Gives up after a few seconds, so
+/// a stalled server doesn't keep the link from opening.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn resolve_redirects(url: &str) -> Result<String, String> {
+    let client = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::limited(MAX_REDIRECTS))
+        .timeout(RESOLVE_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let response = client
+        .head(clean_url(url))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(clean(response.url()).to_string())
+}
+
+/// Redirects can't be followed from the browser, which hides cross-origin
+/// redirect targets from scripts
+#[cfg(target_arch = "wasm32")]
+pub async fn resolve_redirects(_url: &str) -> Result<String, String> {
+    Err("Redirects can't be resolved in the browser".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cleaned(url: &str) -> String {
+        clean(&url::Url::parse(url).unwrap()).to_string()
+    }
+
+    #[test]
+    fn recognizes_tracking_params() {
+        assert!(is_tracking_param("utm_source"));
+        assert!(is_tracking_param("UTM_Campaign"));
+        assert!(is_tracking_param("fbclid"));
+        assert!(is_tracking_param("pk_kwd"));
+        assert!(!is_tracking_param("ref"));
+        assert!(!is_tracking_param("q"));
+        assert!(!is_tracking_param("utm"));
+    }
+
+    #[test]
+    fn strips_tracking_params_and_keeps_the_rest() {
+        assert_eq!(
+            cleaned("https://example.com/post?id=7&utm_source=rss&utm_medium=feed&page=2#comments"),
+            "https://example.com/post?id=7&page=2#comments"
+        );
+        assert_eq!(cleaned("https://example.com/post?fbclid=abc"), "https://example.com/post");
+    }
+
+    #[test]
+    fn keeps_ref() {
+        // Often a branch, tag or referral code the page needs
+        assert_eq!(
+            cleaned("https://github.com/owner/repo/blob/file.rs?ref=main&utm_source=x"),
+            "https://github.com/owner/repo/blob/file.rs?ref=main"
+        );
+    }
+
+    #[test]
+    fn keeps_surviving_params_byte_for_byte() {
+        assert_eq!(
+            cleaned("https://example.com/search?q=a%20b+c&utm_source=x&tilde=~&path=%2Fhome&flag"),
+            "https://example.com/search?q=a%20b+c&tilde=~&path=%2Fhome&flag"
+        );
+        // Nothing to strip leaves the URL as written
+        assert_eq!(cleaned("https://example.com/?a=%7e&b=1+2"), "https://example.com/?a=%7e&b=1+2");
+    }
+
+    #[test]
+    fn decodes_keys_to_spot_tracking_params() {
+        assert_eq!(cleaned("https://example.com/?utm%5Fsource=x&id=1"), "https://example.com/?id=1");
+    }
+
+    #[test]
+    fn unwraps_known_redirectors() {
+        assert_eq!(
+            cleaned("https://www.google.com/url?sa=t&q=https%3A%2F%2Fexample.com%2Fpage%3Fid%3D1&usg=x"),
+            "https://example.com/page?id=1"
+        );
+        assert_eq!(
+            cleaned("https://l.facebook.com/l.php?u=https%3A%2F%2Fexample.com%2F%3Futm_source%3Dfb&h=x"),
+            "https://example.com/"
+        );
+        assert_eq!(
+            cleaned("https://out.reddit.com/t3_abc?url=https%3A%2F%2Fexample.com%2F&token=x"),
+            "https://example.com/"
+        );
+        assert_eq!(cleaned("https://href.li/?https://example.com/page"), "https://example.com/page");
+    }
+
+    #[test]
+    fn unwraps_nested_redirectors() {
+        assert_eq!(
+            cleaned("https://www.google.com/url?q=https%3A%2F%2Fhref.li%2F%3Fhttps%3A%2F%2Fexample.com%2F"),
+            "https://example.com/"
+        );
+    }
+
+    #[test]
+    fn leaves_other_pages_of_redirector_hosts() {
+        assert_eq!(cleaned("https://www.google.com/?q=https://x"), "https://www.google.com/?q=https://x");
+        assert_eq!(
+            cleaned("https://www.google.com/search?q=https://example.com"),
+            "https://www.google.com/search?q=https://example.com"
+        );
+        assert_eq!(
+            cleaned("https://www.youtube.com/watch?v=abc&q=https://example.com"),
+            "https://www.youtube.com/watch?v=abc&q=https://example.com"
+        );
+    }
+
+    #[test]
+    fn ignores_redirect_targets_that_are_not_http() {
+        assert_eq!(
+            cleaned("https://www.google.com/url?q=javascript:alert(1)"),
+            "https://www.google.com/url?q=javascript:alert(1)"
+        );
+    }
+
+    #[test]
+    fn leaves_unparseable_and_other_schemes() {
+        assert_eq!(clean_url("  not a url "), "not a url");
+        assert_eq!(clean_url("mailto:me@example.com?utm_source=x"), "mailto:me@example.com?utm_source=x");
+    }
+}
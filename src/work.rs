@@ -0,0 +1,151 @@
+//! Pool for CPU-heavy parsing, kept off the async tasks that drive the UI.
+//!
+//! Feed documents, OPML imports, article pages and search index entries are
+//! parsed on tokio's blocking threads. At most [`MAX_RUNNING`] jobs run at
+//! once and at most [`MAX_QUEUED`] more wait for a thread; callers past that
+//! wait for a place in the queue, so a huge import slows its own fetches down
+//! instead of piling up parsed documents. The web target has no threads and
+//! runs jobs inline.
+
+use dioxus::prelude::*;
+
+/// Jobs parsing at once
+pub const MAX_RUNNING: usize = 4;
+/// Jobs waiting for a thread, on top of the running ones
+pub const MAX_QUEUED: usize = 64;
+
+#[cfg(not(target_arch = "wasm32"))]
+static RUNNING: tokio::sync::Semaphore = tokio::sync::Semaphore::const_new(MAX_RUNNING);
+#[cfg(not(target_arch = "wasm32"))]
+static QUEUED: tokio::sync::Semaphore = tokio::sync::Semaphore::const_new(MAX_RUNNING + MAX_QUEUED);
+
+/// Progress of the jobs submitted to the pool since it was last idle.
+///
+/// Shown as a status line while a burst of parsing runs, like the feeds of a
+/// large OPML import loading. Provided once at the app root with
+/// `use_context_provider(WorkProgress::new)`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct WorkProgress {
+    submitted: Signal<usize>,
+    finished: Signal<usize>,
+}
+
+impl WorkProgress {
+    pub fn new() -> Self {
+        Self {
+            submitted: Signal::new(0),
+            finished: Signal::new(0),
+        }
+    }
+
+    /// Jobs finished and submitted, while any are still pending
+    pub fn pending(&self) -> Option<(usize, usize)> {
+        let (finished, submitted) = (*self.finished.read(), *self.submitted.read());
+        (finished < submitted).then_some((finished, submitted))
+    }
+}
+
+impl Default for WorkProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Counts a job as finished when dropped, so jobs whose caller went away
+// (e.g. a store reset mid-fetch) don't leave the progress pending
+struct Counted(Option<WorkProgress>);
+
+impl Counted {
+    fn start() -> Self {
+        let mut progress = try_consume_context::<WorkProgress>();
+        if let Some(progress) = progress.as_mut() {
+            progress.submitted += 1;
+        }
+        Self(progress)
+    }
+}
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        let Some(progress) = self.0.as_mut() else {
+            return;
+        };
+        let Ok(mut finished) = progress.finished.try_write() else {
+            return;
+        };
+        *finished += 1;
+        // Back to idle, so the next burst counts from zero
+        if *finished >= *progress.submitted.peek() {
+            *finished = 0;
+            drop(finished);
+            progress.submitted.set(0);
+        }
+    }
+}
+
+/// A job the pool dropped without running to the end, as it does when the
+/// runtime shuts down
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl std::fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the job was cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+/// Run `job` on the blocking pool and wait for its result, counted in the
+/// app's [`WorkProgress`]
+pub async fn run_blocking<T, F>(job: F) -> Result<T, Cancelled>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let _counted = Counted::start();
+    execute(job).await
+}
+
+/// Run a fallible `job` as [`run_blocking`] does, with a cancellation
+/// turned into the job's own error
+pub async fn try_run_blocking<T, E, F>(job: F) -> Result<T, E>
+where
+    F: FnOnce() -> Result<T, E> + Send + 'static,
+    T: Send + 'static,
+    E: From<Cancelled> + Send + 'static,
+{
+    run_blocking(job).await?
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn execute<T, F>(job: F) -> Result<T, Cancelled>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    // The semaphores are static and never closed
+    let queued = QUEUED.acquire().await.expect("work queue closed");
+    let running = RUNNING.acquire().await.expect("work pool closed");
+    // The job holds its places until it returns, even if the caller stops
+    // waiting for it
+    let job = move || {
+        let _permits = (queued, running);
+        job()
+    };
+    match tokio::task::spawn_blocking(job).await {
+        Ok(result) => Ok(result),
+        Err(e) if e.is_cancelled() => Err(Cancelled),
+        // A panicking job panics the caller, as if it had run inline
+        Err(e) => std::panic::resume_unwind(e.into_panic()),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn execute<T, F>(job: F) -> Result<T, Cancelled>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    Ok(job())
+}
@@ -0,0 +1,103 @@
+//! Checks the feature presets that leave out the default features: that the
+//! minimal preset doesn't pull in the feed app's dependencies, and that it
+//! builds.
+//!
+//! The build check compiles the crate again, so it is ignored by default:
+//! `cargo test --test feature_presets -- --ignored`
+
+use std::path::Path;
+use std::process::Command;
+
+// Kept apart from the build running the test, whose lock it would wait on
+const TARGET_DIR: &str = "target/presets";
+
+// Dependencies of the feed app that the minimal preset leaves out
+const EXCLUDED_FROM_MINIMAL: &[&str] = &["chrono", "mime_guess", "reqwest", "scraper", "roxmltree", "imagesize"];
+
+// Left out as dependencies of the crate, but also used by the desktop
+// renderer itself (webview, file dialogs, tray icon, logging) on some
+// platforms
+const EXCLUDED_DIRECT_FROM_MINIMAL: &[&str] = &["urlencoding", "regex", "sha2", "base64", "rand", "dirs"];
+
+fn cargo(args: &[&str]) -> std::process::Output {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    Command::new(env!("CARGO"))
+        .current_dir(manifest_dir)
+        .args(args)
+        .env("CARGO_TARGET_DIR", manifest_dir.join(TARGET_DIR))
+        .output()
+        .expect("failed to run cargo")
+}
+
+fn check(features: &str) {
+    let output = cargo(&["check", "--all-targets", "--no-default-features", "--features", features]);
+
+    assert!(
+        output.status.success(),
+        "`cargo check --no-default-features --features {}` failed:\n{}",
+        features,
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+// Normal dependencies of the package with `features`, as each one's depth
+// in the tree (1 for direct ones) and its name
+fn dependencies(features: &str) -> Vec<(usize, String)> {
+    let output = cargo(&[
+        "tree",
+        "--package",
+        "dioxus-feed",
+        "-e",
+        "normal",
+        "--no-default-features",
+        "--features",
+        features,
+        "--prefix",
+        "depth",
+        "--format",
+        "{p}",
+    ]);
+    assert!(
+        output.status.success(),
+        "`cargo tree --features {}` failed:\n{}",
+        features,
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let digits = line.chars().take_while(char::is_ascii_digit).count();
+            let depth = line[..digits].parse().ok()?;
+            let name = line[digits..].split_whitespace().next()?;
+            Some((depth, name.to_string()))
+        })
+        .collect()
+}
+
+#[test]
+fn minimal_preset_leaves_out_the_feed_apps_dependencies() {
+    let dependencies = dependencies("minimal");
+    assert!(!dependencies.is_empty(), "`cargo tree` listed no dependencies");
+
+    for excluded in EXCLUDED_FROM_MINIMAL {
+        assert!(
+            !dependencies.iter().any(|(_, name)| name == excluded),
+            "the minimal preset depends on {}",
+            excluded
+        );
+    }
+    for excluded in EXCLUDED_DIRECT_FROM_MINIMAL {
+        assert!(
+            !dependencies.iter().any(|(depth, name)| *depth == 1 && name == excluded),
+            "the minimal preset depends on {} directly",
+            excluded
+        );
+    }
+}
+
+#[test]
+#[ignore = "compiles the crate again"]
+fn minimal_preset_builds() {
+    check("minimal");
+}
@@ -0,0 +1,25 @@
+//! Runs the app in end-to-end mode and checks every scroll scenario passed.
+//!
+//! Needs a display for the webview, so it is ignored by default:
+//! `cargo test --test scroll_e2e -- --ignored`
+
+use std::process::Command;
+
+#[test]
+#[ignore = "launches the desktop app and needs a display"]
+fn scroll_scenarios_pass() {
+    let output = Command::new(env!("CARGO_BIN_EXE_dioxus-feed"))
+        .env("DIOXUS_FEED_E2E", "1")
+        .output()
+        .expect("failed to launch the app");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let results: Vec<&str> = stdout.lines().filter(|line| line.starts_with("e2e: ")).collect();
+
+    assert!(
+        results.iter().any(|line| line.starts_with("e2e: PASS")),
+        "no scenarios ran:\n{}",
+        stdout
+    );
+    assert!(output.status.success(), "scenarios failed:\n{}", results.join("\n"));
+}
@@ -0,0 +1,29 @@
+//! Runs the app through hours of simulated polling and checks the feed stayed
+//! bounded.
+//!
+//! Needs a display for the webview and takes about a minute, so it is ignored
+//! by default: `cargo test --test soak -- --ignored`
+
+use std::process::Command;
+
+// Simulated hours; the soak clock runs them in about a minute
+const SOAK_HOURS: &str = "4";
+
+#[test]
+#[ignore = "launches the desktop app and needs a display"]
+fn long_polling_session_stays_bounded() {
+    let output = Command::new(env!("CARGO_BIN_EXE_dioxus-feed"))
+        .env("DIOXUS_FEED_SOAK", SOAK_HOURS)
+        .output()
+        .expect("failed to launch the app");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let samples: Vec<&str> = stdout.lines().filter(|line| line.starts_with("soak: ")).collect();
+
+    assert!(
+        samples.iter().any(|line| line.starts_with("soak: PASS")),
+        "soak run did not finish:\n{}",
+        samples.join("\n")
+    );
+    assert!(output.status.success(), "soak run failed:\n{}", samples.join("\n"));
+}